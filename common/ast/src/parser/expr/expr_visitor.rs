@@ -155,6 +155,16 @@ pub trait ExprVisitor: Sized + Send {
             };
         }
 
+        if let Some(window_spec) = &function.over {
+            for partition_by in &window_spec.partition_by {
+                ExprTraverser::accept(partition_by, self).await?;
+            }
+
+            for order_by in &window_spec.order_by {
+                ExprTraverser::accept(&order_by.expr, self).await?;
+            }
+        }
+
         Ok(())
     }
 
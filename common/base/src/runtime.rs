@@ -126,6 +126,15 @@ impl Runtime {
         Self::create(tracker, runtime_builder.worker_threads(workers))
     }
 
+    /// Spawns a new tokio runtime whose memory usage is tracked by `tracker`.
+    pub fn with_tracker_and_worker_threads(
+        tracker: Arc<RuntimeTracker>,
+        workers: usize,
+    ) -> Result<Self> {
+        let mut runtime_builder = Self::tracker_builder(tracker.clone());
+        Self::create(tracker, runtime_builder.worker_threads(workers))
+    }
+
     pub fn block_on<F: Future>(&self, future: F) -> F::Output {
         self.handle.block_on(future)
     }
@@ -17,6 +17,9 @@ use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
+use common_exception::Result;
+
 #[thread_local]
 static mut TRACKER: *mut ThreadTracker = std::ptr::null_mut();
 
@@ -100,6 +103,8 @@ impl ThreadTracker {
 
 pub struct MemoryTracker {
     memory_usage: AtomicI64,
+    // The maximum memory usage in bytes, 0 means unlimited.
+    memory_limit: AtomicI64,
     parent_memory_tracker: Option<Arc<MemoryTracker>>,
 }
 
@@ -108,6 +113,7 @@ impl MemoryTracker {
         Arc::new(MemoryTracker {
             parent_memory_tracker,
             memory_usage: AtomicI64::new(0),
+            memory_limit: AtomicI64::new(0),
         })
     }
 
@@ -144,6 +150,36 @@ impl MemoryTracker {
     pub fn get_memory_usage(&self) -> i64 {
         self.memory_usage.load(Ordering::Relaxed)
     }
+
+    /// Sets the maximum memory usage in bytes, 0 means unlimited.
+    pub fn set_memory_limit(&self, limit: i64) {
+        self.memory_limit.store(limit, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn get_memory_limit(&self) -> i64 {
+        self.memory_limit.load(Ordering::Relaxed)
+    }
+
+    /// Checks the memory usage against the limits of this tracker and all its parents.
+    ///
+    /// The allocator can not fail an allocation gracefully, so the limits are not enforced
+    /// when allocating, but by the executors calling this periodically.
+    pub fn check_memory_limit(&self) -> Result<()> {
+        let limit = self.get_memory_limit();
+        let usage = self.get_memory_usage();
+        if limit > 0 && usage > limit {
+            return Err(ErrorCode::MemoryLimitExceeded(format!(
+                "Memory limit exceeded, memory usage {} bytes, limit {} bytes",
+                usage, limit
+            )));
+        }
+
+        match &self.parent_memory_tracker {
+            None => Ok(()),
+            Some(parent_memory_tracker) => parent_memory_tracker.check_memory_limit(),
+        }
+    }
 }
 
 pub struct RuntimeTracker {
@@ -152,7 +188,13 @@ pub struct RuntimeTracker {
 
 impl RuntimeTracker {
     pub fn create() -> Arc<RuntimeTracker> {
-        let parent_memory_tracker = MemoryTracker::current();
+        Self::create_with_parent(MemoryTracker::current())
+    }
+
+    /// Creates a tracker which also accounts the memory usage to `parent_memory_tracker`.
+    pub fn create_with_parent(
+        parent_memory_tracker: Option<Arc<MemoryTracker>>,
+    ) -> Arc<RuntimeTracker> {
         Arc::new(RuntimeTracker {
             memory_tracker: MemoryTracker::create(parent_memory_tracker),
         })
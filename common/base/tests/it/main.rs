@@ -14,4 +14,5 @@
 
 mod progress;
 mod runtime;
+mod runtime_tracker;
 mod stoppable;
@@ -0,0 +1,59 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+#[test]
+fn test_memory_tracker_limit() -> Result<()> {
+    let parent = MemoryTracker::create(None);
+    let child = MemoryTracker::create(Some(parent.clone()));
+
+    // unlimited by default
+    child.alloc_memory(1024);
+    assert!(child.check_memory_limit().is_ok());
+    assert_eq!(parent.get_memory_usage(), 1024);
+
+    child.set_memory_limit(2048);
+    assert!(child.check_memory_limit().is_ok());
+
+    child.alloc_memory(2048);
+    let err = child.check_memory_limit().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::MemoryLimitExceededCode());
+
+    child.dealloc_memory(2048);
+    assert!(child.check_memory_limit().is_ok());
+
+    // the limit of the parent applies to the children
+    parent.set_memory_limit(512);
+    let err = child.check_memory_limit().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::MemoryLimitExceededCode());
+    assert!(parent.check_memory_limit().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_runtime_tracker_with_parent() -> Result<()> {
+    let parent = MemoryTracker::create(None);
+    let tracker = RuntimeTracker::create_with_parent(Some(parent.clone()));
+    tracker.get_memory_tracker().alloc_memory(100);
+    assert_eq!(tracker.get_memory_tracker().get_memory_usage(), 100);
+    assert_eq!(parent.get_memory_usage(), 100);
+
+    let runtime = Runtime::with_tracker_and_worker_threads(tracker.clone(), 1)?;
+    assert!(std::sync::Arc::ptr_eq(&runtime.get_tracker(), &tracker));
+    Ok(())
+}
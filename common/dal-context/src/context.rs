@@ -16,13 +16,16 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use common_dal2::error::Result as DalResult;
 use common_dal2::ops::OpDelete;
+use common_dal2::ops::OpList;
 use common_dal2::ops::OpRead;
 use common_dal2::ops::OpStat;
 use common_dal2::ops::OpWrite;
+use common_dal2::ops::OpWriteStream;
 use common_dal2::readers::CallbackReader;
 use common_dal2::Accessor;
 use common_dal2::Layer;
 use common_dal2::Object;
+use common_dal2::ObjectStream;
 use common_dal2::Reader;
 use common_infallible::RwLock;
 
@@ -139,10 +142,24 @@ impl Accessor for DalContext {
             n
         })
     }
+    async fn write_stream(&self, r: Reader, args: &OpWriteStream) -> DalResult<usize> {
+        self.inner
+            .as_ref()
+            .unwrap()
+            .write_stream(r, args)
+            .await
+            .map(|n| {
+                self.inc_write_bytes(n);
+                n
+            })
+    }
     async fn stat(&self, args: &OpStat) -> DalResult<Object> {
         self.inner.as_ref().unwrap().stat(args).await
     }
     async fn delete(&self, args: &OpDelete) -> DalResult<()> {
         self.inner.as_ref().unwrap().delete(args).await
     }
+    async fn list(&self, args: &OpList) -> DalResult<ObjectStream> {
+        self.inner.as_ref().unwrap().list(args).await
+    }
 }
@@ -19,10 +19,13 @@ use futures::AsyncRead;
 
 use crate::error::Result;
 use crate::ops::OpDelete;
+use crate::ops::OpList;
 use crate::ops::OpRead;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
 use crate::Object;
+use crate::ObjectStream;
 
 pub type Reader = Box<dyn AsyncRead + Unpin + Send>;
 
@@ -38,6 +41,13 @@ pub trait Accessor: Send + Sync {
         let (_, _) = (r, args);
         unimplemented!()
     }
+    /// Write data from input reader, whose size is unknown, to the underlying storage.
+    ///
+    /// The data is uploaded part by part, so it never needs to be fully buffered in memory.
+    async fn write_stream(&self, r: Reader, args: &OpWriteStream) -> Result<usize> {
+        let (_, _) = (r, args);
+        unimplemented!()
+    }
     /// Invoke the `stat` operation on the specified path.
     async fn stat(&self, args: &OpStat) -> Result<Object> {
         let _ = args;
@@ -53,6 +63,15 @@ pub trait Accessor: Send + Sync {
         let _ = args;
         unimplemented!()
     }
+    /// Invoke the `list` operation on the specified directory.
+    ///
+    /// ## Behavior
+    ///
+    /// - `List` returns an empty stream if the directory does not exist.
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        let _ = args;
+        unimplemented!()
+    }
 }
 
 /// All functions in `Accessor` only requires `&self`, so it's safe to implement
@@ -65,10 +84,16 @@ impl Accessor for Arc<dyn Accessor> {
     async fn write(&self, r: Reader, args: &OpWrite) -> Result<usize> {
         self.as_ref().write(r, args).await
     }
+    async fn write_stream(&self, r: Reader, args: &OpWriteStream) -> Result<usize> {
+        self.as_ref().write_stream(r, args).await
+    }
     async fn stat(&self, args: &OpStat) -> Result<Object> {
         self.as_ref().stat(args).await
     }
     async fn delete(&self, args: &OpDelete) -> Result<()> {
         self.as_ref().delete(args).await
     }
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        self.as_ref().list(args).await
+    }
 }
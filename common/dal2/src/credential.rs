@@ -12,6 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use hmac::Hmac;
+use hmac::Mac;
+use hmac::NewMac;
+use jsonwebtoken::Algorithm;
+use jsonwebtoken::EncodingKey;
+use jsonwebtoken::Header;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Credential of the services.
+///
+/// How the credentials are used by each service:
+///
+/// | Service  | Credential                                                         |
+/// |----------|--------------------------------------------------------------------|
+/// | `s3`     | `HMAC`: access key id and secret access key                        |
+/// | `azblob` | `HMAC`: account name and account key (Shared Key), `Token`: SAS    |
+/// | `gcs`    | `Token`: OAuth 2.0 access token, `ServiceAccount`: JSON key        |
+///
+/// Requests are sent anonymously if no credential is specified, e.g. to the local emulators
+/// (such as fake-gcs-server) which require no authentication.
 #[derive(Debug, Clone)]
 pub enum Credential {
     /// Basic refers to HTTP Basic Authentication.
@@ -29,6 +54,9 @@ pub enum Credential {
     },
     /// Token refers to static API token.
     Token(String),
+    /// ServiceAccount refers to the JSON key of a Google Cloud service account, which is
+    /// exchanged for the short-lived OAuth 2.0 access tokens.
+    ServiceAccount(String),
 }
 
 impl Credential {
@@ -49,4 +77,105 @@ impl Credential {
     pub fn token(token: &str) -> Credential {
         Credential::Token(token.to_string())
     }
+
+    pub fn service_account(key: &str) -> Credential {
+        Credential::ServiceAccount(key.to_string())
+    }
+}
+
+/// Key of the `Shared Key` authorization of Azure Storage, which is the base64 decoded
+/// account key.
+#[derive(Clone)]
+pub(crate) struct SharedKey {
+    pub account: String,
+    key: Vec<u8>,
+}
+
+impl SharedKey {
+    pub fn try_create(account: &str, account_key: &str) -> Result<Self> {
+        let key = base64::decode(account_key).map_err(|_| Error::BackendConfigurationInvalid {
+            key: "credential".to_string(),
+            value: "".to_string(),
+        })?;
+        Ok(Self {
+            account: account.to_string(),
+            key,
+        })
+    }
+
+    /// Signs the string by HMAC-SHA256, the signature is encoded in base64.
+    pub fn sign(&self, string_to_sign: &str) -> String {
+        // HMAC accepts the key of any size.
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).unwrap();
+        mac.update(string_to_sign.as_bytes());
+        base64::encode(mac.finalize().into_bytes())
+    }
+
+    /// The value of the `Authorization` header.
+    pub fn authorization(&self, string_to_sign: &str) -> String {
+        format!("SharedKey {}:{}", self.account, self.sign(string_to_sign))
+    }
+}
+
+/// The JSON key of a Google Cloud service account, only the fields we care about are listed.
+#[derive(Clone, Deserialize)]
+pub(crate) struct ServiceAccountKey {
+    pub client_email: String,
+    private_key: String,
+    #[serde(default = "default_token_uri")]
+    pub token_uri: String,
+}
+
+fn default_token_uri() -> String {
+    "https://oauth2.googleapis.com/token".to_string()
+}
+
+/// The claims of the JWT which is exchanged for an access token.
+#[derive(Serialize)]
+struct TokenClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+impl ServiceAccountKey {
+    pub fn try_create(key: &str) -> Result<Self> {
+        let key: Self =
+            serde_json::from_str(key).map_err(|_| Error::BackendConfigurationInvalid {
+                key: "credential".to_string(),
+                value: "".to_string(),
+            })?;
+        // Fails early on a malformed private key, instead of on the first request.
+        key.encoding_key()?;
+        Ok(key)
+    }
+
+    /// Builds the JWT assertion of the token request, which is valid for an hour (the max
+    /// lifetime allowed) since `now`, in seconds since the unix epoch.
+    pub fn assertion(&self, scope: &str, now: u64) -> Result<String> {
+        let claims = TokenClaims {
+            iss: &self.client_email,
+            scope,
+            aud: &self.token_uri,
+            iat: now,
+            exp: now + 3600,
+        };
+        jsonwebtoken::encode(
+            &Header::new(Algorithm::RS256),
+            &claims,
+            &self.encoding_key()?,
+        )
+        .map_err(|e| Error::Unexpected(e.to_string()))
+    }
+
+    fn encoding_key(&self) -> Result<EncodingKey> {
+        EncodingKey::from_rsa_pem(self.private_key.as_bytes()).map_err(|_| {
+            Error::BackendConfigurationInvalid {
+                key: "credential".to_string(),
+                value: "".to_string(),
+            }
+        })
+    }
 }
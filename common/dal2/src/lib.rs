@@ -28,6 +28,7 @@ pub use operator::Operator;
 
 mod object;
 pub use object::Object;
+pub use object::ObjectStream;
 
 mod scheme;
 pub use scheme::Scheme;
@@ -12,7 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::SystemTime;
+
+use futures::Stream;
+
+use crate::error::Result;
+
 pub struct Object {
     pub path: String,
     pub size: u64,
+    /// The time the object was last modified, None if it's unknown (e.g. for directories).
+    pub last_modified: Option<SystemTime>,
 }
+
+impl Object {
+    /// Whether the object is a directory listed by `OpList`, whose path ends with `/`.
+    pub fn is_dir(&self) -> bool {
+        self.path.ends_with('/')
+    }
+}
+
+/// The objects returned by `OpList`, the pages are fetched while the stream is consumed.
+pub type ObjectStream = Box<dyn Stream<Item = Result<Object>> + Unpin + Send>;
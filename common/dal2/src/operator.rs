@@ -15,9 +15,11 @@
 use std::sync::Arc;
 
 use crate::ops::OpDelete;
+use crate::ops::OpList;
 use crate::ops::OpRead;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
 use crate::Accessor;
 use crate::Layer;
 
@@ -50,6 +52,10 @@ impl Operator {
         OpWrite::new(self.clone(), path, size)
     }
 
+    pub fn write_stream(&self, path: &str) -> OpWriteStream {
+        OpWriteStream::new(self.clone(), path)
+    }
+
     pub fn stat(&self, path: &str) -> OpStat {
         OpStat::new(self.clone(), path)
     }
@@ -57,4 +63,8 @@ impl Operator {
     pub fn delete(&self, path: &str) -> OpDelete {
         OpDelete::new(self.clone(), path)
     }
+
+    pub fn list(&self, path: &str) -> OpList {
+        OpList::new(self.clone(), path)
+    }
 }
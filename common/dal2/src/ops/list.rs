@@ -0,0 +1,66 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::Result;
+use crate::ObjectStream;
+use crate::Operator;
+
+/// The default number of objects fetched by one request of listing, the maximum of s3.
+pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
+/// Lists the objects under a directory.
+///
+/// The paths of the listed objects are relative to the root of the backend, so they can be
+/// passed to the other operations as they are.
+///
+/// - In the recursive mode, all the objects under the directory and its sub-directories are
+///   listed, but not the sub-directories themselves.
+/// - Otherwise only the direct children are listed, and the sub-directories are listed as
+///   objects whose paths end with `/`.
+pub struct OpList {
+    op: Operator,
+
+    /// The directory to list, the root of the backend if it's empty.
+    pub path: String,
+    pub recursive: bool,
+    /// How many objects are fetched by one request, services without pagination ignore it.
+    pub page_size: usize,
+}
+
+impl OpList {
+    pub fn new(op: Operator, path: &str) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            recursive: false,
+            page_size: DEFAULT_LIST_PAGE_SIZE,
+        }
+    }
+
+    pub fn recursive(&mut self, recursive: bool) -> &mut Self {
+        self.recursive = recursive;
+
+        self
+    }
+
+    pub fn page_size(&mut self, page_size: usize) -> &mut Self {
+        self.page_size = page_size;
+
+        self
+    }
+
+    pub async fn run(&self) -> Result<ObjectStream> {
+        self.op.inner().list(self).await
+    }
+}
@@ -20,7 +20,13 @@ mod read;
 pub use read::OpRead;
 mod write;
 pub use write::OpWrite;
+mod write_stream;
+pub use write_stream::OpWriteStream;
+pub use write_stream::DEFAULT_PART_SIZE;
 mod stat;
 pub use stat::OpStat;
 mod delete;
 pub use delete::OpDelete;
+mod list;
+pub use list::OpList;
+pub use list::DEFAULT_LIST_PAGE_SIZE;
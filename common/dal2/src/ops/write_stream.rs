@@ -0,0 +1,51 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::Result;
+use crate::Operator;
+use crate::Reader;
+
+/// The default size of the parts of a streaming write, 8 MiB.
+pub const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Writes the data of a reader whose size is not known in advance.
+///
+/// Services upload the data in parts of `part_size` bytes (e.g. the multipart upload of s3),
+/// so at most one part is held in memory.
+pub struct OpWriteStream {
+    op: Operator,
+
+    pub path: String,
+    pub part_size: usize,
+}
+
+impl OpWriteStream {
+    pub fn new(op: Operator, path: &str) -> Self {
+        Self {
+            op,
+            path: path.to_string(),
+            part_size: DEFAULT_PART_SIZE,
+        }
+    }
+
+    pub fn part_size(&mut self, part_size: usize) -> &mut Self {
+        self.part_size = part_size;
+
+        self
+    }
+
+    pub async fn run(&mut self, r: Reader) -> Result<usize> {
+        self.op.inner().write_stream(r, self).await
+    }
+}
@@ -15,6 +15,9 @@
 mod callback;
 pub use callback::CallbackReader;
 
+mod part;
+pub(crate) use part::read_part;
+
 mod seekable;
 pub use seekable::SeekableReader;
 
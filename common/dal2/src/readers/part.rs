@@ -0,0 +1,31 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use futures::AsyncReadExt;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::Reader;
+
+/// Reads at most `size` bytes from the reader, less bytes are returned only if the reader is
+/// exhausted.
+pub(crate) async fn read_part(r: &mut Reader, size: usize) -> Result<Vec<u8>> {
+    let mut part = Vec::with_capacity(size);
+    r.take(size as u64)
+        .read_to_end(&mut part)
+        .await
+        .map_err(|e| Error::Unexpected(e.to_string()))?;
+    Ok(part)
+}
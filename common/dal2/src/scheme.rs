@@ -17,9 +17,9 @@ use super::error::Error;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Scheme {
-    // TODO: Although we don't have azblob support for now, but we need to add it for compatibility. We will implement azblob support as soon as possible.
     Azblob,
     Fs,
+    Gcs,
     S3,
 }
 
@@ -31,11 +31,12 @@ impl FromStr for Scheme {
         match s.as_str() {
             "azblob" => Ok(Scheme::Azblob),
             "fs" => Ok(Scheme::Fs),
+            "gcs" => Ok(Scheme::Gcs),
             "s3" => Ok(Scheme::S3),
 
             // TODO: it's used for compatibility with dal1, should be removed in the future
             "local" | "disk" => Ok(Scheme::Fs),
-            "azurestorageblob" => Ok(Scheme::Azblob),
+            "azurestorageblob" | "azure_storage_blob" => Ok(Scheme::Azblob),
 
             _ => Err(Error::BackendNotSupported(s)),
         }
@@ -0,0 +1,591 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use http::header::AUTHORIZATION;
+use http::header::CONTENT_LENGTH;
+use http::header::LAST_MODIFIED;
+use http::Method;
+use http::Request;
+use http::StatusCode;
+use http::Uri;
+use hyper::Body;
+use hyper::Response;
+use quick_xml::events::Event;
+use quick_xml::Reader as XmlReader;
+
+use super::http::body_from_reader;
+use super::http::new_client;
+use super::http::parse_error_response;
+use super::http::parse_header;
+use super::http::parse_http_date;
+use super::http::parse_http_error;
+use super::http::percent_encode_path;
+use super::http::query_string;
+use super::http::read_body;
+use super::http::reader_from_body;
+use super::http::HttpClient;
+use crate::credential::Credential;
+use crate::credential::SharedKey;
+use crate::error::Error;
+use crate::error::Result;
+use crate::ops::HeaderRange;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpRead;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
+use crate::readers::read_part;
+use crate::Accessor;
+use crate::Object;
+use crate::ObjectStream;
+use crate::Reader;
+
+/// The version of the REST API of the blob service.
+const X_MS_VERSION: &str = "2019-12-12";
+
+#[derive(Default, Debug, Clone)]
+pub struct Builder {
+    root: Option<String>,
+
+    container: String,
+    credential: Option<Credential>,
+    /// endpoint must be the full uri of the blob service, e.g.
+    /// - https://<account>.blob.core.windows.net
+    /// - http://127.0.0.1:10000/devstoreaccount1 (Azurite)
+    ///
+    /// If it's not set, the endpoint of Azure is derived from the account of the credential.
+    endpoint: Option<String>,
+}
+
+impl Builder {
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        self.root = Some(root.to_string());
+
+        self
+    }
+
+    pub fn container(&mut self, container: &str) -> &mut Self {
+        self.container = container.to_string();
+
+        self
+    }
+
+    pub fn credential(&mut self, credential: Credential) -> &mut Self {
+        self.credential = Some(credential);
+
+        self
+    }
+
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        self.endpoint = Some(endpoint.to_string());
+
+        self
+    }
+
+    pub async fn finish(&mut self) -> Result<Arc<dyn Accessor>> {
+        if self.container.is_empty() {
+            return Err(Error::BackendConfigurationInvalid {
+                key: "container".to_string(),
+                value: "".to_string(),
+            });
+        }
+
+        // strip the prefix of "/" in root only once.
+        let root = if let Some(root) = &self.root {
+            root.strip_prefix('/').unwrap_or(root).to_string()
+        } else {
+            String::new()
+        };
+
+        let auth = match &self.credential {
+            None => Auth::Anonymous,
+            Some(Credential::HMAC {
+                access_key_id,
+                secret_access_key,
+            }) => Auth::SharedKey(SharedKey::try_create(access_key_id, secret_access_key)?),
+            Some(Credential::Token(sas)) => Auth::Sas(sas.trim_start_matches('?').to_string()),
+            Some(_) => {
+                return Err(Error::BackendConfigurationInvalid {
+                    key: "credential".to_string(),
+                    value: "".to_string(),
+                })
+            }
+        };
+
+        let endpoint = match (&self.endpoint, &auth) {
+            (Some(endpoint), _) => endpoint.trim_end_matches('/').to_string(),
+            (None, Auth::SharedKey(key)) => {
+                format!("https://{}.blob.core.windows.net", key.account)
+            }
+            (None, _) => {
+                return Err(Error::BackendConfigurationInvalid {
+                    key: "endpoint".to_string(),
+                    value: "".to_string(),
+                })
+            }
+        };
+        // The path of the endpoint is part of the signed resource, e.g. the account of Azurite.
+        let endpoint_path = Uri::from_str(&endpoint)
+            .map_err(|_| Error::BackendConfigurationInvalid {
+                key: "endpoint".to_string(),
+                value: endpoint.clone(),
+            })?
+            .path()
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Arc::new(Backend {
+            root,
+            container: self.container.clone(),
+            endpoint,
+            endpoint_path,
+            auth,
+            client: new_client(),
+        }))
+    }
+}
+
+#[derive(Clone)]
+enum Auth {
+    Anonymous,
+    SharedKey(SharedKey),
+    /// The shared access signature, appended to the query of every request.
+    Sas(String),
+}
+
+#[derive(Clone)]
+pub struct Backend {
+    root: String,
+    container: String,
+    endpoint: String,
+    endpoint_path: String,
+    auth: Auth,
+
+    client: HttpClient,
+}
+
+impl Backend {
+    pub fn build() -> Builder {
+        Builder::default()
+    }
+
+    /// get_abs_path will return the absolute path of the given path in the azblob format.
+    /// If user input an absolute path, we will return it as it is with the prefix `/` striped.
+    /// If user input a relative path, we will calculate the absolute path with the root.
+    fn get_abs_path(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            return path.strip_prefix('/').unwrap().to_string();
+        }
+        if self.root.is_empty() {
+            return path.to_string();
+        }
+
+        format!("{}/{}", self.root, path)
+    }
+
+    /// Sends the request on the blob `path`, or on the container if it's None.
+    ///
+    /// Only the headers of `x-ms-*` are expected in `headers`, which are signed along with
+    /// the request.
+    async fn send(
+        &self,
+        method: Method,
+        path: Option<&str>,
+        query: &[(&str, String)],
+        headers: &[(&str, String)],
+        body: Body,
+        content_length: u64,
+    ) -> Result<Response<Body>> {
+        let mut resource = format!("{}/{}", self.endpoint_path, self.container);
+        if let Some(path) = path {
+            resource.push('/');
+            resource.push_str(&percent_encode_path(path));
+        }
+
+        let mut query_str = query_string(query);
+        if let Auth::Sas(sas) = &self.auth {
+            if !query_str.is_empty() {
+                query_str.push('&');
+            }
+            query_str.push_str(sas);
+        }
+        let mut uri = format!("{}/{}", self.endpoint, self.container);
+        if let Some(path) = path {
+            uri.push('/');
+            uri.push_str(&percent_encode_path(path));
+        }
+        if !query_str.is_empty() {
+            uri.push('?');
+            uri.push_str(&query_str);
+        }
+
+        let mut ms_headers = headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.clone()))
+            .collect::<BTreeMap<_, _>>();
+        ms_headers.insert(
+            "x-ms-date".to_string(),
+            httpdate::fmt_http_date(SystemTime::now()),
+        );
+        ms_headers.insert("x-ms-version".to_string(), X_MS_VERSION.to_string());
+
+        let mut req = Request::builder().method(method.clone()).uri(uri);
+        for (k, v) in &ms_headers {
+            req = req.header(k.as_str(), v.as_str());
+        }
+        if method == Method::PUT {
+            req = req.header(CONTENT_LENGTH, content_length);
+        }
+        if let Auth::SharedKey(key) = &self.auth {
+            let string_to_sign = string_to_sign(
+                &method,
+                content_length,
+                &ms_headers,
+                &key.account,
+                &resource,
+                query,
+            );
+            req = req.header(AUTHORIZATION, key.authorization(&string_to_sign));
+        }
+        let req = req
+            .body(body)
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+        self.client.request(req).await.map_err(parse_http_error)
+    }
+
+    async fn put_blob(&self, path: &str, body: Body, size: u64) -> Result<()> {
+        let headers = [("x-ms-blob-type", "BlockBlob".to_string())];
+        let resp = self
+            .send(Method::PUT, Some(path), &[], &headers, body, size)
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED => Ok(()),
+            _ => Err(parse_error_response(path, resp).await),
+        }
+    }
+
+    /// Uploads the first part and the rest data of the reader as blocks, then commits them.
+    ///
+    /// Blocks which are not committed are garbage collected by the service, so nothing need
+    /// to be cleaned up if the upload fails.
+    async fn put_blocks(
+        &self,
+        path: &str,
+        first_part: Vec<u8>,
+        mut r: Reader,
+        part_size: usize,
+    ) -> Result<usize> {
+        let mut block_ids = vec![];
+        let mut size = 0;
+        let mut part = first_part;
+        while !part.is_empty() {
+            let len = part.len();
+            // The ids of the blocks of a blob must be of the same length.
+            let block_id = base64::encode(format!("{:08}", block_ids.len()));
+            let query = [("comp", "block".to_string()), ("blockid", block_id.clone())];
+            let resp = self
+                .send(
+                    Method::PUT,
+                    Some(path),
+                    &query,
+                    &[],
+                    Body::from(part),
+                    len as u64,
+                )
+                .await?;
+            if resp.status() != StatusCode::CREATED {
+                return Err(parse_error_response(path, resp).await);
+            }
+            block_ids.push(block_id);
+            size += len;
+
+            // The last part is the one smaller than `part_size`.
+            if len < part_size {
+                break;
+            }
+            part = read_part(&mut r, part_size).await?;
+        }
+
+        let block_list = block_ids
+            .iter()
+            .map(|id| format!("<Latest>{}</Latest>", id))
+            .collect::<String>();
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList>{}</BlockList>",
+            block_list
+        );
+        let query = [("comp", "blocklist".to_string())];
+        let resp = self
+            .send(
+                Method::PUT,
+                Some(path),
+                &query,
+                &[],
+                Body::from(body.clone()),
+                body.len() as u64,
+            )
+            .await?;
+        match resp.status() {
+            StatusCode::CREATED => Ok(size),
+            _ => Err(parse_error_response(path, resp).await),
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    async fn read(&self, args: &OpRead) -> Result<Reader> {
+        let p = self.get_abs_path(&args.path);
+
+        let mut headers = vec![];
+        if args.offset.is_some() || args.size.is_some() {
+            let range = HeaderRange::new(args.offset, args.size).to_string();
+            headers.push(("x-ms-range", range));
+        }
+
+        let resp = self
+            .send(Method::GET, Some(&p), &[], &headers, Body::empty(), 0)
+            .await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok(reader_from_body(resp.into_body())),
+            _ => Err(parse_error_response(&args.path, resp).await),
+        }
+    }
+
+    async fn write(&self, r: Reader, args: &OpWrite) -> Result<usize> {
+        let p = self.get_abs_path(&args.path);
+
+        self.put_blob(&p, body_from_reader(r), args.size).await?;
+        Ok(args.size as usize)
+    }
+
+    /// Uploads the data as blocks, a block is uploaded once `part_size` bytes are read. The data
+    /// that fits in one part is uploaded by a single `Put Blob` instead.
+    async fn write_stream(&self, mut r: Reader, args: &OpWriteStream) -> Result<usize> {
+        let p = self.get_abs_path(&args.path);
+
+        // An empty part would be taken as the end of the data.
+        let part_size = args.part_size.max(1);
+        let part = read_part(&mut r, part_size).await?;
+        if part.len() < part_size {
+            let size = part.len();
+            self.put_blob(&p, Body::from(part), size as u64).await?;
+            return Ok(size);
+        }
+
+        self.put_blocks(&p, part, r, part_size).await
+    }
+
+    async fn stat(&self, args: &OpStat) -> Result<Object> {
+        let p = self.get_abs_path(&args.path);
+
+        let resp = self
+            .send(Method::HEAD, Some(&p), &[], &[], Body::empty(), 0)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response(&args.path, resp).await);
+        }
+
+        let o = Object {
+            path: args.path.to_string(),
+            size: parse_header(&resp, CONTENT_LENGTH.as_str()).unwrap_or_default(),
+            last_modified: parse_header::<String>(&resp, LAST_MODIFIED.as_str())
+                .and_then(|t| parse_http_date(&t)),
+        };
+
+        Ok(o)
+    }
+
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let p = self.get_abs_path(&args.path);
+
+        let resp = self
+            .send(Method::DELETE, Some(&p), &[], &[], Body::empty(), 0)
+            .await?;
+        match resp.status() {
+            StatusCode::ACCEPTED | StatusCode::OK | StatusCode::NOT_FOUND => Ok(()),
+            _ => Err(parse_error_response(&args.path, resp).await),
+        }
+    }
+
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        let mut prefix = self.get_abs_path(&args.path);
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        // The blobs are listed with the root, which is stripped from the paths of the objects.
+        let root = if self.root.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.root.trim_end_matches('/'))
+        };
+        let recursive = args.recursive;
+        let page_size = args.page_size;
+        let backend = self.clone();
+
+        // The state is the marker of the next page, and None after the last page.
+        let pages =
+            futures::stream::try_unfold(Some(None), move |state: Option<Option<String>>| {
+                let (backend, prefix, root) = (backend.clone(), prefix.clone(), root.clone());
+                async move {
+                    let marker = match state {
+                        Some(marker) => marker,
+                        None => return Ok(None),
+                    };
+
+                    let mut query = vec![
+                        ("restype", "container".to_string()),
+                        ("comp", "list".to_string()),
+                        ("prefix", prefix.clone()),
+                        ("maxresults", page_size.to_string()),
+                    ];
+                    if !recursive {
+                        query.push(("delimiter", "/".to_string()));
+                    }
+                    if let Some(marker) = marker {
+                        query.push(("marker", marker));
+                    }
+
+                    let resp = backend
+                        .send(Method::GET, None, &query, &[], Body::empty(), 0)
+                        .await?;
+                    if resp.status() != StatusCode::OK {
+                        return Err(parse_error_response(&prefix, resp).await);
+                    }
+                    let body = read_body(resp).await?;
+                    let (objects, next_marker) = parse_list_blobs(&body, &root)?;
+                    Ok(Some((objects, next_marker.map(Some))))
+                }
+            });
+
+        let objects = pages
+            .map_ok(|objects| futures::stream::iter(objects.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::new(Box::pin(objects)))
+    }
+}
+
+/// Builds the string to sign of the `Shared Key` authorization.
+///
+/// ref: https://docs.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key
+fn string_to_sign(
+    method: &Method,
+    content_length: u64,
+    ms_headers: &BTreeMap<String, String>,
+    account: &str,
+    resource: &str,
+    query: &[(&str, String)],
+) -> String {
+    // The content length is empty if it's zero.
+    let content_length = match content_length {
+        0 => String::new(),
+        n => n.to_string(),
+    };
+    // VERB, Content-Encoding, Content-Language, Content-Length, Content-MD5, Content-Type, Date,
+    // If-Modified-Since, If-Match, If-None-Match, If-Unmodified-Since, Range
+    let mut s = format!("{}\n\n\n{}\n\n\n\n\n\n\n\n\n", method, content_length);
+    // The headers are sorted by the name, which are in lowercase.
+    for (k, v) in ms_headers {
+        s.push_str(&format!("{}:{}\n", k, v));
+    }
+    s.push_str(&format!("/{}{}", account, resource));
+    // The parameters of the query are sorted by the name.
+    let query = query
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), v))
+        .collect::<BTreeMap<_, _>>();
+    for (k, v) in query {
+        s.push_str(&format!("\n{}:{}", k, v));
+    }
+    s
+}
+
+const LIST_BLOB: &str = "EnumerationResults/Blobs/Blob";
+const LIST_BLOB_NAME: &str = "EnumerationResults/Blobs/Blob/Name";
+const LIST_BLOB_SIZE: &str = "EnumerationResults/Blobs/Blob/Properties/Content-Length";
+const LIST_BLOB_LAST_MODIFIED: &str = "EnumerationResults/Blobs/Blob/Properties/Last-Modified";
+const LIST_BLOB_PREFIX: &str = "EnumerationResults/Blobs/BlobPrefix";
+const LIST_BLOB_PREFIX_NAME: &str = "EnumerationResults/Blobs/BlobPrefix/Name";
+const LIST_NEXT_MARKER: &str = "EnumerationResults/NextMarker";
+
+/// Parses the response of `List Blobs`, returns the objects and the marker of the next page.
+fn parse_list_blobs(body: &[u8], root: &str) -> Result<(Vec<Object>, Option<String>)> {
+    let new_object = || Object {
+        path: String::new(),
+        size: 0,
+        last_modified: None,
+    };
+    let mut objects = vec![];
+    let mut next_marker = None;
+    let mut current = new_object();
+
+    let mut reader = XmlReader::from_reader(body);
+    reader.trim_text(true);
+    let mut buf = vec![];
+    // The elements from the root to the current one.
+    let mut elements: Vec<String> = vec![];
+    loop {
+        let event = reader
+            .read_event(&mut buf)
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+        match event {
+            Event::Start(e) => {
+                elements.push(String::from_utf8_lossy(e.name()).into_owned());
+                if matches!(elements.join("/").as_str(), LIST_BLOB | LIST_BLOB_PREFIX) {
+                    current = new_object();
+                }
+            }
+            Event::Text(e) => {
+                let text = e
+                    .unescape_and_decode(&reader)
+                    .map_err(|e| Error::Unexpected(e.to_string()))?;
+                match elements.join("/").as_str() {
+                    LIST_BLOB_NAME | LIST_BLOB_PREFIX_NAME => {
+                        current.path = text.strip_prefix(root).unwrap_or(&text).to_string()
+                    }
+                    LIST_BLOB_SIZE => current.size = text.parse().unwrap_or_default(),
+                    LIST_BLOB_LAST_MODIFIED => current.last_modified = parse_http_date(&text),
+                    LIST_NEXT_MARKER => next_marker = Some(text),
+                    _ => {}
+                }
+            }
+            Event::End(_) => {
+                match elements.join("/").as_str() {
+                    // Skip the placeholders of the directories.
+                    LIST_BLOB if !current.path.ends_with('/') => {
+                        objects.push(std::mem::replace(&mut current, new_object()))
+                    }
+                    // The prefixes end with the delimiter `/`, as the directories.
+                    LIST_BLOB_PREFIX => objects.push(std::mem::replace(&mut current, new_object())),
+                    _ => {}
+                }
+                elements.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok((objects, next_marker))
+}
@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use async_compat::CompatExt;
 use async_trait::async_trait;
+use futures::TryStreamExt;
 use tokio::fs;
 use tokio::io;
 use tokio::io::AsyncReadExt;
@@ -27,11 +28,14 @@ use tokio::io::AsyncSeekExt;
 use crate::error::Error;
 use crate::error::Result;
 use crate::ops::OpDelete;
+use crate::ops::OpList;
 use crate::ops::OpRead;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
 use crate::Accessor;
 use crate::Object;
+use crate::ObjectStream;
 use crate::Reader;
 
 #[derive(Default)]
@@ -71,35 +75,10 @@ impl Backend {
     pub fn build() -> Builder {
         Builder::default()
     }
-}
-
-#[async_trait]
-impl Accessor for Backend {
-    async fn read(&self, args: &OpRead) -> Result<Reader> {
-        let path = PathBuf::from(&self.root).join(&args.path);
-
-        let mut f = fs::OpenOptions::new()
-            .read(true)
-            .open(&path)
-            .await
-            .map_err(|e| parse_io_error(&e, &path))?;
 
-        if let Some(offset) = args.offset {
-            f.seek(SeekFrom::Start(offset))
-                .await
-                .map_err(|e| parse_io_error(&e, &path))?;
-        };
-
-        let f: Reader = match args.size {
-            Some(size) => Box::new(f.take(size).compat()),
-            None => Box::new(f.compat()),
-        };
-
-        Ok(f)
-    }
-
-    async fn write(&self, mut r: Reader, args: &OpWrite) -> Result<usize> {
-        let path = PathBuf::from(&self.root).join(&args.path);
+    /// Writes the whole data of the reader into the file, which is created or truncated.
+    async fn write_file(&self, path: &str, mut r: Reader) -> Result<usize> {
+        let path = PathBuf::from(&self.root).join(path);
 
         // Create dir before write path.
         //
@@ -117,11 +96,11 @@ impl Accessor for Backend {
         let mut f = fs::OpenOptions::new()
             .create(true)
             .write(true)
+            .truncate(true)
             .open(&path)
             .await
             .map_err(|e| parse_io_error(&e, &path))?;
 
-        // TODO: we should respect the input size.
         let s = io::copy(&mut r.compat_mut(), &mut f)
             .await
             .map_err(|e| parse_io_error(&e, &path))?;
@@ -134,6 +113,58 @@ impl Accessor for Backend {
 
         Ok(s as usize)
     }
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    async fn read(&self, args: &OpRead) -> Result<Reader> {
+        let path = PathBuf::from(&self.root).join(&args.path);
+
+        let mut f = fs::OpenOptions::new()
+            .read(true)
+            .open(&path)
+            .await
+            .map_err(|e| parse_io_error(&e, &path))?;
+
+        if let Some(offset) = args.offset {
+            f.seek(SeekFrom::Start(offset))
+                .await
+                .map_err(|e| parse_io_error(&e, &path))?;
+        };
+
+        let f: Reader = match args.size {
+            Some(size) => Box::new(f.take(size).compat()),
+            None => Box::new(f.compat()),
+        };
+
+        Ok(f)
+    }
+
+    async fn write(&self, r: Reader, args: &OpWrite) -> Result<usize> {
+        // TODO: we should respect the input size.
+        self.write_file(&args.path, r).await
+    }
+
+    /// The data is written into a temporary file beside the target as it arrives, which is
+    /// renamed to the target once the reader is exhausted, so a failed write never leaves a
+    /// truncated file at the path.
+    async fn write_stream(&self, r: Reader, args: &OpWriteStream) -> Result<usize> {
+        let tmp_path = format!("{}.{}.tmp", args.path, uuid::Uuid::new_v4());
+        let tmp = PathBuf::from(&self.root).join(&tmp_path);
+        let path = PathBuf::from(&self.root).join(&args.path);
+
+        let res = match self.write_file(&tmp_path, r).await {
+            Ok(size) => fs::rename(&tmp, &path)
+                .await
+                .map(|_| size)
+                .map_err(|e| parse_io_error(&e, &path)),
+            Err(e) => Err(e),
+        };
+        if res.is_err() {
+            let _ = fs::remove_file(&tmp).await;
+        }
+        res
+    }
 
     async fn stat(&self, args: &OpStat) -> Result<Object> {
         let path = PathBuf::from(&self.root).join(&args.path);
@@ -144,6 +175,7 @@ impl Accessor for Backend {
         let o = Object {
             path: path.to_string_lossy().into_owned(),
             size: meta.len(),
+            last_modified: meta.modified().ok(),
         };
 
         Ok(o)
@@ -172,6 +204,72 @@ impl Accessor for Backend {
 
         f.map_err(|e| parse_io_error(&e, &path))
     }
+
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        let root = PathBuf::from(&self.root);
+        let dir = root.join(&args.path);
+        let recursive = args.recursive;
+
+        // Each step reads a whole directory, the sub-directories to be walked are kept in
+        // `pending` in the recursive mode.
+        let pages = futures::stream::try_unfold(vec![dir], move |mut pending| {
+            let root = root.clone();
+            async move {
+                let dir = match pending.pop() {
+                    Some(dir) => dir,
+                    None => return Ok(None),
+                };
+
+                let mut entries = match fs::read_dir(&dir).await {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        return Ok(Some((vec![], pending)))
+                    }
+                    Err(e) => return Err(parse_io_error(&e, &dir)),
+                };
+
+                let mut objects = vec![];
+                while let Some(entry) = entries
+                    .next_entry()
+                    .await
+                    .map_err(|e| parse_io_error(&e, &dir))?
+                {
+                    let path = entry.path();
+                    let meta = entry
+                        .metadata()
+                        .await
+                        .map_err(|e| parse_io_error(&e, &path))?;
+                    let rel_path = path
+                        .strip_prefix(&root)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .into_owned();
+
+                    if !meta.is_dir() {
+                        objects.push(Object {
+                            path: rel_path,
+                            size: meta.len(),
+                            last_modified: meta.modified().ok(),
+                        });
+                    } else if recursive {
+                        pending.push(path);
+                    } else {
+                        objects.push(Object {
+                            path: format!("{}/", rel_path),
+                            size: 0,
+                            last_modified: None,
+                        });
+                    }
+                }
+                Ok(Some((objects, pending)))
+            }
+        });
+
+        let objects = pages
+            .map_ok(|objects| futures::stream::iter(objects.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::new(Box::pin(objects)))
+    }
 }
 
 /// Parse all path related errors.
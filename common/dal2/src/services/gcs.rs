@@ -0,0 +1,590 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use http::header::AUTHORIZATION;
+use http::header::CONTENT_LENGTH;
+use http::header::CONTENT_RANGE;
+use http::header::CONTENT_TYPE;
+use http::header::LOCATION;
+use http::header::RANGE;
+use http::Method;
+use http::Request;
+use http::StatusCode;
+use hyper::Body;
+use hyper::Response;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use super::http::body_from_reader;
+use super::http::new_client;
+use super::http::parse_error_response;
+use super::http::parse_header;
+use super::http::parse_http_error;
+use super::http::percent_encode;
+use super::http::query_string;
+use super::http::read_body;
+use super::http::reader_from_body;
+use super::http::HttpClient;
+use crate::credential::Credential;
+use crate::credential::ServiceAccountKey;
+use crate::error::Error;
+use crate::error::Result;
+use crate::ops::HeaderRange;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpRead;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
+use crate::readers::read_part;
+use crate::Accessor;
+use crate::Object;
+use crate::ObjectStream;
+use crate::Reader;
+
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// The scope of the access tokens requested by the service accounts.
+const TOKEN_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+
+/// The access tokens are refreshed a while before they expire, so that a request never carries
+/// a token which expires on the way.
+const TOKEN_REFRESH_AHEAD: Duration = Duration::from_secs(300);
+
+/// The chunks of a resumable upload must be multiples of 256 KiB, except the last one.
+const RESUMABLE_CHUNK_ALIGNMENT: usize = 256 * 1024;
+
+#[derive(Default, Debug, Clone)]
+pub struct Builder {
+    root: Option<String>,
+
+    bucket: String,
+    credential: Option<Credential>,
+    /// endpoint must be the full uri of the service, e.g.
+    /// - https://storage.googleapis.com (the default)
+    /// - http://127.0.0.1:4443 (fake-gcs-server)
+    endpoint: Option<String>,
+}
+
+impl Builder {
+    pub fn root(&mut self, root: &str) -> &mut Self {
+        self.root = Some(root.to_string());
+
+        self
+    }
+
+    pub fn bucket(&mut self, bucket: &str) -> &mut Self {
+        self.bucket = bucket.to_string();
+
+        self
+    }
+
+    pub fn credential(&mut self, credential: Credential) -> &mut Self {
+        self.credential = Some(credential);
+
+        self
+    }
+
+    pub fn endpoint(&mut self, endpoint: &str) -> &mut Self {
+        self.endpoint = Some(endpoint.to_string());
+
+        self
+    }
+
+    pub async fn finish(&mut self) -> Result<Arc<dyn Accessor>> {
+        if self.bucket.is_empty() {
+            return Err(Error::BackendConfigurationInvalid {
+                key: "bucket".to_string(),
+                value: "".to_string(),
+            });
+        }
+
+        // strip the prefix of "/" in root only once.
+        let root = if let Some(root) = &self.root {
+            root.strip_prefix('/').unwrap_or(root).to_string()
+        } else {
+            String::new()
+        };
+
+        let auth = match &self.credential {
+            None => Auth::Anonymous,
+            Some(Credential::Token(token)) => Auth::Token(token.clone()),
+            Some(Credential::ServiceAccount(key)) => Auth::ServiceAccount {
+                key: Arc::new(ServiceAccountKey::try_create(key)?),
+                token: Arc::new(Mutex::new(None)),
+            },
+            Some(_) => {
+                return Err(Error::BackendConfigurationInvalid {
+                    key: "credential".to_string(),
+                    value: "".to_string(),
+                })
+            }
+        };
+
+        let endpoint = self
+            .endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_ENDPOINT)
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Arc::new(Backend {
+            root,
+            bucket: self.bucket.clone(),
+            endpoint,
+            auth,
+            client: new_client(),
+        }))
+    }
+}
+
+#[derive(Clone)]
+enum Auth {
+    Anonymous,
+    /// The static OAuth 2.0 access token.
+    Token(String),
+    /// The access token is requested with the service account key, and refreshed before it
+    /// expires. The token is shared by the clones of the backend.
+    ServiceAccount {
+        key: Arc<ServiceAccountKey>,
+        token: Arc<Mutex<Option<AccessToken>>>,
+    },
+}
+
+struct AccessToken {
+    token: String,
+    expires_at: SystemTime,
+}
+
+#[derive(Clone)]
+pub struct Backend {
+    root: String,
+    bucket: String,
+    endpoint: String,
+    auth: Auth,
+
+    client: HttpClient,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    /// The lifetime of the token in seconds.
+    expires_in: u64,
+}
+
+/// The metadata of an object, only the fields we care about are listed.
+#[derive(Deserialize)]
+struct ObjectMeta {
+    name: String,
+    /// The size is encoded as a string in JSON since it's an unsigned 64 bits integer.
+    size: String,
+    updated: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListObjectsResponse {
+    #[serde(default)]
+    items: Vec<ObjectMeta>,
+    #[serde(default)]
+    prefixes: Vec<String>,
+    next_page_token: Option<String>,
+}
+
+impl Backend {
+    pub fn build() -> Builder {
+        Builder::default()
+    }
+
+    /// get_abs_path will return the absolute path of the given path in the gcs format.
+    /// If user input an absolute path, we will return it as it is with the prefix `/` striped.
+    /// If user input a relative path, we will calculate the absolute path with the root.
+    fn get_abs_path(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            return path.strip_prefix('/').unwrap().to_string();
+        }
+        if self.root.is_empty() {
+            return path.to_string();
+        }
+
+        format!("{}/{}", self.root, path)
+    }
+
+    /// The uri of the object, `/` in the name is encoded as well.
+    fn object_uri(&self, name: &str) -> String {
+        format!(
+            "{}/storage/v1/b/{}/o/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode(name)
+        )
+    }
+
+    fn upload_uri(&self, upload_type: &str, name: &str) -> String {
+        format!(
+            "{}/upload/storage/v1/b/{}/o?{}",
+            self.endpoint,
+            self.bucket,
+            query_string(&[
+                ("uploadType", upload_type.to_string()),
+                ("name", name.to_string()),
+            ])
+        )
+    }
+
+    /// The value of the `Authorization` header, None for the anonymous requests.
+    async fn authorization(&self) -> Result<Option<String>> {
+        let token = match &self.auth {
+            Auth::Anonymous => return Ok(None),
+            Auth::Token(token) => token.clone(),
+            Auth::ServiceAccount { key, token } => {
+                // The lock is held during the refresh, so that only one token is requested.
+                let mut token = token.lock().await;
+                let deadline = SystemTime::now() + TOKEN_REFRESH_AHEAD;
+                let fresh = matches!(&*token, Some(t) if t.expires_at > deadline);
+                if !fresh {
+                    *token = Some(self.request_token(key).await?);
+                }
+                // Safety: the token is set above.
+                token.as_ref().unwrap().token.clone()
+            }
+        };
+        Ok(Some(format!("Bearer {}", token)))
+    }
+
+    /// Exchanges a JWT signed by the service account key for an access token.
+    async fn request_token(&self, key: &ServiceAccountKey) -> Result<AccessToken> {
+        let now = SystemTime::now();
+        let iat = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| Error::Unexpected(e.to_string()))?
+            .as_secs();
+        let body = query_string(&[
+            (
+                "grant_type",
+                "urn:ietf:params:oauth:grant-type:jwt-bearer".to_string(),
+            ),
+            ("assertion", key.assertion(TOKEN_SCOPE, iat)?),
+        ]);
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(&key.token_uri)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+        let resp = self.client.request(req).await.map_err(parse_http_error)?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response(&key.token_uri, resp).await);
+        }
+
+        let body = read_body(resp).await?;
+        let resp: TokenResponse =
+            serde_json::from_slice(&body).map_err(|e| Error::Unexpected(e.to_string()))?;
+        Ok(AccessToken {
+            token: resp.access_token,
+            expires_at: now + Duration::from_secs(resp.expires_in),
+        })
+    }
+
+    async fn send(&self, req: http::request::Builder, body: Body) -> Result<Response<Body>> {
+        let req = match self.authorization().await? {
+            Some(authorization) => req.header(AUTHORIZATION, authorization),
+            None => req,
+        };
+        let req = req
+            .body(body)
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+        self.client.request(req).await.map_err(parse_http_error)
+    }
+
+    async fn simple_upload(&self, name: &str, body: Body, size: u64) -> Result<()> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(self.upload_uri("media", name))
+            .header(CONTENT_LENGTH, size);
+        let resp = self.send(req, body).await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::CREATED => Ok(()),
+            _ => Err(parse_error_response(name, resp).await),
+        }
+    }
+
+    /// Initiates a resumable upload, returns the uri of the upload session.
+    async fn start_resumable_upload(&self, name: &str) -> Result<String> {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(self.upload_uri("resumable", name))
+            .header(CONTENT_LENGTH, 0);
+        let resp = self.send(req, Body::empty()).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response(name, resp).await);
+        }
+
+        parse_header::<String>(&resp, LOCATION.as_str()).ok_or_else(|| {
+            Error::Unexpected(format!("no session uri of the resumable upload: {}", name))
+        })
+    }
+
+    /// Uploads the chunks of the reader into the session, the first chunk is read already.
+    ///
+    /// The total size is unknown until the last chunk is read, so we read one chunk ahead to
+    /// tell whether the current one is the last.
+    async fn resumable_upload(
+        &self,
+        name: &str,
+        session: &str,
+        first_chunk: Vec<u8>,
+        mut r: Reader,
+        chunk_size: usize,
+    ) -> Result<usize> {
+        let mut offset = 0;
+        let mut chunk = first_chunk;
+        loop {
+            let next = if chunk.len() < chunk_size {
+                vec![]
+            } else {
+                read_part(&mut r, chunk_size).await?
+            };
+            let is_last = next.is_empty();
+
+            let size = chunk.len();
+            let content_range = match (size, is_last) {
+                // Nothing is left in the chunk, finalize the upload with the total size.
+                (0, _) => format!("bytes */{}", offset),
+                (_, true) => format!("bytes {}-{}/{}", offset, offset + size - 1, offset + size),
+                (_, false) => format!("bytes {}-{}/*", offset, offset + size - 1),
+            };
+            let req = Request::builder()
+                .method(Method::PUT)
+                .uri(session)
+                .header(CONTENT_LENGTH, size)
+                .header(CONTENT_RANGE, content_range);
+            let resp = self.send(req, Body::from(chunk)).await?;
+            offset += size;
+
+            match resp.status() {
+                StatusCode::OK | StatusCode::CREATED if is_last => return Ok(offset),
+                // 308 Resume Incomplete
+                StatusCode::PERMANENT_REDIRECT if !is_last => {}
+                _ => return Err(parse_error_response(name, resp).await),
+            }
+            chunk = next;
+        }
+    }
+
+    async fn cancel_resumable_upload(&self, session: &str) {
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(session)
+            .header(CONTENT_LENGTH, 0);
+        // The session expires in a week anyway, ignore the error of the cancellation.
+        let _ = self.send(req, Body::empty()).await;
+    }
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    async fn read(&self, args: &OpRead) -> Result<Reader> {
+        let p = self.get_abs_path(&args.path);
+
+        let mut req = Request::builder()
+            .method(Method::GET)
+            .uri(format!("{}?alt=media", self.object_uri(&p)));
+        if args.offset.is_some() || args.size.is_some() {
+            req = req.header(RANGE, HeaderRange::new(args.offset, args.size).to_string());
+        }
+
+        let resp = self.send(req, Body::empty()).await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok(reader_from_body(resp.into_body())),
+            _ => Err(parse_error_response(&args.path, resp).await),
+        }
+    }
+
+    async fn write(&self, r: Reader, args: &OpWrite) -> Result<usize> {
+        let p = self.get_abs_path(&args.path);
+
+        self.simple_upload(&p, body_from_reader(r), args.size)
+            .await?;
+        Ok(args.size as usize)
+    }
+
+    /// Uploads the data by a resumable upload in chunks of `part_size`, which is rounded up to
+    /// a multiple of 256 KiB. The data that fits in one chunk is uploaded by a single request
+    /// instead.
+    async fn write_stream(&self, mut r: Reader, args: &OpWriteStream) -> Result<usize> {
+        let p = self.get_abs_path(&args.path);
+
+        let chunk_size = args.part_size.max(1);
+        let chunk_size = (chunk_size + RESUMABLE_CHUNK_ALIGNMENT - 1) / RESUMABLE_CHUNK_ALIGNMENT
+            * RESUMABLE_CHUNK_ALIGNMENT;
+        let chunk = read_part(&mut r, chunk_size).await?;
+        if chunk.len() < chunk_size {
+            let size = chunk.len();
+            self.simple_upload(&p, Body::from(chunk), size as u64)
+                .await?;
+            return Ok(size);
+        }
+
+        let session = self.start_resumable_upload(&p).await?;
+        match self
+            .resumable_upload(&p, &session, chunk, r, chunk_size)
+            .await
+        {
+            Ok(size) => Ok(size),
+            Err(e) => {
+                self.cancel_resumable_upload(&session).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn stat(&self, args: &OpStat) -> Result<Object> {
+        let p = self.get_abs_path(&args.path);
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(self.object_uri(&p));
+        let resp = self.send(req, Body::empty()).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response(&args.path, resp).await);
+        }
+
+        let body = read_body(resp).await?;
+        let meta: ObjectMeta =
+            serde_json::from_slice(&body).map_err(|e| Error::Unexpected(e.to_string()))?;
+
+        let o = Object {
+            path: args.path.to_string(),
+            size: meta.size.parse().unwrap_or_default(),
+            last_modified: meta.updated.as_deref().and_then(parse_rfc3339),
+        };
+
+        Ok(o)
+    }
+
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let p = self.get_abs_path(&args.path);
+
+        let req = Request::builder()
+            .method(Method::DELETE)
+            .uri(self.object_uri(&p));
+        let resp = self.send(req, Body::empty()).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::OK | StatusCode::NOT_FOUND => Ok(()),
+            _ => Err(parse_error_response(&args.path, resp).await),
+        }
+    }
+
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        let mut prefix = self.get_abs_path(&args.path);
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        // The objects are listed with the root, which is stripped from the paths of the objects.
+        let root = if self.root.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.root.trim_end_matches('/'))
+        };
+        let recursive = args.recursive;
+        let page_size = args.page_size;
+        let backend = self.clone();
+
+        // The state is the token of the next page, and None after the last page.
+        let pages =
+            futures::stream::try_unfold(Some(None), move |state: Option<Option<String>>| {
+                let (backend, prefix, root) = (backend.clone(), prefix.clone(), root.clone());
+                async move {
+                    let page_token = match state {
+                        Some(page_token) => page_token,
+                        None => return Ok(None),
+                    };
+
+                    let mut query = vec![
+                        ("prefix", prefix.clone()),
+                        ("maxResults", page_size.to_string()),
+                    ];
+                    if !recursive {
+                        query.push(("delimiter", "/".to_string()));
+                    }
+                    if let Some(page_token) = page_token {
+                        query.push(("pageToken", page_token));
+                    }
+
+                    let req = Request::builder().method(Method::GET).uri(format!(
+                        "{}/storage/v1/b/{}/o?{}",
+                        backend.endpoint,
+                        backend.bucket,
+                        query_string(&query)
+                    ));
+                    let resp = backend.send(req, Body::empty()).await?;
+                    if resp.status() != StatusCode::OK {
+                        return Err(parse_error_response(&prefix, resp).await);
+                    }
+                    let body = read_body(resp).await?;
+                    let output: ListObjectsResponse = serde_json::from_slice(&body)
+                        .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+                    let strip_root =
+                        |path: &str| path.strip_prefix(&root).unwrap_or(path).to_string();
+                    let mut objects = vec![];
+                    for item in output.items {
+                        // Skip the placeholders of the directories.
+                        if item.name.ends_with('/') {
+                            continue;
+                        }
+                        objects.push(Object {
+                            path: strip_root(&item.name),
+                            size: item.size.parse().unwrap_or_default(),
+                            last_modified: item.updated.as_deref().and_then(parse_rfc3339),
+                        });
+                    }
+                    // The prefixes end with the delimiter `/`, as the directories.
+                    for prefix in output.prefixes {
+                        objects.push(Object {
+                            path: strip_root(&prefix),
+                            size: 0,
+                            last_modified: None,
+                        });
+                    }
+
+                    Ok(Some((objects, output.next_page_token.map(Some))))
+                }
+            });
+
+        let objects = pages
+            .map_ok(|objects| futures::stream::iter(objects.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::new(Box::pin(objects)))
+    }
+}
+
+/// Parses the time in the format of RFC 3339, e.g. the `updated` of the objects.
+fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(SystemTime::from)
+}
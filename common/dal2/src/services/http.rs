@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the services talking to the storage through HTTP.
+
+use std::time::SystemTime;
+
+use futures::TryStreamExt;
+use hyper::client::HttpConnector;
+use hyper::Body;
+use hyper::Response;
+use hyper::StatusCode;
+use hyper_rustls::HttpsConnector;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::NON_ALPHANUMERIC;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::readers::ReaderStream;
+use crate::Reader;
+
+pub(crate) type HttpClient = hyper::Client<HttpsConnector<HttpConnector>, Body>;
+
+/// Characters which are kept as they are in the percent encoding, the unreserved characters
+/// of RFC 3986.
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Same as [UNRESERVED], but `/` is kept too, used to encode the paths of the objects.
+const PATH_UNRESERVED: &AsciiSet = &UNRESERVED.remove(b'/');
+
+/// Both `https://` and `http://` (e.g. for the local emulators) endpoints are supported.
+pub(crate) fn new_client() -> HttpClient {
+    hyper::Client::builder().build(HttpsConnector::with_native_roots())
+}
+
+pub(crate) fn percent_encode(s: &str) -> String {
+    utf8_percent_encode(s, UNRESERVED).to_string()
+}
+
+pub(crate) fn percent_encode_path(path: &str) -> String {
+    utf8_percent_encode(path, PATH_UNRESERVED).to_string()
+}
+
+/// Builds the query string, the values are percent encoded.
+pub(crate) fn query_string(query: &[(&str, String)]) -> String {
+    query
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, percent_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+pub(crate) fn body_from_reader(r: Reader) -> Body {
+    Body::wrap_stream(ReaderStream::new(r))
+}
+
+pub(crate) fn reader_from_body(body: Body) -> Reader {
+    Box::new(
+        body.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            .into_async_read(),
+    )
+}
+
+pub(crate) async fn read_body(resp: Response<Body>) -> Result<Vec<u8>> {
+    hyper::body::to_bytes(resp.into_body())
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| Error::Unexpected(e.to_string()))
+}
+
+/// Converts the response of a failed request on `path` into [Error].
+pub(crate) async fn parse_error_response(path: &str, resp: Response<Body>) -> Error {
+    match resp.status() {
+        StatusCode::NOT_FOUND => Error::ObjectNotExist(path.to_string()),
+        StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
+            Error::PermissionDenied(path.to_string())
+        }
+        status => {
+            let body = read_body(resp).await.unwrap_or_default();
+            Error::Unexpected(format!(
+                "status {}, {}",
+                status,
+                String::from_utf8_lossy(&body)
+            ))
+        }
+    }
+}
+
+pub(crate) fn parse_http_error(e: hyper::Error) -> Error {
+    Error::Unexpected(e.to_string())
+}
+
+/// Parses the header of the response, None if it's absent or malformed.
+pub(crate) fn parse_header<T: std::str::FromStr>(resp: &Response<Body>, name: &str) -> Option<T> {
+    resp.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// Parses the time in the format of RFC 1123, e.g. the `Last-Modified` header.
+pub(crate) fn parse_http_date(s: &str) -> Option<SystemTime> {
+    httpdate::parse_http_date(s).ok()
+}
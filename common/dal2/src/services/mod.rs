@@ -18,5 +18,8 @@
 ///
 /// - Builder: responsible for building the service backend.
 /// - Backend: the service backend which implements the `Accessor` trait.
+pub mod azblob;
 pub mod fs;
+pub mod gcs;
+mod http;
 pub mod s3;
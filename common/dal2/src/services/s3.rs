@@ -18,6 +18,9 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use async_trait::async_trait;
 use aws_sdk_s3 as AwsS3;
@@ -30,14 +33,21 @@ use crate::error::Error;
 use crate::error::Result;
 use crate::ops::HeaderRange;
 use crate::ops::OpDelete;
+use crate::ops::OpList;
 use crate::ops::OpRead;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteStream;
+use crate::readers::read_part;
 use crate::readers::ReaderStream;
 use crate::Accessor;
 use crate::Object;
+use crate::ObjectStream;
 use crate::Reader;
 
+/// The min size of the parts of a multipart upload except the last one, 5 MiB.
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
 /// # TODO
 ///
 /// enable_path_style and enable_signature_v2 need sdk support.
@@ -229,6 +239,61 @@ impl Accessor for Backend {
         Ok(args.size as usize)
     }
 
+    /// Uploads the data by the multipart upload, a part is uploaded once `part_size` bytes, which
+    /// is raised to 5 MiB if it's smaller, are read. The data that fits in one part is uploaded by
+    /// a single `PutObject` instead.
+    async fn write_stream(&self, mut r: Reader, args: &OpWriteStream) -> Result<usize> {
+        let p = self.get_abs_path(&args.path);
+        // The smaller parts are rejected by s3 when the upload is completed.
+        let part_size = args.part_size.max(MIN_PART_SIZE);
+
+        let part = read_part(&mut r, part_size).await?;
+        if part.len() < part_size {
+            let size = part.len();
+            self.client
+                .put_object()
+                .bucket(&self.bucket.clone())
+                .key(&p)
+                .content_length(size as i64)
+                .body(ByteStream::from(part))
+                .send()
+                .await
+                .map_err(|e| Error::Unexpected(e.to_string()))?;
+            return Ok(size);
+        }
+
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket.clone())
+            .key(&p)
+            .send()
+            .await
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+        let upload_id = upload.upload_id.ok_or_else(|| {
+            Error::Unexpected(format!(
+                "no upload id of the multipart upload: (path {})",
+                p
+            ))
+        })?;
+
+        match self.upload_parts(&p, &upload_id, part, r, part_size).await {
+            Ok(size) => Ok(size),
+            Err(e) => {
+                // The uploaded parts are charged until the upload is aborted.
+                let _ = self
+                    .client
+                    .abort_multipart_upload()
+                    .bucket(&self.bucket.clone())
+                    .key(&p)
+                    .upload_id(&upload_id)
+                    .send()
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
     async fn stat(&self, args: &OpStat) -> Result<Object> {
         let p = self.get_abs_path(&args.path);
 
@@ -243,6 +308,7 @@ impl Accessor for Backend {
         let o = Object {
             path: args.path.to_string(),
             size: meta.content_length as u64,
+            last_modified: meta.last_modified.map(to_system_time),
         };
 
         Ok(o)
@@ -262,6 +328,159 @@ impl Accessor for Backend {
 
         Ok(())
     }
+
+    async fn list(&self, args: &OpList) -> Result<ObjectStream> {
+        let mut prefix = self.get_abs_path(&args.path);
+        if !prefix.is_empty() && !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+        // The keys are listed with the root, which is stripped from the paths of the objects.
+        let root = if self.root.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.root.trim_end_matches('/'))
+        };
+        let delimiter = if args.recursive {
+            None
+        } else {
+            Some("/".to_string())
+        };
+        let page_size = args.page_size as i32;
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+
+        // The state is the continuation token of the next page, and None after the last page.
+        let pages =
+            futures::stream::try_unfold(Some(None), move |state: Option<Option<String>>| {
+                let (client, bucket, prefix, root, delimiter) = (
+                    client.clone(),
+                    bucket.clone(),
+                    prefix.clone(),
+                    root.clone(),
+                    delimiter.clone(),
+                );
+                async move {
+                    let token = match state {
+                        Some(token) => token,
+                        None => return Ok(None),
+                    };
+
+                    let resp = client
+                        .list_objects_v2()
+                        .bucket(&bucket)
+                        .prefix(&prefix)
+                        .set_delimiter(delimiter)
+                        .max_keys(page_size)
+                        .set_continuation_token(token)
+                        .send()
+                        .await
+                        .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+                    let strip_root = |key: &str| key.strip_prefix(&root).unwrap_or(key).to_string();
+                    let mut objects = vec![];
+                    for common_prefix in resp.common_prefixes.unwrap_or_default() {
+                        if let Some(dir) = common_prefix.prefix {
+                            objects.push(Object {
+                                path: strip_root(&dir),
+                                size: 0,
+                                last_modified: None,
+                            });
+                        }
+                    }
+                    for object in resp.contents.unwrap_or_default() {
+                        match object.key {
+                            // Skip the placeholders of the directories.
+                            Some(key) if !key.ends_with('/') => objects.push(Object {
+                                path: strip_root(&key),
+                                size: object.size as u64,
+                                last_modified: object.last_modified.map(to_system_time),
+                            }),
+                            _ => {}
+                        }
+                    }
+
+                    let next = if resp.is_truncated {
+                        resp.next_continuation_token.map(Some)
+                    } else {
+                        None
+                    };
+                    Ok(Some((objects, next)))
+                }
+            });
+
+        let objects = pages
+            .map_ok(|objects| futures::stream::iter(objects.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(Box::new(Box::pin(objects)))
+    }
+}
+
+impl Backend {
+    /// Uploads the first part and the rest data of the reader, then completes the upload.
+    async fn upload_parts(
+        &self,
+        path: &str,
+        upload_id: &str,
+        first_part: Vec<u8>,
+        mut r: Reader,
+        part_size: usize,
+    ) -> Result<usize> {
+        let mut completed_parts = vec![];
+        let mut size = 0;
+        let mut part = first_part;
+        // The number of parts starts from 1.
+        let mut part_number = 1;
+        while !part.is_empty() {
+            let len = part.len();
+            let resp = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket.clone())
+                .key(path)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .content_length(len as i64)
+                .body(ByteStream::from(part))
+                .send()
+                .await
+                .map_err(|e| Error::Unexpected(e.to_string()))?;
+            completed_parts.push(
+                AwsS3::model::CompletedPart::builder()
+                    .set_e_tag(resp.e_tag)
+                    .part_number(part_number)
+                    .build(),
+            );
+            size += len;
+            part_number += 1;
+
+            // The last part is the one smaller than `part_size`.
+            if len < part_size {
+                break;
+            }
+            part = read_part(&mut r, part_size).await?;
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket.clone())
+            .key(path)
+            .upload_id(upload_id)
+            .multipart_upload(
+                AwsS3::model::CompletedMultipartUpload::builder()
+                    .set_parts(Some(completed_parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+        Ok(size)
+    }
+}
+
+fn to_system_time(t: AwsS3::DateTime) -> SystemTime {
+    let since_epoch = Duration::new(t.secs().max(0) as u64, t.subsec_nanos());
+    UNIX_EPOCH + since_epoch
 }
 
 struct S3Stream(aws_smithy_http::byte_stream::ByteStream);
@@ -0,0 +1,227 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_dal2::credential::Credential;
+use common_dal2::services::azblob;
+use common_dal2::Operator;
+use futures::io::AsyncReadExt;
+use futures::io::Cursor;
+use futures::TryStreamExt;
+
+/// The well-known account and key of Azurite, the emulator of Azure Storage.
+const AZURITE_ACCOUNT: &str = "devstoreaccount1";
+const AZURITE_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+const AZURITE_ENDPOINT: &str = "http://127.0.0.1:10000/devstoreaccount1";
+
+#[tokio::test]
+async fn builder() {
+    let mut builder = azblob::Backend::build();
+
+    let _ = builder
+        .root("/path-to-file")
+        .container("test-container")
+        .credential(Credential::hmac(AZURITE_ACCOUNT, AZURITE_KEY))
+        .endpoint(AZURITE_ENDPOINT)
+        .finish()
+        .await
+        .unwrap();
+
+    // The endpoint of Azure is derived from the account.
+    let _ = azblob::Backend::build()
+        .container("test-container")
+        .credential(Credential::hmac(AZURITE_ACCOUNT, AZURITE_KEY))
+        .finish()
+        .await
+        .unwrap();
+
+    // A SAS works with the endpoint only.
+    let _ = azblob::Backend::build()
+        .container("test-container")
+        .credential(Credential::token("?sv=2019-12-12&sig=signature"))
+        .endpoint(AZURITE_ENDPOINT)
+        .finish()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn builder_invalid() {
+    // container is required.
+    let r = azblob::Backend::build()
+        .credential(Credential::hmac(AZURITE_ACCOUNT, AZURITE_KEY))
+        .finish()
+        .await;
+    assert!(r.is_err());
+
+    // The account key must be encoded in base64.
+    let r = azblob::Backend::build()
+        .container("test-container")
+        .credential(Credential::hmac(AZURITE_ACCOUNT, "not base64!"))
+        .finish()
+        .await;
+    assert!(r.is_err());
+
+    // The endpoint can't be derived without the account.
+    let r = azblob::Backend::build()
+        .container("test-container")
+        .finish()
+        .await;
+    assert!(r.is_err());
+}
+
+async fn new_operator() -> Operator {
+    // The container `testcontainer` must be created in Azurite before running the tests.
+    let da = azblob::Backend::build()
+        .root("tests")
+        .container("testcontainer")
+        .credential(Credential::hmac(AZURITE_ACCOUNT, AZURITE_KEY))
+        .endpoint(AZURITE_ENDPOINT)
+        .finish()
+        .await
+        .unwrap();
+
+    Operator::new(da)
+}
+
+#[tokio::test]
+// TODO: we need to add an integration test for it.
+#[ignore]
+async fn test_write_read() {
+    let op = new_operator().await;
+
+    let content = b"hello, azblob".to_vec();
+    let size = op
+        .write("data/write_read", content.len() as u64)
+        .run(Box::new(Cursor::new(content.clone())))
+        .await
+        .unwrap();
+    assert_eq!(content.len(), size);
+
+    let mut r = op.read("data/write_read").run().await.unwrap();
+    let mut buf: Vec<u8> = Vec::new();
+    r.read_to_end(&mut buf).await.unwrap();
+    assert_eq!(content, buf);
+
+    let mut r = op
+        .read("data/write_read")
+        .offset(7)
+        .size(6)
+        .run()
+        .await
+        .unwrap();
+    let mut buf: Vec<u8> = Vec::new();
+    r.read_to_end(&mut buf).await.unwrap();
+    assert_eq!(b"azblob".to_vec(), buf);
+
+    let o = op.stat("data/write_read").run().await.unwrap();
+    assert_eq!(content.len() as u64, o.size);
+    assert!(o.last_modified.is_some());
+
+    op.delete("data/write_read").run().await.unwrap();
+    assert!(op.stat("data/write_read").run().await.is_err());
+}
+
+#[tokio::test]
+// TODO: we need to add an integration test for it.
+#[ignore]
+async fn test_write_stream() {
+    let op = new_operator().await;
+
+    // 3 blocks are uploaded and committed.
+    let part_size = 1024 * 1024;
+    let content = vec![1u8; part_size * 2 + 1024];
+    let size = op
+        .write_stream("data/write_stream")
+        .part_size(part_size)
+        .run(Box::new(Cursor::new(content.clone())))
+        .await
+        .unwrap();
+    assert_eq!(content.len(), size);
+
+    let o = op.stat("data/write_stream").run().await.unwrap();
+    assert_eq!(content.len() as u64, o.size);
+
+    // A zero part size uploads blocks of one byte.
+    let content = b"abc".to_vec();
+    let size = op
+        .write_stream("data/write_stream")
+        .part_size(0)
+        .run(Box::new(Cursor::new(content.clone())))
+        .await
+        .unwrap();
+    assert_eq!(content.len(), size);
+
+    let o = op.stat("data/write_stream").run().await.unwrap();
+    assert_eq!(content.len() as u64, o.size);
+
+    op.delete("data/write_stream").run().await.unwrap();
+}
+
+#[tokio::test]
+// TODO: we need to add an integration test for it.
+#[ignore]
+async fn test_list() {
+    let op = new_operator().await;
+
+    for path in ["data/list/a", "data/list/b", "data/list/dir/c"] {
+        op.write(path, 1)
+            .run(Box::new(Cursor::new(vec![1u8])))
+            .await
+            .unwrap();
+    }
+
+    // A small page size makes the listing fetch several pages.
+    let mut objects = op
+        .list("data/list")
+        .recursive(true)
+        .page_size(2)
+        .run()
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|o| o.path)
+        .collect::<Vec<_>>();
+    objects.sort();
+    assert_eq!(objects, vec![
+        "data/list/a",
+        "data/list/b",
+        "data/list/dir/c"
+    ]);
+
+    let mut objects = op
+        .list("data/list")
+        .run()
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|o| o.path)
+        .collect::<Vec<_>>();
+    objects.sort();
+    assert_eq!(objects, vec![
+        "data/list/a",
+        "data/list/b",
+        "data/list/dir/"
+    ]);
+
+    for path in ["data/list/a", "data/list/b", "data/list/dir/c"] {
+        op.delete(path).run().await.unwrap();
+    }
+}
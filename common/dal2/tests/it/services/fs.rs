@@ -18,6 +18,7 @@ use common_dal2::services::fs;
 use common_dal2::Operator;
 use futures::io::AsyncReadExt;
 use futures::io::Cursor;
+use futures::TryStreamExt;
 
 #[tokio::test]
 async fn normal() {
@@ -46,3 +47,130 @@ async fn normal() {
     // Test delete
     f.delete(&path).run().await.unwrap();
 }
+
+#[tokio::test]
+async fn write_stream() {
+    let f = Operator::new(fs::Backend::build().finish().await.unwrap());
+
+    let path = format!("/tmp/{}", uuid::Uuid::new_v4());
+
+    // The size is unknown in advance, the parts are appended as they arrive.
+    let content = "Hello, world!".repeat(1024);
+    let x = f
+        .write_stream(&path)
+        .part_size(1000)
+        .run(Box::new(Cursor::new(content.clone())))
+        .await
+        .unwrap();
+    assert_eq!(content.len(), x);
+
+    // Overwrite with a shorter content.
+    let x = f
+        .write_stream(&path)
+        .run(Box::new(Cursor::new("Hello, world!")))
+        .await
+        .unwrap();
+    assert_eq!(13, x);
+
+    let mut buf: Vec<u8> = Vec::new();
+    let mut x = f.read(&path).run().await.unwrap();
+    x.read_to_end(&mut buf).await.unwrap();
+    assert_eq!("Hello, world!", str::from_utf8(&buf).unwrap());
+
+    f.delete(&path).run().await.unwrap();
+}
+
+#[tokio::test]
+async fn list() {
+    let root = format!("/tmp/{}", uuid::Uuid::new_v4());
+    let f = Operator::new(fs::Backend::build().root(&root).finish().await.unwrap());
+
+    for path in ["a.csv", "dir/b.csv", "dir/sub/c.csv"] {
+        f.write(path, 13)
+            .run(Box::new(Cursor::new("Hello, world!")))
+            .await
+            .unwrap();
+    }
+
+    let list = |path: &'static str, recursive: bool| {
+        let f = f.clone();
+        async move {
+            let mut paths = f
+                .list(path)
+                .recursive(recursive)
+                .run()
+                .await
+                .unwrap()
+                .map_ok(|o| o.path)
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            paths.sort();
+            paths
+        }
+    };
+
+    // The direct children, sub-directories are listed with the trailing `/`.
+    assert_eq!(list("", false).await, vec!["a.csv", "dir/"]);
+    assert_eq!(list("dir", false).await, vec!["dir/b.csv", "dir/sub/"]);
+
+    // All the objects under the directory.
+    assert_eq!(list("", true).await, vec![
+        "a.csv",
+        "dir/b.csv",
+        "dir/sub/c.csv"
+    ]);
+    assert_eq!(list("dir/sub", true).await, vec!["dir/sub/c.csv"]);
+
+    // Listing the directory which does not exist returns nothing.
+    assert!(list("not_exist", true).await.is_empty());
+
+    // Files are listed with the last modified time, directories are not.
+    let objects = f
+        .list("dir")
+        .run()
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    for o in objects {
+        assert_eq!(o.is_dir(), o.last_modified.is_none(), "{}", o.path);
+    }
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[tokio::test]
+async fn write_stream_failed() {
+    let dir = format!("/tmp/{}", uuid::Uuid::new_v4());
+    let f = Operator::new(fs::Backend::build().root(&dir).finish().await.unwrap());
+
+    f.write_stream("data")
+        .run(Box::new(Cursor::new("Hello, world!")))
+        .await
+        .unwrap();
+
+    // The reader fails after some data is read.
+    let chunks = futures::stream::iter(vec![
+        Ok(b"Hello, databend!".to_vec()),
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "broken")),
+    ]);
+    let r = f
+        .write_stream("data")
+        .run(Box::new(chunks.into_async_read()))
+        .await;
+    assert!(r.is_err());
+
+    // The file is kept as it was, and the temporary file is removed.
+    let mut buf: Vec<u8> = Vec::new();
+    let mut x = f.read("data").run().await.unwrap();
+    x.read_to_end(&mut buf).await.unwrap();
+    assert_eq!("Hello, world!", str::from_utf8(&buf).unwrap());
+
+    let mut entries = std::fs::read_dir(&dir).unwrap();
+    assert_eq!("data", entries.next().unwrap().unwrap().file_name());
+    assert!(entries.next().is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -11,5 +11,7 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+mod azblob;
 mod fs;
+mod gcs;
 mod s3;
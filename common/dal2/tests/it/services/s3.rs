@@ -15,6 +15,8 @@ use common_dal2::credential::Credential;
 use common_dal2::services::s3;
 use common_dal2::Operator;
 use futures::io::AsyncReadExt;
+use futures::io::Cursor;
+use futures::TryStreamExt;
 
 #[tokio::test]
 async fn builder() {
@@ -59,3 +61,70 @@ async fn test_read() {
     let mut buf: Vec<u8> = Vec::new();
     r.read_to_end(&mut buf).await.unwrap();
 }
+
+#[tokio::test]
+// TODO: we need to add an integration test for it.
+#[ignore]
+async fn test_write_stream() {
+    let mut builder = s3::Backend::build();
+
+    let da = builder
+        .root("tests")
+        .bucket("testbucket")
+        .region("us-east-1")
+        .credential(Credential::hmac("minioadmin", "minioadmin"))
+        .endpoint("http://localhost:9900")
+        .finish()
+        .await
+        .unwrap();
+
+    let op = Operator::new(da);
+
+    // 3 parts are uploaded, the minimal size of the parts except the last one is 5 MiB.
+    let part_size = 5 * 1024 * 1024;
+    let content = vec![1u8; part_size * 2 + 1024];
+    let size = op
+        .write_stream("data/write_stream")
+        .part_size(part_size)
+        .run(Box::new(Cursor::new(content.clone())))
+        .await
+        .unwrap();
+    assert_eq!(content.len(), size);
+
+    let o = op.stat("data/write_stream").run().await.unwrap();
+    assert_eq!(content.len() as u64, o.size);
+
+    op.delete("data/write_stream").run().await.unwrap();
+}
+
+#[tokio::test]
+// TODO: we need to add an integration test for it.
+#[ignore]
+async fn test_list() {
+    let mut builder = s3::Backend::build();
+
+    let da = builder
+        .root("tests")
+        .bucket("testbucket")
+        .region("us-east-1")
+        .credential(Credential::hmac("minioadmin", "minioadmin"))
+        .endpoint("http://localhost:9900")
+        .finish()
+        .await
+        .unwrap();
+
+    let op = Operator::new(da);
+
+    // A small page size makes the listing fetch several pages.
+    let objects = op
+        .list("data")
+        .recursive(true)
+        .page_size(2)
+        .run()
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert!(objects.iter().any(|o| o.path == "data/sample.csv"));
+}
@@ -135,6 +135,9 @@ build_exceptions! {
     // Network error codes.
     NetworkRequestError(1073),
 
+    // Memory error codes.
+    MemoryLimitExceeded(1074),
+
     // Tenant error codes.
     TenantIsEmpty(1101),
 }
@@ -189,6 +192,8 @@ build_exceptions! {
     DalTransportError(3003),
     DalPathNotFound(3004),
     SerdeError(3005),
+    TableHistoricalDataNotFound(3006),
+    StorageQuotaExceeded(3007),
 }
 
 // Cache errors [4001, 5000].
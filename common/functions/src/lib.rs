@@ -16,12 +16,16 @@
 
 pub mod aggregates;
 pub mod scalars;
+pub mod windows;
 
 mod macros;
 
 use aggregates::AggregateFunctionFactory;
 use scalars::Function2Factory;
+use windows::WindowFunctionFactory;
 
 pub fn is_builtin_function(name: &str) -> bool {
-    Function2Factory::instance().check(name) || AggregateFunctionFactory::instance().check(name)
+    Function2Factory::instance().check(name)
+        || AggregateFunctionFactory::instance().check(name)
+        || WindowFunctionFactory::instance().check(name)
 }
@@ -0,0 +1,33 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod window_aggregate;
+mod window_frame;
+mod window_function;
+mod window_function_factory;
+mod window_lag_lead;
+mod window_rank;
+
+pub use window_aggregate::WindowAggregateFunction;
+pub use window_frame::WindowFrame;
+pub use window_frame::WindowFrameBound;
+pub use window_frame::WindowFrameUnits;
+pub use window_frame::WindowPartition;
+pub use window_function::WindowFunction;
+pub use window_function::WindowFunctionRef;
+pub use window_function_factory::WindowFunctionCreator;
+pub use window_function_factory::WindowFunctionDescription;
+pub use window_function_factory::WindowFunctionFactory;
+pub use window_lag_lead::WindowLagLeadFunction;
+pub use window_rank::WindowRankFunction;
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+
+use super::WindowFrame;
+use super::WindowFunction;
+use super::WindowFunctionRef;
+use super::WindowPartition;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::StateAddr;
+
+/// An aggregate function evaluated over the frame of each row.
+pub struct WindowAggregateFunction {
+    display_name: String,
+    nested: AggregateFunctionRef,
+    frame: WindowFrame,
+}
+
+impl WindowAggregateFunction {
+    pub fn try_create(
+        display_name: &str,
+        nested: AggregateFunctionRef,
+        frame: WindowFrame,
+    ) -> Result<WindowFunctionRef> {
+        Ok(Arc::new(WindowAggregateFunction {
+            display_name: display_name.to_string(),
+            nested,
+            frame,
+        }))
+    }
+}
+
+impl WindowFunction for WindowAggregateFunction {
+    fn name(&self) -> &str {
+        "WindowAggregateFunction"
+    }
+
+    fn return_type(&self) -> Result<DataTypePtr> {
+        self.nested.return_type()
+    }
+
+    fn eval_partition(
+        &self,
+        partition: &WindowPartition,
+        array: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let arena = Bump::new();
+        let layout = self.nested.state_layout();
+        let columns = partition.columns;
+
+        // The frames which start from the first row of the partition only grow with the
+        // current row, so the state is accumulated incrementally.
+        let mut running: Option<(StateAddr, usize)> = None;
+        if self.frame.is_start_unbounded() {
+            let place: StateAddr = arena.alloc_layout(layout).into();
+            self.nested.init_state(place);
+            running = Some((place, partition.rows.start));
+        }
+
+        for peer in &partition.peers {
+            for row in peer.clone() {
+                let frame = self.frame.frame_rows(partition, row, peer)?;
+
+                match &mut running {
+                    Some((place, accumulated)) => {
+                        while *accumulated < frame.end {
+                            self.nested.accumulate_row(*place, columns, *accumulated)?;
+                            *accumulated += 1;
+                        }
+                        self.nested.merge_result(*place, array)?;
+                    }
+                    None => {
+                        let place: StateAddr = arena.alloc_layout(layout).into();
+                        self.nested.init_state(place);
+                        for frame_row in frame {
+                            self.nested.accumulate_row(place, columns, frame_row)?;
+                        }
+                        self.nested.merge_result(place, array)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for WindowAggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::ops::Range;
+
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+/// The bound of a window frame, `None` offset means UNBOUNDED.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WindowFrameBound {
+    CurrentRow,
+    Preceding(Option<u64>),
+    Following(Option<u64>),
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    pub end_bound: WindowFrameBound,
+}
+
+/// The rows of one partition of the input, which is sorted by the PARTITION BY and ORDER BY keys.
+pub struct WindowPartition<'a> {
+    /// The argument columns of the whole input.
+    pub columns: &'a [ColumnRef],
+    /// The first ORDER BY column and whether it is ascending, used by RANGE frames with offset.
+    pub order_by: Option<(&'a ColumnRef, bool)>,
+    /// The rows of the partition.
+    pub rows: Range<usize>,
+    /// The rows which are equal on the ORDER BY keys(peers), in order.
+    pub peers: Vec<Range<usize>>,
+}
+
+impl WindowFrame {
+    pub fn create(
+        units: WindowFrameUnits,
+        start_bound: WindowFrameBound,
+        end_bound: WindowFrameBound,
+    ) -> Result<WindowFrame> {
+        if matches!(start_bound, WindowFrameBound::Following(None)) {
+            return Err(ErrorCode::SyntaxException(
+                "Window frame start cannot be UNBOUNDED FOLLOWING",
+            ));
+        }
+
+        if matches!(end_bound, WindowFrameBound::Preceding(None)) {
+            return Err(ErrorCode::SyntaxException(
+                "Window frame end cannot be UNBOUNDED PRECEDING",
+            ));
+        }
+
+        Ok(WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        })
+    }
+
+    pub fn is_start_unbounded(&self) -> bool {
+        matches!(self.start_bound, WindowFrameBound::Preceding(None))
+    }
+
+    /// Returns the rows of the frame of `row`, `peer` is the range of the peers of `row`.
+    pub fn frame_rows(
+        &self,
+        partition: &WindowPartition,
+        row: usize,
+        peer: &Range<usize>,
+    ) -> Result<Range<usize>> {
+        let rows = &partition.rows;
+        let (start, end) = match self.units {
+            WindowFrameUnits::Rows => (
+                Self::rows_bound(&self.start_bound, rows, row),
+                Self::rows_bound(&self.end_bound, rows, row + 1),
+            ),
+            WindowFrameUnits::Range => (
+                Self::range_bound(&self.start_bound, partition, row, peer, true)?,
+                Self::range_bound(&self.end_bound, partition, row, peer, false)?,
+            ),
+        };
+
+        let start = start.clamp(rows.start, rows.end);
+        let end = end.clamp(start, rows.end);
+        Ok(start..end)
+    }
+
+    // `current` is the row for the start bound and the next row for the end bound.
+    fn rows_bound(bound: &WindowFrameBound, rows: &Range<usize>, current: usize) -> usize {
+        match bound {
+            WindowFrameBound::CurrentRow => current,
+            WindowFrameBound::Preceding(None) => rows.start,
+            WindowFrameBound::Preceding(Some(n)) => current.saturating_sub(*n as usize),
+            WindowFrameBound::Following(None) => rows.end,
+            WindowFrameBound::Following(Some(n)) => current.saturating_add(*n as usize),
+        }
+    }
+
+    fn range_bound(
+        bound: &WindowFrameBound,
+        partition: &WindowPartition,
+        row: usize,
+        peer: &Range<usize>,
+        is_start: bool,
+    ) -> Result<usize> {
+        let offset = match bound {
+            WindowFrameBound::Preceding(None) => return Ok(partition.rows.start),
+            WindowFrameBound::Following(None) => return Ok(partition.rows.end),
+            WindowFrameBound::CurrentRow => 0.0,
+            WindowFrameBound::Preceding(Some(n)) => -(*n as f64),
+            WindowFrameBound::Following(Some(n)) => *n as f64,
+        };
+
+        if offset == 0.0 {
+            return Ok(if is_start { peer.start } else { peer.end });
+        }
+
+        let (column, asc) = match partition.order_by {
+            Some(order_by) => order_by,
+            None => {
+                return Err(ErrorCode::BadArguments(
+                    "RANGE frame with offset requires exactly one ORDER BY expression",
+                ));
+            }
+        };
+
+        // The rows whose key is NULL are peers of each other.
+        if column.null_at(row) {
+            return Ok(if is_start { peer.start } else { peer.end });
+        }
+
+        // The keys are sorted, we negate them in descending order to search in ascending order.
+        let key = |row: usize| -> Result<f64> {
+            let value = column.get(row).as_f64()?;
+            Ok(if asc { value } else { -value })
+        };
+        let target = key(row)? + offset;
+
+        // NULLs are sorted to the beginning or the end of the partition as a group of peers.
+        let mut low = partition.rows.start;
+        let mut high = partition.rows.end;
+        if let Some(first) = partition.peers.first() {
+            if column.null_at(first.start) {
+                low = first.end;
+            }
+        }
+        if let Some(last) = partition.peers.last() {
+            if column.null_at(last.start) {
+                high = last.start;
+            }
+        }
+
+        // Binary search the first row whose key >= target(start) or > target(end).
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let value = key(middle)?;
+            let before = match is_start {
+                true => value < target,
+                false => value <= target,
+            };
+
+            match before {
+                true => low = middle + 1,
+                false => high = middle,
+            }
+        }
+
+        Ok(low)
+    }
+}
+
+impl Default for WindowFrame {
+    /// RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, which is the frame of
+    /// all the rows of the partition if there is no ORDER BY.
+    fn default() -> Self {
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start_bound: WindowFrameBound::Preceding(None),
+            end_bound: WindowFrameBound::CurrentRow,
+        }
+    }
+}
+
+impl fmt::Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => write!(f, "rows"),
+            WindowFrameUnits::Range => write!(f, "range"),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "current row"),
+            WindowFrameBound::Preceding(None) => write!(f, "unbounded preceding"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{} preceding", n),
+            WindowFrameBound::Following(None) => write!(f, "unbounded following"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{} following", n),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} between {} and {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+
+use super::WindowPartition;
+
+pub type WindowFunctionRef = Arc<dyn WindowFunction>;
+
+/// WindowFunction
+/// A window function is evaluated for each row of the partitions, the columns of the
+/// partition are full columns sorted by the PARTITION BY and ORDER BY keys.
+pub trait WindowFunction: fmt::Display + Sync + Send {
+    fn name(&self) -> &str;
+    fn return_type(&self) -> Result<DataTypePtr>;
+
+    // append one value for each row of the partition into the column builder
+    fn eval_partition(
+        &self,
+        partition: &WindowPartition,
+        array: &mut dyn MutableColumn,
+    ) -> Result<()>;
+}
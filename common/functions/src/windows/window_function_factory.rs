@@ -0,0 +1,112 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use once_cell::sync::Lazy;
+
+use super::WindowAggregateFunction;
+use super::WindowFrame;
+use super::WindowFunctionRef;
+use super::WindowLagLeadFunction;
+use super::WindowRankFunction;
+use crate::aggregates::AggregateFunctionFactory;
+
+pub type WindowFunctionCreator =
+    Box<dyn Fn(&str, Vec<DataValue>, Vec<DataField>) -> Result<WindowFunctionRef> + Sync + Send>;
+
+static FACTORY: Lazy<Arc<WindowFunctionFactory>> = Lazy::new(|| {
+    let mut factory = WindowFunctionFactory::create();
+    // DatabendQuery always uses lowercase function names to get functions.
+    factory.register("row_number", WindowRankFunction::row_number_desc());
+    factory.register("rank", WindowRankFunction::rank_desc());
+    factory.register("dense_rank", WindowRankFunction::dense_rank_desc());
+    factory.register("lag", WindowLagLeadFunction::lag_desc());
+    factory.register("lead", WindowLagLeadFunction::lead_desc());
+    Arc::new(factory)
+});
+
+pub struct WindowFunctionDescription {
+    window_function_creator: WindowFunctionCreator,
+}
+
+impl WindowFunctionDescription {
+    pub fn creator(creator: WindowFunctionCreator) -> WindowFunctionDescription {
+        WindowFunctionDescription {
+            window_function_creator: creator,
+        }
+    }
+}
+
+/// The window functions, and the aggregate functions which are evaluated over the window frames.
+pub struct WindowFunctionFactory {
+    case_insensitive_desc: HashMap<String, WindowFunctionDescription>,
+}
+
+impl WindowFunctionFactory {
+    fn create() -> WindowFunctionFactory {
+        WindowFunctionFactory {
+            case_insensitive_desc: Default::default(),
+        }
+    }
+
+    pub fn instance() -> &'static WindowFunctionFactory {
+        FACTORY.as_ref()
+    }
+
+    pub fn register(&mut self, name: &str, desc: WindowFunctionDescription) {
+        let case_insensitive_desc = &mut self.case_insensitive_desc;
+        case_insensitive_desc.insert(name.to_lowercase(), desc);
+    }
+
+    /// The frame is only used by the aggregate functions, the other window functions
+    /// are evaluated on the whole partition.
+    pub fn get(
+        &self,
+        name: impl AsRef<str>,
+        params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+        frame: WindowFrame,
+    ) -> Result<WindowFunctionRef> {
+        let name = name.as_ref();
+        if let Some(desc) = self.case_insensitive_desc.get(&name.to_lowercase()) {
+            return (desc.window_function_creator)(name, params, arguments);
+        }
+
+        let aggregate_factory = AggregateFunctionFactory::instance();
+        if aggregate_factory.check(name) {
+            let nested = aggregate_factory.get(name, params, arguments)?;
+            return WindowAggregateFunction::try_create(name, nested, frame);
+        }
+
+        Err(ErrorCode::UnknownFunction(format!(
+            "Unsupported WindowFunction: {}",
+            name
+        )))
+    }
+
+    /// Whether the function can only be used as a window function.
+    pub fn check(&self, name: impl AsRef<str>) -> bool {
+        let lowercase_name = name.as_ref().to_lowercase();
+        self.case_insensitive_desc.contains_key(&lowercase_name)
+    }
+
+    pub fn registered_names(&self) -> Vec<String> {
+        self.case_insensitive_desc.keys().cloned().collect()
+    }
+}
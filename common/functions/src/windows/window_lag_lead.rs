@@ -0,0 +1,114 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+
+use super::WindowFunction;
+use super::WindowFunctionDescription;
+use super::WindowFunctionRef;
+use super::WindowPartition;
+use crate::aggregates::assert_variadic_arguments;
+
+/// lag(expr [, offset [, default]]) and lead(expr [, offset [, default]]), the value of
+/// the row which is `offset`(default 1) rows before or after the current row in its partition,
+/// or `default`(default NULL) if there is no such row.
+#[derive(Clone)]
+pub struct WindowLagLeadFunction {
+    display_name: String,
+    is_lead: bool,
+    arguments: Vec<DataField>,
+}
+
+impl WindowLagLeadFunction {
+    fn try_create(
+        display_name: &str,
+        is_lead: bool,
+        arguments: Vec<DataField>,
+    ) -> Result<WindowFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (1, 3))?;
+        Ok(Arc::new(WindowLagLeadFunction {
+            display_name: display_name.to_string(),
+            is_lead,
+            arguments,
+        }))
+    }
+
+    pub fn lag_desc() -> WindowFunctionDescription {
+        WindowFunctionDescription::creator(Box::new(|name, _params, arguments| {
+            Self::try_create(name, false, arguments)
+        }))
+    }
+
+    pub fn lead_desc() -> WindowFunctionDescription {
+        WindowFunctionDescription::creator(Box::new(|name, _params, arguments| {
+            Self::try_create(name, true, arguments)
+        }))
+    }
+}
+
+impl WindowFunction for WindowLagLeadFunction {
+    fn name(&self) -> &str {
+        "WindowLagLeadFunction"
+    }
+
+    fn return_type(&self) -> Result<DataTypePtr> {
+        Ok(wrap_nullable(self.arguments[0].data_type()))
+    }
+
+    fn eval_partition(
+        &self,
+        partition: &WindowPartition,
+        array: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let columns = partition.columns;
+        let rows = &partition.rows;
+
+        for row in rows.clone() {
+            let offset = match columns.get(1) {
+                None => 1,
+                Some(column) => column.get(row).as_u64()? as usize,
+            };
+
+            let target = match self.is_lead {
+                true => row.checked_add(offset).filter(|target| *target < rows.end),
+                false => row
+                    .checked_sub(offset)
+                    .filter(|target| *target >= rows.start),
+            };
+
+            let value = match (target, columns.get(2)) {
+                (Some(target), _) => columns[0].get(target),
+                (None, Some(default)) => default.get(row),
+                (None, None) => DataValue::Null,
+            };
+
+            match value.is_null() {
+                true => array.append_default(),
+                false => array.append_data_value(value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for WindowLagLeadFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
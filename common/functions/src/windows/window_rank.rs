@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+
+use super::WindowFunction;
+use super::WindowFunctionDescription;
+use super::WindowFunctionRef;
+use super::WindowPartition;
+use crate::aggregates::assert_arguments;
+
+#[derive(Clone, Copy)]
+enum RankKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+}
+
+/// row_number(), rank() and dense_rank(), the number of the row in its partition.
+#[derive(Clone)]
+pub struct WindowRankFunction {
+    display_name: String,
+    kind: RankKind,
+}
+
+impl WindowRankFunction {
+    fn try_create(
+        display_name: &str,
+        kind: RankKind,
+        arguments: Vec<DataField>,
+    ) -> Result<WindowFunctionRef> {
+        assert_arguments(display_name, arguments.len(), 0)?;
+        Ok(Arc::new(WindowRankFunction {
+            display_name: display_name.to_string(),
+            kind,
+        }))
+    }
+
+    pub fn row_number_desc() -> WindowFunctionDescription {
+        WindowFunctionDescription::creator(Box::new(|name, _params, arguments| {
+            Self::try_create(name, RankKind::RowNumber, arguments)
+        }))
+    }
+
+    pub fn rank_desc() -> WindowFunctionDescription {
+        WindowFunctionDescription::creator(Box::new(|name, _params, arguments| {
+            Self::try_create(name, RankKind::Rank, arguments)
+        }))
+    }
+
+    pub fn dense_rank_desc() -> WindowFunctionDescription {
+        WindowFunctionDescription::creator(Box::new(|name, _params, arguments| {
+            Self::try_create(name, RankKind::DenseRank, arguments)
+        }))
+    }
+}
+
+impl WindowFunction for WindowRankFunction {
+    fn name(&self) -> &str {
+        "WindowRankFunction"
+    }
+
+    fn return_type(&self) -> Result<DataTypePtr> {
+        Ok(u64::to_data_type())
+    }
+
+    fn eval_partition(
+        &self,
+        partition: &WindowPartition,
+        array: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let array: &mut MutablePrimitiveColumn<u64> = Series::check_get_mutable_column(array)?;
+
+        let start = partition.rows.start;
+        for (index, peer) in partition.peers.iter().enumerate() {
+            for row in peer.clone() {
+                let number = match self.kind {
+                    RankKind::RowNumber => row - start + 1,
+                    RankKind::Rank => peer.start - start + 1,
+                    RankKind::DenseRank => index + 1,
+                };
+                array.append_value(number as u64);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for WindowRankFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
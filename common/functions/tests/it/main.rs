@@ -14,3 +14,4 @@
 
 mod aggregates;
 mod scalars;
+mod windows;
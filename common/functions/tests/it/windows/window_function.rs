@@ -0,0 +1,200 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_functions::windows::*;
+use pretty_assertions::assert_eq;
+
+struct Partition {
+    columns: Vec<ColumnRef>,
+    order_by: Option<(ColumnRef, bool)>,
+    peers: Vec<std::ops::Range<usize>>,
+}
+
+impl Partition {
+    fn eval(
+        &self,
+        name: &str,
+        arguments: Vec<DataField>,
+        frame: WindowFrame,
+    ) -> Result<Vec<DataValue>> {
+        let func = WindowFunctionFactory::instance().get(name, vec![], arguments, frame)?;
+        let rows = self.peers.first().unwrap().start..self.peers.last().unwrap().end;
+        let partition = WindowPartition {
+            columns: &self.columns,
+            order_by: self.order_by.as_ref().map(|(column, asc)| (column, *asc)),
+            rows: rows.clone(),
+            peers: self.peers.clone(),
+        };
+
+        let mut builder = func.return_type()?.create_mutable(rows.len());
+        func.eval_partition(&partition, builder.as_mut())?;
+        let column = builder.to_column();
+        Ok((0..column.len()).map(|row| column.get(row)).collect())
+    }
+}
+
+fn u64_values(values: &[u64]) -> Vec<DataValue> {
+    values.iter().map(|v| DataValue::UInt64(*v)).collect()
+}
+
+fn i64_values(values: &[i64]) -> Vec<DataValue> {
+    values.iter().map(|v| DataValue::Int64(*v)).collect()
+}
+
+#[test]
+fn test_window_rank_functions() -> Result<()> {
+    // order by keys: [1, 2, 2, 3]
+    let partition = Partition {
+        columns: vec![],
+        order_by: None,
+        peers: vec![0..1, 1..3, 3..4],
+    };
+
+    let frame = WindowFrame::default();
+    assert_eq!(
+        u64_values(&[1, 2, 3, 4]),
+        partition.eval("row_number", vec![], frame)?
+    );
+    assert_eq!(
+        u64_values(&[1, 2, 2, 4]),
+        partition.eval("rank", vec![], frame)?
+    );
+    assert_eq!(
+        u64_values(&[1, 2, 2, 3]),
+        partition.eval("dense_rank", vec![], frame)?
+    );
+
+    let arguments = vec![DataField::new("a", i64::to_data_type())];
+    let result = partition.eval("rank", arguments, frame);
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_window_lag_lead_functions() -> Result<()> {
+    let partition = Partition {
+        columns: vec![
+            Series::from_data(vec![1i64, 2, 3, 4]),
+            Series::from_data(vec![2u64, 2, 2, 2]),
+            Series::from_data(vec![0i64, 0, 0, 0]),
+        ],
+        order_by: None,
+        peers: vec![0..1, 1..2, 2..3, 3..4],
+    };
+
+    let frame = WindowFrame::default();
+    let a = DataField::new("a", i64::to_data_type());
+    let offset = DataField::new("2", u64::to_data_type());
+    let default = DataField::new("0", i64::to_data_type());
+
+    let mut expect = vec![DataValue::Null];
+    expect.extend(i64_values(&[1, 2, 3]));
+    assert_eq!(expect, partition.eval("lag", vec![a.clone()], frame)?);
+
+    let mut expect = i64_values(&[3, 4]);
+    expect.extend(vec![DataValue::Null, DataValue::Null]);
+    let arguments = vec![a.clone(), offset.clone()];
+    assert_eq!(expect, partition.eval("lead", arguments, frame)?);
+
+    let arguments = vec![a, offset, default];
+    assert_eq!(
+        i64_values(&[3, 4, 0, 0]),
+        partition.eval("lead", arguments, frame)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_window_aggregate_functions() -> Result<()> {
+    // order by keys: [1, 2, 2, 5]
+    let keys: ColumnRef = Series::from_data(vec![1i64, 2, 2, 5]);
+    let partition = Partition {
+        columns: vec![Series::from_data(vec![1i64, 2, 3, 4])],
+        order_by: Some((keys, true)),
+        peers: vec![0..1, 1..3, 3..4],
+    };
+
+    let arguments = vec![DataField::new("a", i64::to_data_type())];
+
+    // RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, the peers are included.
+    let frame = WindowFrame::default();
+    assert_eq!(
+        i64_values(&[1, 6, 6, 10]),
+        partition.eval("sum", arguments.clone(), frame)?
+    );
+
+    // ROWS BETWEEN 1 PRECEDING AND CURRENT ROW
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Rows,
+        WindowFrameBound::Preceding(Some(1)),
+        WindowFrameBound::CurrentRow,
+    )?;
+    assert_eq!(
+        i64_values(&[1, 3, 5, 7]),
+        partition.eval("sum", arguments.clone(), frame)?
+    );
+
+    // ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Rows,
+        WindowFrameBound::CurrentRow,
+        WindowFrameBound::Following(None),
+    )?;
+    assert_eq!(
+        u64_values(&[4, 3, 2, 1]),
+        partition.eval("count", arguments.clone(), frame)?
+    );
+
+    // RANGE BETWEEN 1 PRECEDING AND 1 FOLLOWING
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Range,
+        WindowFrameBound::Preceding(Some(1)),
+        WindowFrameBound::Following(Some(1)),
+    )?;
+    assert_eq!(
+        i64_values(&[6, 6, 6, 4]),
+        partition.eval("sum", arguments, frame)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_window_frame() -> Result<()> {
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Rows,
+        WindowFrameBound::Preceding(Some(1)),
+        WindowFrameBound::Following(None),
+    )?;
+    assert_eq!(
+        "rows between 1 preceding and unbounded following",
+        format!("{}", frame)
+    );
+
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Rows,
+        WindowFrameBound::Following(None),
+        WindowFrameBound::CurrentRow,
+    );
+    assert!(frame.is_err());
+
+    let frame = WindowFrame::create(
+        WindowFrameUnits::Range,
+        WindowFrameBound::CurrentRow,
+        WindowFrameBound::Preceding(None),
+    );
+    assert!(frame.is_err());
+    Ok(())
+}
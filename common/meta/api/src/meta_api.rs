@@ -30,9 +30,13 @@ use common_meta_types::GetTableReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -65,5 +69,9 @@ pub trait MetaApi: Send + Sync {
         req: UpsertTableOptionReq,
     ) -> Result<UpsertTableOptionReply>;
 
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply>;
+
+    async fn rename_table(&self, req: RenameTableReq) -> Result<RenameTableReply>;
+
     fn name(&self) -> String;
 }
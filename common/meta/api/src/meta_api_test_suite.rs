@@ -27,9 +27,11 @@ use common_meta_types::GetDatabaseReq;
 use common_meta_types::GetTableReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
 
@@ -644,6 +646,104 @@ impl MetaApiTestSuite {
 
         Ok(())
     }
+
+    pub async fn table_rename_update_meta<MT: MetaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+
+        tracing::info!("--- prepare db1, db2 and table db1.tb1, db1.tb2");
+        {
+            self.create_database(mt, tenant, "db1").await?;
+            self.create_database(mt, tenant, "db2").await?;
+
+            let mut plan = CreateTableReq {
+                if_not_exists: false,
+                tenant: tenant.to_string(),
+                db: "db1".to_string(),
+                table: "tb1".to_string(),
+                table_meta: TableMeta {
+                    schema: Arc::new(DataSchema::new(vec![DataField::new(
+                        "number",
+                        u64::to_data_type(),
+                    )])),
+                    engine: "JSON".to_string(),
+                    ..Default::default()
+                },
+            };
+            mt.create_table(plan.clone()).await?;
+
+            plan.table = "tb2".to_string();
+            mt.create_table(plan).await?;
+        }
+
+        let rename_req = |table: &str, new_db: &str, new_table: &str| RenameTableReq {
+            if_exists: false,
+            tenant: tenant.to_string(),
+            db: "db1".to_string(),
+            table: table.to_string(),
+            new_db: new_db.to_string(),
+            new_table: new_table.to_string(),
+        };
+
+        tracing::info!("--- rename table to an existing name, error");
+        {
+            let res = mt.rename_table(rename_req("tb1", "db1", "tb2")).await;
+            assert_eq!(
+                ErrorCode::TableAlreadyExists("").code(),
+                res.unwrap_err().code()
+            );
+        }
+
+        tracing::info!("--- rename table to another db");
+        {
+            let old = mt.get_table((tenant, "db1", "tb1").into()).await?;
+            let res = mt.rename_table(rename_req("tb1", "db2", "tb3")).await?;
+            assert_eq!(old.ident.table_id, res.table_id);
+
+            let got = mt.get_table((tenant, "db1", "tb1").into()).await;
+            assert_eq!(ErrorCode::UnknownTable("").code(), got.unwrap_err().code());
+
+            let got = mt.get_table((tenant, "db2", "tb3").into()).await?;
+            assert_eq!(old.ident.table_id, got.ident.table_id);
+            assert!(got.ident.version > old.ident.version, "version is bumped");
+        }
+
+        tracing::info!("--- rename unknown table");
+        {
+            let res = mt.rename_table(rename_req("tb1", "db2", "tb4")).await;
+            assert_eq!(ErrorCode::UnknownTable("").code(), res.unwrap_err().code());
+
+            let mut req = rename_req("tb1", "db2", "tb4");
+            req.if_exists = true;
+            mt.rename_table(req).await?;
+        }
+
+        tracing::info!("--- update table meta");
+        {
+            let table = mt.get_table((tenant, "db2", "tb3").into()).await?;
+            let mut new_meta = table.meta.clone();
+            new_meta.schema = Arc::new(DataSchema::new(vec![
+                DataField::new("number", u64::to_data_type()),
+                DataField::new("n", i64::to_data_type()),
+            ]));
+
+            mt.update_table_meta(UpdateTableMetaReq::new(&table.ident, new_meta.clone()))
+                .await?;
+
+            let got = mt.get_table((tenant, "db2", "tb3").into()).await?;
+            assert_eq!(new_meta.schema, got.meta.schema);
+
+            tracing::info!("--- update table meta with an outdated version, error");
+            let res = mt
+                .update_table_meta(UpdateTableMetaReq::new(&table.ident, table.meta.clone()))
+                .await;
+            assert_eq!(
+                ErrorCode::TableVersionMismatched("").code(),
+                res.unwrap_err().code()
+            );
+        }
+
+        Ok(())
+    }
 }
 
 impl MetaApiTestSuite {
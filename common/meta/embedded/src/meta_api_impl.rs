@@ -31,9 +31,13 @@ use common_meta_types::GetTableReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -94,6 +98,16 @@ impl MetaApi for MetaEmbedded {
         sm.upsert_table_option(req).await
     }
 
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        let sm = self.inner.lock().await;
+        sm.update_table_meta(req).await
+    }
+
+    async fn rename_table(&self, req: RenameTableReq) -> Result<RenameTableReply> {
+        let sm = self.inner.lock().await;
+        sm.rename_table(req).await
+    }
+
     fn name(&self) -> String {
         "meta-embedded".to_string()
     }
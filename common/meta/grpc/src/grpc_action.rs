@@ -35,7 +35,11 @@ use common_meta_types::ListTableReq;
 use common_meta_types::MGetKVActionReply;
 use common_meta_types::MetaId;
 use common_meta_types::PrefixListReply;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertKVAction;
 use common_meta_types::UpsertKVActionReply;
 use common_meta_types::UpsertTableOptionReply;
@@ -55,6 +59,8 @@ pub enum MetaGrpcWriteReq {
     CreateTable(CreateTableReq),
     DropTable(DropTableReq),
     CommitTable(UpsertTableOptionReq),
+    UpdateTableMeta(UpdateTableMetaReq),
+    RenameTable(RenameTableReq),
 
     UpsertKV(UpsertKVAction),
 }
@@ -210,6 +216,14 @@ impl RequestFor for UpsertTableOptionReq {
     type Reply = UpsertTableOptionReply;
 }
 
+impl RequestFor for UpdateTableMetaReq {
+    type Reply = UpdateTableMetaReply;
+}
+
+impl RequestFor for RenameTableReq {
+    type Reply = RenameTableReply;
+}
+
 impl RequestFor for ListTableReq {
     type Reply = Vec<Arc<TableInfo>>;
 }
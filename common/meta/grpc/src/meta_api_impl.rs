@@ -29,9 +29,13 @@ use common_meta_types::GetTableReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 
@@ -105,6 +109,20 @@ impl MetaApi for MetaGrpcClient {
         self.do_write(req).await
     }
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> common_exception::Result<UpdateTableMetaReply> {
+        self.do_write(req).await
+    }
+
+    async fn rename_table(
+        &self,
+        req: RenameTableReq,
+    ) -> common_exception::Result<RenameTableReply> {
+        self.do_write(req).await
+    }
+
     fn name(&self) -> String {
         "MetaGrpcClient".to_string()
     }
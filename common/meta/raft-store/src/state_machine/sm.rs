@@ -633,6 +633,133 @@ impl StateMachine {
         )))
     }
 
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_update_table_meta_cmd(
+        &self,
+        req: &common_meta_types::UpdateTableMetaReq,
+        txn_tree: &TransactionSledTree,
+    ) -> MetaStorageResult<AppliedState> {
+        let table_tree = txn_tree.key_space::<Tables>();
+        let prev = table_tree.get(&req.table_id)?;
+
+        // Same as upsert-options, the table must be present.
+        let prev = prev.ok_or_else(|| {
+            MetaStorageError::AppError(AppError::UnknownTableId(UnknownTableId::new(
+                req.table_id,
+                "apply_update_table_meta_cmd".to_string(),
+            )))
+        })?;
+
+        if req.seq.match_seq(&prev).is_err() {
+            let res = AppliedState::TableMeta(Change::new(Some(prev.clone()), Some(prev)));
+            return Ok(res);
+        }
+
+        let new_seq = self.txn_incr_seq(Tables::NAME, txn_tree)?;
+        let sv = SeqV {
+            seq: new_seq,
+            meta: prev.meta.clone(),
+            data: req.new_table_meta.clone(),
+        };
+
+        table_tree.insert(&req.table_id, &sv)?;
+
+        tracing::debug!("applied update TableMeta: {} {:?}", req.table_id, sv);
+
+        Ok(AppliedState::TableMeta(Change::new_with_id(
+            req.table_id,
+            Some(prev),
+            Some(sv),
+        )))
+    }
+
+    /// Rename a table.
+    ///
+    /// - If the source table does not exist, it returns `(None, None)`.
+    /// - If the target table exists, it returns the target unchanged: `(target, target)`.
+    /// - Otherwise the table is moved to the new name and its version is bumped.
+    #[tracing::instrument(level = "debug", skip(self, txn_tree))]
+    fn apply_rename_table_cmd(
+        &self,
+        tenant: &str,
+        db_name: &str,
+        table_name: &str,
+        new_db_name: &str,
+        new_table_name: &str,
+        txn_tree: &TransactionSledTree,
+    ) -> MetaStorageResult<AppliedState> {
+        let db_id = self.txn_get_database_id(tenant, db_name, txn_tree)?;
+        let new_db_id = self.txn_get_database_id(tenant, new_db_name, txn_tree)?;
+
+        let lookup_key = TableLookupKey {
+            database_id: db_id,
+            table_name: table_name.to_string(),
+        };
+        let new_lookup_key = TableLookupKey {
+            database_id: new_db_id,
+            table_name: new_table_name.to_string(),
+        };
+
+        let table_lookup_tree = txn_tree.key_space::<TableLookup>();
+
+        let seq_table_id = table_lookup_tree.get(&lookup_key)?;
+        let table_id = match seq_table_id {
+            None => return Ok(Change::<TableMeta>::new(None, None).into()),
+            Some(u) => u.data.0,
+        };
+
+        if let Some(u) = table_lookup_tree.get(&new_lookup_key)? {
+            let exist_id = u.data.0;
+            let exist = self.txn_get_table_meta_by_id(&exist_id, txn_tree)?;
+            return Ok(Change::nochange_with_id(exist_id, exist).into());
+        }
+
+        self.txn_sub_tree_upsert(
+            &table_lookup_tree,
+            &lookup_key,
+            &MatchSeq::Any,
+            Operation::Delete,
+            None,
+        )?;
+
+        self.txn_sub_tree_upsert(
+            &table_lookup_tree,
+            &new_lookup_key,
+            &MatchSeq::Exact(0),
+            Operation::Update(TableLookupValue(table_id)),
+            None,
+        )?;
+
+        // Bump the table version, thus any table info cached with the old name is outdated.
+        let table_tree = txn_tree.key_space::<Tables>();
+        let prev = table_tree.get(&table_id)?.ok_or_else(|| {
+            MetaStorageError::AppError(AppError::UnknownTableId(UnknownTableId::new(
+                table_id,
+                "apply_rename_table_cmd".to_string(),
+            )))
+        })?;
+
+        let new_seq = self.txn_incr_seq(Tables::NAME, txn_tree)?;
+        let sv = SeqV {
+            seq: new_seq,
+            meta: prev.meta.clone(),
+            data: prev.data.clone(),
+        };
+        table_tree.insert(&table_id, &sv)?;
+
+        self.txn_incr_seq(SEQ_DATABASE_META_ID, txn_tree)?;
+
+        tracing::debug!(
+            "applied rename Table: {}.{} => {}.{}",
+            db_name,
+            table_name,
+            new_db_name,
+            new_table_name
+        );
+
+        Ok(Change::new_with_id(table_id, Some(prev), Some(sv)).into())
+    }
+
     /// Apply a `Cmd` to state machine.
     ///
     /// Already applied log should be filtered out before passing into this function.
@@ -686,6 +813,23 @@ impl StateMachine {
             } => self.apply_update_kv_cmd(key, seq, value_op, value_meta, txn_tree),
 
             Cmd::UpsertTableOptions(ref req) => self.apply_upsert_table_options_cmd(req, txn_tree),
+
+            Cmd::UpdateTableMeta(ref req) => self.apply_update_table_meta_cmd(req, txn_tree),
+
+            Cmd::RenameTable {
+                ref tenant,
+                ref db_name,
+                ref table_name,
+                ref new_db_name,
+                ref new_table_name,
+            } => self.apply_rename_table_cmd(
+                tenant,
+                db_name,
+                table_name,
+                new_db_name,
+                new_table_name,
+                txn_tree,
+            ),
         }
     }
 
@@ -34,9 +34,13 @@ use common_meta_types::GetTableReq;
 use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
@@ -315,6 +319,69 @@ impl MetaApi for StateMachine {
         Ok(UpsertTableOptionReply {})
     }
 
+    async fn update_table_meta(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> Result<UpdateTableMetaReply, ErrorCode> {
+        let cmd = Cmd::UpdateTableMeta(req.clone());
+
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&cmd, &t)?;
+            Ok(r)
+        })?;
+        if !res.changed() {
+            let ch: Change<TableMeta> = res.try_into().unwrap();
+            let (prev, _result) = ch.unwrap();
+
+            return Err(ErrorCode::TableVersionMismatched(format!(
+                "targeting version {:?}, current version {}",
+                req.seq, prev.seq,
+            )));
+        }
+
+        Ok(UpdateTableMetaReply {})
+    }
+
+    async fn rename_table(&self, req: RenameTableReq) -> Result<RenameTableReply, ErrorCode> {
+        let cmd = Cmd::RenameTable {
+            tenant: req.tenant.clone(),
+            db_name: req.db.clone(),
+            table_name: req.table.clone(),
+            new_db_name: req.new_db.clone(),
+            new_table_name: req.new_table.clone(),
+        };
+
+        let res = self.sm_tree.txn(true, |t| {
+            let r = self.apply_cmd(&cmd, &t)?;
+            Ok(r)
+        })?;
+
+        let mut ch: Change<TableMeta, u64> = res.try_into().unwrap();
+        let table_id = ch.ident.take();
+
+        if ch.prev.is_none() {
+            return if req.if_exists {
+                Ok(RenameTableReply { table_id: 0 })
+            } else {
+                Err(ErrorCode::UnknownTable(format!(
+                    "Unknown table: '{:}'",
+                    req.table
+                )))
+            };
+        }
+
+        if !ch.changed() {
+            return Err(ErrorCode::TableAlreadyExists(format!(
+                "table exists: {}",
+                req.new_table
+            )));
+        }
+
+        Ok(RenameTableReply {
+            table_id: table_id.unwrap(),
+        })
+    }
+
     fn name(&self) -> String {
         "StateMachine".to_string()
     }
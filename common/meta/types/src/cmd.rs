@@ -24,6 +24,7 @@ use crate::MatchSeq;
 use crate::Node;
 use crate::Operation;
 use crate::TableMeta;
+use crate::UpdateTableMetaReq;
 use crate::UpsertTableOptionReq;
 
 /// A Cmd describes what a user want to do to raft state machine
@@ -71,6 +72,21 @@ pub enum Cmd {
     /// Otherwise it returns the TableMeta before and after update.
     UpsertTableOptions(UpsertTableOptionReq),
 
+    /// Replace the meta of a table, e.g., to evolve its schema.
+    ///
+    /// Like `UpsertTableOptions`, it requires a present table and returns an unchanged state
+    /// if the seq mismatches.
+    UpdateTableMeta(UpdateTableMetaReq),
+
+    /// Rename a table, possibly moving it to another database of the same tenant.
+    RenameTable {
+        tenant: String,
+        db_name: String,
+        table_name: String,
+        new_db_name: String,
+        new_table_name: String,
+    },
+
     /// Update or insert a general purpose kv store
     UpsertKV {
         key: String,
@@ -142,6 +158,26 @@ impl fmt::Display for Cmd {
                     req.table_id, req.seq, req.options
                 )
             }
+            Cmd::UpdateTableMeta(req) => {
+                write!(
+                    f,
+                    "update-table-meta: table-id:{}({:?}) = {}",
+                    req.table_id, req.seq, req.new_table_meta
+                )
+            }
+            Cmd::RenameTable {
+                tenant,
+                db_name,
+                table_name,
+                new_db_name,
+                new_table_name,
+            } => {
+                write!(
+                    f,
+                    "rename_table:{}/{}-{}=>{}-{}",
+                    tenant, db_name, table_name, new_db_name, new_table_name
+                )
+            }
         }
     }
 }
@@ -121,10 +121,14 @@ pub use table::DropTableReply;
 pub use table::DropTableReq;
 pub use table::GetTableReq;
 pub use table::ListTableReq;
+pub use table::RenameTableReply;
+pub use table::RenameTableReq;
 pub use table::TableIdent;
 pub use table::TableInfo;
 pub use table::TableMeta;
 pub use table::TableNameIndent;
+pub use table::UpdateTableMetaReply;
+pub use table::UpdateTableMetaReq;
 pub use table::UpsertTableOptionReply;
 pub use table::UpsertTableOptionReq;
 pub use user_auth::AuthInfo;
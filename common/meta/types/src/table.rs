@@ -107,6 +107,9 @@ pub struct TableMeta {
     pub engine: String,
     pub engine_options: HashMap<String, String>,
     pub options: HashMap<String, String>,
+    /// Serialized representation of the `CLUSTER BY` expressions, `None` if the table is not clustered.
+    #[serde(default)]
+    pub cluster_keys: Option<String>,
     pub created_on: DateTime<Utc>,
 }
 
@@ -163,6 +166,7 @@ impl Default for TableMeta {
             engine: "".to_string(),
             engine_options: HashMap::new(),
             options: HashMap::new(),
+            cluster_keys: None,
             created_on: Utc::now(),
         }
     }
@@ -242,6 +246,43 @@ impl UpsertTableOptionReq {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct UpsertTableOptionReply {}
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateTableMetaReq {
+    pub table_id: u64,
+    pub seq: MatchSeq,
+
+    /// The table meta that replaces the current one, e.g., with an altered schema.
+    pub new_table_meta: TableMeta,
+}
+
+impl UpdateTableMetaReq {
+    pub fn new(table_ident: &TableIdent, new_table_meta: TableMeta) -> UpdateTableMetaReq {
+        UpdateTableMetaReq {
+            table_id: table_ident.table_id,
+            seq: MatchSeq::Exact(table_ident.version),
+            new_table_meta,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateTableMetaReply {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct RenameTableReq {
+    pub if_exists: bool,
+    pub tenant: String,
+    pub db: String,
+    pub table: String,
+    pub new_db: String,
+    pub new_table: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RenameTableReply {
+    pub table_id: u64,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct GetTableReq {
     pub inner: TableNameIndent,
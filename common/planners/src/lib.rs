@@ -20,6 +20,7 @@ mod plan_copy;
 mod plan_database_create;
 mod plan_database_drop;
 mod plan_database_show_create;
+mod plan_delete;
 mod plan_empty;
 mod plan_explain;
 mod plan_expression;
@@ -37,6 +38,7 @@ mod plan_expression_visitor;
 mod plan_filter;
 mod plan_having;
 mod plan_insert_into;
+mod plan_join;
 mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
@@ -61,12 +63,16 @@ mod plan_show_grants;
 mod plan_sink;
 mod plan_sort;
 mod plan_subqueries_set;
+mod plan_table_alter;
+mod plan_table_analyze;
 mod plan_table_create;
 mod plan_table_describe;
 mod plan_table_drop;
 mod plan_table_optimize;
+mod plan_table_rename;
 mod plan_table_show_create;
 mod plan_table_truncate;
+mod plan_update;
 mod plan_use_database;
 mod plan_user_alter;
 mod plan_user_create;
@@ -79,6 +85,7 @@ mod plan_user_stage_drop;
 mod plan_user_udf_alter;
 mod plan_user_udf_create;
 mod plan_user_udf_drop;
+mod plan_window;
 
 pub use plan_admin_use_tenant::AdminUseTenantPlan;
 pub use plan_aggregator_final::AggregatorFinalPlan;
@@ -89,6 +96,7 @@ pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_create::DatabaseOptions;
 pub use plan_database_drop::DropDatabasePlan;
 pub use plan_database_show_create::ShowCreateDatabasePlan;
+pub use plan_delete::DeletePlan;
 pub use plan_empty::EmptyPlan;
 pub use plan_explain::ExplainPlan;
 pub use plan_explain::ExplainType;
@@ -100,11 +108,14 @@ pub use plan_expression_chain::ExpressionChain;
 pub use plan_expression_column::col;
 pub use plan_expression_common::expand_aggregate_arg_exprs;
 pub use plan_expression_common::expand_wildcard;
+pub use plan_expression_common::expand_window_arg_exprs;
 pub use plan_expression_common::expr_as_column_expr;
 pub use plan_expression_common::extract_aliases;
 pub use plan_expression_common::find_aggregate_exprs;
 pub use plan_expression_common::find_aggregate_exprs_in_expr;
 pub use plan_expression_common::find_columns_not_satisfy_exprs;
+pub use plan_expression_common::find_window_exprs;
+pub use plan_expression_common::find_window_exprs_in_expr;
 pub use plan_expression_common::rebase_expr;
 pub use plan_expression_common::rebase_expr_from_input;
 pub use plan_expression_common::resolve_aliases_to_exprs;
@@ -130,6 +141,8 @@ pub use plan_filter::FilterPlan;
 pub use plan_having::HavingPlan;
 pub use plan_insert_into::InsertInputSource;
 pub use plan_insert_into::InsertPlan;
+pub use plan_join::JoinPlan;
+pub use plan_join::JoinType;
 pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
@@ -157,14 +170,19 @@ pub use plan_sink::SinkPlan;
 pub use plan_sink::SINK_SCHEMA;
 pub use plan_sort::SortPlan;
 pub use plan_subqueries_set::SubQueriesSetPlan;
+pub use plan_table_alter::AlterTableAction;
+pub use plan_table_alter::AlterTablePlan;
+pub use plan_table_analyze::AnalyzeTablePlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_describe::DescribeTablePlan;
 pub use plan_table_drop::DropTablePlan;
 pub use plan_table_optimize::Optimization;
 pub use plan_table_optimize::OptimizeTablePlan;
+pub use plan_table_rename::RenameTablePlan;
 pub use plan_table_show_create::ShowCreateTablePlan;
 pub use plan_table_truncate::TruncateTablePlan;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_user_alter::AlterUserPlan;
 pub use plan_user_create::CreateUserPlan;
@@ -177,3 +195,4 @@ pub use plan_user_stage_drop::DropUserStagePlan;
 pub use plan_user_udf_alter::AlterUserUDFPlan;
 pub use plan_user_udf_create::CreateUserUDFPlan;
 pub use plan_user_udf_drop::DropUserUDFPlan;
+pub use plan_window::WindowFuncPlan;
@@ -21,6 +21,9 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::windows::WindowFrame;
+use common_functions::windows::WindowFunctionFactory;
+use common_functions::windows::WindowFunctionRef;
 use once_cell::sync::Lazy;
 
 use crate::plan_expression_common::ExpressionDataTypeVisitor;
@@ -94,6 +97,19 @@ pub enum Expression {
         args: Vec<Expression>,
     },
 
+    /// WindowFunction with a set of arguments, evaluated over the window of each row.
+    WindowFunction {
+        op: String,
+        params: Vec<DataValue>,
+        args: Vec<Expression>,
+        /// The expressions to partition the rows by
+        partition_by: Vec<Expression>,
+        /// The sort expressions to order the rows of a partition by
+        order_by: Vec<Expression>,
+        /// The frame of the window, RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW if None
+        window_frame: Option<WindowFrame>,
+    },
+
     /// A sort expression, that can be used to sort values.
     Sort {
         /// The expression to sort on
@@ -216,6 +232,33 @@ impl Expression {
                     false => format!("{}({})", prefix, args_column_name.join(", ")),
                 }
             }
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            } => {
+                let args_column_name = args.iter().map(Expression::column_name).collect::<Vec<_>>();
+                let params_name = params
+                    .iter()
+                    .map(|v| DataValue::custom_display(v, true))
+                    .collect::<Vec<_>>();
+
+                let prefix = if params.is_empty() {
+                    op.to_string()
+                } else {
+                    format!("{}({})", op, params_name.join(", "))
+                };
+
+                format!(
+                    "{}({}) over ({})",
+                    prefix,
+                    args_column_name.join(", "),
+                    Self::window_spec_name(partition_by, order_by, window_frame)
+                )
+            }
             Expression::Sort { expr, .. } => expr.column_name(),
             Expression::Cast {
                 expr, data_type, ..
@@ -228,6 +271,40 @@ impl Expression {
         }
     }
 
+    fn window_spec_name(
+        partition_by: &[Expression],
+        order_by: &[Expression],
+        window_frame: &Option<WindowFrame>,
+    ) -> String {
+        let mut spec = vec![];
+        if !partition_by.is_empty() {
+            let names = partition_by
+                .iter()
+                .map(Expression::column_name)
+                .collect::<Vec<_>>();
+            spec.push(format!("partition by {}", names.join(", ")));
+        }
+
+        if !order_by.is_empty() {
+            let names = order_by
+                .iter()
+                .map(|expr| match expr {
+                    Expression::Sort { expr, asc, .. } if !asc => {
+                        format!("{} desc", expr.column_name())
+                    }
+                    _ => expr.column_name(),
+                })
+                .collect::<Vec<_>>();
+            spec.push(format!("order by {}", names.join(", ")));
+        }
+
+        if let Some(window_frame) = window_frame {
+            spec.push(window_frame.to_string());
+        }
+
+        spec.join(" ")
+    }
+
     pub fn to_data_field(&self, input_schema: &DataSchemaRef) -> Result<DataField> {
         let name = self.column_name();
         self.to_data_type(input_schema)
@@ -324,6 +401,29 @@ impl Expression {
         }
     }
 
+    pub fn to_window_function(&self, schema: &DataSchemaRef) -> Result<WindowFunctionRef> {
+        match self {
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                window_frame,
+                ..
+            } => {
+                let mut fields = Vec::with_capacity(args.len());
+                for arg in args.iter() {
+                    fields.push(arg.to_data_field(schema)?);
+                }
+
+                let frame = window_frame.unwrap_or_default();
+                WindowFunctionFactory::instance().get(op, params.clone(), fields, frame)
+            }
+            _ => Err(ErrorCode::LogicalError(
+                "Expression must be window function",
+            )),
+        }
+    }
+
     pub fn to_aggregate_function_names(&self) -> Result<Vec<String>> {
         match self {
             Expression::AggregateFunction { args, .. } => {
@@ -413,6 +513,7 @@ impl fmt::Debug for Expression {
                 Ok(())
             }
 
+            Expression::WindowFunction { .. } => write!(f, "{}", self.column_name()),
             Expression::Sort { expr, .. } => write!(f, "{:?}", expr),
             Expression::Wildcard => write!(f, "*"),
             Expression::Cast {
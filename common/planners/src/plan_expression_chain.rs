@@ -191,6 +191,11 @@ impl ExpressionChain {
                     "Action must be a non-aggregated function.",
                 ));
             }
+            Expression::WindowFunction { .. } => {
+                return Err(ErrorCode::LogicalError(
+                    "Action must be a non-window function.",
+                ));
+            }
             Expression::Wildcard | Expression::Sort { .. } => {}
             Expression::Cast {
                 expr: sub_expr,
@@ -51,6 +51,44 @@ pub fn find_aggregate_exprs_in_expr(expr: &Expression) -> Vec<Expression> {
     })
 }
 
+/// Collect all deeply nested `Expression::WindowFunction`. They are returned in order of
+/// occurrence (depth first), with duplicates omitted.
+pub fn find_window_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    find_exprs_in_exprs(exprs, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+pub fn find_window_exprs_in_expr(expr: &Expression) -> Vec<Expression> {
+    find_exprs_in_expr(expr, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+/// Collect all the expressions which window function depends on: the arguments,
+/// the partition by expressions and the order by expressions.
+/// [WindowFunction(sum(a) over (partition by b order by c))] ---> [a, b, c]
+pub fn expand_window_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    let mut res = vec![];
+    for expr in exprs {
+        if let Expression::WindowFunction {
+            args,
+            partition_by,
+            order_by,
+            ..
+        } = expr
+        {
+            let order_by = order_by.iter().map(sort_to_inner_expr).collect::<Vec<_>>();
+            for arg in args.iter().chain(partition_by).chain(order_by.iter()) {
+                if !res.contains(arg) {
+                    res.push(arg.clone());
+                }
+            }
+        }
+    }
+    res
+}
+
 /// Collect all arguments from aggregation function and append to this exprs
 /// [ColumnExpr(b), Aggr(sum(a, b))] ---> [ColumnExpr(b), ColumnExpr(a)]
 
@@ -303,6 +341,31 @@ where F: Fn(&Expression) -> Result<Option<Expression>> {
                     .collect::<Result<Vec<Expression>>>()?,
             }),
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: args
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                partition_by: partition_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                order_by: order_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                window_frame: *window_frame,
+            }),
+
             Expression::Sort {
                 expr: nested_expr,
                 asc,
@@ -470,6 +533,29 @@ impl ExpressionVisitor for ExpressionDataTypeVisitor {
                 self.stack.push(return_type);
                 Ok(self)
             }
+            expr @ Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                // Pop arguments, partition by and order by expressions.
+                let children = args.len() + partition_by.len() + order_by.len();
+                for index in 0..children {
+                    if self.stack.pop().is_none() {
+                        return Err(ErrorCode::LogicalError(format!(
+                            "Expected {} arguments, actual {}.",
+                            children, index
+                        )));
+                    }
+                }
+
+                let window_function = expr.to_window_function(&self.input_schema)?;
+                let return_type = window_function.return_type()?;
+
+                self.stack.push(return_type);
+                Ok(self)
+            }
             Expression::Cast { data_type, .. } => {
                 let inner_type = match self.stack.pop() {
                     None => Err(ErrorCode::LogicalError(
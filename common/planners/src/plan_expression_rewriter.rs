@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::windows::WindowFrame;
 
 use crate::Expression;
 use crate::ExpressionVisitor;
@@ -124,6 +125,27 @@ pub trait ExpressionRewriter: Sized {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn mutate_window_function(
+        &mut self,
+        name: &str,
+        params: &[DataValue],
+        args: Vec<Expression>,
+        partition_by: Vec<Expression>,
+        order_by: Vec<Expression>,
+        window_frame: &Option<WindowFrame>,
+        _origin_expr: &Expression,
+    ) -> Result<Expression> {
+        Ok(Expression::WindowFunction {
+            op: name.to_string(),
+            params: params.to_owned(),
+            args,
+            partition_by,
+            order_by,
+            window_frame: *window_frame,
+        })
+    }
+
     fn mutate_cast(
         &mut self,
         typ: &DataTypePtr,
@@ -288,6 +310,43 @@ impl<T: ExpressionRewriter> ExpressionVisitor for ExpressionRewriteVisitor<T> {
                 self.stack.push(new_expr);
                 Ok(self)
             }
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            } => {
+                let children = args.len() + partition_by.len() + order_by.len();
+                let mut children_expr = Vec::with_capacity(children);
+
+                for index in 0..children {
+                    match self.stack.pop() {
+                        None => {
+                            return Err(ErrorCode::LogicalError(format!(
+                                "Expected {} arguments, actual {}.",
+                                children, index
+                            )));
+                        }
+                        Some(child) => children_expr.push(child),
+                    };
+                }
+
+                let order_by_expr = children_expr.split_off(args.len() + partition_by.len());
+                let partition_by_expr = children_expr.split_off(args.len());
+                let new_expr = self.inner.mutate_window_function(
+                    op,
+                    params,
+                    children_expr,
+                    partition_by_expr,
+                    order_by_expr,
+                    window_frame,
+                    expr,
+                )?;
+                self.stack.push(new_expr);
+                Ok(self)
+            }
             Expression::Cast {
                 data_type,
                 is_nullable,
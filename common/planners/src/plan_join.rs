@@ -0,0 +1,122 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_datavalues2::DataSchemaRef;
+use common_datavalues2::DataSchemaRefExt;
+
+use crate::Expression;
+use crate::PlanNode;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+    /// Rows of the left side which have at least one match in the right side.
+    LeftSemi,
+    /// Rows of the left side which have no match in the right side.
+    LeftAnti,
+    /// Rows of the right side which have at least one match in the left side.
+    RightSemi,
+    /// Rows of the right side which have no match in the left side.
+    RightAnti,
+}
+
+impl JoinType {
+    /// Whether the columns of the left side may be filled with NULL.
+    pub fn is_left_nullable(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Full)
+    }
+
+    /// Whether the columns of the right side may be filled with NULL.
+    pub fn is_right_nullable(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Full)
+    }
+
+    /// Whether the join has to remember which rows of the right (build) side were matched.
+    /// Such joins can only emit their result once every left (probe) row has been seen.
+    pub fn need_build_side_visited(&self) -> bool {
+        matches!(
+            self,
+            JoinType::Right | JoinType::Full | JoinType::RightSemi | JoinType::RightAnti
+        )
+    }
+
+    pub fn output_left(&self) -> bool {
+        !matches!(self, JoinType::RightSemi | JoinType::RightAnti)
+    }
+
+    pub fn output_right(&self) -> bool {
+        !matches!(self, JoinType::LeftSemi | JoinType::LeftAnti)
+    }
+}
+
+impl Display for JoinType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JoinType::Inner => write!(f, "INNER"),
+            JoinType::Left => write!(f, "LEFT OUTER"),
+            JoinType::Right => write!(f, "RIGHT OUTER"),
+            JoinType::Full => write!(f, "FULL OUTER"),
+            JoinType::Cross => write!(f, "CROSS"),
+            JoinType::LeftSemi => write!(f, "LEFT SEMI"),
+            JoinType::LeftAnti => write!(f, "LEFT ANTI"),
+            JoinType::RightSemi => write!(f, "RIGHT SEMI"),
+            JoinType::RightAnti => write!(f, "RIGHT ANTI"),
+        }
+    }
+}
+
+/// Hash join of two inputs, the right input is the build side and the left input is the probe side.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct JoinPlan {
+    pub join_type: JoinType,
+    /// Equi-join keys evaluated on the left input.
+    pub left_keys: Vec<Expression>,
+    /// Equi-join keys evaluated on the right input, same length and types as `left_keys`.
+    pub right_keys: Vec<Expression>,
+    /// Non equi-join conditions, evaluated on the joined rows.
+    pub other_conditions: Option<Expression>,
+    /// output schema
+    pub schema: DataSchemaRef,
+    pub left: Arc<PlanNode>,
+    pub right: Arc<PlanNode>,
+}
+
+impl JoinPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn set_left(&mut self, node: &PlanNode) {
+        self.left = Arc::new(node.clone());
+    }
+
+    pub fn set_right(&mut self, node: &PlanNode) {
+        self.right = Arc::new(node.clone());
+    }
+
+    /// The schema on which `other_conditions` are evaluated: all the columns of both inputs.
+    pub fn joined_schema(&self) -> DataSchemaRef {
+        let mut fields = self.left.schema().fields().clone();
+        fields.extend(self.right.schema().fields().iter().cloned());
+        DataSchemaRefExt::create(fields)
+    }
+}
@@ -19,8 +19,10 @@ use common_datavalues2::DataSchemaRef;
 use crate::AdminUseTenantPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
+use crate::AnalyzeTablePlan;
 use crate::BroadcastPlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
@@ -28,6 +30,7 @@ use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
 use crate::DropDatabasePlan;
@@ -42,6 +45,7 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
@@ -49,6 +53,7 @@ use crate::OptimizeTablePlan;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
+use crate::RenameTablePlan;
 use crate::RevokePrivilegePlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
@@ -61,7 +66,9 @@ use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowFuncPlan;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
@@ -82,6 +89,8 @@ pub enum PlanNode {
     LimitBy(LimitByPlan),
     ReadSource(ReadDataSourcePlan),
     SubQueryExpression(SubQueriesSetPlan),
+    Join(JoinPlan),
+    WindowFunc(WindowFuncPlan),
 
     // Explain.
     Explain(ExplainPlan),
@@ -94,6 +103,10 @@ pub enum PlanNode {
     Copy(CopyPlan),
     Sink(SinkPlan),
 
+    // Delete and update.
+    Delete(DeletePlan),
+    Update(UpdatePlan),
+
     // Database.
     CreateDatabase(CreateDatabasePlan),
     DropDatabase(DropDatabasePlan),
@@ -103,8 +116,11 @@ pub enum PlanNode {
     // Table.
     CreateTable(CreateTablePlan),
     DropTable(DropTablePlan),
+    AlterTable(AlterTablePlan),
+    RenameTable(RenameTablePlan),
     TruncateTable(TruncateTablePlan),
     OptimizeTable(OptimizeTablePlan),
+    AnalyzeTable(AnalyzeTablePlan),
     DescribeTable(DescribeTablePlan),
     ShowCreateTable(ShowCreateTablePlan),
 
@@ -159,6 +175,8 @@ impl PlanNode {
             PlanNode::ReadSource(v) => v.schema(),
             PlanNode::Sort(v) => v.schema(),
             PlanNode::SubQueryExpression(v) => v.schema(),
+            PlanNode::Join(v) => v.schema(),
+            PlanNode::WindowFunc(v) => v.schema(),
             PlanNode::Sink(v) => v.schema(),
 
             // Explain.
@@ -170,6 +188,10 @@ impl PlanNode {
             // Insert.
             PlanNode::Insert(v) => v.schema(),
 
+            // Delete and update.
+            PlanNode::Delete(v) => v.schema(),
+            PlanNode::Update(v) => v.schema(),
+
             // Copy.
             PlanNode::Copy(v) => v.schema(),
 
@@ -182,8 +204,11 @@ impl PlanNode {
             // Table.
             PlanNode::CreateTable(v) => v.schema(),
             PlanNode::DropTable(v) => v.schema(),
+            PlanNode::AlterTable(v) => v.schema(),
+            PlanNode::RenameTable(v) => v.schema(),
             PlanNode::TruncateTable(v) => v.schema(),
             PlanNode::OptimizeTable(v) => v.schema(),
+            PlanNode::AnalyzeTable(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
 
@@ -239,6 +264,8 @@ impl PlanNode {
             PlanNode::ReadSource(_) => "ReadSourcePlan",
             PlanNode::Sort(_) => "SortPlan",
             PlanNode::SubQueryExpression(_) => "CreateSubQueriesSets",
+            PlanNode::Join(_) => "JoinPlan",
+            PlanNode::WindowFunc(_) => "WindowFuncPlan",
             PlanNode::Sink(_) => "SinkPlan",
 
             // Explain.
@@ -250,6 +277,10 @@ impl PlanNode {
             // Insert.
             PlanNode::Insert(_) => "InsertPlan",
 
+            // Delete and update.
+            PlanNode::Delete(_) => "DeletePlan",
+            PlanNode::Update(_) => "UpdatePlan",
+
             // Copy.
             PlanNode::Copy(_) => "CopyPlan",
 
@@ -262,8 +293,11 @@ impl PlanNode {
             // Table.
             PlanNode::CreateTable(_) => "CreateTablePlan",
             PlanNode::DropTable(_) => "DropTablePlan",
+            PlanNode::AlterTable(_) => "AlterTablePlan",
+            PlanNode::RenameTable(_) => "RenameTablePlan",
             PlanNode::TruncateTable(_) => "TruncateTablePlan",
             PlanNode::OptimizeTable(_) => "OptimizeTablePlan",
+            PlanNode::AnalyzeTable(_) => "AnalyzeTablePlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
 
@@ -314,6 +348,8 @@ impl PlanNode {
             PlanNode::Select(v) => vec![v.input.clone()],
             PlanNode::Sort(v) => vec![v.input.clone()],
             PlanNode::SubQueryExpression(v) => v.get_inputs(),
+            PlanNode::Join(v) => vec![v.left.clone(), v.right.clone()],
+            PlanNode::WindowFunc(v) => vec![v.input.clone()],
             PlanNode::Sink(v) => vec![v.input.clone()],
 
             _ => vec![],
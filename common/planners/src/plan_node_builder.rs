@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
+use common_datavalues2::type_coercion::compare_coercion;
 use common_exception::Result;
 
 use crate::col;
@@ -30,13 +32,17 @@ use crate::Expression;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
 use crate::HavingPlan;
+use crate::JoinPlan;
+use crate::JoinType;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
+use crate::RequireColumnsVisitor;
 use crate::RewriteHelper;
 use crate::SelectPlan;
 use crate::SortPlan;
+use crate::WindowFuncPlan;
 
 pub enum AggregateMode {
     Partial,
@@ -214,6 +220,90 @@ impl PlanBuilder {
         })))
     }
 
+    /// Apply a hash join, `self` is the left(probe) side and `right` is the right(build) side.
+    ///
+    /// The equality conjuncts of `on` which compare a left expression with a right expression
+    /// become the join keys, the others are evaluated on the joined rows.
+    pub fn join(
+        &self,
+        right: &PlanNode,
+        join_type: JoinType,
+        on: Option<Expression>,
+    ) -> Result<Self> {
+        let left_schema = self.plan.schema();
+        let right_schema = right.schema();
+
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        let mut other_conditions: Option<Expression> = None;
+
+        let mut conjunctions = vec![];
+        if let Some(on) = on {
+            validate_expression(&on)?;
+            Self::split_conjunctions(&on, &mut conjunctions);
+        }
+
+        for conjunction in conjunctions {
+            match Self::split_equi_condition(&conjunction, &left_schema, &right_schema)? {
+                Some((left_key, right_key)) => {
+                    let left_type = remove_nullable(&left_key.to_data_type(&left_schema)?);
+                    let right_type = remove_nullable(&right_key.to_data_type(&right_schema)?);
+                    let key_type = compare_coercion(&left_type, &right_type)?;
+
+                    left_keys.push(Self::cast_join_key(left_key, &left_schema, &key_type)?);
+                    right_keys.push(Self::cast_join_key(right_key, &right_schema, &key_type)?);
+                }
+                None => {
+                    other_conditions = Some(match other_conditions {
+                        None => conjunction,
+                        Some(conditions) => conditions.and(conjunction),
+                    });
+                }
+            }
+        }
+
+        let join_type = match join_type {
+            JoinType::Cross if other_conditions.is_some() || !left_keys.is_empty() => {
+                JoinType::Inner
+            }
+            join_type => join_type,
+        };
+
+        let mut fields = vec![];
+        if join_type.output_left() {
+            for field in left_schema.fields() {
+                match join_type.is_left_nullable() {
+                    true => fields.push(DataField::new_nullable(
+                        field.name(),
+                        field.data_type().clone(),
+                    )),
+                    false => fields.push(field.clone()),
+                }
+            }
+        }
+        if join_type.output_right() {
+            for field in right_schema.fields() {
+                match join_type.is_right_nullable() {
+                    true => fields.push(DataField::new_nullable(
+                        field.name(),
+                        field.data_type().clone(),
+                    )),
+                    false => fields.push(field.clone()),
+                }
+            }
+        }
+
+        Ok(Self::from(&PlanNode::Join(JoinPlan {
+            join_type,
+            left_keys,
+            right_keys,
+            other_conditions,
+            schema: DataSchemaRefExt::create(fields),
+            left: Arc::new(self.plan.clone()),
+            right: Arc::new(right.clone()),
+        })))
+    }
+
     pub fn sort(&self, exprs: &[Expression]) -> Result<Self> {
         Ok(Self::from(&PlanNode::Sort(SortPlan {
             order_by: exprs.to_vec(),
@@ -222,6 +312,19 @@ impl PlanBuilder {
         })))
     }
 
+    /// Apply a window function and append its result to the input fields.
+    pub fn window_func(&self, expr: Expression) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let mut fields = input_schema.fields().clone();
+        fields.push(expr.to_data_field(&input_schema)?);
+
+        Ok(Self::from(&PlanNode::WindowFunc(WindowFuncPlan {
+            window_func: expr,
+            input: Arc::new(self.plan.clone()),
+            schema: DataSchemaRefExt::create(fields),
+        })))
+    }
+
     /// Apply a limit
     pub fn limit(&self, n: usize) -> Result<Self> {
         Ok(Self::from(&PlanNode::Limit(LimitPlan {
@@ -266,6 +369,67 @@ impl PlanBuilder {
         Ok(self.plan.clone())
     }
 
+    fn split_conjunctions(expr: &Expression, conjunctions: &mut Vec<Expression>) {
+        match expr {
+            Expression::BinaryExpression { left, op, right } if op.to_lowercase() == "and" => {
+                Self::split_conjunctions(left, conjunctions);
+                Self::split_conjunctions(right, conjunctions);
+            }
+            _ => conjunctions.push(expr.clone()),
+        }
+    }
+
+    // Returns the (left, right) expressions if expr is `left = right` with each side referring to only one input.
+    fn split_equi_condition(
+        expr: &Expression,
+        left_schema: &DataSchemaRef,
+        right_schema: &DataSchemaRef,
+    ) -> Result<Option<(Expression, Expression)>> {
+        if let Expression::BinaryExpression { left, op, right } = expr {
+            if op == "=" {
+                let lhs = RequireColumnsVisitor::collect_columns_from_expr(left)?;
+                let rhs = RequireColumnsVisitor::collect_columns_from_expr(right)?;
+
+                let belong_to = |columns: &HashSet<String>, schema: &DataSchemaRef| {
+                    !columns.is_empty() && columns.iter().all(|name| schema.has_field(name))
+                };
+
+                if belong_to(&lhs, left_schema) && belong_to(&rhs, right_schema) {
+                    return Ok(Some((left.as_ref().clone(), right.as_ref().clone())));
+                }
+
+                if belong_to(&lhs, right_schema) && belong_to(&rhs, left_schema) {
+                    return Ok(Some((right.as_ref().clone(), left.as_ref().clone())));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Join keys are compared by their serialized values, so both sides must have the same type.
+    fn cast_join_key(
+        key: Expression,
+        schema: &DataSchemaRef,
+        key_type: &DataTypePtr,
+    ) -> Result<Expression> {
+        let data_type = key.to_data_type(schema)?;
+        if remove_nullable(&data_type).data_type_id() == key_type.data_type_id() {
+            return Ok(key);
+        }
+
+        let data_type = match data_type.is_nullable() {
+            true => wrap_nullable(key_type),
+            false => key_type.clone(),
+        };
+
+        Ok(Expression::Cast {
+            expr: Box::new(key),
+            data_type,
+            is_nullable: false,
+        })
+    }
+
     fn wrap_subquery_plan(&self, exprs: &[Expression]) -> Result<Arc<PlanNode>> {
         let input = &self.plan;
         let sub_queries = RewriteHelper::collect_exprs_sub_queries(exprs)?;
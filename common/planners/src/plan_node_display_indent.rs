@@ -24,6 +24,7 @@ use crate::DropDatabasePlan;
 use crate::DropTablePlan;
 use crate::Expression;
 use crate::ExpressionPlan;
+use crate::JoinPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
@@ -31,6 +32,7 @@ use crate::ReadDataSourcePlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
+use crate::WindowFuncPlan;
 
 pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
@@ -67,6 +69,8 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
             PlanNode::ReadSource(plan) => Self::format_read_source(f, plan),
+            PlanNode::Join(plan) => Self::format_join(f, plan),
+            PlanNode::WindowFunc(plan) => Self::format_window_func(f, plan),
             PlanNode::CreateDatabase(plan) => Self::format_create_database(f, plan),
             PlanNode::DropDatabase(plan) => Self::format_drop_database(f, plan),
             PlanNode::CreateTable(plan) => Self::format_create_table(f, plan),
@@ -203,6 +207,21 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         write!(f, "Create sub queries sets: [{}]", names.join(", "))
     }
 
+    fn format_join(f: &mut Formatter, plan: &JoinPlan) -> fmt::Result {
+        write!(f, "HashJoin: {}", plan.join_type)?;
+        if !plan.left_keys.is_empty() {
+            write!(f, ", keys: {:?} = {:?}", plan.left_keys, plan.right_keys)?;
+        }
+        if let Some(conditions) = &plan.other_conditions {
+            write!(f, ", conditions: {:?}", conditions)?;
+        }
+        Ok(())
+    }
+
+    fn format_window_func(f: &mut Formatter, plan: &WindowFuncPlan) -> fmt::Result {
+        write!(f, "WindowFunc: {:?}", plan.window_func)
+    }
+
     fn format_read_source(f: &mut Formatter, plan: &ReadDataSourcePlan) -> fmt::Result {
         write!(
             f,
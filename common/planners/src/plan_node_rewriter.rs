@@ -26,14 +26,17 @@ use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AdminUseTenantPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
+use crate::AnalyzeTablePlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
 use crate::DropDatabasePlan;
@@ -51,6 +54,7 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
@@ -60,6 +64,7 @@ use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
+use crate::RenameTablePlan;
 use crate::RevokePrivilegePlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
@@ -71,7 +76,9 @@ use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowFuncPlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
 /// By default, a `PlanRewriter` will traverse the plan tree in pre-order and return rewritten plan tree.
@@ -111,6 +118,8 @@ pub trait PlanRewriter: Sized {
             PlanNode::LimitBy(plan) => self.rewrite_limit_by(plan),
             PlanNode::ReadSource(plan) => self.rewrite_read_data_source(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
+            PlanNode::Join(plan) => self.rewrite_join(plan),
+            PlanNode::WindowFunc(plan) => self.rewrite_window_func(plan),
             PlanNode::Sink(plan) => self.rewrite_sink(plan),
 
             // Query.
@@ -122,6 +131,10 @@ pub trait PlanRewriter: Sized {
             // Insert.
             PlanNode::Insert(plan) => self.rewrite_insert_into(plan),
 
+            // Delete and update.
+            PlanNode::Delete(plan) => self.rewrite_delete(plan),
+            PlanNode::Update(plan) => self.rewrite_update(plan),
+
             // Copy.
             PlanNode::Copy(plan) => self.rewrite_copy(plan),
 
@@ -134,8 +147,11 @@ pub trait PlanRewriter: Sized {
             // Table.
             PlanNode::CreateTable(plan) => self.rewrite_create_table(plan),
             PlanNode::DropTable(plan) => self.rewrite_drop_table(plan),
+            PlanNode::AlterTable(plan) => self.rewrite_alter_table(plan),
+            PlanNode::RenameTable(plan) => self.rewrite_rename_table(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
             PlanNode::OptimizeTable(plan) => self.rewrite_optimize_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.rewrite_analyze_table(plan),
             PlanNode::DescribeTable(plan) => self.rewrite_describe_table(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
 
@@ -273,6 +289,32 @@ pub trait PlanRewriter: Sized {
         self.rewrite_plan_node(plan.input.as_ref())
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        let new_left_keys = self.rewrite_exprs(&new_left.schema(), &plan.left_keys)?;
+        let new_right_keys = self.rewrite_exprs(&new_right.schema(), &plan.right_keys)?;
+
+        let mut new_plan = plan.clone();
+        new_plan.set_left(&new_left);
+        new_plan.set_right(&new_right);
+        new_plan.left_keys = new_left_keys;
+        new_plan.right_keys = new_right_keys;
+        if let Some(conditions) = &plan.other_conditions {
+            let joined_schema = new_plan.joined_schema();
+            new_plan.other_conditions = Some(self.rewrite_expr(&joined_schema, conditions)?);
+        }
+        Ok(PlanNode::Join(new_plan))
+    }
+
+    fn rewrite_window_func(&mut self, plan: &WindowFuncPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_window_func = self.rewrite_expr(&new_input.schema(), &plan.window_func)?;
+        PlanBuilder::from(&new_input)
+            .window_func(new_window_func)?
+            .build()
+    }
+
     fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
         let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
         let new_predicate = self.rewrite_expr(&new_input.schema(), &plan.predicate)?;
@@ -330,6 +372,10 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::OptimizeTable(plan.clone()))
     }
 
+    fn rewrite_analyze_table(&mut self, plan: &AnalyzeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AnalyzeTable(plan.clone()))
+    }
+
     fn rewrite_create_database(&mut self, plan: &CreateDatabasePlan) -> Result<PlanNode> {
         Ok(PlanNode::CreateDatabase(plan.clone()))
     }
@@ -358,6 +404,14 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::DropTable(plan.clone()))
     }
 
+    fn rewrite_alter_table(&mut self, plan: &AlterTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterTable(plan.clone()))
+    }
+
+    fn rewrite_rename_table(&mut self, plan: &RenameTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::RenameTable(plan.clone()))
+    }
+
     fn rewrite_drop_database(&mut self, plan: &DropDatabasePlan) -> Result<PlanNode> {
         Ok(PlanNode::DropDatabase(plan.clone()))
     }
@@ -366,6 +420,14 @@ pub trait PlanRewriter: Sized {
         Ok(PlanNode::Insert(plan.clone()))
     }
 
+    fn rewrite_delete(&mut self, plan: &DeletePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Delete(plan.clone()))
+    }
+
+    fn rewrite_update(&mut self, plan: &UpdatePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Update(plan.clone()))
+    }
+
     fn rewrite_copy(&mut self, plan: &CopyPlan) -> Result<PlanNode> {
         Ok(PlanNode::Copy(plan.clone()))
     }
@@ -581,6 +643,50 @@ impl RewriteHelper {
                 }
             }
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                window_frame,
+            } => {
+                let new_args = args
+                    .iter()
+                    .map(|v| RewriteHelper::expr_rewrite_alias(v, data))
+                    .collect::<Result<Vec<_>>>()?;
+                let new_partition_by = partition_by
+                    .iter()
+                    .map(|v| RewriteHelper::expr_rewrite_alias(v, data))
+                    .collect::<Result<Vec<_>>>()?;
+                let new_order_by = order_by
+                    .iter()
+                    .map(|v| match v {
+                        Expression::Sort {
+                            expr,
+                            asc,
+                            nulls_first,
+                            origin_expr,
+                        } => Ok(Expression::Sort {
+                            expr: Box::new(RewriteHelper::expr_rewrite_alias(expr, data)?),
+                            asc: *asc,
+                            nulls_first: *nulls_first,
+                            origin_expr: origin_expr.clone(),
+                        }),
+                        other => RewriteHelper::expr_rewrite_alias(other, data),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: new_args,
+                    partition_by: new_partition_by,
+                    order_by: new_order_by,
+                    window_frame: *window_frame,
+                })
+            }
+
             Expression::Alias(alias, plan) => {
                 if data.inside_aliases.contains(alias) {
                     return Result::Err(ErrorCode::SyntaxException(format!(
@@ -677,6 +783,17 @@ impl RewriteHelper {
             }
             Expression::ScalarFunction { args, .. } => args.clone(),
             Expression::AggregateFunction { args, .. } => args.clone(),
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut v = args.clone();
+                v.extend_from_slice(partition_by);
+                v.extend_from_slice(order_by);
+                v
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => vec![expr.as_ref().clone()],
             Expression::Cast { expr, .. } => vec![expr.as_ref().clone()],
@@ -715,6 +832,19 @@ impl RewriteHelper {
                 }
                 v
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut v = vec![];
+                for arg in args.iter().chain(partition_by).chain(order_by) {
+                    let mut col = Self::expression_plan_columns(arg)?;
+                    v.append(&mut col);
+                }
+                v
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => Self::expression_plan_columns(expr)?,
             Expression::Cast { expr, .. } => Self::expression_plan_columns(expr)?,
@@ -787,6 +917,25 @@ impl RewriteHelper {
                 params: params.clone(),
                 args: expressions.to_vec(),
             },
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                window_frame,
+                ..
+            } => {
+                let (args_expr, others) = expressions.split_at(args.len());
+                let (partition_by_expr, order_by_expr) = others.split_at(partition_by.len());
+                Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: args_expr.to_vec(),
+                    partition_by: partition_by_expr.to_vec(),
+                    order_by: order_by_expr.to_vec(),
+                    window_frame: *window_frame,
+                }
+            }
             other => other.clone(),
         }
     }
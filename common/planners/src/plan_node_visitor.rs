@@ -19,14 +19,17 @@ use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AdminUseTenantPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
 use crate::AlterUserPlan;
 use crate::AlterUserUDFPlan;
+use crate::AnalyzeTablePlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
 use crate::CreateUserStagePlan;
 use crate::CreateUserUDFPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
 use crate::DescribeUserStagePlan;
 use crate::DropDatabasePlan;
@@ -42,6 +45,7 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
@@ -50,6 +54,7 @@ use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
+use crate::RenameTablePlan;
 use crate::RevokePrivilegePlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
@@ -61,7 +66,9 @@ use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowFuncPlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
 ///
@@ -123,6 +130,8 @@ pub trait PlanVisitor {
             PlanNode::LimitBy(plan) => self.visit_limit_by(plan),
             PlanNode::ReadSource(plan) => self.visit_read_data_source(plan),
             PlanNode::SubQueryExpression(plan) => self.visit_sub_queries_sets(plan),
+            PlanNode::Join(plan) => self.visit_join(plan),
+            PlanNode::WindowFunc(plan) => self.visit_window_func(plan),
             PlanNode::Sink(plan) => self.visit_append(plan),
 
             // Query.
@@ -134,6 +143,10 @@ pub trait PlanVisitor {
             // Insert.
             PlanNode::Insert(plan) => self.visit_insert_into(plan),
 
+            // Delete and update.
+            PlanNode::Delete(plan) => self.visit_delete(plan),
+            PlanNode::Update(plan) => self.visit_update(plan),
+
             // Copy.
             PlanNode::Copy(plan) => self.visit_copy(plan),
 
@@ -146,10 +159,13 @@ pub trait PlanVisitor {
             // Table.
             PlanNode::CreateTable(plan) => self.visit_create_table(plan),
             PlanNode::DropTable(plan) => self.visit_drop_table(plan),
+            PlanNode::AlterTable(plan) => self.visit_alter_table(plan),
+            PlanNode::RenameTable(plan) => self.visit_rename_table(plan),
             PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
             PlanNode::OptimizeTable(plan) => self.visit_optimize_table(plan),
+            PlanNode::AnalyzeTable(plan) => self.visit_analyze_table(plan),
 
             // User.
             PlanNode::CreateUser(plan) => self.visit_create_user(plan),
@@ -252,6 +268,22 @@ pub trait PlanVisitor {
         self.visit_exprs(&plan.expressions)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref())?;
+        self.visit_plan_node(plan.right.as_ref())?;
+        self.visit_exprs(&plan.left_keys)?;
+        self.visit_exprs(&plan.right_keys)?;
+        match &plan.other_conditions {
+            None => Ok(()),
+            Some(conditions) => self.visit_expr(conditions),
+        }
+    }
+
+    fn visit_window_func(&mut self, plan: &WindowFuncPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_expr(&plan.window_func)
+    }
+
     fn visit_filter(&mut self, plan: &FilterPlan) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref())?;
         self.visit_expr(&plan.predicate)
@@ -327,6 +359,10 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_analyze_table(&mut self, _: &AnalyzeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_describe_user_stage(&mut self, _: &DescribeUserStagePlan) -> Result<()> {
         Ok(())
     }
@@ -335,6 +371,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_alter_table(&mut self, _: &AlterTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_rename_table(&mut self, _: &RenameTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_use_database(&mut self, _: &UseDatabasePlan) -> Result<()> {
         Ok(())
     }
@@ -351,6 +395,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_delete(&mut self, _: &DeletePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_update(&mut self, _: &UpdatePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_copy(&mut self, _: &CopyPlan) -> Result<()> {
         Ok(())
     }
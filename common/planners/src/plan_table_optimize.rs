@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use bitflags::bitflags;
 use common_datavalues2::DataSchema;
@@ -24,6 +25,7 @@ bitflags! {
         const PURGE   = 0b00000001;
         const COMPACT = 0b00000010;
         const ALL = Self::PURGE.bits | Self::COMPACT.bits;
+        const PURGE_ORPHANS = 0b00000100;
     }
 }
 
@@ -32,6 +34,9 @@ pub struct OptimizeTablePlan {
     pub database: String,
     pub table: String,
     pub operation: Optimization,
+    /// Only the orphan files older than it are purged, None for the default retention.
+    #[serde(default)]
+    pub orphans_older_than: Option<Duration>,
 }
 
 impl OptimizeTablePlan {
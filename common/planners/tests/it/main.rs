@@ -22,8 +22,10 @@ mod plan_expression_monotonicity;
 mod plan_extras;
 mod plan_filter;
 mod plan_having;
+mod plan_join;
 mod plan_limit;
 mod plan_projection;
 mod plan_rewriter;
 mod plan_select;
+mod plan_window;
 mod test;
@@ -0,0 +1,86 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+
+use crate::test::Test;
+
+#[test]
+fn test_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(100)?)
+        .project(&[col("number").alias("b")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .join(
+            &right,
+            JoinType::Left,
+            Some(col("number").eq(col("b")).and(col("b").gt(lit(1i64)))),
+        )?
+        .build()?;
+
+    let expect = "\
+    HashJoin: LEFT OUTER, keys: [number] = [b], conditions: (b > 1)\
+    \n  ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000, partitions_scanned: 8, partitions_total: 8]\
+    \n  Projection: number as b:UInt64\
+    \n    ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800, partitions_scanned: 8, partitions_total: 8]";
+    assert_eq!(expect, format!("{:?}", plan));
+
+    // The right side of a left outer join is filled with NULL for unmatched rows.
+    let schema = plan.schema();
+    assert_eq!(2, schema.fields().len());
+    assert!(!schema.field_with_name("number")?.is_nullable());
+    assert!(schema.field_with_name("b")?.is_nullable());
+
+    Ok(())
+}
+
+#[test]
+fn test_cross_join_plan() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[col("number").alias("b")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .join(&right, JoinType::Cross, None)?
+        .build()?;
+    match plan {
+        PlanNode::Join(join) => {
+            assert_eq!(JoinType::Cross, join.join_type);
+            assert!(join.left_keys.is_empty());
+            assert!(join.other_conditions.is_none());
+        }
+        _ => unreachable!(),
+    }
+
+    // Cross join with conditions is an inner join.
+    let plan = PlanBuilder::from(&left)
+        .join(&right, JoinType::Cross, Some(col("number").eq(col("b"))))?
+        .build()?;
+    match plan {
+        PlanNode::Join(join) => {
+            assert_eq!(JoinType::Inner, join.join_type);
+            assert_eq!(1, join.left_keys.len());
+            assert_eq!(1, join.right_keys.len());
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
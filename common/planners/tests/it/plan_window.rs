@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_functions::windows::WindowFrame;
+use common_functions::windows::WindowFrameBound;
+use common_functions::windows::WindowFrameUnits;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+
+#[test]
+fn test_window_func_plan() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let window_frame = WindowFrame::create(
+        WindowFrameUnits::Rows,
+        WindowFrameBound::Preceding(Some(1)),
+        WindowFrameBound::CurrentRow,
+    )?;
+
+    let window_func = Expression::WindowFunction {
+        op: "sum".to_string(),
+        params: vec![],
+        args: vec![col("number")],
+        partition_by: vec![],
+        order_by: vec![sort("number", false, false)],
+        window_frame: Some(window_frame),
+    };
+
+    let plan = PlanBuilder::from(&source)
+        .window_func(window_func)?
+        .project(&[col(
+            "sum(number) over (order by number desc rows between 1 preceding and current row)",
+        )])?
+        .build()?;
+
+    let expect = "\
+    Projection: sum(number) over (order by number desc rows between 1 preceding and current row):UInt64\
+    \n  WindowFunc: sum(number) over (order by number desc rows between 1 preceding and current row)\
+    \n    ReadDataSource: scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000, partitions_scanned: 8, partitions_total: 8]";
+    assert_eq!(expect, format!("{:?}", plan));
+    Ok(())
+}
+
+#[test]
+fn test_window_func_plan_schema() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10)?;
+    let window_func = Expression::WindowFunction {
+        op: "row_number".to_string(),
+        params: vec![],
+        args: vec![],
+        partition_by: vec![col("number")],
+        order_by: vec![],
+        window_frame: None,
+    };
+
+    let plan = PlanBuilder::from(&source)
+        .window_func(window_func)?
+        .build()?;
+    let schema = plan.schema();
+    assert_eq!(2, schema.fields().len());
+    assert_eq!("number", schema.field(0).name());
+    assert_eq!(
+        "row_number() over (partition by number)",
+        schema.field(1).name()
+    );
+    Ok(())
+}
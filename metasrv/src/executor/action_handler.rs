@@ -83,6 +83,14 @@ impl ActionHandler {
                 let r = self.handle(a).await.map_err(SerializedError::from);
                 RaftReply::from(r)
             }
+            MetaGrpcWriteReq::UpdateTableMeta(a) => {
+                let r = self.handle(a).await.map_err(SerializedError::from);
+                RaftReply::from(r)
+            }
+            MetaGrpcWriteReq::RenameTable(a) => {
+                let r = self.handle(a).await.map_err(SerializedError::from);
+                RaftReply::from(r)
+            }
         }
     }
 
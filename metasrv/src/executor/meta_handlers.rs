@@ -23,6 +23,8 @@ use common_meta_types::Cmd::CreateDatabase;
 use common_meta_types::Cmd::CreateTable;
 use common_meta_types::Cmd::DropDatabase;
 use common_meta_types::Cmd::DropTable;
+use common_meta_types::Cmd::RenameTable;
+use common_meta_types::Cmd::UpdateTableMeta;
 use common_meta_types::Cmd::UpsertTableOptions;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateDatabaseReq;
@@ -40,9 +42,13 @@ use common_meta_types::ListDatabaseReq;
 use common_meta_types::ListTableReq;
 use common_meta_types::LogEntry;
 use common_meta_types::OkOrExist;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
@@ -296,3 +302,81 @@ impl RequestHandler<UpsertTableOptionReq> for ActionHandler {
         Ok(UpsertTableOptionReply {})
     }
 }
+
+#[async_trait::async_trait]
+impl RequestHandler<UpdateTableMetaReq> for ActionHandler {
+    async fn handle(
+        &self,
+        req: UpdateTableMetaReq,
+    ) -> common_exception::Result<UpdateTableMetaReply> {
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpdateTableMeta(req.clone()),
+        };
+
+        let res = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        if !res.changed() {
+            let ch: Change<TableMeta> = res.try_into().unwrap();
+            let (prev, _result) = ch.unwrap();
+
+            return Err(ErrorCode::TableVersionMismatched(format!(
+                "targeting version {:?}, current version {}",
+                req.seq, prev.seq,
+            )));
+        }
+
+        Ok(UpdateTableMetaReply {})
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<RenameTableReq> for ActionHandler {
+    async fn handle(&self, req: RenameTableReq) -> common_exception::Result<RenameTableReply> {
+        let cr = LogEntry {
+            txid: None,
+            cmd: RenameTable {
+                tenant: req.tenant.clone(),
+                db_name: req.db.clone(),
+                table_name: req.table.clone(),
+                new_db_name: req.new_db.clone(),
+                new_table_name: req.new_table.clone(),
+            },
+        };
+
+        let res = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        let mut ch: Change<TableMeta> = res.try_into().unwrap();
+        let table_id = ch.ident.take();
+
+        if ch.prev.is_none() {
+            return if req.if_exists {
+                Ok(RenameTableReply { table_id: 0 })
+            } else {
+                Err(ErrorCode::UnknownTable(format!(
+                    "Unknown table: '{:}'",
+                    req.table
+                )))
+            };
+        }
+
+        if !ch.changed() {
+            return Err(ErrorCode::TableAlreadyExists(format!(
+                "table exists: {}",
+                req.new_table
+            )));
+        }
+
+        Ok(RenameTableReply {
+            table_id: table_id.unwrap(),
+        })
+    }
+}
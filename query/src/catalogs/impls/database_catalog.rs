@@ -24,9 +24,13 @@ use common_meta_types::DropDatabaseReq;
 use common_meta_types::DropTableReply;
 use common_meta_types::DropTableReq;
 use common_meta_types::MetaId;
+use common_meta_types::RenameTableReply;
+use common_meta_types::RenameTableReq;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_meta_types::UpdateTableMetaReply;
+use common_meta_types::UpdateTableMetaReq;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
 use common_tracing::tracing;
@@ -276,6 +280,33 @@ impl Catalog for DatabaseCatalog {
         self.mutable_catalog.upsert_table_option(req).await
     }
 
+    async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        // update table meta in BOTTOM layer only
+        self.mutable_catalog.update_table_meta(req).await
+    }
+
+    async fn rename_table(&self, req: RenameTableReq) -> Result<RenameTableReply> {
+        if req.tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while rename table)",
+            ));
+        }
+        tracing::info!("Rename table from req:{:?}", req);
+
+        if self
+            .immutable_catalog
+            .exists_database(&req.tenant, &req.db)
+            .await?
+            || self
+                .immutable_catalog
+                .exists_database(&req.tenant, &req.new_db)
+                .await?
+        {
+            return self.immutable_catalog.rename_table(req).await;
+        }
+        self.mutable_catalog.rename_table(req).await
+    }
+
     fn get_table_function(
         &self,
         func_name: &str,
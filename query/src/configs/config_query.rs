@@ -63,6 +63,8 @@ const QUERY_TABLE_ENGINE_PARQUET_ENABLED: &str = "QUERY_TABLE_ENGINE_PARQUET_ENA
 const QUERY_TABLE_ENGINE_MEMORY_ENABLED: &str = "QUERY_TABLE_ENGINE_MEMORY_ENABLED";
 const QUERY_DATABASE_ENGINE_GITHUB_ENABLED: &str = "QUERY_DATABASE_ENGINE_GITHUB_ENABLED";
 
+const QUERY_QUERY_RESULT_CACHE_COUNT: &str = "QUERY_QUERY_RESULT_CACHE_COUNT";
+
 const QUERY_MANAGEMENT_MODE: &str = "QUERY_MANAGEMENT_MODE";
 const QUERY_JWT_KEY_FILE: &str = "QUERY_JWT_KEY_FILE";
 
@@ -183,6 +185,10 @@ pub struct QueryConfig {
     #[clap(long, env = QUERY_MAX_QUERY_LOG_SIZE, default_value = "10000")]
     pub max_query_log_size: usize,
 
+    /// Max number of cached SELECT query results
+    #[clap(long, env = QUERY_QUERY_RESULT_CACHE_COUNT, default_value = "1024")]
+    pub query_result_cache_count: u64,
+
     /// Table Cached enabled
     #[clap(long, env = QUERY_TABLE_CACHE_ENABLED)]
     pub table_cache_enabled: bool,
@@ -252,6 +258,7 @@ impl Default for QueryConfig {
             database_engine_github_enabled: true,
             wait_timeout_mills: 5000,
             max_query_log_size: 10000,
+            query_result_cache_count: 1024,
             table_cache_enabled: false,
             table_cache_snapshot_count: 256,
             table_cache_segment_count: 10240,
@@ -451,6 +458,13 @@ impl QueryConfig {
             bool,
             QUERY_DATABASE_ENGINE_GITHUB_ENABLED
         );
+        env_helper!(
+            mut_config,
+            query,
+            query_result_cache_count,
+            u64,
+            QUERY_QUERY_RESULT_CACHE_COUNT
+        );
         env_helper!(
             mut_config,
             query,
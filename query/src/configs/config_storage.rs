@@ -41,11 +41,17 @@ const AZURE_STORAGE_ACCOUNT: &str = "AZURE_STORAGE_ACCOUNT";
 const AZURE_BLOB_MASTER_KEY: &str = "AZURE_BLOB_MASTER_KEY";
 const AZURE_BLOB_CONTAINER: &str = "AZURE_BLOB_CONTAINER";
 
+// Google Cloud Storage env.
+const GCS_STORAGE_ENDPOINT_URL: &str = "GCS_STORAGE_ENDPOINT_URL";
+const GCS_STORAGE_BUCKET: &str = "GCS_STORAGE_BUCKET";
+const GCS_STORAGE_CREDENTIAL: &str = "GCS_STORAGE_CREDENTIAL";
+
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum StorageType {
     Disk,
     S3,
     AzureStorageBlob,
+    Gcs,
 }
 
 // Implement the trait
@@ -57,6 +63,7 @@ impl FromStr for StorageType {
             "disk" => Ok(StorageType::Disk),
             "s3" => Ok(StorageType::S3),
             "azure_storage_blob" => Ok(StorageType::AzureStorageBlob),
+            "gcs" => Ok(StorageType::Gcs),
             _ => Err("no match for storage type"),
         }
     }
@@ -168,12 +175,48 @@ impl fmt::Debug for AzureStorageBlobConfig {
     }
 }
 
+#[derive(Clone, PartialEq, Serialize, Deserialize, Args)]
+#[serde(default)]
+pub struct GcsStorageConfig {
+    /// Endpoint URL for GCS storage, https://storage.googleapis.com if it's empty
+    #[clap(long = "gcs-endpoint-url", env = GCS_STORAGE_ENDPOINT_URL, default_value = "")]
+    pub endpoint_url: String,
+
+    /// Bucket for GCS storage
+    #[clap(long = "gcs-bucket", env = GCS_STORAGE_BUCKET, default_value = "")]
+    pub bucket: String,
+
+    /// Base64 encoded JSON key of the service account for GCS storage, the requests are sent
+    /// anonymously if it's empty
+    #[clap(long = "gcs-credential", env = GCS_STORAGE_CREDENTIAL, default_value = "")]
+    pub credential: String,
+}
+
+impl Default for GcsStorageConfig {
+    fn default() -> Self {
+        Self {
+            endpoint_url: "".to_string(),
+            bucket: "".to_string(),
+            credential: "".to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for GcsStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, "gcs.storage.endpoint_url: \"{}\", ", self.endpoint_url)?;
+        write!(f, "gcs.storage.bucket: \"{}\", ", self.bucket)?;
+        write!(f, "}}")
+    }
+}
+
 /// Storage config group.
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Args)]
 #[serde(default)]
 pub struct StorageConfig {
-    /// Current storage type: disk|s3
+    /// Current storage type: disk|s3|azure_storage_blob|gcs
     #[clap(long, env = STORAGE_TYPE, default_value = "disk")]
     pub storage_type: String,
 
@@ -188,6 +231,10 @@ pub struct StorageConfig {
     // azure storage blob config.
     #[clap(flatten)]
     pub azure_storage_blob: AzureStorageBlobConfig,
+
+    // google cloud storage config.
+    #[clap(flatten)]
+    pub gcs: GcsStorageConfig,
 }
 
 impl Default for StorageConfig {
@@ -197,6 +244,7 @@ impl Default for StorageConfig {
             disk: DiskStorageConfig::default(),
             s3: S3StorageConfig::default(),
             azure_storage_blob: AzureStorageBlobConfig::default(),
+            gcs: GcsStorageConfig::default(),
         }
     }
 }
@@ -262,5 +310,22 @@ impl StorageConfig {
             String,
             AZURE_BLOB_MASTER_KEY
         );
+
+        // Google Cloud Storage.
+        env_helper!(
+            mut_config.storage,
+            gcs,
+            endpoint_url,
+            String,
+            GCS_STORAGE_ENDPOINT_URL
+        );
+        env_helper!(mut_config.storage, gcs, bucket, String, GCS_STORAGE_BUCKET);
+        env_helper!(
+            mut_config.storage,
+            gcs,
+            credential,
+            String,
+            GCS_STORAGE_CREDENTIAL
+        );
     }
 }
@@ -28,5 +28,6 @@ pub use config_meta::MetaConfig;
 pub use config_query::QueryConfig;
 pub use config_storage::AzureStorageBlobConfig;
 pub use config_storage::DiskStorageConfig;
+pub use config_storage::GcsStorageConfig;
 pub use config_storage::S3StorageConfig;
 pub use config_storage::StorageConfig;
@@ -44,6 +44,7 @@ impl ManagementModeAccess {
                 // Table.
                 | PlanNode::CreateTable(_)
                 | PlanNode::DropTable(_)
+                | PlanNode::RenameTable(_)
                 | PlanNode::DescribeTable(_)
                 | PlanNode::ShowCreateTable(_)
 
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_dal2::credential::Credential;
 use common_dal2::readers::SeekableReader;
 use common_dal2::services::s3;
+use common_dal2::Object;
 use common_dal2::Operator as DalOperator;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -28,10 +29,12 @@ use common_streams::SourceFactory;
 use common_streams::SourceParams;
 use common_streams::SourceStream;
 use futures::io::BufReader;
+use futures::StreamExt;
 use futures::TryStreamExt;
 use nom::bytes::complete::tag;
 use nom::bytes::complete::take_until;
 use nom::IResult;
+use regex::Regex;
 
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -73,22 +76,34 @@ impl Interpreter for CopyInterpreter {
         let (stage, path) = c.unwrap();
 
         let acc = get_dal_by_stage(self.ctx.clone(), stage).await?;
+        let files = list_files(&acc, path).await?;
+        if files.is_empty() {
+            return Err(ErrorCode::BadArguments(format!(
+                "No file matches {}",
+                location
+            )));
+        }
+
         let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
-        let o = acc.stat(path).run().await.unwrap();
-        let reader = SeekableReader::new(acc, path, o.size);
         let read_buffer_size = self.ctx.get_settings().get_storage_read_buffer_size()?;
-        let reader = BufReader::with_capacity(read_buffer_size as usize, reader);
-        let source_params = SourceParams {
-            reader,
-            path,
-            format: self.plan.format.as_str(),
-            schema: self.plan.schema.clone(),
-            max_block_size,
-            projection: (0..self.plan.schema().fields().len()).collect(),
-            options: &self.plan.options,
-        };
-        let source_stream = SourceStream::new(SourceFactory::try_get(source_params)?);
-        let input_stream = source_stream.execute().await?;
+        let mut file_streams = Vec::with_capacity(files.len());
+        for file in &files {
+            let reader = SeekableReader::new(acc.clone(), &file.path, file.size);
+            let reader = BufReader::with_capacity(read_buffer_size as usize, reader);
+            let source_params = SourceParams {
+                reader,
+                path: &file.path,
+                format: self.plan.format.as_str(),
+                schema: self.plan.schema.clone(),
+                max_block_size,
+                projection: (0..self.plan.schema().fields().len()).collect(),
+                options: &self.plan.options,
+            };
+            let source_stream = SourceStream::new(SourceFactory::try_get(source_params)?);
+            file_streams.push(source_stream.execute().await?);
+        }
+        // the files are loaded one by one, and committed at once
+        let input_stream = Box::pin(futures::stream::iter(file_streams).flatten());
         let progress_stream = Box::pin(ProgressStream::try_create(
             input_stream,
             self.ctx.get_scan_progress(),
@@ -116,6 +131,64 @@ fn extract_stage_location(path: &str) -> IResult<&str, &str> {
     Ok((stage, path))
 }
 
+/// The files to be copied from the path of a stage:
+/// - all the files under the directory if the path ends with `/`, e.g. `/tutorials/`.
+/// - the files of the directory matching the path, if it contains wildcards `*` or `?`,
+///   e.g. `/tutorials/*.csv`.
+/// - otherwise the file of the path.
+async fn list_files(acc: &DalOperator, path: &str) -> Result<Vec<Object>> {
+    if path.ends_with('/') {
+        return acc
+            .list(path)
+            .recursive(true)
+            .run()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()));
+    }
+
+    if path.contains(|c| c == '*' || c == '?') {
+        let (dir, _) = path.rsplit_once('/').unwrap_or(("", path));
+        // the listed paths are relative to the root, without the leading `/`
+        let pattern = glob_to_regex(path.trim_start_matches('/'))?;
+        let objects: Vec<Object> = acc
+            .list(dir)
+            .run()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+        return Ok(objects
+            .into_iter()
+            .filter(|o| !o.is_dir() && pattern.is_match(&o.path))
+            .collect());
+    }
+
+    let o = acc
+        .stat(path)
+        .run()
+        .await
+        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+    Ok(vec![o])
+}
+
+/// `*` matches any characters except `/`, and `?` matches one of them.
+fn glob_to_regex(pattern: &str) -> Result<Regex> {
+    let mut re = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).map_err(|e| ErrorCode::BadArguments(e.to_string()))
+}
+
 //  this is mock implementation from env
 //  todo: support get the stage config from metadata
 async fn get_dal_by_stage(ctx: Arc<QueryContext>, _stage_name: &str) -> Result<DalOperator> {
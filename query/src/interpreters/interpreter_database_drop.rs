@@ -53,6 +53,10 @@ impl Interpreter for DropDatabaseInterpreter {
 
         let catalog = self.ctx.get_catalog();
         catalog.drop_database(self.plan.clone().into()).await?;
+        // the tables of the dropped database are not accounted to the storage quota anymore
+        self.ctx
+            .get_storage_usage_cache()
+            .invalidate(&self.ctx.get_tenant());
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
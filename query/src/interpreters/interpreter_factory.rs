@@ -19,14 +19,17 @@ use common_exception::Result;
 use common_planners::PlanNode;
 
 use super::DescribeUserStageInterpreter;
+use crate::interpreters::AlterTableInterpreter;
 use crate::interpreters::AlterUserInterpreter;
 use crate::interpreters::AlterUserUDFInterpreter;
+use crate::interpreters::AnalyzeTableInterpreter;
 use crate::interpreters::CopyInterpreter;
 use crate::interpreters::CreateDatabaseInterpreter;
 use crate::interpreters::CreateTableInterpreter;
 use crate::interpreters::CreateUserInterpreter;
 use crate::interpreters::CreateUserStageInterpreter;
 use crate::interpreters::CreateUserUDFInterpreter;
+use crate::interpreters::DeleteInterpreter;
 use crate::interpreters::DescribeTableInterpreter;
 use crate::interpreters::DropDatabaseInterpreter;
 use crate::interpreters::DropTableInterpreter;
@@ -40,6 +43,7 @@ use crate::interpreters::InterceptorInterpreter;
 use crate::interpreters::Interpreter;
 use crate::interpreters::KillInterpreter;
 use crate::interpreters::OptimizeTableInterpreter;
+use crate::interpreters::RenameTableInterpreter;
 use crate::interpreters::RevokePrivilegeInterpreter;
 use crate::interpreters::SelectInterpreter;
 use crate::interpreters::SettingInterpreter;
@@ -48,6 +52,7 @@ use crate::interpreters::ShowCreateTableInterpreter;
 use crate::interpreters::ShowDatabasesInterpreter;
 use crate::interpreters::ShowGrantsInterpreter;
 use crate::interpreters::TruncateTableInterpreter;
+use crate::interpreters::UpdateInterpreter;
 use crate::interpreters::UseDatabaseInterpreter;
 use crate::interpreters::UseTenantInterpreter;
 use crate::sessions::QueryContext;
@@ -70,6 +75,10 @@ impl InterpreterFactory {
             // Copy.
             PlanNode::Copy(v) => CopyInterpreter::try_create(ctx_clone, v),
 
+            // Delete and update.
+            PlanNode::Delete(v) => DeleteInterpreter::try_create(ctx_clone, v),
+            PlanNode::Update(v) => UpdateInterpreter::try_create(ctx_clone, v),
+
             // Database.
             PlanNode::CreateDatabase(v) => CreateDatabaseInterpreter::try_create(ctx_clone, v),
             PlanNode::DropDatabase(v) => DropDatabaseInterpreter::try_create(ctx_clone, v),
@@ -81,9 +90,12 @@ impl InterpreterFactory {
             // Table.
             PlanNode::CreateTable(v) => CreateTableInterpreter::try_create(ctx_clone, v),
             PlanNode::DropTable(v) => DropTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::AlterTable(v) => AlterTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::RenameTable(v) => RenameTableInterpreter::try_create(ctx_clone, v),
             PlanNode::DescribeTable(v) => DescribeTableInterpreter::try_create(ctx_clone, v),
             PlanNode::TruncateTable(v) => TruncateTableInterpreter::try_create(ctx_clone, v),
             PlanNode::OptimizeTable(v) => OptimizeTableInterpreter::try_create(ctx_clone, v),
+            PlanNode::AnalyzeTable(v) => AnalyzeTableInterpreter::try_create(ctx_clone, v),
 
             // User.
             PlanNode::CreateUser(v) => CreateUserInterpreter::try_create(ctx_clone, v),
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::GrantObject;
@@ -24,6 +25,7 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use futures::TryStreamExt;
 
+use super::interpreter_insert_materialized_views::InsertIntoMaterializedViews;
 use super::interpreter_insert_with_stream::SendableWithSchema;
 use crate::interpreters::interpreter_insert_with_stream::InsertWithStream;
 use crate::interpreters::plan_schedulers::InsertWithPlan;
@@ -106,13 +108,21 @@ impl Interpreter for InsertInterpreter {
                 with_stream.append_stream(stream).await
             }
         }?;
+        let append_logs: Vec<DataBlock> = append_logs.try_collect().await?;
+
+        // apply the appended blocks to the materialized views of the table, before the table
+        // is committed, so that a failing view leaves the table unchanged
+        let materialized_views = InsertIntoMaterializedViews::new(&self.ctx);
+        let view_insertions = materialized_views
+            .prepare(table.as_ref(), &append_logs, self.plan.overwrite)
+            .await?;
+
         // feed back the append operation logs to table
         table
-            .commit_insertion(
-                self.ctx.clone(),
-                append_logs.try_collect().await?,
-                self.plan.overwrite,
-            )
+            .commit_insertion(self.ctx.clone(), append_logs, self.plan.overwrite)
+            .await?;
+        materialized_views
+            .commit(view_insertions, self.plan.overwrite)
             .await?;
 
         Ok(Box::pin(DataBlockStream::create(
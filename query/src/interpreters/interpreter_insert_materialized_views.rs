@@ -0,0 +1,269 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UpsertTableOptionReq;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ReadDataSourcePlan;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use futures::TryStreamExt;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::plan_schedulers::InsertWithPlan;
+use crate::sessions::QueryContext;
+use crate::sql::PlanParser;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::FuseTable;
+use crate::storages::view::is_materialized_view;
+use crate::storages::view::materialized_view_query;
+use crate::storages::view::materialized_view_source;
+use crate::storages::view::MaterializedViewRef;
+use crate::storages::view::MATERIALIZED_VIEWS;
+use crate::storages::Table;
+
+// The max number of retries to update the materialized views of a source table being changed.
+const MAX_UPDATE_RETRIES: usize = 10;
+
+/// The blocks appended to a materialized view by an insertion, not committed yet.
+pub struct MaterializedViewInsertion {
+    view: Arc<dyn Table>,
+    append_logs: Vec<DataBlock>,
+}
+
+/// Applies the insertion into a source table to its materialized views, the query of a view
+/// runs over the blocks appended to the source only, and the result is appended to the view.
+///
+/// The queries of the views run before the source table is committed, so a failing query
+/// fails the insertion and leaves the source table unchanged. The views are committed right
+/// after the source table, if the commit of a view fails, e.g. the view is dropped or changed
+/// concurrently, the insertion fails while the rows committed to the source table are kept,
+/// and the view misses them.
+pub struct InsertIntoMaterializedViews<'a> {
+    ctx: &'a Arc<QueryContext>,
+}
+
+impl<'a> InsertIntoMaterializedViews<'a> {
+    pub fn new(ctx: &'a Arc<QueryContext>) -> Self {
+        Self { ctx }
+    }
+
+    /// Runs the queries of the views over the blocks appended to the source, the views of the
+    /// views are prepared recursively, so the future is boxed.
+    pub fn prepare<'b>(
+        &'b self,
+        source: &'b dyn Table,
+        append_logs: &'b [DataBlock],
+        overwrite: bool,
+    ) -> BoxFuture<'b, Result<Vec<MaterializedViewInsertion>>> {
+        async move {
+            let views = MaterializedViewRef::list(source.get_table_info().options())?;
+            if views.is_empty() {
+                return Ok(vec![]);
+            }
+
+            let mut blocks = vec![];
+            for append_log in append_logs {
+                let append_log = AppendOperationLogEntry::try_from(append_log)?;
+                blocks.extend(append_log.segment_info.blocks);
+            }
+
+            // The views are replaced by the result over the new blocks, if the source is overwritten.
+            if blocks.is_empty() && !overwrite {
+                return Ok(vec![]);
+            }
+
+            let mut insertions = vec![];
+            for view_ref in &views {
+                if let Some(view) = self.get_view(source, view_ref).await? {
+                    let append_logs = self.run_view_query(source, view.as_ref(), &blocks).await?;
+                    let view_insertions =
+                        self.prepare(view.as_ref(), &append_logs, overwrite).await?;
+                    insertions.push(MaterializedViewInsertion { view, append_logs });
+                    insertions.extend(view_insertions);
+                }
+            }
+            Ok(insertions)
+        }
+        .boxed()
+    }
+
+    /// Commits the prepared insertions, the views are committed before their own views.
+    pub async fn commit(
+        &self,
+        insertions: Vec<MaterializedViewInsertion>,
+        overwrite: bool,
+    ) -> Result<()> {
+        for insertion in insertions {
+            insertion
+                .view
+                .commit_insertion(self.ctx.clone(), insertion.append_logs, overwrite)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_view(
+        &self,
+        source: &dyn Table,
+        view_ref: &MaterializedViewRef,
+    ) -> Result<Option<Arc<dyn Table>>> {
+        let view = match self
+            .ctx
+            .get_table(&view_ref.database, &view_ref.table)
+            .await
+        {
+            Ok(view) => view,
+            // The view is dropped.
+            Err(cause)
+                if cause.code() == ErrorCode::UnknownTable("").code()
+                    || cause.code() == ErrorCode::UnknownDatabase("").code() =>
+            {
+                return Ok(None);
+            }
+            Err(cause) => return Err(cause),
+        };
+
+        // Another table of the same name is created after the view is dropped.
+        let view_info = view.get_table_info();
+        if !is_materialized_view(view_info)
+            || materialized_view_source(view_info)? != source.get_id()
+        {
+            return Ok(None);
+        }
+        Ok(Some(view))
+    }
+
+    async fn run_view_query(
+        &self,
+        source: &dyn Table,
+        view: &dyn Table,
+        blocks: &[BlockMeta],
+    ) -> Result<Vec<DataBlock>> {
+        let query = materialized_view_query(view.get_table_info())?;
+        let plan = PlanParser::parse(self.ctx.clone(), query).await?;
+        let mut rewriter = NewBlocksRewriter {
+            source_id: source.get_id(),
+            blocks,
+        };
+        let plan = rewriter.rewrite_plan_node(&plan)?;
+
+        let schema = view.schema();
+        let with_plan = InsertWithPlan::new(self.ctx, &schema, &plan);
+        with_plan.execute(view).await?.try_collect::<Vec<_>>().await
+    }
+}
+
+/// Registers the materialized view to its source table, so that the insertions into the
+/// source table are applied to the view too.
+pub async fn register_materialized_view(
+    ctx: &QueryContext,
+    source_id: u64,
+    view_ref: MaterializedViewRef,
+) -> Result<()> {
+    update_materialized_views(ctx, source_id, |views| {
+        if views.contains(&view_ref) {
+            return false;
+        }
+        views.push(view_ref.clone());
+        true
+    })
+    .await
+}
+
+/// Removes the dropped materialized view from its source table, nothing to do if the source
+/// table is dropped already.
+pub async fn unregister_materialized_view(
+    ctx: &QueryContext,
+    source_id: u64,
+    view_ref: &MaterializedViewRef,
+) -> Result<()> {
+    let res = update_materialized_views(ctx, source_id, |views| {
+        let len = views.len();
+        views.retain(|view| view != view_ref);
+        views.len() != len
+    })
+    .await;
+
+    match res {
+        Err(cause)
+            if cause.code() == ErrorCode::UnknownTable("").code()
+                || cause.code() == ErrorCode::UnknownTableId("").code() =>
+        {
+            Ok(())
+        }
+        res => res,
+    }
+}
+
+/// Updates the materialized views registered to the source table, `update` returns false if
+/// there is nothing to change.
+async fn update_materialized_views(
+    ctx: &QueryContext,
+    source_id: u64,
+    update: impl Fn(&mut Vec<MaterializedViewRef>) -> bool,
+) -> Result<()> {
+    let catalog = ctx.get_catalog();
+    let mut retries = 0;
+    loop {
+        let (ident, meta) = catalog.get_table_meta_by_id(source_id).await?;
+        let mut views = MaterializedViewRef::list(&meta.options)?;
+        if !update(&mut views) {
+            return Ok(());
+        }
+
+        let value = MaterializedViewRef::to_option_value(&views)?;
+        let req = UpsertTableOptionReq::new(&ident, MATERIALIZED_VIEWS, value);
+        match catalog.upsert_table_option(req).await {
+            Ok(_) => return Ok(()),
+            // The source table is changed concurrently, e.g. by an insertion.
+            Err(cause)
+                if cause.code() == ErrorCode::table_version_mismatched_code()
+                    && retries < MAX_UPDATE_RETRIES =>
+            {
+                retries += 1;
+            }
+            Err(cause) => return Err(cause),
+        }
+    }
+}
+
+/// Makes the query of a materialized view read the new blocks of its source table only.
+struct NewBlocksRewriter<'a> {
+    source_id: u64,
+    blocks: &'a [BlockMeta],
+}
+
+impl PlanRewriter for NewBlocksRewriter<'_> {
+    fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
+        let mut plan = plan.clone();
+        if plan.table_info.ident.table_id == self.source_id {
+            let (mut statistics, parts) =
+                FuseTable::to_partitions(self.blocks, plan.push_downs.clone());
+            statistics.partitions_scanned = parts.len();
+            statistics.partitions_total = parts.len();
+
+            plan.statistics = statistics;
+            plan.parts = parts;
+        }
+        Ok(PlanNode::ReadSource(plan))
+    }
+}
@@ -72,6 +72,7 @@ pub struct LogEvent {
     pub result_bytes: u64,
     pub cpu_usage: u32,
     pub memory_usage: u64,
+    pub stage_retries: u64,
 
     // Client.
     pub client_info: String,
@@ -139,6 +140,7 @@ impl InterpreterQueryLog {
             Series::from_data(vec![event.result_bytes as u64]),
             Series::from_data(vec![event.cpu_usage]),
             Series::from_data(vec![event.memory_usage as u64]),
+            Series::from_data(vec![event.stage_retries as u64]),
             // Client.
             Series::from_data(vec![event.client_info.as_str()]),
             Series::from_data(vec![event.client_address.as_str()]),
@@ -198,6 +200,7 @@ impl InterpreterQueryLog {
         let result_bytes = 0u64;
         let cpu_usage = self.ctx.get_settings().get_max_threads()? as u32;
         let memory_usage = self.ctx.get_current_session().get_memory_usage() as u64;
+        let stage_retries = 0u64;
 
         // Client.
         let client_address = format!("{:?}", self.ctx.get_client_address());
@@ -233,6 +236,7 @@ impl InterpreterQueryLog {
             result_bytes,
             cpu_usage,
             memory_usage,
+            stage_retries,
             client_info: "".to_string(),
             client_address,
 
@@ -280,6 +284,7 @@ impl InterpreterQueryLog {
         let total_partitions = dal_metrics.partitions_total as u64;
         let cpu_usage = self.ctx.get_settings().get_max_threads()? as u32;
         let memory_usage = self.ctx.get_current_session().get_memory_usage() as u64;
+        let stage_retries = self.ctx.get_stage_retries() as u64;
 
         // Result.
         let result_rows = self.ctx.get_result_progress_value().read_rows as u64;
@@ -321,6 +326,7 @@ impl InterpreterQueryLog {
             result_bytes,
             cpu_usage,
             memory_usage,
+            stage_retries,
             client_info: "".to_string(),
             client_address,
             current_database,
@@ -17,7 +17,9 @@ use std::sync::Arc;
 use common_datavalues2::DataSchemaRef;
 use common_exception::Result;
 use common_planners::PlanNode;
+use common_planners::PlanVisitor;
 use common_planners::SelectPlan;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
@@ -25,7 +27,11 @@ use crate::interpreters::plan_schedulers;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::optimizers::Optimizers;
+use crate::pipelines::new::executor::PipelinePullingExecutor;
+use crate::pipelines::new::QueryPipelineBuilder;
 use crate::sessions::QueryContext;
+use crate::sessions::QueryResultCacheKey;
+use crate::sessions::QueryResultCacheStream;
 
 pub struct SelectInterpreter {
     ctx: Arc<QueryContext>,
@@ -43,6 +49,58 @@ impl SelectInterpreter {
             &self.select.input,
         )
     }
+
+    /// Execute the plan on the new processors pipeline, the output is pulled from the
+    /// executor workers as a stream.
+    fn execute_new_pipeline(&self, plan: &PlanNode) -> Result<SendableDataBlockStream> {
+        let mut builder = QueryPipelineBuilder::create(self.ctx.clone());
+        builder.visit_plan_node(plan)?;
+
+        let pipeline = builder.finalize()?;
+        let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
+        let executor =
+            PipelinePullingExecutor::try_create(pipeline, std::cmp::max(max_threads, 1))?;
+        Ok(Box::pin(executor))
+    }
+
+    async fn execute_plan(&self) -> Result<SendableDataBlockStream> {
+        // TODO: maybe panic?
+        let optimized_plan = self.rewrite_plan()?;
+
+        // The plans which are not supported by the new processors yet, e.g. the joins and
+        // the distributed queries, still run on the old pipeline.
+        let settings = self.ctx.get_settings();
+        if settings.get_enable_new_processor_framework()? != 0
+            && QueryPipelineBuilder::is_supported(&optimized_plan)
+        {
+            return self.execute_new_pipeline(&optimized_plan);
+        }
+
+        plan_schedulers::schedule_query(&self.ctx, &optimized_plan).await
+    }
+
+    /// Returns the cached result if the query ran before and the tables it reads are not changed
+    /// since then, otherwise executes the query and caches its result.
+    async fn execute_with_result_cache(
+        &self,
+        key: QueryResultCacheKey,
+    ) -> Result<SendableDataBlockStream> {
+        let result_cache = self.ctx.get_query_result_cache();
+
+        if let Some(blocks) = result_cache.get(&key) {
+            let stream = DataBlockStream::create(self.select.schema(), None, blocks);
+            return Ok(Box::pin(stream));
+        }
+
+        let max_bytes = self.ctx.get_settings().get_query_result_cache_max_bytes()? as usize;
+        let stream = self.execute_plan().await?;
+        Ok(Box::pin(QueryResultCacheStream::create(
+            stream,
+            result_cache,
+            key,
+            max_bytes,
+        )))
+    }
 }
 
 #[async_trait::async_trait]
@@ -60,8 +118,12 @@ impl Interpreter for SelectInterpreter {
         &self,
         _input_stream: Option<SendableDataBlockStream>,
     ) -> Result<SendableDataBlockStream> {
-        // TODO: maybe panic?
-        let optimized_plan = self.rewrite_plan()?;
-        plan_schedulers::schedule_query(&self.ctx, &optimized_plan).await
+        if self.ctx.get_settings().get_enable_query_result_cache()? != 0 {
+            if let Some(key) = QueryResultCacheKey::try_create(&self.ctx, &self.select.input)? {
+                return self.execute_with_result_cache(key).await;
+            }
+        }
+
+        self.execute_plan().await
     }
 }
@@ -28,9 +28,14 @@ use common_streams::SendableDataBlockStream;
 
 use super::InsertInterpreter;
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_insert_materialized_views::register_materialized_view;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
+use crate::storages::view::is_materialized_view;
+use crate::storages::view::materialized_view_source;
+use crate::storages::view::MaterializedViewRef;
+use crate::storages::view::MATERIALIZED_VIEW_SOURCE;
 
 pub struct CreateTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -123,10 +128,41 @@ impl CreateTableInterpreter {
         let catalog = self.ctx.get_catalog();
         catalog.create_table(self.plan.clone().into()).await?;
 
+        if self
+            .plan
+            .table_meta
+            .options
+            .contains_key(MATERIALIZED_VIEW_SOURCE)
+        {
+            self.register_materialized_view().await?;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
             vec![],
         )))
     }
+
+    /// Registers the materialized view to its source table, so that the insertions into the
+    /// source table are applied to the view too.
+    async fn register_materialized_view(&self) -> Result<()> {
+        let tenant = self.ctx.get_tenant();
+        let catalog = self.ctx.get_catalog();
+        let view = catalog
+            .get_table(tenant.as_str(), &self.plan.db, &self.plan.table)
+            .await?;
+
+        // An existing table of the same name, with IF NOT EXISTS.
+        if !is_materialized_view(view.get_table_info()) {
+            return Ok(());
+        }
+
+        let source_id = materialized_view_source(view.get_table_info())?;
+        let view_ref = MaterializedViewRef {
+            database: self.plan.db.clone(),
+            table: self.plan.table.clone(),
+        };
+        register_materialized_view(&self.ctx, source_id, view_ref).await
+    }
 }
@@ -22,9 +22,13 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::interpreters::interpreter_insert_materialized_views::unregister_materialized_view;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
+use crate::storages::view::is_materialized_view;
+use crate::storages::view::materialized_view_source;
+use crate::storages::view::MaterializedViewRef;
 
 pub struct DropTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -58,10 +62,24 @@ impl Interpreter for DropTableInterpreter {
 
         let catalog = self.ctx.get_catalog();
         catalog.drop_table(self.plan.clone().into()).await?;
+        // the dropped table is not accounted to the storage quota anymore
+        self.ctx
+            .get_storage_usage_cache()
+            .invalidate(&self.ctx.get_tenant());
 
         // `drop_table` throws several types of exceptions
         // thus `optimize` operation is executed after it.
         if let Some(tbl) = tbl {
+            // the insertions into the source table are not applied to the dropped view anymore
+            if is_materialized_view(tbl.get_table_info()) {
+                let source_id = materialized_view_source(tbl.get_table_info())?;
+                let view_ref = MaterializedViewRef {
+                    database: self.plan.db.clone(),
+                    table: self.plan.table.clone(),
+                };
+                unregister_materialized_view(&self.ctx, source_id, &view_ref).await?;
+            }
+
             let keep_last_snapshot = false;
             tbl.optimize(self.ctx.clone(), keep_last_snapshot).await?;
         }
@@ -19,14 +19,11 @@ use common_planners::Optimization;
 use common_planners::OptimizeTablePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
-use futures::StreamExt;
 
 use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
-use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
-use crate::sql::PlanParser;
 
 pub struct OptimizeTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -57,15 +54,7 @@ impl Interpreter for OptimizeTableInterpreter {
         let do_compact = operation.contains(Optimization::COMPACT);
 
         if do_compact {
-            // it is a "simple and violent" strategy, to be optimized later
-            let obj_name = format!("{}.{}", &plan.database, &plan.table);
-            let rewritten_query =
-                format!("INSERT OVERWRITE {} SELECT * FROM {}", obj_name, obj_name);
-            let rewritten_plan =
-                PlanParser::parse(self.ctx.clone(), rewritten_query.as_str()).await?;
-            let interpreter = InterpreterFactory::get(self.ctx.clone(), rewritten_plan)?;
-            let mut stream = interpreter.execute(None).await?;
-            while let Some(Ok(_)) = stream.next().await {}
+            table.compact(self.ctx.clone()).await?;
             if do_purge {
                 // currently, context caches the table, we have to "refresh"
                 // the table by using the catalog API directly
@@ -82,6 +71,12 @@ impl Interpreter for OptimizeTableInterpreter {
             table.optimize(self.ctx.clone(), true).await?;
         }
 
+        if operation.contains(Optimization::PURGE_ORPHANS) {
+            table
+                .purge_orphans(self.ctx.clone(), plan.orphans_older_than)
+                .await?;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
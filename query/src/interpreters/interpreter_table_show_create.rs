@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::ShowCreateTablePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -26,6 +27,7 @@ use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
+use crate::storages::view::ViewTable;
 
 pub struct ShowCreateTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -59,26 +61,41 @@ impl Interpreter for ShowCreateTableInterpreter {
         let engine = table.engine();
         let schema = table.schema();
 
-        let mut table_info = format!("CREATE TABLE `{}` (\n", name);
-        for field in schema.fields().iter() {
-            let column = format!(
-                "  `{}` {},\n",
-                field.name(),
-                format_data_type_sql(field.data_type())
-            );
-            table_info.push_str(column.as_str());
-        }
-        let table_engine = format!(") ENGINE={}", engine);
-        table_info.push_str(table_engine.as_str());
-        table_info.push_str(
-            table
-                .options()
-                .iter()
-                .map(|(k, v)| format!(" {}='{}'", k.to_uppercase(), v))
-                .collect::<Vec<_>>()
-                .join("")
-                .as_str(),
-        );
+        let table_info = match table.as_any().downcast_ref::<ViewTable>() {
+            Some(view) => format!("CREATE VIEW `{}` AS {}", name, view.query()?),
+            None => {
+                let mut table_info = format!("CREATE TABLE `{}` (\n", name);
+                for field in schema.fields().iter() {
+                    let column = format!(
+                        "  `{}` {},\n",
+                        field.name(),
+                        format_data_type_sql(field.data_type())
+                    );
+                    table_info.push_str(column.as_str());
+                }
+                let table_engine = format!(") ENGINE={}", engine);
+                table_info.push_str(table_engine.as_str());
+                if let Some(cluster_keys) = &table.get_table_info().meta.cluster_keys {
+                    let cluster_keys: Vec<Expression> = serde_json::from_str(cluster_keys)?;
+                    let cluster_keys = cluster_keys
+                        .iter()
+                        .map(|key| format!("{:?}", key))
+                        .collect::<Vec<_>>();
+                    table_info
+                        .push_str(format!(" CLUSTER BY ({})", cluster_keys.join(", ")).as_str());
+                }
+                table_info.push_str(
+                    table
+                        .options()
+                        .iter()
+                        .map(|(k, v)| format!(" {}='{}'", k.to_uppercase(), v))
+                        .collect::<Vec<_>>()
+                        .join("")
+                        .as_str(),
+                );
+                table_info
+            }
+        };
 
         let show_fields = vec![
             DataField::new("Table", Vu8::to_data_type()),
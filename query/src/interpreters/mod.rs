@@ -20,10 +20,12 @@ mod interpreter_copy;
 mod interpreter_database_create;
 mod interpreter_database_drop;
 mod interpreter_database_show_create;
+mod interpreter_delete;
 mod interpreter_explain;
 mod interpreter_factory;
 mod interpreter_factory_interceptor;
 mod interpreter_insert;
+mod interpreter_insert_materialized_views;
 mod interpreter_insert_with_stream;
 mod interpreter_kill;
 mod interpreter_query_log;
@@ -31,12 +33,16 @@ mod interpreter_select;
 mod interpreter_setting;
 mod interpreter_show_databases;
 mod interpreter_show_grants;
+mod interpreter_table_alter;
+mod interpreter_table_analyze;
 mod interpreter_table_create;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
 mod interpreter_table_optimize;
+mod interpreter_table_rename;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
+mod interpreter_update;
 mod interpreter_use_database;
 mod interpreter_user_alter;
 mod interpreter_user_create;
@@ -58,6 +64,7 @@ pub use interpreter_copy::CopyInterpreter;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
 pub use interpreter_database_drop::DropDatabaseInterpreter;
 pub use interpreter_database_show_create::ShowCreateDatabaseInterpreter;
+pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_explain::ExplainInterpreter;
 pub use interpreter_factory::InterpreterFactory;
 pub use interpreter_factory_interceptor::InterceptorInterpreter;
@@ -70,12 +77,16 @@ pub use interpreter_select::SelectInterpreter;
 pub use interpreter_setting::SettingInterpreter;
 pub use interpreter_show_databases::ShowDatabasesInterpreter;
 pub use interpreter_show_grants::ShowGrantsInterpreter;
+pub use interpreter_table_alter::AlterTableInterpreter;
+pub use interpreter_table_analyze::AnalyzeTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
+pub use interpreter_table_rename::RenameTableInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
+pub use interpreter_update::UpdateInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
 pub use interpreter_user_alter::AlterUserInterpreter;
 pub use interpreter_user_create::CreateUserInterpreter;
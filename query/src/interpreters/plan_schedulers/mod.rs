@@ -17,6 +17,7 @@ mod plan_scheduler;
 mod plan_scheduler_error;
 mod plan_scheduler_insert;
 mod plan_scheduler_query;
+mod plan_scheduler_retry;
 mod plan_scheduler_rewriter;
 mod plan_scheduler_stream;
 
@@ -29,6 +29,7 @@ use common_planners::ExpressionPlan;
 use common_planners::Expressions;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::Partitions;
@@ -42,6 +43,7 @@ use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::WindowFuncPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
@@ -310,6 +312,8 @@ impl PlanScheduler {
             PlanNode::Having(plan) => self.visit_having(plan, tasks),
             PlanNode::Expression(plan) => self.visit_expression(plan, tasks),
             PlanNode::SubQueryExpression(plan) => self.visit_subqueries_set(plan, tasks),
+            PlanNode::Join(plan) => self.visit_join(plan, tasks),
+            PlanNode::WindowFunc(plan) => self.visit_window_func(plan, tasks),
             _ => Err(ErrorCode::UnImplement("")),
         }
     }
@@ -645,6 +649,38 @@ impl PlanScheduler {
         Ok(subquery_scheduler.nodes_plan)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref(), tasks)?;
+        let right_nodes_plan = self.visit_subquery(plan.right.as_ref(), tasks)?;
+
+        if right_nodes_plan.len() != self.nodes_plan.len() {
+            return Err(ErrorCode::LogicalError(
+                "Join right side size miss match nodes plan",
+            ));
+        }
+
+        match self.running_mode {
+            RunningMode::Cluster => {
+                for index in 0..self.nodes_plan.len() {
+                    self.visit_join_node(plan, index, &right_nodes_plan[index]);
+                }
+            }
+            RunningMode::Standalone => {
+                let local_pos = self.local_pos;
+                self.visit_join_node(plan, local_pos, &right_nodes_plan[local_pos]);
+            }
+        };
+
+        Ok(())
+    }
+
+    fn visit_join_node(&mut self, plan: &JoinPlan, index: usize, right: &PlanNode) {
+        let mut new_plan = plan.clone();
+        new_plan.set_left(&self.nodes_plan[index]);
+        new_plan.set_right(right);
+        self.nodes_plan[index] = PlanNode::Join(new_plan);
+    }
+
     fn visit_filter(&mut self, plan: &FilterPlan, tasks: &mut Tasks) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref(), tasks)?;
         match self.running_mode {
@@ -780,6 +816,29 @@ impl PlanScheduler {
         }
     }
 
+    fn visit_window_func(&mut self, plan: &WindowFuncPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_window_func(plan),
+            RunningMode::Standalone => self.visit_local_window_func(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_window_func(&mut self, plan: &WindowFuncPlan) {
+        let mut new_plan = plan.clone();
+        new_plan.set_input(&self.nodes_plan[self.local_pos]);
+        self.nodes_plan[self.local_pos] = PlanNode::WindowFunc(new_plan);
+    }
+
+    fn visit_cluster_window_func(&mut self, plan: &WindowFuncPlan) {
+        for index in 0..self.nodes_plan.len() {
+            let mut new_plan = plan.clone();
+            new_plan.set_input(&self.nodes_plan[index]);
+            self.nodes_plan[index] = PlanNode::WindowFunc(new_plan);
+        }
+    }
+
     fn visit_data_source(&mut self, plan: &ReadDataSourcePlan, _: &mut Tasks) -> Result<()> {
         let table = self.query_context.build_table_from_source_plan(plan)?;
 
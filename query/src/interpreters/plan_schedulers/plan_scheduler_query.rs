@@ -17,10 +17,16 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_planners::PlanNode;
+use common_planners::PlanRewriter;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 
 use crate::interpreters::plan_schedulers;
+use crate::interpreters::plan_schedulers::plan_scheduler_retry::discover_healthy_cluster;
+use crate::interpreters::plan_schedulers::plan_scheduler_retry::is_aborted;
+use crate::interpreters::plan_schedulers::plan_scheduler_retry::is_idempotent;
+use crate::interpreters::plan_schedulers::plan_scheduler_retry::StagesEraser;
 use crate::interpreters::plan_schedulers::Scheduled;
 use crate::interpreters::plan_schedulers::ScheduledStream;
 use crate::interpreters::PlanScheduler;
@@ -31,6 +37,57 @@ use crate::sessions::QueryContext;
 pub async fn schedule_query(
     ctx: &Arc<QueryContext>,
     plan: &PlanNode,
+) -> Result<SendableDataBlockStream> {
+    // Only the read only queries are rescheduled after node failures.
+    let max_retries = match is_idempotent(plan) {
+        true => ctx.get_settings().get_max_stage_retries()?,
+        false => 0,
+    };
+
+    let mut retries = 0;
+    let mut plan = plan.clone();
+    loop {
+        let mut failed_nodes = vec![];
+        let cause = match schedule_query_impl(ctx, &plan, &mut failed_nodes).await {
+            Ok(stream) => return Ok(stream),
+            Err(cause) => cause,
+        };
+
+        let is_standalone = ctx.get_cluster().is_empty();
+        if is_standalone || retries >= max_retries || is_aborted(&cause) {
+            return Err(cause);
+        }
+
+        let healthy_cluster = match discover_healthy_cluster(ctx, &failed_nodes).await {
+            Ok(Some(healthy_cluster)) => healthy_cluster,
+            Ok(None) => return Err(cause),
+            Err(discover_failure) => {
+                tracing::error!("Cannot discover healthy nodes, cause: {}", discover_failure);
+                return Err(cause);
+            }
+        };
+
+        tracing::warn!(
+            "Reschedule query {} on the healthy nodes, cause: {}",
+            ctx.get_id(),
+            cause
+        );
+
+        // The stages cannot run in standalone mode.
+        if healthy_cluster.is_empty() {
+            plan = StagesEraser.rewrite_plan_node(&plan)?;
+        }
+
+        retries += 1;
+        ctx.add_stage_retry();
+        ctx.set_cluster(healthy_cluster);
+    }
+}
+
+async fn schedule_query_impl(
+    ctx: &Arc<QueryContext>,
+    plan: &PlanNode,
+    failed_nodes: &mut Vec<String>,
 ) -> Result<SendableDataBlockStream> {
     let scheduler = PlanScheduler::try_create(ctx.clone())?;
     let scheduled_tasks = scheduler.reschedule(plan)?;
@@ -41,10 +98,17 @@ pub async fn schedule_query(
     let timeout = ctx.get_settings().get_flight_client_timeout()?;
     let mut scheduled = Scheduled::new();
     for (node, action) in remote_stage_actions {
-        let mut flight_client = cluster.create_node_conn(&node.id, &config).await?;
-        let executing_action = flight_client.execute_action(action.clone(), timeout);
+        let executing_action = async {
+            let mut flight_client = cluster.create_node_conn(&node.id, &config).await?;
+            flight_client.execute_action(action.clone(), timeout).await
+        };
+
+        if let Err(cause) = executing_action.await {
+            failed_nodes.push(node.id.clone());
+            plan_schedulers::handle_error(ctx, scheduled, timeout).await;
+            return Err(cause);
+        }
 
-        executing_action.await?;
         scheduled.insert(node.id.clone(), node.clone());
     }
 
@@ -52,10 +116,28 @@ pub async fn schedule_query(
     let mut in_local_pipeline = pipeline_builder.build(&scheduled_tasks.get_local_task())?;
 
     match in_local_pipeline.execute().await {
-        Ok(stream) => Ok(ScheduledStream::create(ctx.clone(), scheduled, stream)),
+        Ok(stream) if scheduled.is_empty() => {
+            Ok(ScheduledStream::create(ctx.clone(), scheduled, stream))
+        }
+        Ok(stream) => {
+            wait_first_block(ScheduledStream::create(ctx.clone(), scheduled, stream)).await
+        }
         Err(error) => {
             plan_schedulers::handle_error(ctx, scheduled, timeout).await;
             Err(error)
         }
     }
 }
+
+// The failures of the remote stages mostly come out before the first block,
+// wait for it so that they can be retried.
+async fn wait_first_block(mut stream: SendableDataBlockStream) -> Result<SendableDataBlockStream> {
+    match stream.next().await {
+        None => Ok(Box::pin(futures::stream::empty())),
+        Some(Err(cause)) => Err(cause),
+        Some(Ok(block)) => {
+            let first_block = futures::stream::once(async move { Ok(block) });
+            Ok(Box::pin(first_block.chain(stream)))
+        }
+    }
+}
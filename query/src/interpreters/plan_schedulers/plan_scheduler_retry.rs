@@ -0,0 +1,107 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::BroadcastPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::StagePlan;
+use common_tracing::tracing;
+
+use crate::clusters::Cluster;
+use crate::sessions::QueryContext;
+
+/// The stages of a read only query can run again without any side effect.
+pub fn is_idempotent(plan: &PlanNode) -> bool {
+    match plan {
+        PlanNode::Sink(_) => false,
+        _ => plan.inputs().iter().all(|input| is_idempotent(input)),
+    }
+}
+
+/// The killed queries are never retried.
+pub fn is_aborted(cause: &ErrorCode) -> bool {
+    cause.code() == ErrorCode::AbortedQueryCode()
+}
+
+/// Discover the alive nodes from the heartbeats in the meta service, and remove the nodes
+/// which are not alive or failed to accept the stages from the cluster of the query.
+/// Returns None if all the nodes of the cluster are healthy, the failure is not caused by them.
+pub async fn discover_healthy_cluster(
+    ctx: &Arc<QueryContext>,
+    failed_nodes: &[String],
+) -> Result<Option<Arc<Cluster>>> {
+    let cluster = ctx.get_cluster();
+    let discovery = ctx
+        .get_current_session()
+        .get_session_manager()
+        .get_cluster_discovery();
+    let alive_nodes = discovery.discover().await?.get_nodes();
+
+    let nodes = cluster.get_nodes();
+    let mut healthy_nodes = Vec::with_capacity(nodes.len());
+    for node in &nodes {
+        let is_alive = alive_nodes.iter().any(|alive| alive.id == node.id);
+        let is_failed = failed_nodes.iter().any(|failed| failed == &node.id);
+
+        // The local node is always healthy, it is scheduling the query.
+        if cluster.is_local(node) || (is_alive && !is_failed) {
+            healthy_nodes.push(node.clone());
+        } else {
+            tracing::warn!("Node {} is unhealthy, remove it from query", node.id);
+        }
+    }
+
+    match healthy_nodes.len() == nodes.len() {
+        true => Ok(None),
+        false => Ok(Some(Cluster::create(healthy_nodes, cluster.local_id()))),
+    }
+}
+
+/// Remove the stages from a distributed plan, so that it runs in the local node only.
+pub struct StagesEraser;
+
+impl PlanRewriter for StagesEraser {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        PlanBuilder::from(&new_input)
+            .aggregate_final(
+                plan.schema_before_group_by.clone(),
+                &plan.aggr_expr,
+                &plan.group_expr,
+            )?
+            .build()
+    }
+
+    fn rewrite_stage(&mut self, plan: &StagePlan) -> Result<PlanNode> {
+        self.rewrite_plan_node(plan.input.as_ref())
+    }
+
+    fn rewrite_broadcast(&mut self, plan: &BroadcastPlan) -> Result<PlanNode> {
+        self.rewrite_plan_node(plan.input.as_ref())
+    }
+}
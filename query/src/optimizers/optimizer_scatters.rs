@@ -22,6 +22,7 @@ use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::BroadcastPlan;
 use common_planners::Expression;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::PlanBuilder;
@@ -31,6 +32,7 @@ use common_planners::ReadDataSourcePlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::WindowFuncPlan;
 
 use crate::optimizers::Optimizer;
 use crate::sessions::QueryContext;
@@ -190,6 +192,68 @@ impl ScattersOptimizerImpl {
         }))
     }
 
+    fn rewrite_join_input(&self, input: &PlanNode) -> Result<(PlanNode, RunningMode)> {
+        let input_ctx = QueryContext::create_from(self.ctx.clone());
+        let mut input_optimizer = ScattersOptimizerImpl::create(input_ctx);
+        let rewritten_input = input_optimizer.rewrite_plan_node(input)?;
+        Ok((rewritten_input, input_optimizer.running_mode))
+    }
+
+    fn converge_join_input(input: PlanNode, mode: RunningMode) -> Result<PlanNode> {
+        match mode {
+            RunningMode::Standalone => Ok(input),
+            RunningMode::Cluster => Self::convergent_shuffle_stage(input),
+        }
+    }
+
+    // Rows with equal join keys have an equal first key, so hashing it is enough
+    // to send them to the same node.
+    fn join_shuffle_stage(keys: &[Expression], input: PlanNode) -> Result<PlanNode> {
+        let scatters_expr = Expression::ScalarFunction {
+            op: String::from("sipHash"),
+            args: vec![keys[0].clone()],
+        };
+
+        Ok(PlanNode::Stage(StagePlan {
+            scatters_expr,
+            kind: StageKind::Normal,
+            input: Arc::new(input),
+        }))
+    }
+
+    fn can_broadcast_join(&self, plan: &JoinPlan) -> Result<bool> {
+        // The build side is matched on every node, its unmatched rows would be
+        // emitted once per node.
+        if plan.join_type.need_build_side_visited() {
+            return Ok(false);
+        }
+
+        if plan.right_keys.is_empty() {
+            return Ok(true);
+        }
+
+        let threshold = self.ctx.get_settings().get_broadcast_join_threshold()?;
+        Ok(Self::estimated_read_bytes(plan.right.as_ref()) <= threshold as usize)
+    }
+
+    fn estimated_read_bytes(plan: &PlanNode) -> usize {
+        match plan {
+            PlanNode::ReadSource(read_plan) => read_plan.statistics.read_bytes,
+            _ => plan
+                .inputs()
+                .iter()
+                .map(|input| Self::estimated_read_bytes(input.as_ref()))
+                .sum(),
+        }
+    }
+
+    fn join_with_inputs(plan: &JoinPlan, left: &PlanNode, right: &PlanNode) -> PlanNode {
+        let mut new_plan = plan.clone();
+        new_plan.set_left(left);
+        new_plan.set_right(right);
+        PlanNode::Join(new_plan)
+    }
+
     fn normal_shuffle_stage(key: impl Into<String>, input: PlanNode) -> Result<PlanNode> {
         let scatters_expr = Expression::ScalarFunction {
             op: String::from("sipHash"),
@@ -278,6 +342,53 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_window_func(&mut self, plan: &WindowFuncPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+
+        match self.running_mode {
+            RunningMode::Cluster => {
+                // Window function we convergent it in local node
+                self.running_mode = RunningMode::Standalone;
+                Self::convergent_shuffle_stage_builder(Arc::new(new_input))
+                    .window_func(plan.window_func.clone())?
+                    .build()
+            }
+            RunningMode::Standalone => PlanBuilder::from(&new_input)
+                .window_func(plan.window_func.clone())?
+                .build(),
+        }
+    }
+
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let (new_left, left_mode) = self.rewrite_join_input(plan.left.as_ref())?;
+        let (new_right, right_mode) = self.rewrite_join_input(plan.right.as_ref())?;
+
+        match (left_mode, right_mode) {
+            (RunningMode::Cluster, _) if self.can_broadcast_join(plan)? => {
+                // Broadcast join, the left input keeps running on every node.
+                self.running_mode = RunningMode::Cluster;
+                let new_right = PlanNode::Broadcast(BroadcastPlan {
+                    input: Arc::new(new_right),
+                });
+                Ok(Self::join_with_inputs(plan, &new_left, &new_right))
+            }
+            (RunningMode::Cluster, RunningMode::Cluster) if !plan.left_keys.is_empty() => {
+                // Shuffle join, both inputs are partitioned by the join keys.
+                self.running_mode = RunningMode::Cluster;
+                let new_left = Self::join_shuffle_stage(&plan.left_keys, new_left)?;
+                let new_right = Self::join_shuffle_stage(&plan.right_keys, new_right)?;
+                Ok(Self::join_with_inputs(plan, &new_left, &new_right))
+            }
+            (left_mode, right_mode) => {
+                // Join is executed in local node, we convergent the cluster inputs.
+                self.running_mode = RunningMode::Standalone;
+                let new_left = Self::converge_join_input(new_left, left_mode)?;
+                let new_right = Self::converge_join_input(new_right, right_mode)?;
+                Ok(Self::join_with_inputs(plan, &new_left, &new_right))
+            }
+        }
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         let t = self.ctx.build_table_from_source_plan(plan)?;
 
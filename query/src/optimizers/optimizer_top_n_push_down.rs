@@ -75,6 +75,31 @@ impl PlanRewriter for TopNPushDownImpl {
         }
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        // The rows of join are not the rows of inputs, we clear the top n option.
+        self.limit = None;
+        self.order_by.clear();
+
+        let new_left = self.rewrite_plan_node(&plan.left)?;
+        let new_right = self.rewrite_plan_node(&plan.right)?;
+        let mut new_plan = plan.clone();
+        new_plan.set_left(&new_left);
+        new_plan.set_right(&new_right);
+        Ok(PlanNode::Join(new_plan))
+    }
+
+    fn rewrite_window_func(&mut self, plan: &WindowFuncPlan) -> Result<PlanNode> {
+        // The window function depends on all the rows of its partition, we clear the top n option.
+        self.limit = None;
+        self.order_by.clear();
+
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        let new_window_func = self.rewrite_expr(&new_input.schema(), &plan.window_func)?;
+        PlanBuilder::from(&new_input)
+            .window_func(new_window_func)?
+            .build()
+    }
+
     fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
         let current_limit = self.limit;
         let current_order_by = self.order_by.clone();
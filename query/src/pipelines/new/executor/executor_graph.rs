@@ -15,6 +15,8 @@
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use common_exception::Result;
@@ -86,6 +88,7 @@ impl Node {
 
 struct ExecutingGraph {
     graph: StableGraph<Arc<Node>, ()>,
+    finished_nodes: AtomicUsize,
 }
 
 type StateLockGuard<'a> = RwLockUpgradableReadGuard<'a, ExecutingGraph>;
@@ -179,7 +182,10 @@ impl ExecutingGraph {
 
         // Assert no output.
         assert_eq!(node_stack.len(), 0);
-        Ok(ExecutingGraph { graph })
+        Ok(ExecutingGraph {
+            graph,
+            finished_nodes: AtomicUsize::new(0),
+        })
     }
 
     /// # Safety
@@ -231,7 +237,13 @@ impl ExecutingGraph {
                 };
 
                 *node_status = match node.processor.event()? {
-                    Event::Finished => State::Finished,
+                    Event::Finished => {
+                        if !matches!(*node_status, State::Finished) {
+                            locker.finished_nodes.fetch_add(1, Ordering::SeqCst);
+                        }
+
+                        State::Finished
+                    }
                     Event::NeedData | Event::NeedConsume => State::Idle,
                     Event::Sync => {
                         schedule_queue.push_sync(node.processor.clone());
@@ -344,6 +356,12 @@ impl RunningGraph {
         ExecutingGraph::schedule_queue(&self.0.upgradable_read(), node_index, &mut schedule_queue)?;
         Ok(schedule_queue)
     }
+
+    /// Whether all the processors of the graph are finished.
+    pub fn is_finished(&self) -> bool {
+        let graph = self.0.read();
+        graph.finished_nodes.load(Ordering::SeqCst) == graph.graph.node_count()
+    }
 }
 
 impl Debug for Node {
@@ -32,6 +32,7 @@ impl WorkerNotify {
 }
 
 struct WorkersNotifyMutable {
+    pub finished: bool,
     pub waiting_size: usize,
     pub workers_waiting: Vec<bool>,
 }
@@ -54,6 +55,7 @@ impl WorkersNotify {
         Arc::new(WorkersNotify {
             workers_notify,
             mutable_state: Mutex::new(WorkersNotifyMutable {
+                finished: false,
                 waiting_size: 0,
                 workers_waiting,
             }),
@@ -93,6 +95,12 @@ impl WorkersNotify {
         }
     }
 
+    /// Wake up all the waiting workers, the workers never wait again after it.
+    pub fn finish(&self) {
+        self.mutable_state.lock().finished = true;
+        self.wakeup_all();
+    }
+
     pub fn wakeup_all(&self) {
         let mut mutable_state = self.mutable_state.lock();
         if mutable_state.waiting_size > 0 {
@@ -116,6 +124,10 @@ impl WorkersNotify {
 
     pub fn wait(&self, worker_id: usize) {
         let mut mutable_state = self.mutable_state.lock();
+        if mutable_state.finished {
+            return;
+        }
+
         mutable_state.waiting_size += 1;
         mutable_state.workers_waiting[worker_id] = true;
         let mut waiting = self.workers_notify[worker_id].waiting.lock();
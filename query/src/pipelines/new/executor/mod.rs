@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod pipeline_executor;
+mod pipeline_pulling_executor;
 mod pipeline_runtime_executor;
 mod pipeline_threads_executor;
 
@@ -23,3 +24,4 @@ mod executor_worker_context;
 
 pub use executor_graph::RunningGraph;
 pub use pipeline_executor::PipelineExecutor;
+pub use pipeline_pulling_executor::PipelinePullingExecutor;
@@ -44,6 +44,12 @@ impl PipelineExecutor {
             }
 
             global_tasks_queue.init_tasks(tasks);
+
+            // All the processors are finished before being scheduled, e.g. there are no sources.
+            if graph.is_finished() {
+                global_tasks_queue.finish();
+            }
+
             Ok(Arc::new(PipelineExecutor {
                 graph,
                 workers_notify,
@@ -54,13 +60,26 @@ impl PipelineExecutor {
 
     pub fn finish(&self) {
         self.global_tasks_queue.finish();
-        self.workers_notify.wakeup_all();
+        self.workers_notify.finish();
     }
 
+    /// Run the processors on the current thread until the pipeline is finished.
+    /// The whole pipeline is finished once any of the workers fails.
+    ///
     /// # Safety
     ///
     /// Method is thread unsafe and require thread safe call
     pub unsafe fn execute_with_single_worker(&self, worker_num: usize) -> Result<()> {
+        let res = self.execute_worker_tasks(worker_num);
+
+        if res.is_err() {
+            self.finish();
+        }
+
+        res
+    }
+
+    unsafe fn execute_worker_tasks(&self, worker_num: usize) -> Result<()> {
         let workers_notify = self.workers_notify.clone();
         let mut context = ExecutorWorkerContext::create(worker_num, workers_notify);
 
@@ -78,6 +97,10 @@ impl PipelineExecutor {
                 let schedule_queue = self.graph.schedule_queue(executed_pid)?;
                 schedule_queue.schedule(&self.global_tasks_queue, &mut context);
             }
+
+            if self.graph.is_finished() {
+                self.finish();
+            }
         }
 
         Ok(())
@@ -0,0 +1,82 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_base::tokio::runtime::Handle;
+use common_base::tokio::sync::mpsc::channel;
+use common_base::tokio::sync::mpsc::Receiver;
+use common_base::Thread;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+
+use crate::pipelines::new::executor::pipeline_executor::PipelineExecutor;
+use crate::pipelines::new::pipeline::NewPipeline;
+use crate::pipelines::new::processors::SyncSenderSink;
+
+/// Executes the pipeline on the worker threads, and pulls the output of the pipeline as a
+/// stream of blocks. The errors of the workers are delivered through the stream too.
+///
+/// Dropping the stream finishes the execution of the pipeline.
+pub struct PipelinePullingExecutor {
+    executor: Arc<PipelineExecutor>,
+    receiver: Receiver<Result<DataBlock>>,
+}
+
+impl PipelinePullingExecutor {
+    pub fn try_create(mut pipeline: NewPipeline, workers: usize) -> Result<Self> {
+        let (sender, receiver) = channel(std::cmp::max(pipeline.output_len(), 1));
+        pipeline.add_sink(|input| Ok(SyncSenderSink::create(sender.clone(), input)))?;
+
+        // The sources may read the storage with tokio, so the workers enter the current runtime.
+        let handle = Handle::try_current().map_err(|cause| {
+            ErrorCode::TokioError(format!("Cannot get the current runtime, cause {}", cause))
+        })?;
+
+        let executor = PipelineExecutor::create(pipeline, workers)?;
+        for worker_num in 0..workers {
+            let worker = executor.clone();
+            let sender = sender.clone();
+            let handle = handle.clone();
+            Thread::spawn(move || unsafe {
+                let _guard = handle.enter();
+                if let Err(cause) = worker.execute_with_single_worker(worker_num) {
+                    // The receiver may be dropped already, nobody cares about the error then.
+                    let _ = sender.blocking_send(Err(cause));
+                }
+            });
+        }
+
+        Ok(PipelinePullingExecutor { executor, receiver })
+    }
+}
+
+impl Stream for PipelinePullingExecutor {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for PipelinePullingExecutor {
+    fn drop(&mut self) {
+        self.executor.finish();
+    }
+}
@@ -21,3 +21,4 @@ mod unsafe_cell_wrap;
 
 pub use pipe::NewPipe;
 pub use pipeline::NewPipeline;
+pub use pipeline_builder::QueryPipelineBuilder;
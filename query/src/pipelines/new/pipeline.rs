@@ -12,10 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::pipelines::new::pipe::NewPipe;
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::processor::ProcessorPtr;
 use crate::pipelines::new::processors::ResizeProcessor;
 
@@ -32,6 +36,59 @@ impl NewPipeline {
         self.pipes.push(pipe);
     }
 
+    /// The number of the output ports of the last pipe.
+    pub fn output_len(&self) -> usize {
+        match self.pipes.last() {
+            None => 0,
+            Some(pipe) => pipe.size(),
+        }
+    }
+
+    /// Add a transform processor for each output port of the last pipe.
+    pub fn add_transform<F>(&mut self, f: F) -> Result<()>
+    where F: Fn(Arc<InputPort>, Arc<OutputPort>) -> Result<ProcessorPtr> {
+        let mut processors = Vec::with_capacity(self.output_len());
+        let mut inputs_port = Vec::with_capacity(self.output_len());
+        let mut outputs_port = Vec::with_capacity(self.output_len());
+
+        for _index in 0..self.output_len() {
+            let input = InputPort::create();
+            let output = OutputPort::create();
+
+            processors.push(f(input.clone(), output.clone())?);
+            inputs_port.push(input);
+            outputs_port.push(output);
+        }
+
+        self.add_pipe(NewPipe::SimplePipe {
+            processors,
+            inputs_port,
+            outputs_port,
+        });
+        Ok(())
+    }
+
+    /// Add a sink processor for each output port of the last pipe.
+    pub fn add_sink<F>(&mut self, f: F) -> Result<()>
+    where F: Fn(Arc<InputPort>) -> Result<ProcessorPtr> {
+        let mut processors = Vec::with_capacity(self.output_len());
+        let mut inputs_port = Vec::with_capacity(self.output_len());
+
+        for _index in 0..self.output_len() {
+            let input = InputPort::create();
+
+            processors.push(f(input.clone())?);
+            inputs_port.push(input);
+        }
+
+        self.add_pipe(NewPipe::SimplePipe {
+            processors,
+            inputs_port,
+            outputs_port: vec![],
+        });
+        Ok(())
+    }
+
     pub fn resize(&mut self, new_size: usize) -> Result<()> {
         match self.pipes.last() {
             None => Err(ErrorCode::LogicalError("")),
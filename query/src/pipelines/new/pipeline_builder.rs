@@ -16,35 +16,79 @@ use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::ExpressionPlan;
 use common_planners::FilterPlan;
+use common_planners::HavingPlan;
+use common_planners::LimitPlan;
 use common_planners::PlanNode;
 use common_planners::PlanVisitor;
+use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
+use common_planners::SelectPlan;
+use common_planners::SortPlan;
 
 use crate::pipelines::new::pipe::SourcePipeBuilder;
 use crate::pipelines::new::pipeline::NewPipeline;
 use crate::pipelines::new::processors::port::OutputPort;
 use crate::pipelines::new::processors::TableSource;
+use crate::pipelines::new::processors::TransformAggregatorFinal;
+use crate::pipelines::new::processors::TransformAggregatorPartial;
+use crate::pipelines::new::processors::TransformExpression;
+use crate::pipelines::new::processors::TransformFilter;
+use crate::pipelines::new::processors::TransformGroupByFinal;
+use crate::pipelines::new::processors::TransformGroupByPartial;
+use crate::pipelines::new::processors::TransformHaving;
+use crate::pipelines::new::processors::TransformLimit;
+use crate::pipelines::new::processors::TransformProjection;
+use crate::pipelines::new::processors::TransformSortMerge;
+use crate::pipelines::new::processors::TransformSortPartial;
+use crate::pipelines::transforms::get_sort_descriptions;
 use crate::sessions::QueryContext;
 
-#[allow(dead_code)]
-struct QueryPipelineBuilder {
+/// Builds the processors pipeline of a local query plan.
+///
+/// The plan nodes which are not ported to the new processors yet, e.g. the joins or the
+/// stages of the distributed queries, fail the build with `UnImplement`.
+pub struct QueryPipelineBuilder {
     ctx: Arc<QueryContext>,
     pipeline: NewPipeline,
+
+    limit: Option<usize>,
+    offset: usize,
 }
 
-#[allow(dead_code)]
 impl QueryPipelineBuilder {
     pub fn create(ctx: Arc<QueryContext>) -> QueryPipelineBuilder {
         QueryPipelineBuilder {
             ctx,
             pipeline: NewPipeline::create(),
+            limit: None,
+            offset: 0,
         }
     }
 
     pub fn finalize(self) -> Result<NewPipeline> {
         Ok(self.pipeline)
     }
+
+    /// Whether all the nodes of the plan can be built into the new processors.
+    pub fn is_supported(plan: &PlanNode) -> bool {
+        match plan {
+            PlanNode::ReadSource(_) => true,
+            PlanNode::Projection(_)
+            | PlanNode::Expression(_)
+            | PlanNode::AggregatorPartial(_)
+            | PlanNode::AggregatorFinal(_)
+            | PlanNode::Filter(_)
+            | PlanNode::Having(_)
+            | PlanNode::Sort(_)
+            | PlanNode::Limit(_)
+            | PlanNode::Select(_) => plan.inputs().iter().all(|input| Self::is_supported(input)),
+            _ => false,
+        }
+    }
 }
 
 impl PlanVisitor for QueryPipelineBuilder {
@@ -58,20 +102,205 @@ impl PlanVisitor for QueryPipelineBuilder {
             PlanNode::Having(n) => self.visit_having(n),
             PlanNode::Sort(n) => self.visit_sort(n),
             PlanNode::Limit(n) => self.visit_limit(n),
-            PlanNode::LimitBy(n) => self.visit_limit_by(n),
             PlanNode::ReadSource(n) => self.visit_read_data_source(n),
             PlanNode::Select(n) => self.visit_select(n),
-            PlanNode::Explain(n) => self.visit_explain(n),
-            _ => Err(ErrorCode::UnImplement("")),
+            other => Err(ErrorCode::UnImplement(format!(
+                "Build new pipeline from the plan node unsupported:{:?}",
+                other.name()
+            ))),
+        }
+    }
+
+    fn visit_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+
+        let ctx = self.ctx.clone();
+        let schema = plan.schema();
+        let input_schema = plan.input.schema();
+        match plan.group_expr.is_empty() {
+            true => self.pipeline.add_transform(|input, output| {
+                TransformAggregatorPartial::try_create(
+                    input,
+                    output,
+                    ctx.clone(),
+                    schema.clone(),
+                    input_schema.clone(),
+                    plan.aggr_expr.clone(),
+                )
+            }),
+            false => self.pipeline.add_transform(|input, output| {
+                TransformGroupByPartial::try_create(
+                    input,
+                    output,
+                    ctx.clone(),
+                    schema.clone(),
+                    input_schema.clone(),
+                    plan.aggr_expr.clone(),
+                    plan.group_expr.clone(),
+                )
+            }),
+        }
+    }
+
+    fn visit_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+        self.pipeline.resize(1)?;
+
+        let ctx = self.ctx.clone();
+        let schema = plan.schema();
+        let schema_before_group_by = plan.schema_before_group_by.clone();
+        match plan.group_expr.is_empty() {
+            true => self.pipeline.add_transform(|input, output| {
+                TransformAggregatorFinal::try_create(
+                    input,
+                    output,
+                    ctx.clone(),
+                    schema.clone(),
+                    schema_before_group_by.clone(),
+                    plan.aggr_expr.clone(),
+                )
+            }),
+            false => {
+                let settings = self.ctx.get_settings();
+                let max_block_size = settings.get_max_block_size()? as usize;
+                self.pipeline.add_transform(|input, output| {
+                    TransformGroupByFinal::try_create(
+                        input,
+                        output,
+                        ctx.clone(),
+                        schema.clone(),
+                        max_block_size,
+                        schema_before_group_by.clone(),
+                        plan.aggr_expr.clone(),
+                        plan.group_expr.clone(),
+                    )
+                })?;
+
+                // Spread the blocks of the groups to the processors of the next steps.
+                let max_threads = settings.get_max_threads()? as usize;
+                self.pipeline.resize(std::cmp::max(max_threads, 1))
+            }
         }
     }
 
+    fn visit_projection(&mut self, plan: &ProjectionPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+
+        let schema = plan.schema();
+        let input_schema = plan.input.schema();
+        self.pipeline.add_transform(|input, output| {
+            TransformProjection::try_create(
+                input_schema.clone(),
+                schema.clone(),
+                plan.expr.clone(),
+                input,
+                output,
+            )
+        })
+    }
+
+    fn visit_expression(&mut self, plan: &ExpressionPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+
+        let input_schema = plan.input.schema();
+        self.pipeline.add_transform(|input, output| {
+            TransformExpression::try_create(
+                input_schema.clone(),
+                plan.schema.clone(),
+                plan.exprs.clone(),
+                input,
+                output,
+            )
+        })
+    }
+
     fn visit_filter(&mut self, plan: &FilterPlan) -> Result<()> {
         self.visit_plan_node(&plan.input)?;
-        // TODO:
+
+        let schema = plan.schema();
+        self.pipeline.add_transform(|input, output| {
+            TransformFilter::try_create(schema.clone(), plan.predicate.clone(), input, output)
+        })
+    }
+
+    fn visit_having(&mut self, plan: &HavingPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+
+        let schema = plan.schema();
+        self.pipeline.add_transform(|input, output| {
+            TransformHaving::try_create(schema.clone(), plan.predicate.clone(), input, output)
+        })
+    }
+
+    fn visit_sort(&mut self, plan: &SortPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)?;
+
+        // The number of rows should be limit + offset. For example, for the query
+        // 'select * from numbers(100) order by number desc limit 10 offset 5', the
+        // sort pipeline should return at least 15 rows.
+        let rows_limit = self.limit.map(|limit| limit + self.offset);
+        let sort_columns_descriptions = get_sort_descriptions(&plan.schema, &plan.order_by)?;
+        let ctx = self.ctx.clone();
+
+        // processor 1: block ---> sorted block
+        // processor 2: block ---> sorted block
+        self.pipeline.add_transform(|input, output| {
+            TransformSortPartial::try_create(
+                input,
+                output,
+                rows_limit,
+                sort_columns_descriptions.clone(),
+            )
+        })?;
+
+        // processor 1: [sorted blocks ...] ---> merge to one sorted block
+        // processor 2: [sorted blocks ...] ---> merge to one sorted block
+        self.pipeline.add_transform(|input, output| {
+            TransformSortMerge::try_create(
+                input,
+                output,
+                ctx.clone(),
+                rows_limit,
+                sort_columns_descriptions.clone(),
+            )
+        })?;
+
+        // processor1 sorted block --
+        //                             \
+        //                               ---> processor --> merge to one sorted block
+        //                             /
+        // processor2 sorted block --
+        if self.pipeline.output_len() > 1 {
+            self.pipeline.resize(1)?;
+            self.pipeline.add_transform(|input, output| {
+                TransformSortMerge::try_create(
+                    input,
+                    output,
+                    ctx.clone(),
+                    rows_limit,
+                    sort_columns_descriptions.clone(),
+                )
+            })?;
+        }
+
         Ok(())
     }
 
+    fn visit_limit(&mut self, plan: &LimitPlan) -> Result<()> {
+        self.limit = plan.n;
+        self.offset = plan.offset;
+        self.visit_plan_node(&plan.input)?;
+
+        self.pipeline.resize(1)?;
+        self.pipeline.add_transform(|input, output| {
+            TransformLimit::try_create(plan.n, plan.offset, input, output)
+        })
+    }
+
+    fn visit_select(&mut self, plan: &SelectPlan) -> Result<()> {
+        self.visit_plan_node(&plan.input)
+    }
+
     fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
         // Bind plan partitions to context.
         self.ctx.try_set_partitions(plan.parts.clone())?;
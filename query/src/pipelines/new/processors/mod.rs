@@ -31,4 +31,15 @@ pub use resize_processor::ResizeProcessor;
 pub use sinks::SyncSenderSink;
 pub use sources::SyncReceiverSource;
 pub use sources::TableSource;
+pub use transforms::TransformAggregatorFinal;
+pub use transforms::TransformAggregatorPartial;
 pub use transforms::TransformDummy;
+pub use transforms::TransformExpression;
+pub use transforms::TransformFilter;
+pub use transforms::TransformGroupByFinal;
+pub use transforms::TransformGroupByPartial;
+pub use transforms::TransformHaving;
+pub use transforms::TransformLimit;
+pub use transforms::TransformProjection;
+pub use transforms::TransformSortMerge;
+pub use transforms::TransformSortPartial;
@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_base::tokio::sync::mpsc::Sender;
 use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::pipelines::new::processors::port::InputPort;
@@ -24,12 +25,14 @@ use crate::pipelines::new::processors::sinks::sync_sink::Sink;
 use crate::pipelines::new::processors::sinks::sync_sink::Sinker;
 
 pub struct SyncSenderSink {
-    sender: Sender<Result<DataBlock>>,
+    sender: Option<Sender<Result<DataBlock>>>,
 }
 
 impl SyncSenderSink {
     pub fn create(sender: Sender<Result<DataBlock>>, input: Arc<InputPort>) -> ProcessorPtr {
-        Sinker::create(input, SyncSenderSink { sender })
+        Sinker::create(input, SyncSenderSink {
+            sender: Some(sender),
+        })
     }
 }
 
@@ -37,8 +40,21 @@ impl SyncSenderSink {
 impl Sink for SyncSenderSink {
     const NAME: &'static str = "SyncSenderSink";
 
-    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
-        self.sender.blocking_send(Ok(data_block)).unwrap();
+    // Close the channel once the input is finished, the receiver gets the end of the data.
+    fn on_finish(&mut self) -> Result<()> {
+        drop(self.sender.take());
         Ok(())
     }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        match &self.sender {
+            None => Err(ErrorCode::LogicalError("Logical error: sink is finished.")),
+            Some(sender) => match sender.blocking_send(Ok(data_block)) {
+                Ok(_) => Ok(()),
+                Err(_) => Err(ErrorCode::AbortedQuery(
+                    "Aborted query, because the receiver of the result is closed.",
+                )),
+            },
+        }
+    }
 }
@@ -71,6 +71,9 @@ impl AsyncSource for TableSource {
             self.initialize().await?;
         }
 
+        // fails the query once its memory usage exceeds the limits
+        self.ctx.check_memory_limit()?;
+
         match &mut self.wrap_stream {
             None => Err(ErrorCode::LogicalError("")),
             Some(stream) => match stream.next().await {
@@ -13,6 +13,27 @@
 // limitations under the License.
 
 mod transform;
+mod transform_accumulating;
+mod transform_aggregator;
 mod transform_dummy;
+mod transform_expression;
+mod transform_filter;
+mod transform_group_by_final;
+mod transform_group_by_partial;
+mod transform_limit;
+mod transform_projection;
+mod transform_sort_merge;
+mod transform_sort_partial;
 
+pub use transform_aggregator::TransformAggregatorFinal;
+pub use transform_aggregator::TransformAggregatorPartial;
 pub use transform_dummy::TransformDummy;
+pub use transform_expression::TransformExpression;
+pub use transform_filter::TransformFilter;
+pub use transform_filter::TransformHaving;
+pub use transform_group_by_final::TransformGroupByFinal;
+pub use transform_group_by_partial::TransformGroupByPartial;
+pub use transform_limit::TransformLimit;
+pub use transform_projection::TransformProjection;
+pub use transform_sort_merge::TransformSortMerge;
+pub use transform_sort_partial::TransformSortPartial;
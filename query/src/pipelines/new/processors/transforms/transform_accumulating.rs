@@ -0,0 +1,128 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+use crate::sessions::QueryContext;
+
+/// A transform which has to see all the input before producing any output, such as the
+/// aggregation and the sort merge.
+pub trait AccumulatingTransform: Send {
+    const NAME: &'static str;
+
+    fn transform(&mut self, data: DataBlock) -> Result<()>;
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>>;
+}
+
+pub struct AccumulatingTransformer<T: AccumulatingTransform + 'static> {
+    inner: T,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    ctx: Arc<QueryContext>,
+
+    called_on_finish: bool,
+    input_data: Option<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+}
+
+impl<T: AccumulatingTransform + 'static> AccumulatingTransformer<T> {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        inner: T,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Box::new(AccumulatingTransformer {
+            inner,
+            input,
+            output,
+            ctx,
+            called_on_finish: false,
+            input_data: None,
+            output_data: VecDeque::new(),
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: AccumulatingTransform + 'static> Processor for AccumulatingTransformer<T> {
+    fn name(&self) -> &'static str {
+        T::NAME
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.pop_front() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            return match self.called_on_finish {
+                true => {
+                    self.output.finish();
+                    Ok(Event::Finished)
+                }
+                false => Ok(Event::Sync),
+            };
+        }
+
+        match self.input.has_data() {
+            true => {
+                self.input_data = Some(self.input.pull_data().unwrap()?);
+                Ok(Event::Sync)
+            }
+            false => {
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            // The input is held until the end, fail before it grows over the memory limits.
+            self.ctx.check_memory_limit()?;
+            self.inner.transform(data_block)?;
+        } else if !self.called_on_finish {
+            self.called_on_finish = true;
+            self.output_data.extend(self.inner.on_finish()?);
+        }
+
+        Ok(())
+    }
+}
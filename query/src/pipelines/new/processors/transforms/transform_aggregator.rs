@@ -0,0 +1,214 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::BorrowMut;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_functions::aggregates::get_layout_offsets;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_io::prelude::*;
+use common_planners::Expression;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransform;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransformer;
+use crate::sessions::QueryContext;
+
+/// The states of the aggregate functions without group by, allocated in the arena.
+struct AggregatorStates {
+    funcs: Vec<AggregateFunctionRef>,
+    places: Vec<usize>,
+    arena: Bump,
+}
+
+impl AggregatorStates {
+    fn create(funcs: Vec<AggregateFunctionRef>) -> AggregatorStates {
+        let arena = Bump::new();
+        let (layout, offsets_aggregate_states) = unsafe { get_layout_offsets(&funcs) };
+
+        let place: StateAddr = arena.alloc_layout(layout).into();
+        let places = funcs
+            .iter()
+            .enumerate()
+            .map(|(idx, func)| {
+                let arg_place = place.next(offsets_aggregate_states[idx]);
+                func.init_state(arg_place);
+                arg_place.addr()
+            })
+            .collect();
+
+        AggregatorStates {
+            funcs,
+            places,
+            arena,
+        }
+    }
+}
+
+/// Accumulates the input into the states of the aggregate functions, and outputs the
+/// serialized states as one block at the end.
+pub struct TransformAggregatorPartial {
+    schema: DataSchemaRef,
+    arg_names: Vec<Vec<String>>,
+    states: AggregatorStates,
+}
+
+impl TransformAggregatorPartial {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        schema_before_group_by: DataSchemaRef,
+        exprs: Vec<Expression>,
+    ) -> Result<ProcessorPtr> {
+        let funcs = exprs
+            .iter()
+            .map(|expr| expr.to_aggregate_function(&schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
+        let arg_names = exprs
+            .iter()
+            .map(|expr| expr.to_aggregate_function_names())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AccumulatingTransformer::create(
+            input,
+            output,
+            ctx,
+            TransformAggregatorPartial {
+                schema,
+                arg_names,
+                states: AggregatorStates::create(funcs),
+            },
+        ))
+    }
+}
+
+impl AccumulatingTransform for TransformAggregatorPartial {
+    const NAME: &'static str = "AggregatorPartialTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<()> {
+        let rows = block.num_rows();
+        for (idx, func) in self.states.funcs.iter().enumerate() {
+            let mut arg_columns = vec![];
+            for name in self.arg_names[idx].iter() {
+                arg_columns.push(block.try_column_by_name(name)?.clone());
+            }
+            let place = self.states.places[idx].into();
+            func.accumulate(place, &arg_columns, None, rows)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>> {
+        let mut columns = Vec::with_capacity(self.states.funcs.len());
+        let mut bytes = BytesMut::new();
+
+        for (idx, func) in self.states.funcs.iter().enumerate() {
+            let place = self.states.places[idx].into();
+            func.serialize(place, &mut bytes)?;
+            let mut array_builder = MutableStringColumn::with_capacity(4);
+            array_builder.append_value(&bytes[..]);
+            bytes.clear();
+            columns.push(array_builder.to_column());
+        }
+
+        Ok(vec![DataBlock::create(self.schema.clone(), columns)])
+    }
+}
+
+/// Merges the serialized states of the partial aggregations, and outputs the results of the
+/// aggregate functions as one block at the end.
+pub struct TransformAggregatorFinal {
+    schema: DataSchemaRef,
+    states: AggregatorStates,
+}
+
+impl TransformAggregatorFinal {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        schema_before_group_by: DataSchemaRef,
+        exprs: Vec<Expression>,
+    ) -> Result<ProcessorPtr> {
+        let funcs = exprs
+            .iter()
+            .map(|expr| expr.to_aggregate_function(&schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AccumulatingTransformer::create(
+            input,
+            output,
+            ctx,
+            TransformAggregatorFinal {
+                schema,
+                states: AggregatorStates::create(funcs),
+            },
+        ))
+    }
+}
+
+impl AccumulatingTransform for TransformAggregatorFinal {
+    const NAME: &'static str = "AggregatorFinalTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<()> {
+        let states = &self.states;
+        for (idx, func) in states.funcs.iter().enumerate() {
+            let place = states.places[idx].into();
+
+            let binary_array = block.column(idx);
+            let binary_array: &StringColumn = Series::check_get(binary_array)?;
+
+            for row in 0..binary_array.len() {
+                let mut data = binary_array.get_data(row);
+                let temp_addr = states.arena.alloc_layout(func.state_layout()).into();
+                func.init_state(temp_addr);
+
+                func.deserialize(temp_addr, &mut data)?;
+                func.merge(place, temp_addr)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>> {
+        let funcs = &self.states.funcs;
+        if funcs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut columns = Vec::with_capacity(funcs.len());
+        for (idx, func) in funcs.iter().enumerate() {
+            let place = self.states.places[idx].into();
+            let mut builder = func.return_type()?.create_mutable(1024);
+            let array: &mut dyn MutableColumn = builder.borrow_mut();
+            func.merge_result(place, array)?;
+            columns.push(builder.to_column());
+        }
+
+        Ok(vec![DataBlock::create(self.schema.clone(), columns)])
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::BorrowMut;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethodKind;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_functions::aggregates::get_layout_offsets;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_planners::Expression;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransform;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransformer;
+use crate::sessions::QueryContext;
+
+/// Merges the serialized states of the groups from the partial aggregations, and outputs
+/// the results of the aggregate functions of all the groups at the end.
+pub struct TransformGroupByFinal {
+    max_block_size: usize,
+    schema: DataSchemaRef,
+    schema_before_group_by: DataSchemaRef,
+    funcs: Vec<AggregateFunctionRef>,
+    group_cols: Vec<String>,
+    group_fields: Vec<DataField>,
+    blocks: Vec<DataBlock>,
+}
+
+impl TransformGroupByFinal {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        max_block_size: usize,
+        schema_before_group_by: DataSchemaRef,
+        aggr_exprs: Vec<Expression>,
+        group_exprs: Vec<Expression>,
+    ) -> Result<ProcessorPtr> {
+        let funcs = aggr_exprs
+            .iter()
+            .map(|x| x.to_aggregate_function(&schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
+        let group_cols = group_exprs
+            .iter()
+            .map(|x| x.column_name())
+            .collect::<Vec<_>>();
+
+        let group_fields = group_exprs
+            .iter()
+            .map(|c| c.to_data_field(&schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(AccumulatingTransformer::create(
+            input,
+            output,
+            ctx,
+            TransformGroupByFinal {
+                max_block_size,
+                schema,
+                schema_before_group_by,
+                funcs,
+                group_cols,
+                group_fields,
+                blocks: vec![],
+            },
+        ))
+    }
+}
+
+impl AccumulatingTransform for TransformGroupByFinal {
+    const NAME: &'static str = "GroupByFinalTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<()> {
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>> {
+        let funcs = &self.funcs;
+        let aggr_funcs_len = funcs.len();
+        let group_expr_len = self.group_fields.len();
+
+        let sample_block = DataBlock::empty_with_schema(self.schema_before_group_by.clone());
+        let method = DataBlock::choose_hash_method(&sample_block, &self.group_cols)?;
+
+        let (layout, offsets_aggregate_states) = unsafe { get_layout_offsets(funcs) };
+
+        macro_rules! apply {
+            ($hash_method: ident, $key_column_type: ty, $key_type: ty) => {{
+                let arena = Bump::new();
+                let mut groups = HashMap::<$key_type, usize, ahash::RandomState>::default();
+
+                for block in &self.blocks {
+                    let key_array = block.column(aggr_funcs_len);
+                    let key_array: $key_column_type = Series::check_get(key_array)?;
+
+                    let mut states_binary_columns = Vec::with_capacity(aggr_funcs_len);
+                    for idx in 0..aggr_funcs_len {
+                        let aggr_column: &StringColumn = Series::check_get(block.column(idx))?;
+                        states_binary_columns.push(aggr_column);
+                    }
+
+                    for row in 0..block.num_rows() {
+                        let group_key = $hash_method.get_key(&key_array, row);
+                        match groups.get(&group_key) {
+                            None => {
+                                if aggr_funcs_len == 0 {
+                                    groups.insert(group_key, 0usize);
+                                } else {
+                                    let place: StateAddr = arena.alloc_layout(layout).into();
+                                    for (idx, func) in funcs.iter().enumerate() {
+                                        let arg_place = place.next(offsets_aggregate_states[idx]);
+
+                                        let mut data = states_binary_columns[idx].get_data(row);
+                                        func.init_state(arg_place);
+                                        func.deserialize(arg_place, &mut data)?;
+                                    }
+                                    groups.insert(group_key, place.addr());
+                                }
+                            }
+                            Some(place) => {
+                                let place: StateAddr = (*place).into();
+
+                                for (idx, func) in funcs.iter().enumerate() {
+                                    let arg_place = place.next(offsets_aggregate_states[idx]);
+
+                                    let mut data = states_binary_columns[idx].get_data(row);
+                                    let temp_addr = arena.alloc_layout(func.state_layout()).into();
+
+                                    func.init_state(temp_addr);
+                                    func.deserialize(temp_addr, &mut data)?;
+                                    func.merge(arg_place, temp_addr)?;
+                                }
+                            }
+                        };
+                    }
+                }
+
+                if groups.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                let mut aggr_builders = funcs
+                    .iter()
+                    .map(|func| Ok(func.return_type()?.create_mutable(groups.len())))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut keys = Vec::with_capacity(groups.len());
+                for (key, place) in groups.iter() {
+                    keys.push(key.clone());
+
+                    let place: StateAddr = (*place).into();
+                    for (idx, func) in funcs.iter().enumerate() {
+                        let arg_place = place.next(offsets_aggregate_states[idx]);
+                        let builder: &mut dyn MutableColumn = aggr_builders[idx].borrow_mut();
+                        func.merge_result(arg_place, builder)?;
+                    }
+                }
+
+                let mut columns: Vec<ColumnRef> =
+                    Vec::with_capacity(aggr_funcs_len + group_expr_len);
+                for mut builder in aggr_builders {
+                    columns.push(builder.to_column());
+                }
+
+                let group_columns = $hash_method.de_group_columns(keys, &self.group_fields)?;
+                columns.extend_from_slice(&group_columns);
+
+                let block = DataBlock::create(self.schema.clone(), columns);
+                DataBlock::split_block_by_size(&block, self.max_block_size)
+            }};
+        }
+
+        match method {
+            HashMethodKind::Serializer(hash_method) => {
+                apply! { hash_method, &StringColumn, Vec<u8> }
+            }
+            HashMethodKind::KeysU8(hash_method) => {
+                apply! { hash_method, &UInt8Column, u8 }
+            }
+            HashMethodKind::KeysU16(hash_method) => {
+                apply! { hash_method, &UInt16Column, u16 }
+            }
+            HashMethodKind::KeysU32(hash_method) => {
+                apply! { hash_method, &UInt32Column, u32 }
+            }
+            HashMethodKind::KeysU64(hash_method) => {
+                apply! { hash_method, &UInt64Column, u64 }
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodKind;
+use common_datavalues2::DataSchemaRef;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransform;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransformer;
+use crate::pipelines::transforms::group_by::Aggregator;
+use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::sessions::QueryContext;
+
+pub struct TransformGroupByPartial;
+
+impl TransformGroupByPartial {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        schema: DataSchemaRef,
+        schema_before_group_by: DataSchemaRef,
+        aggr_exprs: Vec<Expression>,
+        group_exprs: Vec<Expression>,
+    ) -> Result<ProcessorPtr> {
+        let group_cols = group_exprs
+            .iter()
+            .map(|x| x.column_name())
+            .collect::<Vec<_>>();
+
+        let sample_block = DataBlock::empty_with_schema(schema_before_group_by.clone());
+        let hash_method = DataBlock::choose_hash_method(&sample_block, &group_cols)?;
+        let params = AggregatorParams::try_create(schema_before_group_by, &aggr_exprs)?;
+
+        macro_rules! create_with_method {
+            ($method: ident) => {{
+                let state = $method.aggregate_state();
+                let aggregator = Aggregator::create($method, params);
+                Ok(AccumulatingTransformer::create(
+                    input,
+                    output,
+                    ctx,
+                    TransformGroupByPartialImpl {
+                        schema,
+                        group_cols,
+                        aggregator,
+                        state,
+                    },
+                ))
+            }};
+        }
+
+        match hash_method {
+            HashMethodKind::KeysU8(method) => create_with_method!(method),
+            HashMethodKind::KeysU16(method) => create_with_method!(method),
+            HashMethodKind::KeysU32(method) => create_with_method!(method),
+            HashMethodKind::KeysU64(method) => create_with_method!(method),
+            HashMethodKind::Serializer(method) => create_with_method!(method),
+        }
+    }
+}
+
+/// Accumulates the input into the states of the groups, and outputs the serialized states
+/// of all the groups at the end.
+pub struct TransformGroupByPartialImpl<Method>
+where Method: HashMethod + PolymorphicKeysHelper<Method>
+{
+    schema: DataSchemaRef,
+    group_cols: Vec<String>,
+    aggregator: Aggregator<Method>,
+    state: Method::State,
+}
+
+impl<Method> AccumulatingTransform for TransformGroupByPartialImpl<Method>
+where Method: HashMethod + PolymorphicKeysHelper<Method> + Send
+{
+    const NAME: &'static str = "GroupByPartialTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<()> {
+        self.aggregator
+            .aggregate_block(&self.group_cols, &block, &mut self.state)
+    }
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>> {
+        let block = self
+            .aggregator
+            .finalized_block(&self.state, self.schema.clone())?;
+        Ok(block.into_iter().collect())
+    }
+}
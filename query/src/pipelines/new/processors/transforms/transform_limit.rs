@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::Event;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::Processor;
+
+/// Skips the first `offset` rows and takes at most `limit` rows of the input. The input is
+/// finished as soon as the limit is reached, so that the upstream processors stop early.
+pub struct TransformLimit {
+    limit: Option<usize>,
+    offset: usize,
+
+    skipped_rows: usize,
+    taken_rows: usize,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformLimit {
+    pub fn try_create(
+        limit: Option<usize>,
+        offset: usize,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformLimit {
+            limit,
+            offset,
+            skipped_rows: 0,
+            taken_rows: 0,
+            input,
+            output,
+            input_data: None,
+            output_data: None,
+        })))
+    }
+
+    fn is_limit_reached(&self) -> bool {
+        matches!(self.limit, Some(limit) if self.taken_rows >= limit)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformLimit {
+    fn name(&self) -> &'static str {
+        "LimitTransform"
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.is_limit_reached() || self.input.is_finished() {
+            self.input.finish();
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        match self.input.has_data() {
+            true => {
+                self.input_data = Some(self.input.pull_data().unwrap()?);
+                Ok(Event::Sync)
+            }
+            false => {
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            let rows = data_block.num_rows();
+            let skip = std::cmp::min(self.offset - self.skipped_rows, rows);
+            self.skipped_rows += skip;
+
+            let take = match self.limit {
+                None => rows - skip,
+                Some(limit) => std::cmp::min(limit - self.taken_rows, rows - skip),
+            };
+            self.taken_rows += take;
+
+            if take > 0 {
+                self.output_data = Some(match skip == 0 && take == rows {
+                    true => data_block,
+                    false => data_block.slice(skip, take),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_exception::Result;
+
+use crate::pipelines::new::processors::port::InputPort;
+use crate::pipelines::new::processors::port::OutputPort;
+use crate::pipelines::new::processors::processor::ProcessorPtr;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransform;
+use crate::pipelines::new::processors::transforms::transform_accumulating::AccumulatingTransformer;
+use crate::sessions::QueryContext;
+
+/// Merges all the sorted blocks of the input into one sorted block.
+pub struct TransformSortMerge {
+    blocks: Vec<DataBlock>,
+    limit: Option<usize>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+}
+
+impl TransformSortMerge {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        limit: Option<usize>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+    ) -> Result<ProcessorPtr> {
+        Ok(AccumulatingTransformer::create(
+            input,
+            output,
+            ctx,
+            TransformSortMerge {
+                blocks: vec![],
+                limit,
+                sort_columns_descriptions,
+            },
+        ))
+    }
+}
+
+impl AccumulatingTransform for TransformSortMerge {
+    const NAME: &'static str = "SortMergeTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<()> {
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<Vec<DataBlock>> {
+        if self.blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![DataBlock::merge_sort_blocks(
+            &self.blocks,
+            &self.sort_columns_descriptions,
+            self.limit,
+        )?])
+    }
+}
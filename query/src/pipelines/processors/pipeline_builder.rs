@@ -21,6 +21,7 @@ use common_planners::BroadcastPlan;
 use common_planners::ExpressionPlan;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::PlanNode;
@@ -32,6 +33,7 @@ use common_planners::SinkPlan;
 use common_planners::SortPlan;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::WindowFuncPlan;
 use common_tracing::tracing;
 
 use crate::api::FlightTicket;
@@ -42,7 +44,9 @@ use crate::pipelines::transforms::CreateSetsTransform;
 use crate::pipelines::transforms::ExpressionTransform;
 use crate::pipelines::transforms::GroupByFinalTransform;
 use crate::pipelines::transforms::GroupByPartialTransform;
+use crate::pipelines::transforms::HashJoinTransform;
 use crate::pipelines::transforms::HavingTransform;
+use crate::pipelines::transforms::JoinHashTablePuller;
 use crate::pipelines::transforms::LimitByTransform;
 use crate::pipelines::transforms::LimitTransform;
 use crate::pipelines::transforms::ProjectionTransform;
@@ -51,8 +55,10 @@ use crate::pipelines::transforms::SinkTransform;
 use crate::pipelines::transforms::SortMergeTransform;
 use crate::pipelines::transforms::SortPartialTransform;
 use crate::pipelines::transforms::SourceTransform;
+use crate::pipelines::transforms::SpillConfig;
 use crate::pipelines::transforms::SubQueriesPuller;
 use crate::pipelines::transforms::WhereTransform;
+use crate::pipelines::transforms::WindowFuncTransform;
 use crate::sessions::QueryContext;
 
 pub struct PipelineBuilder {
@@ -96,6 +102,8 @@ impl PipelineBuilder {
             PlanNode::LimitBy(node) => self.visit_limit_by(node),
             PlanNode::ReadSource(node) => self.visit_read_data_source(node),
             PlanNode::SubQueryExpression(node) => self.visit_create_sets(node),
+            PlanNode::Join(node) => self.visit_join(node),
+            PlanNode::WindowFunc(node) => self.visit_window_func(node),
             PlanNode::Sink(node) => self.visit_sink(node),
             other => Result::Err(ErrorCode::UnknownPlan(format!(
                 "Build pipeline from the plan node unsupported:{:?}",
@@ -168,18 +176,26 @@ impl PipelineBuilder {
         if node.group_expr.is_empty() {
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(AggregatorPartialTransform::try_create(
+                    self.ctx.clone(),
                     node.schema(),
                     node.input.schema(),
                     node.aggr_expr.clone(),
                 )?))
             })?;
         } else {
+            let max_bytes = self
+                .ctx
+                .get_settings()
+                .get_max_bytes_before_external_group_by()?;
+            let spill = self.spill_config(max_bytes);
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByPartialTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     node.input.schema(),
                     node.aggr_expr.clone(),
                     node.group_expr.clone(),
+                    spill.clone(),
                 )))
             })?;
         }
@@ -193,6 +209,7 @@ impl PipelineBuilder {
         if node.group_expr.is_empty() {
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(AggregatorFinalTransform::try_create(
+                    self.ctx.clone(),
                     node.schema(),
                     node.schema_before_group_by.clone(),
                     node.aggr_expr.clone(),
@@ -200,13 +217,20 @@ impl PipelineBuilder {
             })?;
         } else {
             let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+            let max_bytes = self
+                .ctx
+                .get_settings()
+                .get_max_bytes_before_external_group_by()?;
+            let spill = self.spill_config(max_bytes);
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByFinalTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     max_block_size,
                     node.schema_before_group_by.clone(),
                     node.aggr_expr.clone(),
                     node.group_expr.clone(),
+                    spill.clone(),
                 )))
             })?;
             pipeline.mixed_processor(self.ctx.get_settings().get_max_threads()? as usize)?;
@@ -243,6 +267,11 @@ impl PipelineBuilder {
         // 'select * from numbers(100) order by number desc limit 10 offset 5', the
         // sort pipeline should return at least 15 rows.
         let rows_limit = self.limit.map(|limit| limit + self.offset);
+        let max_bytes = self
+            .ctx
+            .get_settings()
+            .get_max_bytes_before_external_sort()?;
+        let spill = self.spill_config(max_bytes);
 
         // processor 1: block ---> sort_stream
         // processor 2: block ---> sort_stream
@@ -260,9 +289,11 @@ impl PipelineBuilder {
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                self.ctx.clone(),
                 plan.schema(),
                 plan.order_by.clone(),
                 rows_limit,
+                spill.clone(),
             )?))
         })?;
 
@@ -275,15 +306,22 @@ impl PipelineBuilder {
             pipeline.merge_processor()?;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(SortMergeTransform::try_create(
+                    self.ctx.clone(),
                     plan.schema(),
                     plan.order_by.clone(),
                     rows_limit,
+                    spill.clone(),
                 )?))
             })?;
         }
         Ok(pipeline)
     }
 
+    fn spill_config(&self, max_bytes: u64) -> Option<SpillConfig> {
+        let temp_data_path = self.ctx.get_config().storage.disk.temp_data_path;
+        SpillConfig::try_create(max_bytes, &temp_data_path)
+    }
+
     fn visit_limit(&mut self, node: &LimitPlan) -> Result<Pipeline> {
         self.limit = node.n;
         self.offset = node.offset;
@@ -308,6 +346,23 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_window_func(&mut self, node: &WindowFuncPlan) -> Result<Pipeline> {
+        // The window function depends on all the rows of its partition.
+        self.limit = None;
+        self.offset = 0;
+
+        let mut pipeline = self.visit(&*node.input)?;
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(WindowFuncTransform::create(
+                node.window_func.clone(),
+                node.schema(),
+                node.input.schema(),
+            )))
+        })?;
+        Ok(pipeline)
+    }
+
     fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<Pipeline> {
         // Bind plan partitions to context.
         self.ctx.try_set_partitions(plan.parts.clone())?;
@@ -337,6 +392,35 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<Pipeline> {
+        // The limit of join output can't be pushed down to the inputs.
+        self.limit = None;
+        self.offset = 0;
+
+        let mut pipeline = self.visit(&*plan.left)?;
+
+        // The rows of build side which depend on all the probe rows can only be emitted once.
+        if plan.join_type.need_build_side_visited() {
+            pipeline.merge_processor()?;
+        }
+
+        let context = self.ctx.clone();
+        let hash_table_puller = JoinHashTablePuller::create(
+            context.clone(),
+            plan.right.as_ref().clone(),
+            plan.right_keys.clone(),
+        );
+        pipeline.add_simple_transform(move || {
+            Ok(Box::new(HashJoinTransform::try_create(
+                context.clone(),
+                plan,
+                hash_table_puller.clone(),
+            )?))
+        })?;
+
+        Ok(pipeline)
+    }
+
     fn visit_create_sets(&mut self, plan: &SubQueriesSetPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*plan.input)?;
         let schema = plan.schema();
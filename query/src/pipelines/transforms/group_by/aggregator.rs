@@ -29,6 +29,8 @@ use crate::pipelines::transforms::group_by::aggregator_params::AggregatorParamsR
 use crate::pipelines::transforms::group_by::aggregator_state::AggregatorState;
 use crate::pipelines::transforms::group_by::aggregator_state_entity::StateEntity;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::transforms::spill::SpillConfig;
+use crate::pipelines::transforms::spill::SpillWriter;
 
 pub struct Aggregator<Method: HashMethod> {
     method: Method,
@@ -42,45 +44,66 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
     // If we set it to inline(performance degradation).
     // Because it will make other internal functions to no inline
+    //
+    // If spill is set, once the states grow beyond the threshold, they are serialized into the
+    // spill file as partial results and the aggregation restarts with empty states. The spilled
+    // partial results are merged with the others by the final aggregation.
     #[inline(never)]
     pub async fn aggregate(
         &self,
         group_cols: Vec<String>,
         mut stream: SendableDataBlockStream,
-    ) -> Result<Method::State> {
+        spill: Option<(&SpillConfig, DataSchemaRef)>,
+    ) -> Result<(Method::State, Option<SpillWriter>)> {
         // This may be confusing
         // It will help us improve performance ~10% when we declare local references for them.
         let hash_method = &self.method;
-        let aggregator_params = self.params.as_ref();
 
         let mut state = hash_method.aggregate_state();
-
-        match aggregator_params.aggregate_functions.is_empty() {
-            true => {
-                while let Some(block) = stream.next().await {
-                    let block = block?;
-
-                    // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
-                    self.lookup_key(group_keys, &mut state);
+        let mut spilled: Option<SpillWriter> = None;
+
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            self.aggregate_block(&group_cols, &block, &mut state)?;
+
+            if let Some((config, schema)) = &spill {
+                if state.allocated_bytes() > config.max_bytes {
+                    if spilled.is_none() {
+                        spilled = Some(SpillWriter::try_create(config, schema.clone()).await?);
+                    }
+
+                    if let Some(block) = self.finalized_block(&state, schema.clone())? {
+                        spilled.as_mut().unwrap().write(block).await?;
+                    }
+                    state = hash_method.aggregate_state();
                 }
             }
-            false => {
-                while let Some(block) = stream.next().await {
-                    let block = block?;
+        }
 
-                    // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
+        Ok((state, spilled))
+    }
 
-                    let places = self.lookup_state(group_keys, &mut state);
-                    Self::execute(aggregator_params, &block, &places)?;
-                }
+    /// Accumulate one block into the states of its groups.
+    #[inline(always)]
+    pub fn aggregate_block(
+        &self,
+        group_cols: &[String],
+        block: &DataBlock,
+        state: &mut Method::State,
+    ) -> Result<()> {
+        // 1.1 and 1.2.
+        let group_columns = Self::group_columns(group_cols, block)?;
+        let group_keys = self.method.build_keys(&group_columns, block.num_rows())?;
+
+        match self.params.aggregate_functions.is_empty() {
+            true => self.lookup_key(group_keys, state),
+            false => {
+                let places = self.lookup_state(group_keys, state);
+                Self::execute(self.params.as_ref(), block, &places)?;
             }
         }
 
-        Ok(state)
+        Ok(())
     }
 
     #[inline(always)]
@@ -175,12 +198,24 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
         groups: &Method::State,
         schema: DataSchemaRef,
     ) -> Result<SendableDataBlockStream> {
-        if groups.len() == 0 {
-            return Ok(Box::pin(DataBlockStream::create(
+        match self.finalized_block(groups, schema.clone())? {
+            None => Ok(Box::pin(DataBlockStream::create(
                 DataSchemaRefExt::create(vec![]),
                 None,
                 vec![],
-            )));
+            ))),
+            Some(block) => Ok(Box::pin(DataBlockStream::create(schema, None, vec![block]))),
+        }
+    }
+
+    // Serialize the states of all the groups, None if there is no group.
+    pub fn finalized_block(
+        &self,
+        groups: &Method::State,
+        schema: DataSchemaRef,
+    ) -> Result<Option<DataBlock>> {
+        if groups.len() == 0 {
+            return Ok(None);
         }
 
         let aggregator_params = self.params.as_ref();
@@ -215,7 +250,6 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
         }
 
         columns.push(group_key_builder.finish());
-        let block = DataBlock::create(schema.clone(), columns);
-        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+        Ok(Some(DataBlock::create(schema, columns)))
     }
 }
@@ -47,6 +47,10 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
 
     fn len(&self) -> usize;
 
+    /// The approximate memory held by the keys and the states, it doesn't count the memory
+    /// allocated by the aggregate functions outside of the states themselves.
+    fn allocated_bytes(&self) -> usize;
+
     fn iter(&self) -> Self::Iterator;
 
     fn alloc_layout(&self, params: &AggregatorParams) -> StateAddr;
@@ -117,6 +121,11 @@ where
         self.size
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes()
+            + self.max_size * std::mem::size_of::<ShortFixedKeysStateEntity<T>>()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         Self::Iterator::create(self.data, self.max_size as isize)
@@ -186,6 +195,10 @@ where
         self.data.len()
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes() + self.data.len() * std::mem::size_of::<Self::Entity>()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         self.data.iter()
@@ -236,6 +249,12 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
         self.data_state_map.len()
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.len() * std::mem::size_of::<Self::Entity>()
+    }
+
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
@@ -20,6 +20,7 @@ mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
+mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
 mod transform_projection;
@@ -27,12 +28,21 @@ mod transform_remote;
 mod transform_sort_merge;
 mod transform_sort_partial;
 mod transform_source;
+mod transform_window_func;
 
-mod group_by;
+pub(crate) mod group_by;
+mod spill;
 mod streams;
 mod transform_sink;
 
+pub use spill::blocking_stream;
+pub use spill::SortedRun;
+pub use spill::SortedRunsMerger;
+pub use spill::SpillConfig;
+pub use spill::SpillReader;
+pub use spill::SpillWriter;
 pub use streams::AddOnStream;
+pub use streams::MemoryLimitStream;
 pub use transform_aggregator_final::AggregatorFinalTransform;
 pub use transform_aggregator_partial::AggregatorPartialTransform;
 pub use transform_create_sets::CreateSetsTransform;
@@ -43,6 +53,10 @@ pub use transform_filter::HavingTransform;
 pub use transform_filter::WhereTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
 pub use transform_group_by_partial::GroupByPartialTransform;
+pub use transform_hash_join::HashJoinTransform;
+pub use transform_hash_join::JoinHashTable;
+pub use transform_hash_join::JoinHashTablePuller;
+pub use transform_hash_join::JoinKeysExecutor;
 pub use transform_limit::LimitTransform;
 pub use transform_limit_by::LimitByTransform;
 pub use transform_projection::ProjectionTransform;
@@ -52,3 +66,4 @@ pub use transform_sort_merge::SortMergeTransform;
 pub use transform_sort_partial::get_sort_descriptions;
 pub use transform_sort_partial::SortPartialTransform;
 pub use transform_source::SourceTransform;
+pub use transform_window_func::WindowFuncTransform;
@@ -0,0 +1,193 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::compute::merge_sort::build_comparator;
+use common_arrow::arrow::compute::sort::SortOptions;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+
+pub type SortedRun = Box<dyn Iterator<Item = Result<DataBlock>> + Send>;
+
+/// Merges sorted runs into one sorted sequence of blocks.
+///
+/// Only the current block of each run is held in memory. In each round, the run whose current
+/// block ends with the smallest row bounds the output: the rows not greater than it are taken
+/// from the head of every current block and merged, the rest of the blocks are kept for the
+/// next round.
+pub struct SortedRunsMerger {
+    runs: Vec<SortedRun>,
+    current: Vec<Option<DataBlock>>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    limit: Option<usize>,
+    num_rows: usize,
+}
+
+impl SortedRunsMerger {
+    pub fn create(
+        runs: Vec<SortedRun>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        limit: Option<usize>,
+    ) -> Self {
+        let current = runs.iter().map(|_| None).collect();
+        SortedRunsMerger {
+            runs,
+            current,
+            sort_columns_descriptions,
+            limit,
+            num_rows: 0,
+        }
+    }
+
+    fn fill_current_blocks(&mut self) -> Result<()> {
+        for (run, current) in self.runs.iter_mut().zip(self.current.iter_mut()) {
+            while current.is_none() {
+                match run.next() {
+                    None => break,
+                    Some(block) => {
+                        let block = block?;
+                        if block.num_rows() > 0 {
+                            *current = Some(block);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_next(&mut self) -> Result<Option<DataBlock>> {
+        self.fill_current_blocks()?;
+
+        let active = (0..self.current.len())
+            .filter(|idx| self.current[*idx].is_some())
+            .collect::<Vec<_>>();
+
+        match active.len() {
+            0 => return Ok(None),
+            1 => return Ok(self.current[active[0]].take()),
+            _ => {}
+        }
+
+        let blocks = active
+            .iter()
+            .map(|idx| self.current[*idx].take().unwrap())
+            .collect::<Vec<_>>();
+
+        let sort_arrays = self
+            .sort_columns_descriptions
+            .iter()
+            .map(|f| {
+                blocks
+                    .iter()
+                    .map(|block| Ok(block.try_column_by_name(&f.column_name)?.as_arrow_array()))
+                    .collect::<Result<Vec<ArrayRef>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let sort_dyn_arrays = sort_arrays
+            .iter()
+            .map(|arrays| arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let sort_options = self
+            .sort_columns_descriptions
+            .iter()
+            .map(|f| SortOptions {
+                descending: !f.asc,
+                nulls_first: f.nulls_first,
+            })
+            .collect::<Vec<_>>();
+
+        let sort_options_with_array = sort_dyn_arrays
+            .iter()
+            .zip(sort_options.iter())
+            .map(|(arrays, opt)| {
+                let pairs: (&[&dyn Array], &SortOptions) = (arrays, opt);
+                pairs
+            })
+            .collect::<Vec<_>>();
+
+        let comparator = build_comparator(&sort_options_with_array)?;
+        let last_row = |idx: usize| blocks[idx].num_rows() - 1;
+
+        let mut bound = 0;
+        for idx in 1..blocks.len() {
+            if comparator(idx, last_row(idx), bound, last_row(bound)) == Ordering::Less {
+                bound = idx;
+            }
+        }
+
+        let mut heads = Vec::with_capacity(blocks.len());
+        for (idx, block) in blocks.iter().enumerate() {
+            let rows = block.num_rows();
+
+            // The rows are sorted, binary search the number of rows not greater than the bound.
+            let mut taken = rows;
+            if idx != bound {
+                let (mut low, mut high) = (0, rows);
+                while low < high {
+                    let mid = (low + high) / 2;
+                    match comparator(idx, mid, bound, last_row(bound)) {
+                        Ordering::Greater => high = mid,
+                        _ => low = mid + 1,
+                    }
+                }
+                taken = low;
+            }
+
+            if taken > 0 {
+                heads.push(block.slice(0, taken));
+            }
+            if taken < rows {
+                self.current[active[idx]] = Some(block.slice(taken, rows - taken));
+            }
+        }
+
+        let merged =
+            DataBlock::merge_sort_blocks(&heads, &self.sort_columns_descriptions, self.limit)?;
+        Ok(Some(merged))
+    }
+}
+
+impl Iterator for SortedRunsMerger {
+    type Item = Result<DataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if matches!(self.limit, Some(limit) if self.num_rows >= limit) {
+            return None;
+        }
+
+        match self.merge_next() {
+            Ok(None) => None,
+            Err(cause) => Some(Err(cause)),
+            Ok(Some(block)) => {
+                let block = match self.limit {
+                    Some(limit) if self.num_rows + block.num_rows() > limit => {
+                        block.slice(0, limit - self.num_rows)
+                    }
+                    _ => block,
+                };
+
+                self.num_rows += block.num_rows();
+                Some(Ok(block))
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+/// Where and when the blocking transforms (GROUP BY, ORDER BY) spill their state to disk.
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    /// Memory threshold in bytes, the state is spilled once it grows beyond it.
+    pub max_bytes: usize,
+    pub temp_dir: PathBuf,
+}
+
+impl SpillConfig {
+    pub fn create(max_bytes: usize, temp_dir: PathBuf) -> Self {
+        SpillConfig {
+            max_bytes,
+            temp_dir,
+        }
+    }
+
+    /// Returns None if spilling is disabled, e.g. the threshold is 0.
+    ///
+    /// The spill files are placed under `temp_data_path` of the disk storage config,
+    /// or under the temp dir of the system if it is not set.
+    pub fn try_create(max_bytes: u64, temp_data_path: &str) -> Option<Self> {
+        if max_bytes == 0 {
+            return None;
+        }
+
+        let temp_dir = match temp_data_path.is_empty() {
+            true => std::env::temp_dir(),
+            false => PathBuf::from(temp_data_path),
+        };
+        Some(SpillConfig::create(max_bytes as usize, temp_dir))
+    }
+}
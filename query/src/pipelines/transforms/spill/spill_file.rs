@@ -0,0 +1,269 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::io::ipc::IpcSchema;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_base::tokio;
+use common_base::tokio::sync::mpsc;
+use common_datablocks::DataBlock;
+use common_datavalues2::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::pipelines::transforms::spill::SpillConfig;
+
+/// The framed blocks are buffered in memory, and written to the file by a blocking task
+/// once the buffer grows beyond this size.
+const WRITE_BUFFER_SIZE: usize = 1024 * 1024;
+
+// The spill file is removed once both the writer and the reader of it are gone.
+struct TempPath(PathBuf);
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if let Err(cause) = std::fs::remove_file(&self.0) {
+            tracing::warn!("Cannot remove spill file {:?}: {}", self.0, cause);
+        }
+    }
+}
+
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ErrorCode::TokioError(e.to_string()))?
+}
+
+/// Writes data blocks into a temporary file.
+///
+/// Blocks are stored in the arrow ipc format, the same as the blocks are exchanged
+/// between the nodes, each one is framed as:
+/// | header len (u32) | header | body len (u64) | body |
+pub struct SpillWriter {
+    path: TempPath,
+    file: Arc<File>,
+    buffer: Vec<u8>,
+    schema: DataSchemaRef,
+    num_blocks: usize,
+    num_bytes: usize,
+}
+
+impl SpillWriter {
+    pub async fn try_create(config: &SpillConfig, schema: DataSchemaRef) -> Result<SpillWriter> {
+        let temp_dir = config.temp_dir.clone();
+        let (path, file) = run_blocking(move || {
+            std::fs::create_dir_all(&temp_dir)?;
+
+            let path = temp_dir.join(format!("spill-{}", uuid::Uuid::new_v4().simple()));
+            // The file is read back through the same handle once the writing is finished.
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)?;
+            Ok((TempPath(path), file))
+        })
+        .await?;
+
+        Ok(SpillWriter {
+            path,
+            file: Arc::new(file),
+            buffer: Vec::with_capacity(WRITE_BUFFER_SIZE),
+            schema,
+            num_blocks: 0,
+            num_bytes: 0,
+        })
+    }
+
+    pub async fn write(&mut self, block: DataBlock) -> Result<()> {
+        if block.is_empty() {
+            return Ok(());
+        }
+
+        let record_batch: RecordBatch = block.try_into()?;
+        let arrow_schema = self.schema.to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+        let options = WriteOptions { compression: None };
+        let (_, flight_data) = serialize_batch(&record_batch, &ipc_fields, &options);
+
+        let header = &flight_data.data_header;
+        let body = &flight_data.data_body;
+        self.buffer
+            .extend_from_slice(&(header.len() as u32).to_le_bytes());
+        self.buffer.extend_from_slice(header);
+        self.buffer
+            .extend_from_slice(&(body.len() as u64).to_le_bytes());
+        self.buffer.extend_from_slice(body);
+
+        self.num_blocks += 1;
+        self.num_bytes += 12 + header.len() + body.len();
+
+        if self.buffer.len() >= WRITE_BUFFER_SIZE {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let file = self.file.clone();
+        let buffer = std::mem::take(&mut self.buffer);
+        let mut buffer = run_blocking(move || {
+            (&*file).write_all(&buffer)?;
+            Ok(buffer)
+        })
+        .await?;
+
+        buffer.clear();
+        self.buffer = buffer;
+        Ok(())
+    }
+
+    pub fn num_blocks(&self) -> usize {
+        self.num_blocks
+    }
+
+    pub fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+
+    /// Finish the writing and read the blocks back, in the order they were written.
+    ///
+    /// The reader does blocking file I/O, consume it by [blocking_stream] in async contexts.
+    pub async fn into_reader(mut self) -> Result<SpillReader> {
+        self.flush().await?;
+
+        let SpillWriter {
+            path,
+            file,
+            schema,
+            num_blocks,
+            ..
+        } = self;
+
+        // No blocking task holds the file once the flush is done.
+        let mut file = Arc::try_unwrap(file)
+            .map_err(|_| ErrorCode::LogicalError("Spill file is still being written"))?;
+        file.seek(SeekFrom::Start(0))?;
+
+        let arrow_schema = Arc::new(schema.to_arrow());
+        let ipc_schema = IpcSchema {
+            fields: default_ipc_fields(&arrow_schema.fields),
+            is_little_endian: true,
+        };
+
+        Ok(SpillReader {
+            _path: path,
+            reader: BufReader::new(file),
+            schema,
+            arrow_schema,
+            ipc_schema,
+            remaining: num_blocks,
+        })
+    }
+}
+
+/// Reads the blocks of a spill file back, one at a time, by blocking file I/O.
+pub struct SpillReader {
+    _path: TempPath,
+    reader: BufReader<File>,
+    schema: DataSchemaRef,
+    arrow_schema: Arc<common_arrow::arrow::datatypes::Schema>,
+    ipc_schema: IpcSchema,
+    remaining: usize,
+}
+
+impl SpillReader {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    fn read_block(&mut self) -> Result<DataBlock> {
+        let mut header_len = [0u8; 4];
+        self.reader.read_exact(&mut header_len)?;
+        let mut data_header = vec![0u8; u32::from_le_bytes(header_len) as usize];
+        self.reader.read_exact(&mut data_header)?;
+
+        let mut body_len = [0u8; 8];
+        self.reader.read_exact(&mut body_len)?;
+        let mut data_body = vec![0u8; u64::from_le_bytes(body_len) as usize];
+        self.reader.read_exact(&mut data_body)?;
+
+        let flight_data = FlightData {
+            data_header,
+            data_body,
+            ..Default::default()
+        };
+        let batch = deserialize_batch(
+            &flight_data,
+            self.arrow_schema.clone(),
+            &self.ipc_schema,
+            &Default::default(),
+        )?;
+        batch.try_into()
+    }
+}
+
+impl Iterator for SpillReader {
+    type Item = Result<DataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(self.read_block())
+    }
+}
+
+/// Pulls the blocks of the iterator by a blocking task, so that reading the spill files never
+/// blocks the async executor. The reading stops once the stream is dropped.
+pub fn blocking_stream<I>(blocks: I) -> SendableDataBlockStream
+where I: Iterator<Item = Result<DataBlock>> + Send + 'static {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        for block in blocks {
+            if tx.blocking_send(block).is_err() {
+                break;
+            }
+        }
+    });
+    Box::pin(ReceiverStream::new(rx))
+}
@@ -0,0 +1,55 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::sessions::QueryContext;
+
+/// Fails the query once its memory usage exceeds the limits.
+///
+/// The limits are checked each time a block is pulled from the input, so the transforms
+/// holding all their input, such as the aggregation and the sort merge, are stopped while
+/// they are accumulating, not only once they produce their output.
+pub struct MemoryLimitStream {
+    ctx: Arc<QueryContext>,
+    input: SendableDataBlockStream,
+}
+
+impl MemoryLimitStream {
+    pub fn create(ctx: Arc<QueryContext>, input: SendableDataBlockStream) -> Self {
+        MemoryLimitStream { ctx, input }
+    }
+}
+
+impl Stream for MemoryLimitStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Err(cause) = self.ctx.check_memory_limit() {
+            return Poll::Ready(Some(Err(cause)));
+        }
+        self.input.poll_next_unpin(ctx)
+    }
+}
@@ -32,8 +32,11 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
 
 pub struct AggregatorFinalTransform {
+    ctx: Arc<QueryContext>,
     funcs: Vec<AggregateFunctionRef>,
     schema: DataSchemaRef,
     input: Arc<dyn Processor>,
@@ -41,6 +44,7 @@ pub struct AggregatorFinalTransform {
 
 impl AggregatorFinalTransform {
     pub fn try_create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         schema_before_group_by: DataSchemaRef,
         exprs: Vec<Expression>,
@@ -50,6 +54,7 @@ impl AggregatorFinalTransform {
             .map(|expr| expr.to_aggregate_function(&schema_before_group_by))
             .collect::<Result<Vec<_>>>()?;
         Ok(AggregatorFinalTransform {
+            ctx,
             funcs,
             schema,
             input: Arc::new(EmptyProcessor::create()),
@@ -81,7 +86,7 @@ impl Processor for AggregatorFinalTransform {
         tracing::debug!("execute...");
 
         let funcs = self.funcs.clone();
-        let mut stream = self.input.execute().await?;
+        let mut stream = MemoryLimitStream::create(self.ctx.clone(), self.input.execute().await?);
 
         let start = Instant::now();
         let arena = bumpalo::Bump::new();
@@ -32,8 +32,11 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
 
 pub struct AggregatorPartialTransform {
+    ctx: Arc<QueryContext>,
     funcs: Vec<AggregateFunctionRef>,
     arg_names: Vec<Vec<String>>,
 
@@ -43,6 +46,7 @@ pub struct AggregatorPartialTransform {
 
 impl AggregatorPartialTransform {
     pub fn try_create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         schema_before_group_by: DataSchemaRef,
         exprs: Vec<Expression>,
@@ -58,6 +62,7 @@ impl AggregatorPartialTransform {
             .collect::<Result<Vec<_>>>()?;
 
         Ok(AggregatorPartialTransform {
+            ctx,
             funcs,
             arg_names,
             schema,
@@ -91,7 +96,7 @@ impl Processor for AggregatorPartialTransform {
         let start = Instant::now();
 
         let funcs = self.funcs.clone();
-        let mut stream = self.input.execute().await?;
+        let mut stream = MemoryLimitStream::create(self.ctx.clone(), self.input.execute().await?);
         let arg_names = self.arg_names.clone();
 
         let arena = Bump::new();
@@ -15,6 +15,9 @@
 use std::any::Any;
 use std::borrow::BorrowMut;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -35,33 +38,148 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::spill::blocking_stream;
+use crate::pipelines::transforms::spill::SpillConfig;
+use crate::pipelines::transforms::spill::SpillWriter;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
+
+// The number of partitions the input is spilled into, once it exceeds the memory threshold.
+const SPILL_PARTITIONS: usize = 16;
 
 pub struct GroupByFinalTransform {
+    ctx: Arc<QueryContext>,
     max_block_size: usize,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
     schema: DataSchemaRef,
     schema_before_group_by: DataSchemaRef,
+    spill: Option<SpillConfig>,
     input: Arc<dyn Processor>,
 }
 
 impl GroupByFinalTransform {
     pub fn create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         max_block_size: usize,
         schema_before_group_by: DataSchemaRef,
         aggr_exprs: Vec<Expression>,
         group_exprs: Vec<Expression>,
+        spill: Option<SpillConfig>,
     ) -> Self {
         Self {
+            ctx,
             max_block_size,
             aggr_exprs,
             group_exprs,
             schema,
             schema_before_group_by,
+            spill,
             input: Arc::new(EmptyProcessor::create()),
         }
     }
+
+    /// Split the input into partitions, the same group key always goes to the same partition.
+    ///
+    /// If the input fits in the memory threshold, it is returned as the only partition. Otherwise,
+    /// the input is scattered by the hash of the group keys into spill files, so that every
+    /// partition can be merged alone.
+    async fn partition_input(
+        spill: &SpillConfig,
+        mut stream: SendableDataBlockStream,
+        key_index: usize,
+    ) -> Result<Vec<SendableDataBlockStream>> {
+        let mut blocks = vec![];
+        let mut blocks_size = 0;
+        while blocks_size <= spill.max_bytes {
+            match stream.next().await {
+                None => {
+                    let blocks = futures::stream::iter(blocks.into_iter().map(Ok));
+                    return Ok(vec![Box::pin(blocks) as SendableDataBlockStream]);
+                }
+                Some(block) => {
+                    let block = block?;
+                    blocks_size += block.memory_size();
+                    blocks.push(block);
+                }
+            }
+        }
+
+        let schema = blocks[0].schema().clone();
+        let mut partitions = Vec::with_capacity(SPILL_PARTITIONS);
+        for _ in 0..SPILL_PARTITIONS {
+            partitions.push(SpillWriter::try_create(spill, schema.clone()).await?);
+        }
+
+        let hash_state = ahash::RandomState::new();
+        for block in blocks {
+            Self::scatter_to_partitions(&block, key_index, &hash_state, &mut partitions).await?;
+        }
+        while let Some(block) = stream.next().await {
+            Self::scatter_to_partitions(&block?, key_index, &hash_state, &mut partitions).await?;
+        }
+
+        tracing::debug!(
+            "Group by final spilled {} bytes into {} partitions",
+            partitions.iter().map(|p| p.num_bytes()).sum::<usize>(),
+            SPILL_PARTITIONS
+        );
+
+        let mut streams = Vec::with_capacity(partitions.len());
+        for partition in partitions {
+            streams.push(blocking_stream(partition.into_reader().await?));
+        }
+        Ok(streams)
+    }
+
+    async fn scatter_to_partitions(
+        block: &DataBlock,
+        key_index: usize,
+        hash_state: &ahash::RandomState,
+        partitions: &mut [SpillWriter],
+    ) -> Result<()> {
+        let keys = block.column(key_index);
+        let partitions_len = partitions.len();
+        let partition_of = |key: &dyn Fn(&mut ahash::AHasher)| {
+            let mut hasher = hash_state.build_hasher();
+            key(&mut hasher);
+            hasher.finish() as usize % partitions_len
+        };
+
+        let indices = match keys.data_type_id() {
+            TypeID::UInt8 => Series::check_get_scalar::<u8>(keys)?
+                .values()
+                .iter()
+                .map(|v| partition_of(&|h| v.hash(h)))
+                .collect::<Vec<_>>(),
+            TypeID::UInt16 => Series::check_get_scalar::<u16>(keys)?
+                .values()
+                .iter()
+                .map(|v| partition_of(&|h| v.hash(h)))
+                .collect::<Vec<_>>(),
+            TypeID::UInt32 => Series::check_get_scalar::<u32>(keys)?
+                .values()
+                .iter()
+                .map(|v| partition_of(&|h| v.hash(h)))
+                .collect::<Vec<_>>(),
+            TypeID::UInt64 => Series::check_get_scalar::<u64>(keys)?
+                .values()
+                .iter()
+                .map(|v| partition_of(&|h| v.hash(h)))
+                .collect::<Vec<_>>(),
+            _ => Series::check_get_scalar::<Vec<u8>>(keys)?
+                .iter()
+                .map(|v| partition_of(&|h| v.hash(h)))
+                .collect::<Vec<_>>(),
+        };
+
+        let scattered = DataBlock::scatter_block(block, &indices, partitions.len())?;
+        for (partition, block) in partitions.iter_mut().zip(scattered.into_iter()) {
+            partition.write(block).await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -107,9 +225,14 @@ impl Processor for GroupByFinalTransform {
             .collect::<Result<Vec<_>>>()?;
 
         let start = Instant::now();
-        let arena = Bump::new();
 
-        let mut stream = self.input.execute().await?;
+        let stream = self.input.execute().await?;
+        let stream: SendableDataBlockStream =
+            Box::pin(MemoryLimitStream::create(self.ctx.clone(), stream));
+        let partitions = match &self.spill {
+            None => vec![stream],
+            Some(spill) => Self::partition_input(spill, stream, aggr_funcs_len).await?,
+        };
         let sample_block = DataBlock::empty_with_schema(self.schema_before_group_by.clone());
         let method = DataBlock::choose_hash_method(&sample_block, &group_cols)?;
 
@@ -118,105 +241,111 @@ impl Processor for GroupByFinalTransform {
         macro_rules! apply {
             ($hash_method: ident, $key_column_type: ty, $group_func_table: ty) => {{
                 type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
-
-                while let Some(block) = stream.next().await {
-                    let mut groups = groups_locker.write();
-                    let block = block?;
-
-                    let key_array = block.column(aggr_funcs_len);
-                    let key_array: $key_column_type = Series::check_get(key_array)?;
-
-                    let states_columns = (0..aggr_funcs_len)
-                        .map(|i| block.column(i))
-                        .collect::<Vec<_>>();
-                    let mut states_binary_columns = Vec::with_capacity(states_columns.len());
+                let mut blocks = vec![];
 
-                    for agg in states_columns.iter().take(aggr_funcs_len) {
-                        let aggr_column: &StringColumn = Series::check_get(agg)?;
-                        states_binary_columns.push(aggr_column);
-                    }
+                // The partitions never share a group key, each one is merged alone.
+                for mut stream in partitions {
+                    let arena = Bump::new();
+                    let groups_locker = GroupFuncTable::default();
+
+                    while let Some(block) = stream.next().await {
+                        let mut groups = groups_locker.write();
+                        let block = block?;
+
+                        let key_array = block.column(aggr_funcs_len);
+                        let key_array: $key_column_type = Series::check_get(key_array)?;
+
+                        let states_columns = (0..aggr_funcs_len)
+                            .map(|i| block.column(i))
+                            .collect::<Vec<_>>();
+                        let mut states_binary_columns = Vec::with_capacity(states_columns.len());
+
+                        for agg in states_columns.iter().take(aggr_funcs_len) {
+                            let aggr_column: &StringColumn = Series::check_get(agg)?;
+                            states_binary_columns.push(aggr_column);
+                        }
+
+                        for row in 0..block.num_rows() {
+                            let group_key = $hash_method.get_key(&key_array, row);
+                            match groups.get(&group_key) {
+                                None => {
+                                    if aggr_funcs_len == 0 {
+                                        groups.insert(group_key, 0usize);
+                                    } else {
+                                        let place: StateAddr = arena.alloc_layout(layout).into();
+                                        for (idx, func) in funcs.iter().enumerate() {
+                                            let arg_place =
+                                                place.next(offsets_aggregate_states[idx]);
+
+                                            let mut data = states_binary_columns[idx].get_data(row);
+                                            func.init_state(arg_place);
+                                            func.deserialize(arg_place, &mut data)?;
+                                        }
+                                        groups.insert(group_key, place.addr());
+                                    }
+                                }
+                                Some(place) => {
+                                    let place: StateAddr = (*place).into();
 
-                    for row in 0..block.num_rows() {
-                        let group_key = $hash_method.get_key(&key_array, row);
-                        match groups.get(&group_key) {
-                            None => {
-                                if aggr_funcs_len == 0 {
-                                    groups.insert(group_key, 0usize);
-                                } else {
-                                    let place: StateAddr = arena.alloc_layout(layout).into();
                                     for (idx, func) in funcs.iter().enumerate() {
                                         let arg_place = place.next(offsets_aggregate_states[idx]);
 
                                         let mut data = states_binary_columns[idx].get_data(row);
-                                        func.init_state(arg_place);
-                                        func.deserialize(arg_place, &mut data)?;
-                                    }
-                                    groups.insert(group_key, place.addr());
-                                }
-                            }
-                            Some(place) => {
-                                let place: StateAddr = (*place).into();
-
-                                for (idx, func) in funcs.iter().enumerate() {
-                                    let arg_place = place.next(offsets_aggregate_states[idx]);
-
-                                    let mut data = states_binary_columns[idx].get_data(row);
-                                    let temp = arena.alloc_layout(funcs[idx].state_layout());
-                                    let temp_addr = temp.into();
+                                        let temp = arena.alloc_layout(funcs[idx].state_layout());
+                                        let temp_addr = temp.into();
 
-                                    funcs[idx].init_state(temp_addr);
-                                    func.deserialize(temp_addr, &mut data)?;
-                                    func.merge(arg_place, temp_addr)?;
+                                        funcs[idx].init_state(temp_addr);
+                                        func.deserialize(temp_addr, &mut data)?;
+                                        func.merge(arg_place, temp_addr)?;
+                                    }
                                 }
-                            }
-                        };
+                            };
+                        }
                     }
-                }
-                let delta = start.elapsed();
-                tracing::debug!("Group by final cost: {:?}", delta);
-
-                // Collect the merge states.
-                let groups = groups_locker.read();
-
-                let mut aggr_builders: Vec<Box<dyn MutableColumn>> = {
-                    let mut values = vec![];
-                    for func in &funcs {
-                        let builder = func.return_type()?.create_mutable(1024);
-                        values.push(builder)
-                    }
-                    values
-                };
-
-                let mut keys = Vec::with_capacity(groups.len());
-                for (key, place) in groups.iter() {
-                    keys.push(key.clone());
-
-                    let place: StateAddr = (*place).into();
-                    for (idx, func) in funcs.iter().enumerate() {
-                        let arg_place = place.next(offsets_aggregate_states[idx]);
-                        let builder: &mut dyn MutableColumn = aggr_builders[idx].borrow_mut();
-                        func.merge_result(arg_place, builder)?;
+                    let delta = start.elapsed();
+                    tracing::debug!("Group by final cost: {:?}", delta);
+
+                    // Collect the merge states.
+                    let groups = groups_locker.read();
+
+                    let mut aggr_builders: Vec<Box<dyn MutableColumn>> = {
+                        let mut values = vec![];
+                        for func in &funcs {
+                            let builder = func.return_type()?.create_mutable(1024);
+                            values.push(builder)
+                        }
+                        values
+                    };
+
+                    let mut keys = Vec::with_capacity(groups.len());
+                    for (key, place) in groups.iter() {
+                        keys.push(key.clone());
+
+                        let place: StateAddr = (*place).into();
+                        for (idx, func) in funcs.iter().enumerate() {
+                            let arg_place = place.next(offsets_aggregate_states[idx]);
+                            let builder: &mut dyn MutableColumn = aggr_builders[idx].borrow_mut();
+                            func.merge_result(arg_place, builder)?;
+                        }
                     }
-                }
 
-                // Build final state block.
-                let mut columns: Vec<ColumnRef> =
-                    Vec::with_capacity(aggr_funcs_len + group_expr_len);
-                for mut array in aggr_builders {
-                    let col = array.to_column();
-                    columns.push(col);
-                }
+                    // Build final state block.
+                    let mut columns: Vec<ColumnRef> =
+                        Vec::with_capacity(aggr_funcs_len + group_expr_len);
+                    for mut array in aggr_builders {
+                        let col = array.to_column();
+                        columns.push(col);
+                    }
 
-                {
-                    let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
-                    columns.extend_from_slice(&group_columns);
-                }
+                    {
+                        let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
+                        columns.extend_from_slice(&group_columns);
+                    }
 
-                let mut blocks = vec![];
-                if !columns.is_empty() {
-                    let block = DataBlock::create(self.schema.clone(), columns);
-                    blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                    if !columns.is_empty() {
+                        let block = DataBlock::create(self.schema.clone(), columns);
+                        blocks.extend(DataBlock::split_block_by_size(&block, self.max_block_size)?);
+                    }
                 }
 
                 Ok(Box::pin(DataBlockStream::create(
@@ -24,34 +24,45 @@ use common_exception::Result;
 use common_planners::Expression;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::group_by::Aggregator;
 use crate::pipelines::transforms::group_by::AggregatorParams;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::transforms::spill::blocking_stream;
+use crate::pipelines::transforms::spill::SpillConfig;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
 
 pub struct GroupByPartialTransform {
+    ctx: Arc<QueryContext>,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
 
     schema: DataSchemaRef,
     schema_before_group_by: DataSchemaRef,
+    spill: Option<SpillConfig>,
     input: Arc<dyn Processor>,
 }
 
 impl GroupByPartialTransform {
     pub fn create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         schema_before_group_by: DataSchemaRef,
         aggr_exprs: Vec<Expression>,
         group_exprs: Vec<Expression>,
+        spill: Option<SpillConfig>,
     ) -> Self {
         Self {
+            ctx,
             aggr_exprs,
             group_exprs,
             schema,
             schema_before_group_by,
+            spill,
             input: Arc::new(EmptyProcessor::create()),
         }
     }
@@ -72,18 +83,33 @@ impl GroupByPartialTransform {
         let start = Instant::now();
 
         let stream = self.input.execute().await?;
+        let stream: SendableDataBlockStream =
+            Box::pin(MemoryLimitStream::create(self.ctx.clone(), stream));
         let aggr_exprs = &self.aggr_exprs;
         let schema = self.schema_before_group_by.clone();
         let aggregator_params = AggregatorParams::try_create(schema, aggr_exprs)?;
 
         let aggregator = Aggregator::create(method, aggregator_params);
-        let state = aggregator.aggregate(group_cols, stream).await?;
+        let spill = self.spill.as_ref().map(|c| (c, self.schema.clone()));
+        let (state, spilled) = aggregator.aggregate(group_cols, stream, spill).await?;
 
         let delta = start.elapsed();
         tracing::debug!("Group by partial cost: {:?}", delta);
 
         let finalized_schema = self.schema.clone();
-        aggregator.aggregate_finalized(&state, finalized_schema)
+        let finalized = aggregator.aggregate_finalized(&state, finalized_schema)?;
+        match spilled {
+            None => Ok(finalized),
+            Some(spilled) => {
+                tracing::debug!(
+                    "Group by partial spilled {} blocks, {} bytes",
+                    spilled.num_blocks(),
+                    spilled.num_bytes()
+                );
+                let spilled = blocking_stream(spilled.into_reader().await?);
+                Ok(Box::pin(spilled.chain(finalized)))
+            }
+        }
     }
 }
 
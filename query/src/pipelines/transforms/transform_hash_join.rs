@@ -0,0 +1,614 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_base::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::JoinPlan;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use futures::FutureExt;
+use tokio_stream::StreamExt;
+
+use crate::common::HashMap;
+use crate::common::HashTableEntity;
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::group_by::KeysRef;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
+
+const END_OF_CHAIN: u32 = u32::MAX;
+
+/// The hash table of the build(right) side.
+///
+/// All the rows of the build side are kept in one block, rows with the same keys are chained
+/// by `next`, the hash map stores the head of each chain.
+pub struct JoinHashTable {
+    block: DataBlock,
+    keys_area: Bump,
+    hash_map: HashMap<KeysRef, u32>,
+    next: Vec<u32>,
+}
+
+// The *mut KeyValueEntity is only used while building, the hash table is read only after that.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Send for JoinHashTable {}
+
+unsafe impl Sync for JoinHashTable {}
+
+impl JoinHashTable {
+    pub fn try_create(
+        schema: DataSchemaRef,
+        keys_executor: &JoinKeysExecutor,
+        blocks: Vec<DataBlock>,
+    ) -> Result<JoinHashTable> {
+        let block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(schema),
+            false => DataBlock::concat_blocks(&blocks)?,
+        };
+
+        let mut hash_table = JoinHashTable {
+            next: vec![END_OF_CHAIN; block.num_rows()],
+            keys_area: Bump::new(),
+            hash_map: HashMap::create(),
+            block,
+        };
+
+        let keys = keys_executor.build_keys(&hash_table.block)?;
+        for (row, keys) in keys.iter().enumerate() {
+            // NULL never equals to anything.
+            if let Some(keys) = keys {
+                hash_table.insert(keys, row as u32);
+            }
+        }
+
+        Ok(hash_table)
+    }
+
+    fn insert(&mut self, keys: &[u8], row: u32) {
+        let mut inserted = false;
+        let mut keys_ref = KeysRef::create(keys.as_ptr() as usize, keys.len());
+        let entity = self.hash_map.insert_key(&keys_ref, &mut inserted);
+
+        match inserted {
+            true => unsafe {
+                // Keys will be destroyed after call we need copy the keys to the memory pool.
+                let global_keys = self.keys_area.alloc_slice_copy(keys);
+                let inserted_hash = entity.get_hash();
+                keys_ref.address = global_keys.as_ptr() as usize;
+                entity.set_key_and_hash(&keys_ref, inserted_hash);
+                entity.set_value(row);
+            },
+            false => {
+                self.next[row as usize] = *entity.get_value();
+                entity.set_value(row);
+            }
+        }
+    }
+
+    fn probe(&self, keys: &[u8], rows: &mut Vec<u32>) {
+        let keys_ref = KeysRef::create(keys.as_ptr() as usize, keys.len());
+        if let Some(entity) = self.hash_map.find_key(&keys_ref) {
+            let mut row = *entity.get_value();
+            while row != END_OF_CHAIN {
+                rows.push(row);
+                row = self.next[row as usize];
+            }
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.block.num_rows()
+    }
+}
+
+/// Evaluates the join keys of a block and serializes them, rows with NULL keys have no keys.
+pub struct JoinKeysExecutor {
+    executor: Option<ExpressionExecutor>,
+}
+
+impl JoinKeysExecutor {
+    pub fn try_create(schema: &DataSchemaRef, keys: &[Expression]) -> Result<JoinKeysExecutor> {
+        if keys.is_empty() {
+            return Ok(JoinKeysExecutor { executor: None });
+        }
+
+        let mut fields = Vec::with_capacity(keys.len());
+        for key in keys {
+            fields.push(key.to_data_field(schema)?);
+        }
+
+        let executor = ExpressionExecutor::try_create(
+            "join keys executor",
+            schema.clone(),
+            DataSchemaRefExt::create(fields),
+            keys.to_vec(),
+            false,
+        )?;
+        executor.validate()?;
+        Ok(JoinKeysExecutor {
+            executor: Some(executor),
+        })
+    }
+
+    pub fn build_keys(&self, block: &DataBlock) -> Result<Vec<Option<Vec<u8>>>> {
+        let rows = block.num_rows();
+        let executor = match &self.executor {
+            // Cross join, all the rows have the same keys.
+            None => return Ok(vec![Some(vec![]); rows]),
+            Some(executor) => executor,
+        };
+
+        let keys_block = executor.execute(block)?;
+        let columns = keys_block
+            .columns()
+            .iter()
+            .map(|column| column.convert_full_column())
+            .collect::<Vec<_>>();
+
+        let mut valid = vec![true; rows];
+        for column in &columns {
+            match column.validity() {
+                (true, _) => valid.iter_mut().for_each(|v| *v = false),
+                (false, Some(bitmap)) => {
+                    for (row, v) in valid.iter_mut().enumerate() {
+                        *v = *v && bitmap.get_bit(row);
+                    }
+                }
+                (false, None) => {}
+            }
+        }
+
+        let columns = columns.iter().collect::<Vec<_>>();
+        let keys = HashMethodSerializer::default().build_keys(&columns, rows)?;
+        Ok(keys
+            .into_iter()
+            .zip(valid.into_iter())
+            .map(|(keys, valid)| valid.then(|| keys))
+            .collect())
+    }
+}
+
+type JoinHashTableResult = Result<Arc<JoinHashTable>>;
+type SharedFuture<'a> = Shared<BoxFuture<'a, JoinHashTableResult>>;
+
+/// Builds the hash table once and shares it with all the probe processors.
+pub struct JoinHashTablePuller<'a> {
+    ctx: Arc<QueryContext>,
+    build_plan: PlanNode,
+    build_keys: Vec<Expression>,
+    hash_table: Option<SharedFuture<'a>>,
+}
+
+impl<'a> JoinHashTablePuller<'a> {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        build_plan: PlanNode,
+        build_keys: Vec<Expression>,
+    ) -> Arc<Mutex<JoinHashTablePuller<'a>>> {
+        Arc::new(Mutex::new(JoinHashTablePuller {
+            ctx,
+            build_plan,
+            build_keys,
+            hash_table: None,
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn take_hash_table(&mut self) -> Result<SharedFuture<'a>> {
+        if self.hash_table.is_none() {
+            self.init()?;
+        }
+
+        match &self.hash_table {
+            Some(hash_table) => Ok(hash_table.clone()),
+            None => Err(ErrorCode::LogicalError(
+                "Logical error: join hash table is not initialized, it's a bug.",
+            )),
+        }
+    }
+
+    fn init(&mut self) -> Result<()> {
+        // The build side reads its own partitions.
+        let build_ctx = QueryContext::create_from(self.ctx.clone());
+        let builder = PipelineBuilder::create(build_ctx);
+        let mut pipeline = builder.build(&self.build_plan)?;
+
+        let schema = self.build_plan.schema();
+        let keys_executor = JoinKeysExecutor::try_create(&schema, &self.build_keys)?;
+        let ctx = self.ctx.clone();
+        let build_future = async move {
+            let mut blocks = vec![];
+            // the hash table holds the whole build side
+            let mut stream = MemoryLimitStream::create(ctx, pipeline.execute().await?);
+            while let Some(data_block) = stream.next().await {
+                let data_block = data_block?;
+                if !data_block.is_empty() {
+                    blocks.push(data_block);
+                }
+            }
+
+            let hash_table = JoinHashTable::try_create(schema, &keys_executor, blocks)?;
+            Ok(Arc::new(hash_table))
+        };
+
+        self.hash_table = Some(build_future.boxed().shared());
+        Ok(())
+    }
+}
+
+pub struct HashJoinTransform {
+    ctx: Arc<QueryContext>,
+    state: Arc<HashJoinState>,
+    hash_table_puller: Arc<Mutex<JoinHashTablePuller<'static>>>,
+    input: Arc<dyn Processor>,
+}
+
+impl HashJoinTransform {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: &JoinPlan,
+        hash_table_puller: Arc<Mutex<JoinHashTablePuller<'static>>>,
+    ) -> Result<HashJoinTransform> {
+        let joined_schema = plan.joined_schema();
+        let other_conditions = match &plan.other_conditions {
+            None => None,
+            Some(conditions) => {
+                let field = conditions.to_data_field(&joined_schema)?;
+                let executor = ExpressionExecutor::try_create(
+                    "join conditions executor",
+                    joined_schema.clone(),
+                    DataSchemaRefExt::create(vec![field]),
+                    vec![conditions.clone()],
+                    false,
+                )?;
+                executor.validate()?;
+                Some(executor)
+            }
+        };
+
+        let probe_schema = plan.left.schema();
+        Ok(HashJoinTransform {
+            ctx,
+            state: Arc::new(HashJoinState {
+                join_type: plan.join_type,
+                schema: plan.schema(),
+                probe_keys: JoinKeysExecutor::try_create(&probe_schema, &plan.left_keys)?,
+                probe_schema,
+                joined_schema,
+                other_conditions,
+                visited: Mutex::new(vec![]),
+            }),
+            hash_table_puller,
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+
+    async fn hash_table(&self) -> Result<Arc<JoinHashTable>> {
+        let future = self.hash_table_puller.lock().take_hash_table()?;
+        match self.ctx.try_spawn(future)?.await {
+            Ok(hash_table) => hash_table,
+            Err(error) => Err(ErrorCode::TokioError(format!(
+                "Cannot build join hash table. cause: {}",
+                error
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for HashJoinTransform {
+    fn name(&self) -> &str {
+        "HashJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[tracing::instrument(level = "debug", name = "hash_join_execute", skip(self))]
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let hash_table = self.hash_table().await?;
+        let input_stream = self.input.execute().await?;
+
+        let state = self.state.clone();
+        if state.join_type.need_build_side_visited() {
+            *state.visited.lock() = vec![false; hash_table.num_rows()];
+        }
+
+        let probe_state = state.clone();
+        let probe_hash_table = hash_table.clone();
+        let probe_stream = input_stream.filter_map(move |data_block| match data_block {
+            Err(fail) => Some(Err(fail)),
+            Ok(data_block) => probe_state
+                .probe(&probe_hash_table, &data_block)
+                .transpose(),
+        });
+
+        match state.join_type.need_build_side_visited() {
+            false => Ok(Box::pin(probe_stream)),
+            true => {
+                // Emit the rows of build side once all the rows of probe side are joined.
+                let finish_stream = tokio_stream::iter(vec![()])
+                    .filter_map(move |_| state.finish(&hash_table).transpose());
+                Ok(Box::pin(probe_stream.chain(finish_stream)))
+            }
+        }
+    }
+}
+
+struct HashJoinState {
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    probe_schema: DataSchemaRef,
+    joined_schema: DataSchemaRef,
+    probe_keys: JoinKeysExecutor,
+    other_conditions: Option<ExpressionExecutor>,
+    // Whether the rows of build side are matched.
+    visited: Mutex<Vec<bool>>,
+}
+
+impl HashJoinState {
+    fn probe(&self, hash_table: &JoinHashTable, block: &DataBlock) -> Result<Option<DataBlock>> {
+        let keys = self.probe_keys.build_keys(block)?;
+
+        let mut build_rows = vec![];
+        let mut probe_indices = vec![];
+        let mut build_indices = vec![];
+        for (row, keys) in keys.iter().enumerate() {
+            if let Some(keys) = keys {
+                build_rows.clear();
+                hash_table.probe(keys, &mut build_rows);
+                probe_indices.extend(std::iter::repeat(row as u32).take(build_rows.len()));
+                build_indices.extend_from_slice(&build_rows);
+            }
+        }
+
+        if let Some(executor) = &self.other_conditions {
+            let filter = self.eval_other_conditions(
+                executor,
+                block,
+                &probe_indices,
+                &hash_table.block,
+                &build_indices,
+            )?;
+
+            let mut index = 0;
+            probe_indices.retain(|_| {
+                index += 1;
+                filter[index - 1]
+            });
+
+            let mut index = 0;
+            build_indices.retain(|_| {
+                index += 1;
+                filter[index - 1]
+            });
+        }
+
+        if self.join_type.need_build_side_visited() {
+            let mut visited = self.visited.lock();
+            for index in &build_indices {
+                visited[*index as usize] = true;
+            }
+        }
+
+        let mut probe_matched = vec![false; block.num_rows()];
+        for index in &probe_indices {
+            probe_matched[*index as usize] = true;
+        }
+
+        let block = match self.join_type {
+            JoinType::Inner | JoinType::Cross | JoinType::Right => {
+                let probe_indices = probe_indices.into_iter().map(Some).collect::<Vec<_>>();
+                let build_indices = build_indices.into_iter().map(Some).collect::<Vec<_>>();
+                self.joined_block(block, &probe_indices, &hash_table.block, &build_indices)?
+            }
+            JoinType::Left | JoinType::Full => {
+                let mut probe_indices = probe_indices.into_iter().map(Some).collect::<Vec<_>>();
+                let mut build_indices = build_indices.into_iter().map(Some).collect::<Vec<_>>();
+                for (row, matched) in probe_matched.iter().enumerate() {
+                    if !matched {
+                        probe_indices.push(Some(row as u32));
+                        build_indices.push(None);
+                    }
+                }
+                self.joined_block(block, &probe_indices, &hash_table.block, &build_indices)?
+            }
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                let expect = self.join_type == JoinType::LeftSemi;
+                let indices = Self::rows_matched(&probe_matched, expect);
+                self.side_block(block, &indices)?
+            }
+            JoinType::RightSemi | JoinType::RightAnti => return Ok(None),
+        };
+
+        match block.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(block)),
+        }
+    }
+
+    // Emit the rows of build side which depend on all the rows of probe side.
+    fn finish(&self, hash_table: &JoinHashTable) -> Result<Option<DataBlock>> {
+        let visited = self.visited.lock();
+        let block = match self.join_type {
+            JoinType::Right | JoinType::Full => {
+                let build_indices = Self::rows_matched(&visited, false);
+                let probe_block = DataBlock::empty_with_schema(self.probe_schema.clone());
+                let probe_indices = vec![None; build_indices.len()];
+                let build_indices = build_indices.into_iter().map(Some).collect::<Vec<_>>();
+                self.joined_block(
+                    &probe_block,
+                    &probe_indices,
+                    &hash_table.block,
+                    &build_indices,
+                )?
+            }
+            JoinType::RightSemi => {
+                let indices = Self::rows_matched(&visited, true);
+                self.side_block(&hash_table.block, &indices)?
+            }
+            JoinType::RightAnti => {
+                let indices = Self::rows_matched(&visited, false);
+                self.side_block(&hash_table.block, &indices)?
+            }
+            _ => return Ok(None),
+        };
+
+        match block.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(block)),
+        }
+    }
+
+    fn rows_matched(matched: &[bool], expect: bool) -> Vec<u32> {
+        matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| **matched == expect)
+            .map(|(row, _)| row as u32)
+            .collect()
+    }
+
+    fn eval_other_conditions(
+        &self,
+        executor: &ExpressionExecutor,
+        probe_block: &DataBlock,
+        probe_indices: &[u32],
+        build_block: &DataBlock,
+        build_indices: &[u32],
+    ) -> Result<Vec<bool>> {
+        if probe_indices.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut columns = Vec::with_capacity(self.joined_schema.fields().len());
+        for column in probe_block.columns() {
+            columns.push(Series::take(column, probe_indices)?);
+        }
+        for column in build_block.columns() {
+            columns.push(Series::take(column, build_indices)?);
+        }
+
+        let joined_block = DataBlock::create(self.joined_schema.clone(), columns);
+        let predicate = executor.execute(&joined_block)?;
+        let predicate = DataBlock::cast_to_nonull_boolean(predicate.column(0))?;
+
+        if predicate.is_const() {
+            let value = predicate.get_bool(0)?;
+            return Ok(vec![value; probe_indices.len()]);
+        }
+
+        let predicate: &BooleanColumn = Series::check_get(&predicate)?;
+        Ok(predicate.values().iter().collect())
+    }
+
+    fn side_block(&self, block: &DataBlock, indices: &[u32]) -> Result<DataBlock> {
+        let mut columns = Vec::with_capacity(block.num_columns());
+        for column in block.columns() {
+            columns.push(Series::take(column, indices)?);
+        }
+
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+
+    fn joined_block(
+        &self,
+        probe_block: &DataBlock,
+        probe_indices: &[Option<u32>],
+        build_block: &DataBlock,
+        build_indices: &[Option<u32>],
+    ) -> Result<DataBlock> {
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for column in probe_block.columns() {
+            columns.push(take_column(
+                column,
+                probe_indices,
+                self.join_type.is_left_nullable(),
+            )?);
+        }
+
+        for column in build_block.columns() {
+            columns.push(take_column(
+                column,
+                build_indices,
+                self.join_type.is_right_nullable(),
+            )?);
+        }
+
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+}
+
+// Take the rows of column, the missing rows are filled with NULL.
+fn take_column(column: &ColumnRef, indices: &[Option<u32>], nullable: bool) -> Result<ColumnRef> {
+    let take_indices = indices
+        .iter()
+        .map(|index| index.unwrap_or(0))
+        .collect::<Vec<_>>();
+
+    if !nullable {
+        return Series::take(column, &take_indices);
+    }
+
+    let data_type = wrap_nullable(&column.data_type());
+    if column.is_empty() || !data_type.is_nullable() {
+        if indices.iter().any(Option::is_some) {
+            return Series::take(column, &take_indices);
+        }
+
+        let column = data_type.create_constant_column(&DataValue::Null, indices.len())?;
+        return Ok(column.convert_full_column());
+    }
+
+    let column = Series::take(&column.convert_full_column(), &take_indices)?;
+    let (_, validity) = column.validity();
+
+    let mut bitmap = MutableBitmap::with_capacity(indices.len());
+    for (row, index) in indices.iter().enumerate() {
+        let valid = validity.map(|bitmap| bitmap.get_bit(row)).unwrap_or(true);
+        bitmap.push(index.is_some() && valid);
+    }
+
+    let column = Series::remove_nullable(&column);
+    Ok(Arc::new(NullableColumn::new(column, bitmap.into())))
+}
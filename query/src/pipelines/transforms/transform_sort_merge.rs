@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
 use common_datavalues2::DataSchemaRef;
 use common_exception::Result;
 use common_planners::Expression;
@@ -28,28 +29,67 @@ use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::spill::blocking_stream;
+use crate::pipelines::transforms::spill::SortedRun;
+use crate::pipelines::transforms::spill::SortedRunsMerger;
+use crate::pipelines::transforms::spill::SpillConfig;
+use crate::pipelines::transforms::spill::SpillWriter;
 use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+use crate::pipelines::transforms::MemoryLimitStream;
+use crate::sessions::QueryContext;
+
+// The number of rows of each block in a spilled sorted run, a block of every run
+// is held in memory while merging the runs.
+const SPILLED_RUN_BLOCK_ROWS: usize = 65536;
 
 pub struct SortMergeTransform {
+    ctx: Arc<QueryContext>,
     schema: DataSchemaRef,
     exprs: Vec<Expression>,
     limit: Option<usize>,
+    spill: Option<SpillConfig>,
     input: Arc<dyn Processor>,
 }
 
 impl SortMergeTransform {
     pub fn try_create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         exprs: Vec<Expression>,
         limit: Option<usize>,
+        spill: Option<SpillConfig>,
     ) -> Result<Self> {
         Ok(SortMergeTransform {
+            ctx,
             schema,
             exprs,
             limit,
+            spill,
             input: Arc::new(EmptyProcessor::create()),
         })
     }
+
+    // Sort the blocks into one run, and write it to a temp file.
+    async fn spill_sorted_run(
+        &self,
+        spill: &SpillConfig,
+        blocks: &[DataBlock],
+        sort_columns_descriptions: &[SortColumnDescription],
+    ) -> Result<SortedRun> {
+        let run = DataBlock::merge_sort_blocks(blocks, sort_columns_descriptions, self.limit)?;
+
+        let mut writer = SpillWriter::try_create(spill, self.schema.clone()).await?;
+        for block in DataBlock::split_block_by_size(&run, SPILLED_RUN_BLOCK_ROWS)? {
+            writer.write(block).await?;
+        }
+
+        tracing::debug!(
+            "Sort merge spilled a run of {} rows, {} bytes",
+            run.num_rows(),
+            writer.num_bytes()
+        );
+        Ok(Box::new(writer.into_reader().await?))
+    }
 }
 
 #[async_trait]
@@ -77,10 +117,43 @@ impl Processor for SortMergeTransform {
 
         let sort_columns_descriptions = get_sort_descriptions(&self.schema, &self.exprs)?;
         let mut blocks = vec![];
-        let mut stream = self.input.execute().await?;
+        let mut blocks_size = 0;
+        let mut spilled_runs: Vec<SortedRun> = vec![];
+        let mut stream = MemoryLimitStream::create(self.ctx.clone(), self.input.execute().await?);
 
         while let Some(block) = stream.next().await {
-            blocks.push(block?);
+            let block = block?;
+            blocks_size += block.memory_size();
+            blocks.push(block);
+
+            if let Some(spill) = &self.spill {
+                if blocks_size > spill.max_bytes {
+                    let run = self
+                        .spill_sorted_run(spill, &blocks, &sort_columns_descriptions)
+                        .await?;
+                    spilled_runs.push(run);
+                    blocks.clear();
+                    blocks_size = 0;
+                }
+            }
+        }
+
+        if !spilled_runs.is_empty() {
+            // The blocks left are below the threshold, they are merged as an in memory run.
+            if !blocks.is_empty() {
+                let run =
+                    DataBlock::merge_sort_blocks(&blocks, &sort_columns_descriptions, self.limit)?;
+                let run = DataBlock::split_block_by_size(&run, SPILLED_RUN_BLOCK_ROWS)?;
+                spilled_runs.push(Box::new(run.into_iter().map(Ok)));
+            }
+
+            // The merger reads the spilled runs, it's driven by a blocking task.
+            let merger =
+                SortedRunsMerger::create(spilled_runs, sort_columns_descriptions, self.limit);
+            return Ok(Box::pin(CorrectWithSchemaStream::new(
+                blocking_stream(merger),
+                self.schema.clone(),
+            )));
         }
 
         let results = match blocks.len() {
@@ -25,6 +25,7 @@ use common_tracing::tracing;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::MemoryLimitStream;
 use crate::sessions::QueryContext;
 
 pub struct SourceTransform {
@@ -44,8 +45,10 @@ impl SourceTransform {
         let progress_stream =
             ProgressStream::try_create(table_stream.await?, self.ctx.get_scan_progress())?;
 
+        let limited_stream = MemoryLimitStream::create(self.ctx.clone(), Box::pin(progress_stream));
+
         Ok(Box::pin(
-            self.ctx.try_create_abortable(Box::pin(progress_stream))?,
+            self.ctx.try_create_abortable(Box::pin(limited_stream))?,
         ))
     }
 }
@@ -0,0 +1,209 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::windows::WindowPartition;
+use common_planners::Expression;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::get_sort_descriptions;
+
+/// Evaluate one window function over all the input rows.
+/// The rows are sorted by the PARTITION BY and ORDER BY keys, and the result column is appended.
+pub struct WindowFuncTransform {
+    window_func: Expression,
+    schema: DataSchemaRef,
+    input_schema: DataSchemaRef,
+    input: Arc<dyn Processor>,
+}
+
+impl WindowFuncTransform {
+    pub fn create(
+        window_func: Expression,
+        schema: DataSchemaRef,
+        input_schema: DataSchemaRef,
+    ) -> Self {
+        WindowFuncTransform {
+            window_func,
+            schema,
+            input_schema,
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    fn evaluate(&self, block: &DataBlock) -> Result<DataBlock> {
+        let (args, partition_by, order_by) = match &self.window_func {
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => (args, partition_by, order_by),
+            _ => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Window expression must be window function, but got: {:?}",
+                    self.window_func
+                )));
+            }
+        };
+
+        let mut sort_columns_descriptions = Vec::with_capacity(partition_by.len() + order_by.len());
+        for partition_expr in partition_by {
+            sort_columns_descriptions.push(SortColumnDescription {
+                column_name: partition_expr.column_name(),
+                asc: true,
+                nulls_first: true,
+            });
+        }
+
+        let order_by_descriptions = get_sort_descriptions(&self.input_schema, order_by)?;
+        let order_by_names = order_by_descriptions
+            .iter()
+            .map(|description| description.column_name.clone())
+            .collect::<Vec<_>>();
+        let order_by_asc = order_by_descriptions
+            .iter()
+            .map(|description| description.asc)
+            .collect::<Vec<_>>();
+        sort_columns_descriptions.extend(order_by_descriptions);
+
+        let block = match sort_columns_descriptions.is_empty() {
+            true => block.clone(),
+            false => DataBlock::sort_block(block, &sort_columns_descriptions, None)?,
+        };
+
+        let partition_columns =
+            Self::columns(&block, partition_by.iter().map(|e| e.column_name()))?;
+        let order_by_columns = Self::columns(&block, order_by_names.into_iter())?;
+        let arg_columns = Self::columns(&block, args.iter().map(|e| e.column_name()))?;
+
+        let order_by = match order_by_asc.len() {
+            1 => Some((&order_by_columns[0], order_by_asc[0])),
+            _ => None,
+        };
+
+        let func = self.window_func.to_window_function(&self.input_schema)?;
+        let mut builder = func.return_type()?.create_mutable(block.num_rows());
+
+        let mut start = 0;
+        while start < block.num_rows() {
+            let end = Self::next_group(&partition_columns, start, block.num_rows());
+
+            let mut peers = vec![];
+            let mut peer_start = start;
+            while peer_start < end {
+                let peer_end = Self::next_group(&order_by_columns, peer_start, end);
+                peers.push(peer_start..peer_end);
+                peer_start = peer_end;
+            }
+
+            let partition = WindowPartition {
+                columns: &arg_columns,
+                order_by,
+                rows: start..end,
+                peers,
+            };
+            func.eval_partition(&partition, builder.as_mut())?;
+            start = end;
+        }
+
+        let field = self.window_func.to_data_field(&self.input_schema)?;
+        block.add_column(builder.to_column(), field)
+    }
+
+    fn columns<I: Iterator<Item = String>>(block: &DataBlock, names: I) -> Result<Vec<ColumnRef>> {
+        names
+            .map(|name| Ok(block.try_column_by_name(&name)?.convert_full_column()))
+            .collect()
+    }
+
+    // Returns the end of the rows starting at `start` which are equal on all the columns.
+    fn next_group(columns: &[ColumnRef], start: usize, end: usize) -> usize {
+        let values = columns
+            .iter()
+            .map(|column| column.get(start))
+            .collect::<Vec<_>>();
+
+        for row in (start + 1)..end {
+            let equal = columns
+                .iter()
+                .zip(values.iter())
+                .all(|(column, value)| &column.get(row) == value);
+
+            if !equal {
+                return row;
+            }
+        }
+
+        end
+    }
+}
+
+#[async_trait]
+impl Processor for WindowFuncTransform {
+    fn name(&self) -> &str {
+        "WindowFuncTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[tracing::instrument(level = "debug", name = "window_func_execute", skip(self))]
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let mut blocks = vec![];
+        let mut stream = self.input.execute().await?;
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if !block.is_empty() {
+                blocks.push(block);
+            }
+        }
+
+        let results = match blocks.is_empty() {
+            true => vec![],
+            false => vec![self.evaluate(&DataBlock::concat_blocks(&blocks)?)?],
+        };
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            results,
+        )))
+    }
+}
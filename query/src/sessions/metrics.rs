@@ -14,3 +14,5 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+pub static METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS: &str = "query.result_cache_hit_numbers";
+pub static METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS: &str = "query.result_cache_miss_numbers";
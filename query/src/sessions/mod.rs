@@ -15,6 +15,7 @@
 mod metrics;
 mod query_ctx;
 mod query_ctx_shared;
+mod query_result_cache;
 mod session;
 mod session_ctx;
 mod session_info;
@@ -25,6 +26,11 @@ mod session_settings;
 
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
+pub use query_result_cache::normalize_query;
+pub use query_result_cache::QueryResultCache;
+pub use query_result_cache::QueryResultCacheKey;
+pub use query_result_cache::QueryResultCacheStream;
+pub use query_result_cache::TableSnapshotRef;
 pub use session::Session;
 pub use session_ctx::SessionContext;
 pub use session_info::ProcessInfo;
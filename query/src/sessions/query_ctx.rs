@@ -25,7 +25,9 @@ use common_base::Progress;
 use common_base::ProgressValues;
 use common_base::TrySpawn;
 use common_dal2::credential::Credential;
+use common_dal2::services::azblob;
 use common_dal2::services::fs;
+use common_dal2::services::gcs;
 use common_dal2::services::s3;
 use common_dal2::Accessor;
 use common_dal2::Operator;
@@ -52,10 +54,12 @@ use crate::configs::Config;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
+use crate::sessions::QueryResultCache;
 use crate::sessions::Session;
 use crate::sessions::SessionRef;
 use crate::sessions::Settings;
 use crate::storages::cache::CacheManager;
+use crate::storages::fuse::operations::StorageUsageCache;
 use crate::storages::Table;
 use crate::users::UserApiProvider;
 
@@ -114,6 +118,11 @@ impl QueryContext {
         self.shared.scan_progress.as_ref().get_values()
     }
 
+    /// Fails if the memory usage of the query or its user exceeds the limits.
+    pub fn check_memory_limit(&self) -> Result<()> {
+        self.shared.check_memory_limit()
+    }
+
     pub fn get_result_progress(&self) -> Arc<Progress> {
         self.shared.result_progress.clone()
     }
@@ -171,6 +180,20 @@ impl QueryContext {
         self.shared.get_cluster()
     }
 
+    /// Replace the cluster of the query, only the plan scheduler does it when it
+    /// reschedules the query on the healthy nodes after a node failure.
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.shared.set_cluster(cluster)
+    }
+
+    pub fn add_stage_retry(&self) {
+        self.shared.stage_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_stage_retries(&self) -> usize {
+        self.shared.stage_retries.load(Ordering::Relaxed)
+    }
+
     pub fn get_catalog(&self) -> Arc<DatabaseCatalog> {
         self.shared.get_catalog()
     }
@@ -186,6 +209,11 @@ impl QueryContext {
         self.shared.get_table(database, table).await
     }
 
+    /// Returns the tables (and views) resolved by the query so far.
+    pub fn get_tables_refs(&self) -> Vec<Arc<dyn Table>> {
+        self.shared.get_tables_refs()
+    }
+
     pub fn get_id(&self) -> String {
         self.shared.init_query_id.as_ref().read().clone()
     }
@@ -293,6 +321,16 @@ impl QueryContext {
         self.shared.session.session_mgr.get_storage_cache_manager()
     }
 
+    /// Get the result cache of the SELECT queries
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.shared.session.session_mgr.get_query_result_cache()
+    }
+
+    /// Get the cached storage usage of the tenants, checked against the storage quota
+    pub fn get_storage_usage_cache(&self) -> Arc<StorageUsageCache> {
+        self.shared.session.session_mgr.get_storage_usage_cache()
+    }
+
     // Get the storage data accessor by config.
     // TODO(xuanwo): we can build dal backend only once.
     pub async fn get_storage_accessor(&self) -> Result<Operator> {
@@ -316,13 +354,38 @@ impl QueryContext {
                     .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
             }
             DalSchema::Azblob => {
-                todo!()
+                let conf = &storage_conf.azure_storage_blob;
+                azblob::Backend::build()
+                    .container(&conf.container)
+                    .credential(Credential::hmac(&conf.account, &conf.master_key))
+                    .finish()
+                    .await
+                    .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
             }
             DalSchema::Fs => fs::Backend::build()
                 .root(&storage_conf.disk.data_path)
                 .finish()
                 .await
                 .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?,
+            DalSchema::Gcs => {
+                let conf = &storage_conf.gcs;
+                let mut builder = gcs::Backend::build();
+                builder.bucket(&conf.bucket);
+                if !conf.endpoint_url.is_empty() {
+                    builder.endpoint(&conf.endpoint_url);
+                }
+                if !conf.credential.is_empty() {
+                    let key = base64::decode(&conf.credential)
+                        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+                    let key = String::from_utf8(key)
+                        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+                    builder.credential(Credential::service_account(&key));
+                }
+                builder
+                    .finish()
+                    .await
+                    .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
+            }
         };
 
         Ok(Operator::new(da).layer(self.shared.dal_ctx.clone()))
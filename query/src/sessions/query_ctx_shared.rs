@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use common_base::Progress;
 use common_base::Runtime;
+use common_base::RuntimeTracker;
 use common_dal_context::DalContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -56,7 +57,7 @@ pub struct QueryContextShared {
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
-    pub(in crate::sessions) cluster_cache: Arc<Cluster>,
+    pub(in crate::sessions) cluster_cache: Arc<RwLock<Arc<Cluster>>>,
     pub(in crate::sessions) sources_abort_handle: Arc<RwLock<Vec<AbortHandle>>>,
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
@@ -65,6 +66,7 @@ pub struct QueryContextShared {
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
     pub(in crate::sessions) dal_ctx: Arc<DalContext>,
+    pub(in crate::sessions) stage_retries: Arc<AtomicUsize>,
 }
 
 impl QueryContextShared {
@@ -79,7 +81,7 @@ impl QueryContextShared {
             scan_progress: Arc::new(Progress::create()),
             result_progress: Arc::new(Progress::create()),
             session,
-            cluster_cache,
+            cluster_cache: Arc::new(RwLock::new(cluster_cache)),
             runtime: Arc::new(RwLock::new(None)),
             sources_abort_handle: Arc::new(RwLock::new(Vec::new())),
             ref_count: Arc::new(AtomicUsize::new(0)),
@@ -89,6 +91,7 @@ impl QueryContextShared {
             running_plan: Arc::new(RwLock::new(None)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
             dal_ctx: Arc::new(Default::default()),
+            stage_retries: Arc::new(AtomicUsize::new(0)),
         }))
     }
 
@@ -108,7 +111,11 @@ impl QueryContextShared {
     }
 
     pub fn get_cluster(&self) -> Arc<Cluster> {
-        self.cluster_cache.clone()
+        self.cluster_cache.read().clone()
+    }
+
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        *self.cluster_cache.write() = cluster;
     }
 
     pub fn get_current_database(&self) -> String {
@@ -155,6 +162,10 @@ impl QueryContextShared {
         }
     }
 
+    pub fn get_tables_refs(&self) -> Vec<Arc<dyn Table>> {
+        self.tables_refs.lock().values().cloned().collect()
+    }
+
     async fn get_table_to_cache(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
         let tenant = self.get_tenant();
         let catalog = self.get_catalog();
@@ -178,13 +189,43 @@ impl QueryContextShared {
             None => {
                 let settings = self.get_settings();
                 let max_threads = settings.get_max_threads()? as usize;
-                let runtime = Arc::new(Runtime::with_worker_threads(max_threads)?);
+                let max_memory_usage = settings.get_max_memory_usage()? as i64;
+
+                // the memory usage of the query is also accounted to its user
+                let session_mgr = self.session.get_session_manager();
+                let user_memory_tracker = match self.get_current_user() {
+                    Ok(user) => {
+                        Some(session_mgr.get_user_memory_tracker(&self.get_tenant(), &user))
+                    }
+                    Err(_) => session_mgr.get_global_memory_tracker(),
+                };
+                let tracker = RuntimeTracker::create_with_parent(user_memory_tracker);
+                tracker
+                    .get_memory_tracker()
+                    .set_memory_limit(max_memory_usage);
+
+                let runtime = Arc::new(Runtime::with_tracker_and_worker_threads(
+                    tracker,
+                    max_threads,
+                )?);
                 *query_runtime = Some(runtime.clone());
                 Ok(runtime)
             }
         }
     }
 
+    /// Fails if the memory usage of the query or its user exceeds the limits.
+    pub fn check_memory_limit(&self) -> Result<()> {
+        let runtime = self.runtime.read();
+        match &*runtime {
+            None => Ok(()),
+            Some(runtime) => runtime
+                .get_tracker()
+                .get_memory_tracker()
+                .check_memory_limit(),
+        }
+    }
+
     pub fn attach_http_query_handle(&self, handle: HttpQueryHandle) {
         let mut http_query = self.http_query.write();
         *http_query = Some(handle);
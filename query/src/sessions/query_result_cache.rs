@@ -0,0 +1,339 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_cache::Cache;
+use common_cache::Count;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_functions::scalars::Function2Factory;
+use common_infallible::Mutex;
+use common_meta_types::TableIdent;
+use common_metrics::label_counter;
+use common_planners::Expression;
+use common_planners::ExpressionVisitor;
+use common_planners::PlanNode;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Recursion;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::configs::QueryConfig;
+use crate::sessions::metrics::METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS;
+use crate::sessions::metrics::METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::storages::view::VIEW_ENGINE;
+
+/// The snapshot of a FUSE table read by a cached query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSnapshotRef {
+    pub table_id: u64,
+    pub snapshot_loc: Option<String>,
+}
+
+/// The key of a SELECT query in the result cache, the snapshots of the tables it reads and
+/// the versions of the views it expands.
+#[derive(Clone, Debug)]
+pub struct QueryResultCacheKey {
+    pub key: String,
+    pub snapshots: Vec<TableSnapshotRef>,
+    pub views: Vec<TableIdent>,
+}
+
+impl QueryResultCacheKey {
+    /// Returns None if the result of the query can't be cached, e.g. the query reads tables
+    /// other than the FUSE tables, or it calls the non-deterministic functions like `now()`.
+    pub fn try_create(ctx: &QueryContext, plan: &PlanNode) -> Result<Option<Self>> {
+        let mut collector = SnapshotsCollector {
+            cacheable: true,
+            snapshots: vec![],
+        };
+        collector.visit_plan_node(plan)?;
+
+        // The views are expanded inline by the planner, which leaves no trace of them in the
+        // plan, they are tracked through the tables resolved by the query instead.
+        let mut views = vec![];
+        for table in ctx.get_tables_refs() {
+            let engine = table.engine();
+            if engine.eq_ignore_ascii_case(VIEW_ENGINE) {
+                views.push(table.get_table_info().ident.clone());
+            } else if !engine.eq_ignore_ascii_case("FUSE") {
+                collector.cacheable = false;
+            }
+        }
+        views.sort_by_key(|ident| ident.table_id);
+
+        let query = normalize_query(&ctx.get_query_str());
+        if !collector.cacheable || collector.snapshots.is_empty() || query.is_empty() {
+            return Ok(None);
+        }
+
+        let user = ctx.get_current_user()?;
+        let key = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            ctx.get_tenant(),
+            user.identity(),
+            ctx.get_current_database(),
+            ctx.get_settings().get_setting_fingerprint(),
+            query,
+        );
+
+        Ok(Some(QueryResultCacheKey {
+            key,
+            snapshots: collector.snapshots,
+            views,
+        }))
+    }
+}
+
+struct CachedResult {
+    snapshots: Vec<TableSnapshotRef>,
+    views: Vec<TableIdent>,
+    blocks: Vec<DataBlock>,
+}
+
+/// The results of the SELECT queries shared by all the sessions, a cached result is only
+/// returned if none of the tables it reads have a new snapshot, and none of the views it
+/// expands are redefined, since it was cached.
+pub struct QueryResultCache {
+    tenant_id: String,
+    cluster_id: String,
+    cache: Mutex<LruCache<String, Arc<CachedResult>, DefaultHashBuilder, Count>>,
+}
+
+impl QueryResultCache {
+    pub fn create(conf: &QueryConfig) -> QueryResultCache {
+        QueryResultCache {
+            tenant_id: conf.tenant_id.clone(),
+            cluster_id: conf.cluster_id.clone(),
+            cache: Mutex::new(LruCache::new(conf.query_result_cache_count)),
+        }
+    }
+
+    pub fn get(&self, key: &QueryResultCacheKey) -> Option<Vec<DataBlock>> {
+        let blocks = self.get_valid(key);
+        let metric = match blocks {
+            Some(_) => METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS,
+            None => METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS,
+        };
+        label_counter(metric, &self.tenant_id, &self.cluster_id);
+        blocks
+    }
+
+    fn get_valid(&self, key: &QueryResultCacheKey) -> Option<Vec<DataBlock>> {
+        let mut cache = self.cache.lock();
+        let cached = cache.get(&key.key)?.clone();
+
+        if cached.snapshots != key.snapshots || cached.views != key.views {
+            // The tables or views are changed since the result was cached.
+            cache.pop(&key.key);
+            return None;
+        }
+
+        Some(cached.blocks.clone())
+    }
+
+    pub fn put(&self, key: QueryResultCacheKey, blocks: Vec<DataBlock>) {
+        let cached = CachedResult {
+            snapshots: key.snapshots,
+            views: key.views,
+            blocks,
+        };
+        self.cache.lock().put(key.key, Arc::new(cached));
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.lock().is_empty()
+    }
+}
+
+/// Passes the result blocks of a query through, and puts them into the result cache once
+/// the query is finished successfully, unless they are larger than `max_bytes`.
+pub struct QueryResultCacheStream {
+    input: SendableDataBlockStream,
+    cache: Arc<QueryResultCache>,
+    key: Option<QueryResultCacheKey>,
+    max_bytes: usize,
+    bytes: usize,
+    blocks: Vec<DataBlock>,
+}
+
+impl QueryResultCacheStream {
+    pub fn create(
+        input: SendableDataBlockStream,
+        cache: Arc<QueryResultCache>,
+        key: QueryResultCacheKey,
+        max_bytes: usize,
+    ) -> Self {
+        QueryResultCacheStream {
+            input,
+            cache,
+            key: Some(key),
+            max_bytes,
+            bytes: 0,
+            blocks: vec![],
+        }
+    }
+}
+
+impl Stream for QueryResultCacheStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match this.input.poll_next_unpin(ctx) {
+            Poll::Ready(Some(Ok(block))) => {
+                if this.key.is_some() {
+                    this.bytes += block.memory_size();
+                    if this.bytes > this.max_bytes {
+                        this.key = None;
+                        this.blocks.clear();
+                    } else {
+                        this.blocks.push(block.clone());
+                    }
+                }
+                Poll::Ready(Some(Ok(block)))
+            }
+            Poll::Ready(Some(Err(cause))) => {
+                // Never cache a partial result.
+                this.key = None;
+                this.blocks.clear();
+                Poll::Ready(Some(Err(cause)))
+            }
+            Poll::Ready(None) => {
+                if let Some(key) = this.key.take() {
+                    let blocks = std::mem::take(&mut this.blocks);
+                    this.cache.put(key, blocks);
+                }
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Collapses the whitespaces outside the quoted strings and removes the trailing semicolons,
+/// so that the same query formatted differently shares the cached result.
+pub fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut pending_space = false;
+
+    for c in query
+        .trim()
+        .trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+        .chars()
+    {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                normalized.push(c);
+            }
+            None if c.is_whitespace() => pending_space = true,
+            None => {
+                if pending_space {
+                    normalized.push(' ');
+                    pending_space = false;
+                }
+                if c == '\'' || c == '"' || c == '`' {
+                    quote = Some(c);
+                }
+                normalized.push(c);
+            }
+        }
+    }
+
+    normalized
+}
+
+struct SnapshotsCollector {
+    cacheable: bool,
+    snapshots: Vec<TableSnapshotRef>,
+}
+
+impl PlanVisitor for SnapshotsCollector {
+    fn visit_expr(&mut self, expr: &Expression) -> Result<()> {
+        let visitor = expr.accept(FunctionsCollector {
+            deterministic: true,
+            subqueries: vec![],
+        })?;
+
+        self.cacheable &= visitor.deterministic;
+        for subquery in &visitor.subqueries {
+            self.visit_plan_node(subquery)?;
+        }
+        Ok(())
+    }
+
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        let table_info = &plan.table_info;
+        if !table_info.engine().eq_ignore_ascii_case("FUSE") {
+            self.cacheable = false;
+            return Ok(());
+        }
+
+        self.snapshots.push(TableSnapshotRef {
+            table_id: table_info.ident.table_id,
+            snapshot_loc: table_info.options().get(TBL_OPT_KEY_SNAPSHOT_LOC).cloned(),
+        });
+        Ok(())
+    }
+}
+
+struct FunctionsCollector {
+    deterministic: bool,
+    subqueries: Vec<Arc<PlanNode>>,
+}
+
+impl ExpressionVisitor for FunctionsCollector {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        match expr {
+            Expression::ScalarFunction { op, .. } => {
+                let factory = Function2Factory::instance();
+                self.deterministic &= match factory.get_features(op) {
+                    Ok(features) => features.is_deterministic,
+                    Err(_) => false,
+                };
+            }
+            Expression::Subquery { query_plan, .. }
+            | Expression::ScalarSubquery { query_plan, .. } => {
+                self.subqueries.push(query_plan.clone());
+            }
+            _ => {}
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
@@ -17,13 +17,16 @@ use std::collections::hash_map::Entry::Vacant;
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
+use std::sync::Weak;
 use std::time::Duration;
 
 use common_base::tokio;
+use common_base::MemoryTracker;
 use common_base::SignalStream;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
+use common_meta_types::UserInfo;
 use common_metrics::label_counter;
 use common_tracing::tracing;
 use futures::future::Either;
@@ -36,7 +39,9 @@ use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryResultCache;
 use crate::storages::cache::CacheManager;
+use crate::storages::fuse::operations::StorageUsageCache;
 use crate::users::auth::auth_mgr::AuthMgr;
 use crate::users::UserApiProvider;
 
@@ -51,6 +56,12 @@ pub struct SessionManager {
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
     pub(in crate::sessions) storage_cache_manager: Arc<CacheManager>,
+    pub(in crate::sessions) query_result_cache: Arc<QueryResultCache>,
+    pub(in crate::sessions) storage_usage_cache: Arc<StorageUsageCache>,
+    // The memory tracker of the main runtime, accounting the memory usage of the whole process.
+    pub(in crate::sessions) global_memory_tracker: Option<Arc<MemoryTracker>>,
+    // The memory trackers of the users with running queries, shared by the queries of a user.
+    pub(in crate::sessions) user_memory_trackers: Arc<RwLock<HashMap<String, Weak<MemoryTracker>>>>,
 }
 
 impl SessionManager {
@@ -67,6 +78,7 @@ impl SessionManager {
         let http_query_manager = HttpQueryManager::create_global(conf.clone()).await?;
 
         let max_active_sessions = conf.query.max_active_sessions as usize;
+        let query_result_cache = QueryResultCache::create(&conf.query);
         Ok(Arc::new(SessionManager {
             catalog,
             conf,
@@ -77,6 +89,11 @@ impl SessionManager {
             max_sessions: max_active_sessions,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
             storage_cache_manager: Arc::new(storage_cache_mgr),
+            query_result_cache: Arc::new(query_result_cache),
+            storage_usage_cache: Arc::new(StorageUsageCache::create()),
+            // the session manager is created on the main runtime
+            global_memory_tracker: MemoryTracker::current(),
+            user_memory_trackers: Arc::new(RwLock::new(HashMap::new())),
         }))
     }
 
@@ -109,6 +126,40 @@ impl SessionManager {
         self.storage_cache_manager.as_ref()
     }
 
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.query_result_cache.clone()
+    }
+
+    pub fn get_storage_usage_cache(&self) -> Arc<StorageUsageCache> {
+        self.storage_usage_cache.clone()
+    }
+
+    /// Get the memory tracker of the whole process, the parent of the query memory trackers.
+    pub fn get_global_memory_tracker(&self) -> Option<Arc<MemoryTracker>> {
+        self.global_memory_tracker.clone()
+    }
+
+    /// Get the memory tracker accounting the memory usage of all the running queries of the user,
+    /// limited by `UserQuota.max_memory_in_bytes`.
+    pub fn get_user_memory_tracker(&self, tenant: &str, user: &UserInfo) -> Arc<MemoryTracker> {
+        let key = format!("{}/{}", tenant, user.identity());
+        let mut trackers = self.user_memory_trackers.write();
+        let tracker = match trackers.get(&key).and_then(|v| v.upgrade()) {
+            Some(tracker) => tracker,
+            None => {
+                // the trackers of the users without running queries are dropped
+                trackers.retain(|_, v| v.strong_count() > 0);
+                let tracker = MemoryTracker::create(self.get_global_memory_tracker());
+                trackers.insert(key, Arc::downgrade(&tracker));
+                tracker
+            }
+        };
+
+        // the quota may be altered since the tracker is created
+        tracker.set_memory_limit(user.quota.max_memory_in_bytes as i64);
+        tracker
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
         let mut sessions = self.active_sessions.write();
         match sessions.len() == self.max_sessions {
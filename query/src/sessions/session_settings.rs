@@ -61,7 +61,6 @@ pub struct Settings {
     settings: Arc<RwLock<HashMap<String, SettingValue>>>,
     #[allow(dead_code)]
     user_api: Arc<UserApiProvider>,
-    #[allow(dead_code)]
     session_ctx: Arc<SessionContext>,
 }
 
@@ -135,6 +134,70 @@ impl Settings {
                 level: ScopeLevel::Session,
                 desc:"The maximum elapsed time after the occ starts, beyond which there will be no more retries. By default, it is 2 minutes.",
             },
+
+            // max_bytes_before_external_group_by
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("max_bytes_before_external_group_by", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc:"The memory threshold in bytes, beyond which the group by state is spilled to disk. By default, it is 0, which disables spilling.",
+            },
+
+            // max_bytes_before_external_sort
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("max_bytes_before_external_sort", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc:"The memory threshold in bytes, beyond which the sorted data is spilled to disk. By default, it is 0, which disables spilling.",
+            },
+
+            // max_memory_usage
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("max_memory_usage", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc:"The maximum memory usage in bytes of a query. By default, it is 0, which means unlimited.",
+            },
+
+            // enable_new_processor_framework
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_new_processor_framework", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc:"Execute the SELECT queries on the new processor pipeline if it is 1. By default, it is 0.",
+            },
+
+            // broadcast_join_threshold
+            SettingValue {
+                default_value: DataValue::UInt64(10 * 1024 * 1024),
+                user_setting: UserSetting::create("broadcast_join_threshold", DataValue::UInt64(10 * 1024 * 1024)),
+                level: ScopeLevel::Session,
+                desc:"The maximum estimated bytes of the right input of a join to broadcast it to every node, larger ones are shuffled. By default, it is 10MB.",
+            },
+
+            // max_stage_retries
+            SettingValue {
+                default_value: DataValue::UInt64(3),
+                user_setting: UserSetting::create("max_stage_retries", DataValue::UInt64(3)),
+                level: ScopeLevel::Session,
+                desc:"The maximum number of times to reschedule a distributed query on the healthy nodes after a node failure. By default, it is 3.",
+            },
+
+            // enable_query_result_cache
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create("enable_query_result_cache", DataValue::UInt64(0)),
+                level: ScopeLevel::Session,
+                desc:"Reuse the result of an identical SELECT query if the tables it reads are not changed since then, if it is 1. By default, it is 0.",
+            },
+
+            // query_result_cache_max_bytes
+            SettingValue {
+                default_value: DataValue::UInt64(1024 * 1024),
+                user_setting: UserSetting::create("query_result_cache_max_bytes", DataValue::UInt64(1024 * 1024)),
+                level: ScopeLevel::Session,
+                desc:"The maximum bytes of a SELECT query result to put into the result cache. By default, it is 1MB.",
+            },
         ];
 
         let settings = Arc::new(RwLock::new(HashMap::default()));
@@ -174,10 +237,16 @@ impl Settings {
         self.try_get_u64(key)
     }
 
-    // Get max_threads.
+    // Get max_threads, capped by the cpu quota of the current user.
     pub fn get_max_threads(&self) -> Result<u64> {
         let key = "max_threads";
-        self.try_get_u64(key)
+        let max_threads = self.try_get_u64(key)?;
+        match self.session_ctx.get_current_user() {
+            Some(user) if user.quota.max_cpu > 0 => {
+                Ok(std::cmp::min(max_threads, user.quota.max_cpu))
+            }
+            _ => Ok(max_threads),
+        }
     }
 
     // Set max_threads.
@@ -222,6 +291,54 @@ impl Settings {
         self.try_get_u64(key)
     }
 
+    // Get max bytes before external group by.
+    pub fn get_max_bytes_before_external_group_by(&self) -> Result<u64> {
+        let key = "max_bytes_before_external_group_by";
+        self.try_get_u64(key)
+    }
+
+    // Get max bytes before external sort.
+    pub fn get_max_bytes_before_external_sort(&self) -> Result<u64> {
+        let key = "max_bytes_before_external_sort";
+        self.try_get_u64(key)
+    }
+
+    // Get max memory usage of a query.
+    pub fn get_max_memory_usage(&self) -> Result<u64> {
+        let key = "max_memory_usage";
+        self.try_get_u64(key)
+    }
+
+    // Get enable_new_processor_framework.
+    pub fn get_enable_new_processor_framework(&self) -> Result<u64> {
+        let key = "enable_new_processor_framework";
+        self.try_get_u64(key)
+    }
+
+    // Get broadcast_join_threshold.
+    pub fn get_broadcast_join_threshold(&self) -> Result<u64> {
+        let key = "broadcast_join_threshold";
+        self.try_get_u64(key)
+    }
+
+    // Get max_stage_retries.
+    pub fn get_max_stage_retries(&self) -> Result<u64> {
+        let key = "max_stage_retries";
+        self.try_get_u64(key)
+    }
+
+    // Get enable_query_result_cache.
+    pub fn get_enable_query_result_cache(&self) -> Result<u64> {
+        let key = "enable_query_result_cache";
+        self.try_get_u64(key)
+    }
+
+    // Get query_result_cache_max_bytes.
+    pub fn get_query_result_cache_max_bytes(&self) -> Result<u64> {
+        let key = "query_result_cache_max_bytes";
+        self.try_get_u64(key)
+    }
+
     fn check_and_get_setting_value(&self, key: &str) -> Result<SettingValue> {
         let settings = self.settings.read();
         let setting = settings
@@ -257,6 +374,19 @@ impl Settings {
         Ok(())
     }
 
+    // Get the values of all the settings sorted by name, e.g. `max_threads=8`, which tells
+    // whether two sessions run the queries with the same settings.
+    pub fn get_setting_fingerprint(&self) -> String {
+        let settings = self.settings.read();
+
+        let mut values = settings
+            .iter()
+            .map(|(k, v)| format!("{}={:?}", k, v.user_setting.value))
+            .collect::<Vec<_>>();
+        values.sort();
+        values.join(",")
+    }
+
     pub fn get_setting_values(&self) -> Vec<DataValue> {
         let settings = self.settings.read();
 
@@ -30,6 +30,7 @@ impl DataSchemaHelper {
         match expr.plan().as_ref() {
             Plan::PhysicalScan(plan) => Self::build_scan(plan, metadata),
             Plan::PhysicalProject(plan) => Self::build_project(plan, &expr.children()[0], metadata),
+            Plan::PhysicalFilter(_) => Self::build(&expr.children()[0], metadata),
             _ => Err(ErrorCode::LogicalError(format!(
                 "Invalid physical plan: {:?}",
                 expr.plan()
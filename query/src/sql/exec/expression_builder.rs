@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_datavalues2::DataValue;
 use common_exception::Result;
 use common_planners::Expression;
 
+use crate::sql::AggregateFunction;
+use crate::sql::AndExpr;
 use crate::sql::BoundVariable;
+use crate::sql::ComparisonExpr;
+use crate::sql::ConstantExpr;
 use crate::sql::Metadata;
 use crate::sql::ScalarExpr;
 
@@ -31,12 +36,70 @@ impl<'a> ExpressionBuilder<'a> {
     pub fn build(&self, scalar: &ScalarExpr) -> Result<Expression> {
         match scalar {
             ScalarExpr::BoundVariable(expr) => self.build_bound_variable(expr),
+            ScalarExpr::ConstantExpr(expr) => self.build_constant(expr),
+            ScalarExpr::ComparisonExpr(expr) => self.build_comparison(expr),
+            ScalarExpr::AndExpr(expr) => self.build_and(expr),
+            ScalarExpr::AggregateFunction(expr) => self.build_aggregate_function(expr),
         }
     }
 
+    /// Build the conjunction of `predicates`
+    pub fn build_conjunction(&self, predicates: &[ScalarExpr]) -> Result<Expression> {
+        let mut result: Option<Expression> = None;
+        for predicate in predicates {
+            let expr = self.build(predicate)?;
+            result = Some(match result {
+                None => expr,
+                Some(left) => Expression::BinaryExpression {
+                    left: Box::new(left),
+                    op: "and".to_string(),
+                    right: Box::new(expr),
+                },
+            });
+        }
+        Ok(result.unwrap_or_else(|| Expression::create_literal(DataValue::Boolean(true))))
+    }
+
     fn build_bound_variable(&self, bound_variable: &BoundVariable) -> Result<Expression> {
         let column_entry = self.metadata.column(bound_variable.index);
         let result = Expression::Column(column_entry.name.clone());
         Ok(result)
     }
+
+    fn build_constant(&self, constant: &ConstantExpr) -> Result<Expression> {
+        Ok(Expression::create_literal_with_type(
+            constant.value.clone(),
+            constant.data_type.clone(),
+        ))
+    }
+
+    fn build_comparison(&self, comparison: &ComparisonExpr) -> Result<Expression> {
+        Ok(Expression::BinaryExpression {
+            left: Box::new(self.build(&comparison.left)?),
+            op: comparison.op.to_func_name().to_string(),
+            right: Box::new(self.build(&comparison.right)?),
+        })
+    }
+
+    fn build_and(&self, and: &AndExpr) -> Result<Expression> {
+        Ok(Expression::BinaryExpression {
+            left: Box::new(self.build(&and.left)?),
+            op: "and".to_string(),
+            right: Box::new(self.build(&and.right)?),
+        })
+    }
+
+    fn build_aggregate_function(&self, aggregate: &AggregateFunction) -> Result<Expression> {
+        let args = aggregate
+            .args
+            .iter()
+            .map(|arg| self.build(arg))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Expression::AggregateFunction {
+            op: aggregate.func_name.clone(),
+            distinct: aggregate.distinct,
+            params: vec![],
+            args,
+        })
+    }
 }
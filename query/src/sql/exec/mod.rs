@@ -27,12 +27,14 @@ use common_planners::ReadDataSourcePlan;
 use crate::pipelines::processors::Pipeline;
 use crate::pipelines::transforms::ProjectionTransform;
 use crate::pipelines::transforms::SourceTransform;
+use crate::pipelines::transforms::WhereTransform;
 use crate::sessions::QueryContext;
 use crate::sql::exec::data_schema_helper::DataSchemaHelper;
 use crate::sql::exec::expression_builder::ExpressionBuilder;
 use crate::sql::exec::util::check_physical;
 use crate::sql::optimizer::SExpr;
 use crate::sql::Metadata;
+use crate::sql::PhysicalFilter;
 use crate::sql::PhysicalProject;
 use crate::sql::PhysicalScan;
 use crate::sql::Plan;
@@ -63,6 +65,10 @@ impl Executor {
                 self.build_project(project, expression.children().as_slice())
                     .await
             }
+            Plan::PhysicalFilter(filter) => {
+                self.build_filter(filter, expression.children().as_slice())
+                    .await
+            }
             _ => Err(ErrorCode::LogicalError(format!(
                 "Invalid physical plan: {:?}",
                 expression
@@ -103,6 +109,22 @@ impl Executor {
         Ok(pipeline)
     }
 
+    async fn build_filter(&self, filter: &PhysicalFilter, children: &[SExpr]) -> Result<Pipeline> {
+        let child = &children[0];
+        let input_schema = Arc::new(DataSchemaHelper::build(child, &self.metadata)?);
+        let builder = ExpressionBuilder::create(&self.metadata);
+        let predicate = builder.build_conjunction(&filter.predicates)?;
+
+        let mut pipeline = self.build_pipeline(child).await?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(WhereTransform::try_create(
+                input_schema.clone(),
+                predicate.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
     async fn build_table_scan(&self, scan: &PhysicalScan) -> Result<Pipeline> {
         let table = self.metadata.table(scan.table_index).table.clone();
         let (statistics, parts) = table.read_partitions(self.ctx.clone(), None).await?;
@@ -16,7 +16,14 @@ use crate::sql::optimizer::RuleID;
 use crate::sql::optimizer::RuleSet;
 
 pub fn get_implement_rule_set() -> RuleSet {
-    RuleSet::create_with_ids(vec![RuleID::ImplementGet, RuleID::ImplementProject]).unwrap()
+    RuleSet::create_with_ids(vec![
+        RuleID::ImplementGet,
+        RuleID::ImplementProject,
+        RuleID::ImplementFilter,
+        RuleID::ImplementInnerJoin,
+        RuleID::ImplementAggregate,
+    ])
+    .unwrap()
 }
 
 #[cfg(test)]
@@ -15,16 +15,18 @@
 mod explore_rules;
 mod implement_rules;
 
+use std::collections::HashMap;
+
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sql::optimizer::cascades::explore_rules::get_explore_rule_set;
 use crate::sql::optimizer::cascades::implement_rules::get_implement_rule_set;
-use crate::sql::optimizer::group::Group;
+use crate::sql::optimizer::cost::Cost;
+use crate::sql::optimizer::cost::CostModel;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::memo::Memo;
 use crate::sql::optimizer::optimize_context::OptimizeContext;
-use crate::sql::optimizer::rule::RulePtr;
 use crate::sql::optimizer::rule::RuleSet;
 use crate::sql::optimizer::rule::TransformState;
 use crate::sql::optimizer::PhysicalProperty;
@@ -35,21 +37,29 @@ use crate::sql::IndexType;
 /// A cascades-style search engine to enumerate possible alternations of a relational expression and
 /// find the optimal one.
 ///
-/// NOTICE: we don't support cost-based optimization and lower bound searching for now.
+/// NOTICE: we don't support lower bound searching for now, all the alternations are enumerated
+/// before costing.
 pub struct CascadesOptimizer {
     optimize_context: OptimizeContext,
     memo: Memo,
     explore_rules: RuleSet,
     implement_rules: RuleSet,
+    cost_model: CostModel,
+
+    /// The optimal plan and its cost of a group, keyed by group index and required columns
+    best_plans: HashMap<(IndexType, Vec<IndexType>), (Cost, SExpr)>,
 }
 
 impl CascadesOptimizer {
     pub fn create(optimize_context: OptimizeContext) -> Self {
+        let cost_model = CostModel::create(optimize_context.metadata().clone());
         CascadesOptimizer {
             optimize_context,
             memo: Memo::create(),
             explore_rules: get_explore_rule_set(),
             implement_rules: get_implement_rule_set(),
+            cost_model,
+            best_plans: HashMap::new(),
         }
     }
 
@@ -62,13 +72,30 @@ impl CascadesOptimizer {
     pub fn optimize(&mut self, expression: SExpr) -> Result<SExpr> {
         self.init(expression)?;
 
-        self.explore_group(self.memo.root().unwrap().group_index())?;
+        self.explore()?;
 
-        self.implement_group(self.memo.root().unwrap().group_index())?;
+        self.implement()?;
 
         self.find_optimal_plan()
     }
 
+    /// Apply explore rules until no more alternation can be found. Every group is explored in
+    /// each round, since rules may match the alternations found in the children groups.
+    fn explore(&mut self) -> Result<()> {
+        loop {
+            let m_expr_count = self.memo.m_expr_count();
+            for group_index in 0..self.memo.group_count() {
+                self.explore_group(group_index)?;
+            }
+
+            if self.memo.m_expr_count() == m_expr_count {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     fn explore_group(&mut self, group_index: IndexType) -> Result<()> {
         let group = self.memo.group(group_index);
         let expressions: Vec<MExpr> = group.iter().cloned().collect();
@@ -80,15 +107,20 @@ impl CascadesOptimizer {
     }
 
     fn explore_expr(&mut self, m_expr: MExpr) -> Result<()> {
-        for child in m_expr.children() {
-            self.explore_group(*child)?;
-        }
-
-        let mut state = TransformState::create();
+        let mut state = TransformState::create_with_memo(&self.memo);
         for rule in self.explore_rules.iter() {
             m_expr.apply_rule(&self.memo, rule, &mut state)?;
         }
-        self.insert_from_transform_state(m_expr.group_index(), state)?;
+        let results = state.results().clone();
+        self.insert_expressions(m_expr.group_index(), results)?;
+
+        Ok(())
+    }
+
+    fn implement(&mut self) -> Result<()> {
+        for group_index in 0..self.memo.group_count() {
+            self.implement_group(group_index)?;
+        }
 
         Ok(())
     }
@@ -104,91 +136,123 @@ impl CascadesOptimizer {
     }
 
     fn implement_expr(&mut self, m_expr: MExpr) -> Result<()> {
-        for child in m_expr.children() {
-            self.implement_group(*child)?;
-        }
-
-        let mut state = TransformState::create();
+        let mut state = TransformState::create_with_memo(&self.memo);
         for rule in self.implement_rules.iter() {
             m_expr.apply_rule(&self.memo, rule, &mut state)?;
         }
-        self.insert_from_transform_state(m_expr.group_index(), state)?;
+        let results = state.results().clone();
+        self.insert_expressions(m_expr.group_index(), results)?;
 
         Ok(())
     }
 
-    fn insert_from_transform_state(
-        &mut self,
-        group_index: IndexType,
-        state: TransformState,
-    ) -> Result<()> {
-        for result in state.results() {
-            self.insert_expression(group_index, result)?;
+    fn insert_expressions(&mut self, group_index: IndexType, results: Vec<SExpr>) -> Result<()> {
+        for result in results {
+            self.memo.insert(Some(group_index), result)?;
         }
 
         Ok(())
     }
 
-    fn insert_expression(&mut self, group_index: IndexType, expression: &SExpr) -> Result<()> {
-        self.memo.insert(Some(group_index), expression.clone())?;
-
-        Ok(())
-    }
-
-    #[allow(dead_code)]
-    fn apply_rule(&self, m_expr: &MExpr, rule: &RulePtr, state: &mut TransformState) -> Result<()> {
-        m_expr.apply_rule(&self.memo, rule, state)?;
-
-        Ok(())
-    }
-
-    fn find_optimal_plan(&self) -> Result<SExpr> {
-        println!("{:?}", self.memo);
-
+    fn find_optimal_plan(&mut self) -> Result<SExpr> {
         let root_group = self.memo.root().unwrap();
+        let root_index = root_group.group_index();
+
+        // Columns of the query which are not output by the root are resolved by the binder
+        let output_columns = root_group
+            .relational_prop()
+            .cloned()
+            .unwrap_or_default()
+            .output_columns()
+            .clone();
+        let required_columns = self
+            .optimize_context
+            .required_prop()
+            .required_columns()
+            .intersection(&output_columns)
+            .cloned()
+            .collect();
+        let required_prop = RequiredProperty::create(required_columns);
+
+        let (_, result) = self.optimize_group(root_index, &required_prop)?;
+        Ok(result)
+    }
 
-        let required_prop = self.optimize_context.required_prop().clone();
+    /// Find the physical plan with the lowest cost in a group that satisfies given
+    /// `RequiredProperty`.
+    fn optimize_group(
+        &mut self,
+        group_index: IndexType,
+        required_prop: &RequiredProperty,
+    ) -> Result<(Cost, SExpr)> {
+        let mut required_columns: Vec<IndexType> =
+            required_prop.required_columns().iter().cloned().collect();
+        required_columns.sort_unstable();
+        let key = (group_index, required_columns);
+        if let Some(best_plan) = self.best_plans.get(&key) {
+            return Ok(best_plan.clone());
+        }
 
-        self.optimize_group(root_group, &required_prop)
-    }
+        let group = self.memo.group(group_index);
+        let relational_prop = group.relational_prop().cloned().unwrap_or_default();
+        let dummy_physical_prop = PhysicalProperty::default();
+        if !required_prop.provided_by(&relational_prop, &dummy_physical_prop) {
+            return Err(ErrorCode::LogicalError("Cannot find an appropriate plan"));
+        }
 
-    /// We don't have cost mechanism for evaluate cost of plans, so we just extract
-    /// first physical plan in a group that satisfies given RequiredProperty.
-    fn optimize_group(&self, group: &Group, required_prop: &RequiredProperty) -> Result<SExpr> {
-        for m_expr in group.iter() {
-            if m_expr.plan().is_physical() {
-                let plan = m_expr.plan().clone();
-
-                // Chek properties
-                let physical = plan.as_physical_plan().unwrap();
-                let relational_prop = group.relational_prop().unwrap();
-                let dummy_physical_prop = PhysicalProperty::default();
-                let required_prop = physical.compute_required_prop(required_prop);
-
-                if !required_prop.provided_by(relational_prop, &dummy_physical_prop) {
-                    continue;
-                }
-
-                let children = self.optimize_m_expr(m_expr, &required_prop)?;
-                let result = SExpr::create(plan, children, None);
-                return Ok(result);
+        let expressions: Vec<MExpr> = group
+            .iter()
+            .filter(|m_expr| m_expr.plan().is_physical())
+            .cloned()
+            .collect();
+        let mut best_plan: Option<(Cost, SExpr)> = None;
+        for m_expr in expressions {
+            let (children_cost, children) = self.optimize_m_expr(&m_expr, required_prop)?;
+            let cost = self.cost_model.compute_cost(&self.memo, &m_expr)? + children_cost;
+            if matches!(&best_plan, Some((best_cost, _)) if *best_cost <= cost) {
+                continue;
             }
+            best_plan = Some((cost, SExpr::create(m_expr.plan(), children, None)));
         }
 
-        Err(ErrorCode::LogicalError("Cannot find an appropriate plan"))
+        let best_plan =
+            best_plan.ok_or_else(|| ErrorCode::LogicalError("Cannot find an appropriate plan"))?;
+        self.best_plans.insert(key, best_plan.clone());
+        Ok(best_plan)
     }
 
+    /// Optimize the children of a physical `MExpr`, each child is required to provide the
+    /// columns used by the `MExpr` which come from the child.
     fn optimize_m_expr(
-        &self,
+        &mut self,
         m_expr: &MExpr,
         required_prop: &RequiredProperty,
-    ) -> Result<Vec<SExpr>> {
+    ) -> Result<(Cost, Vec<SExpr>)> {
+        let plan = m_expr.plan();
+        let physical = plan.as_physical_plan().unwrap();
+        let required_prop = physical.compute_required_prop(required_prop);
+
+        let mut cost = 0.0;
         let mut children = vec![];
         for child in m_expr.children() {
-            let group = self.memo.group(*child);
-            children.push(self.optimize_group(group, required_prop)?);
+            let child_prop = self
+                .memo
+                .group(*child)
+                .relational_prop()
+                .cloned()
+                .unwrap_or_default();
+            let child_required_prop = RequiredProperty::create(
+                required_prop
+                    .required_columns()
+                    .intersection(child_prop.output_columns())
+                    .cloned()
+                    .collect(),
+            );
+            let (child_cost, child_expr) = self.optimize_group(*child, &child_required_prop)?;
+            cost += child_cost;
+            children.push(child_expr);
         }
 
-        Ok(children)
+        Ok((cost, children))
     }
 }
@@ -0,0 +1,291 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues2::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::memo::Memo;
+use crate::sql::AndExpr;
+use crate::sql::BoundVariable;
+use crate::sql::ComparisonExpr;
+use crate::sql::ComparisonOp;
+use crate::sql::ConstantExpr;
+use crate::sql::IndexType;
+use crate::sql::LogicalAggregate;
+use crate::sql::LogicalFilter;
+use crate::sql::LogicalGet;
+use crate::sql::LogicalInnerJoin;
+use crate::sql::Metadata;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+use crate::storages::index::ColumnDistribution;
+use crate::storages::index::ColumnStatistics;
+use crate::storages::TableStatistics;
+
+/// Number of rows assumed for tables without statistics
+const DEFAULT_TABLE_ROWS: f64 = 1000.0;
+/// Selectivity of predicates whose selectivity cannot be derived from statistics
+const DEFAULT_SELECTIVITY: f64 = 0.1;
+/// Selectivity of range predicates without min/max statistics
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// Estimate the number of rows produced by the groups of `Memo` with the table statistics
+/// collected in `Metadata`.
+pub struct CardinalityEstimator {
+    metadata: Metadata,
+    cardinalities: HashMap<IndexType, f64>,
+}
+
+impl CardinalityEstimator {
+    pub fn create(metadata: Metadata) -> Self {
+        CardinalityEstimator {
+            metadata,
+            cardinalities: HashMap::new(),
+        }
+    }
+
+    /// All the expressions in a group are logically equivalent, so the cardinality is derived
+    /// from any logical expression of the group.
+    pub fn estimate_group(&mut self, memo: &Memo, group_index: IndexType) -> Result<f64> {
+        if let Some(cardinality) = self.cardinalities.get(&group_index) {
+            return Ok(*cardinality);
+        }
+
+        let m_expr = memo
+            .group(group_index)
+            .iter()
+            .find(|m_expr| m_expr.plan().is_logical())
+            .cloned()
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Cannot estimate cardinality of group {} without logical expression",
+                    group_index
+                ))
+            })?;
+        let children = m_expr.children();
+
+        let cardinality = match m_expr.plan().as_ref() {
+            Plan::LogicalGet(get) => self.estimate_get(get),
+            Plan::LogicalProject(_) => self.estimate_group(memo, children[0])?,
+            Plan::LogicalFilter(filter) => {
+                let input = self.estimate_group(memo, children[0])?;
+                self.estimate_filter(filter, input)
+            }
+            Plan::LogicalInnerJoin(join) => {
+                let left = self.estimate_group(memo, children[0])?;
+                let right = self.estimate_group(memo, children[1])?;
+                self.estimate_inner_join(join, left, right)
+            }
+            Plan::LogicalAggregate(aggregate) => {
+                let input = self.estimate_group(memo, children[0])?;
+                self.estimate_aggregate(aggregate, input)
+            }
+            plan => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Cannot estimate cardinality of plan: {:?}",
+                    plan
+                )))
+            }
+        };
+
+        self.cardinalities.insert(group_index, cardinality);
+        Ok(cardinality)
+    }
+
+    fn estimate_get(&self, get: &LogicalGet) -> f64 {
+        self.metadata
+            .table(get.table_index)
+            .statistics
+            .as_ref()
+            .map(|statistics| statistics.num_rows as f64)
+            .unwrap_or(DEFAULT_TABLE_ROWS)
+    }
+
+    fn estimate_filter(&self, filter: &LogicalFilter, input: f64) -> f64 {
+        filter
+            .predicates
+            .iter()
+            .fold(input, |cardinality, predicate| {
+                cardinality * self.selectivity(predicate)
+            })
+    }
+
+    /// |L ⋈ R| = |L| * |R| / max(ndv(l), ndv(r)) for each condition `l = r`
+    fn estimate_inner_join(&self, join: &LogicalInnerJoin, left: f64, right: f64) -> f64 {
+        let mut cardinality = left * right;
+        for (left_condition, right_condition) in join
+            .left_conditions
+            .iter()
+            .zip(join.right_conditions.iter())
+        {
+            let left_ndv = self.ndv(left_condition).unwrap_or(left).min(left);
+            let right_ndv = self.ndv(right_condition).unwrap_or(right).min(right);
+            cardinality /= left_ndv.max(right_ndv).max(1.0);
+        }
+        cardinality
+    }
+
+    fn estimate_aggregate(&self, aggregate: &LogicalAggregate, input: f64) -> f64 {
+        if aggregate.group_items.is_empty() {
+            return 1.0;
+        }
+
+        let groups = aggregate.group_items.iter().fold(1.0, |groups, item| {
+            groups * self.ndv(&item.expr).unwrap_or(input)
+        });
+        groups.min(input)
+    }
+
+    fn selectivity(&self, predicate: &ScalarExpr) -> f64 {
+        match predicate {
+            ScalarExpr::AndExpr(AndExpr { left, right }) => {
+                self.selectivity(left) * self.selectivity(right)
+            }
+            ScalarExpr::ComparisonExpr(comparison) => self.comparison_selectivity(comparison),
+            ScalarExpr::ConstantExpr(ConstantExpr {
+                value: DataValue::Boolean(value),
+                ..
+            }) => {
+                if *value {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn comparison_selectivity(&self, comparison: &ComparisonExpr) -> f64 {
+        // Normalize the comparison into `column op constant`
+        let (column, op, constant) = match (comparison.left.as_ref(), comparison.right.as_ref()) {
+            (ScalarExpr::BoundVariable(column), ScalarExpr::ConstantExpr(constant)) => {
+                (column, comparison.op, constant)
+            }
+            (ScalarExpr::ConstantExpr(constant), ScalarExpr::BoundVariable(column)) => {
+                (column, comparison.op.reverse(), constant)
+            }
+            (left, right) if comparison.op == ComparisonOp::Equal => {
+                return match (self.ndv(left), self.ndv(right)) {
+                    (Some(left), Some(right)) => 1.0 / left.max(right).max(1.0),
+                    _ => DEFAULT_SELECTIVITY,
+                };
+            }
+            _ => return DEFAULT_SELECTIVITY,
+        };
+
+        let equal_selectivity = self
+            .column_ndv(column)
+            .map(|ndv| 1.0 / ndv.max(1.0))
+            .unwrap_or(DEFAULT_SELECTIVITY);
+        match op {
+            ComparisonOp::Equal => equal_selectivity,
+            ComparisonOp::NotEqual => 1.0 - equal_selectivity,
+            ComparisonOp::LT | ComparisonOp::LTE | ComparisonOp::GT | ComparisonOp::GTE => self
+                .range_selectivity(column, op, &constant.value)
+                .unwrap_or(DEFAULT_RANGE_SELECTIVITY),
+        }
+    }
+
+    /// Interpolate the selectivity with the histogram of the column if it's analyzed, otherwise
+    /// with the min/max of the column, assuming the values are distributed uniformly.
+    fn range_selectivity(
+        &self,
+        column: &BoundVariable,
+        op: ComparisonOp,
+        value: &DataValue,
+    ) -> Option<f64> {
+        if let Some(below) = self
+            .column_distribution(column.index)
+            .and_then(|distribution| distribution.histogram.fraction_below(value.as_f64().ok()?))
+        {
+            return match op {
+                ComparisonOp::LT | ComparisonOp::LTE => Some(below),
+                _ => Some(1.0 - below),
+            };
+        }
+
+        let statistics = self.column_statistics(column.index)?;
+        let min = statistics.min.as_f64().ok()?;
+        let max = statistics.max.as_f64().ok()?;
+        let value = value.as_f64().ok()?;
+        if max <= min {
+            return None;
+        }
+
+        let below = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        match op {
+            ComparisonOp::LT | ComparisonOp::LTE => Some(below),
+            _ => Some(1.0 - below),
+        }
+    }
+
+    /// Number of distinct values of a scalar, only available for columns of base tables
+    fn ndv(&self, scalar: &ScalarExpr) -> Option<f64> {
+        match scalar {
+            ScalarExpr::BoundVariable(column) => self.column_ndv(column),
+            ScalarExpr::ConstantExpr(_) => Some(1.0),
+            _ => None,
+        }
+    }
+
+    /// The NDV estimated by `ANALYZE TABLE`. If the table is not analyzed, it's bounded by the
+    /// range of integer columns and the number of rows of the table.
+    fn column_ndv(&self, column: &BoundVariable) -> Option<f64> {
+        if let Some(distribution) = self.column_distribution(column.index) {
+            return Some((distribution.ndv as f64).max(1.0));
+        }
+
+        let table_index = self.metadata.column(column.index).table_index?;
+        let num_rows = self
+            .metadata
+            .table(table_index)
+            .statistics
+            .as_ref()?
+            .num_rows as f64;
+        let statistics = self.column_statistics(column.index)?;
+        if !statistics.min.is_integer() || !statistics.max.is_integer() {
+            return Some(num_rows);
+        }
+
+        let min = statistics.min.as_f64().ok()?;
+        let max = statistics.max.as_f64().ok()?;
+        Some((max - min + 1.0).min(num_rows).max(1.0))
+    }
+
+    fn column_statistics(&self, column_index: IndexType) -> Option<&ColumnStatistics> {
+        let (statistics, position) = self.table_statistics(column_index)?;
+        statistics.column_statistics.get(&position)
+    }
+
+    fn column_distribution(&self, column_index: IndexType) -> Option<&ColumnDistribution> {
+        let (statistics, position) = self.table_statistics(column_index)?;
+        statistics.column_distributions.get(&position)
+    }
+
+    /// The statistics of the table of a column, and the position of the column in the table.
+    fn table_statistics(&self, column_index: IndexType) -> Option<(&TableStatistics, u32)> {
+        let table_index = self.metadata.column(column_index).table_index?;
+        let statistics = self.metadata.table(table_index).statistics.as_ref()?;
+        let position = self
+            .metadata
+            .columns_by_table_index(table_index)
+            .iter()
+            .position(|column| column.column_index == column_index)?;
+        Some((statistics, position as u32))
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::cost::CardinalityEstimator;
+use crate::sql::optimizer::m_expr::MExpr;
+use crate::sql::optimizer::memo::Memo;
+use crate::sql::Metadata;
+use crate::sql::Plan;
+
+pub type Cost = f64;
+
+/// Building a hash table is more expensive than probing it
+const HASH_BUILD_FACTOR: f64 = 2.0;
+
+/// Cost model based on the number of rows processed by each operator.
+pub struct CostModel {
+    estimator: CardinalityEstimator,
+}
+
+impl CostModel {
+    pub fn create(metadata: Metadata) -> Self {
+        CostModel {
+            estimator: CardinalityEstimator::create(metadata),
+        }
+    }
+
+    /// Cost of a physical `MExpr` itself, excluding the cost of its children.
+    pub fn compute_cost(&mut self, memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
+        let children = m_expr.children();
+        match m_expr.plan().as_ref() {
+            Plan::PhysicalScan(_) => self.estimator.estimate_group(memo, m_expr.group_index()),
+            Plan::PhysicalProject(_) | Plan::PhysicalFilter(_) => {
+                self.estimator.estimate_group(memo, children[0])
+            }
+            Plan::PhysicalHashJoin(_) => {
+                let probe = self.estimator.estimate_group(memo, children[0])?;
+                let build = self.estimator.estimate_group(memo, children[1])?;
+                let output = self.estimator.estimate_group(memo, m_expr.group_index())?;
+                Ok(build * HASH_BUILD_FACTOR + probe + output)
+            }
+            Plan::PhysicalAggregate(_) => {
+                let input = self.estimator.estimate_group(memo, children[0])?;
+                let output = self.estimator.estimate_group(memo, m_expr.group_index())?;
+                Ok(input + output)
+            }
+            plan => Err(ErrorCode::LogicalError(format!(
+                "Cannot compute cost of plan: {:?}",
+                plan
+            ))),
+        }
+    }
+}
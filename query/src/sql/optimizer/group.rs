@@ -49,6 +49,14 @@ impl Group {
         self.expressions.iter()
     }
 
+    pub fn len(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
+
     pub fn insert(&mut self, group_expression: MExpr) -> Result<()> {
         self.expressions.push(group_expression);
         Ok(())
@@ -12,51 +12,72 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod prune_columns;
 mod rule_list;
 
 use common_exception::Result;
 
+use crate::sql::optimizer::heuristic::prune_columns::ColumnPruner;
 use crate::sql::optimizer::heuristic::rule_list::RuleList;
+use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::TransformState;
+use crate::sql::optimizer::RequiredProperty;
 use crate::sql::optimizer::SExpr;
 
-/// A heuristic
+/// Rules applied by `HeuristicOptimizer`, in order.
+pub static DEFAULT_REWRITE_RULES: [RuleID; 3] = [
+    RuleID::MergeFilter,
+    RuleID::PushDownFilterProject,
+    RuleID::PushDownFilterJoin,
+];
+
+/// A heuristic optimizer which rewrites the expression with transformation rules that always
+/// produce better plans, then prunes the columns not required by the query.
 pub struct HeuristicOptimizer {
     rules: RuleList,
+    required_prop: RequiredProperty,
 }
 
 impl HeuristicOptimizer {
-    pub fn create() -> Result<Self> {
+    pub fn create(required_prop: RequiredProperty) -> Result<Self> {
         Ok(HeuristicOptimizer {
-            rules: RuleList::create(vec![])?,
+            rules: RuleList::create(DEFAULT_REWRITE_RULES.to_vec())?,
+            required_prop,
         })
     }
 
     pub fn optimize(&mut self, expression: SExpr) -> Result<SExpr> {
         let result = self.optimize_expression(&expression)?;
-        Ok(result)
+        let pruner = ColumnPruner::create();
+        pruner.prune(&result, self.required_prop.required_columns())
     }
 
+    /// Optimize the expression bottom-up
     fn optimize_expression(&self, expression: &SExpr) -> Result<SExpr> {
-        let mut result = expression.clone();
+        let mut optimized_children = Vec::with_capacity(expression.arity());
         for expr in expression.children() {
-            result = self.apply_transform_rules(expr, &self.rules)?;
+            optimized_children.push(self.optimize_expression(expr)?);
         }
+        let optimized_expression = SExpr::create(expression.plan(), optimized_children, None);
 
-        Ok(result)
+        self.apply_transform_rules(&optimized_expression, &self.rules)
     }
 
     fn apply_transform_rules(&self, expression: &SExpr, rule_list: &RuleList) -> Result<SExpr> {
-        let mut result = expression.clone();
-
         for rule in rule_list.iter() {
+            if !expression.match_pattern(rule.pattern()) {
+                continue;
+            }
+
             let mut state = TransformState::create();
-            rule.apply(&result, &mut state)?;
+            rule.apply(expression, &mut state)?;
             if !state.results().is_empty() {
-                result = state.results()[0].clone();
+                // The transformed expression may be matched by the rules again
+                let result = state.results()[0].clone();
+                return self.optimize_expression(&result);
             }
         }
 
-        Ok(result)
+        Ok(expression.clone())
     }
 }
@@ -0,0 +1,129 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalAggregate;
+use crate::sql::LogicalFilter;
+use crate::sql::LogicalGet;
+use crate::sql::LogicalProject;
+use crate::sql::Plan;
+
+/// Prune the columns which are not required by the parent operators, top-down.
+pub struct ColumnPruner;
+
+impl ColumnPruner {
+    pub fn create() -> Self {
+        ColumnPruner {}
+    }
+
+    pub fn prune(&self, expression: &SExpr, required: &ColumnSet) -> Result<SExpr> {
+        match expression.plan().as_ref() {
+            Plan::LogicalGet(get) => {
+                let mut columns: ColumnSet = get.columns.intersection(required).cloned().collect();
+                if columns.is_empty() {
+                    // Keep at least one column to read the rows of the table
+                    columns.extend(get.columns.iter().min());
+                }
+                let get = LogicalGet {
+                    table_index: get.table_index,
+                    columns,
+                };
+                Ok(SExpr::create_leaf(Rc::new(Plan::LogicalGet(get))))
+            }
+            Plan::LogicalProject(project) => {
+                let mut items: Vec<_> = project
+                    .items
+                    .iter()
+                    .filter(|item| required.contains(&item.index))
+                    .cloned()
+                    .collect();
+                if items.is_empty() {
+                    items.extend(project.items.first().cloned());
+                }
+                let mut child_required = ColumnSet::new();
+                for item in items.iter() {
+                    child_required.extend(item.expr.used_columns());
+                }
+                let child = self.prune(&expression.children()[0], &child_required)?;
+                Ok(SExpr::create_unary(
+                    Rc::new(Plan::LogicalProject(LogicalProject::create(items))),
+                    child,
+                ))
+            }
+            Plan::LogicalFilter(filter) => {
+                let mut child_required = required.clone();
+                for predicate in filter.predicates.iter() {
+                    child_required.extend(predicate.used_columns());
+                }
+                let child = self.prune(&expression.children()[0], &child_required)?;
+                Ok(SExpr::create_unary(
+                    Rc::new(Plan::LogicalFilter(LogicalFilter::create(
+                        filter.predicates.clone(),
+                    ))),
+                    child,
+                ))
+            }
+            Plan::LogicalInnerJoin(join) => {
+                let mut used_columns = required.clone();
+                for condition in join
+                    .left_conditions
+                    .iter()
+                    .chain(join.right_conditions.iter())
+                {
+                    used_columns.extend(condition.used_columns());
+                }
+                let mut children = Vec::with_capacity(expression.arity());
+                for child in expression.children() {
+                    let prop = child.compute_relational_prop();
+                    let child_required = used_columns
+                        .intersection(prop.output_columns())
+                        .cloned()
+                        .collect();
+                    children.push(self.prune(child, &child_required)?);
+                }
+                Ok(SExpr::create(expression.plan(), children, None))
+            }
+            Plan::LogicalAggregate(aggregate) => {
+                let aggregate_functions: Vec<_> = aggregate
+                    .aggregate_functions
+                    .iter()
+                    .filter(|item| required.contains(&item.index))
+                    .cloned()
+                    .collect();
+                let mut child_required = ColumnSet::new();
+                for item in aggregate
+                    .group_items
+                    .iter()
+                    .chain(aggregate_functions.iter())
+                {
+                    child_required.extend(item.expr.used_columns());
+                }
+                let child = self.prune(&expression.children()[0], &child_required)?;
+                Ok(SExpr::create_unary(
+                    Rc::new(Plan::LogicalAggregate(LogicalAggregate::create(
+                        aggregate.group_items.clone(),
+                        aggregate_functions,
+                    ))),
+                    child,
+                ))
+            }
+            _ => Ok(expression.clone()),
+        }
+    }
+}
@@ -18,7 +18,6 @@ use crate::sql::optimizer::rule::RuleFactory;
 use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RulePtr;
 
-#[allow(dead_code)]
 // Ordered list of rules, may contain duplicated rules.
 pub struct RuleList {
     rules: Vec<RulePtr>,
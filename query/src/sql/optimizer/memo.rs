@@ -19,8 +19,10 @@ use common_exception::Result;
 
 use crate::sql::optimizer::group::Group;
 use crate::sql::optimizer::m_expr::MExpr;
+use crate::sql::optimizer::property::RelationalProperty;
 use crate::sql::optimizer::s_expr::SExpr;
 use crate::sql::IndexType;
+use crate::sql::Plan;
 
 /// `Memo` is a search space which memoize possible plans of a query.
 /// The plans inside `Memo` are organized with `Group`s.
@@ -72,6 +74,13 @@ impl Memo {
             return Ok(group_index);
         }
 
+        let plan = expression.plan();
+
+        if let Some(group_index) = self.lookup(target_group, &plan, &children_group) {
+            // An identical expression has been memoized already
+            return Ok(group_index);
+        }
+
         let mut new_group = false;
 
         // Create new group if not specified
@@ -84,19 +93,59 @@ impl Memo {
         };
 
         if new_group {
+            let children_props: Vec<RelationalProperty> = children_group
+                .iter()
+                .map(|child| {
+                    self.group(*child)
+                        .relational_prop()
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect();
+            let relational_prop = plan
+                .compute_relational_prop(&children_props)
+                .unwrap_or_default();
             let group = self.group_mut(group_index);
-            let relational_prop = expression.compute_relational_prop();
             group.set_relational_prop(relational_prop);
         }
 
-        let plan = expression.plan();
-
         let group_expression = MExpr::create(group_index, plan, children_group);
         self.insert_m_expr(group_index, group_expression)?;
 
         Ok(group_index)
     }
 
+    /// Find the group containing an `MExpr` with the same plan and children, only the target group
+    /// is searched if specified.
+    fn lookup(
+        &self,
+        target_group: Option<IndexType>,
+        plan: &Plan,
+        children: &[IndexType],
+    ) -> Option<IndexType> {
+        let candidates: Vec<&Group> = match target_group {
+            Some(index) => vec![self.group(index)],
+            None => self.groups.iter().collect(),
+        };
+        candidates
+            .into_iter()
+            .find(|group| {
+                group
+                    .iter()
+                    .any(|m_expr| m_expr.children() == children && m_expr.plan().as_ref() == plan)
+            })
+            .map(|group| group.group_index())
+    }
+
+    pub fn group_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Total number of `MExpr`s in all groups
+    pub fn m_expr_count(&self) -> usize {
+        self.groups.iter().map(|group| group.len()).sum()
+    }
+
     pub fn group(&self, index: IndexType) -> &Group {
         &self.groups[index]
     }
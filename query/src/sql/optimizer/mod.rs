@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod cascades;
+mod cost;
 mod group;
 mod heuristic;
 mod m_expr;
@@ -29,6 +30,7 @@ pub use memo::Memo;
 pub use optimize_context::OptimizeContext;
 pub use pattern_extractor::PatternExtractor;
 pub use property::ColumnSet;
+pub use property::NamedColumn;
 pub use property::PhysicalProperty;
 pub use property::RelationalProperty;
 pub use property::RequiredProperty;
@@ -40,7 +42,7 @@ use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RuleSet;
 
 pub fn optimize(expression: SExpr, ctx: OptimizeContext) -> Result<SExpr> {
-    let mut heuristic = HeuristicOptimizer::create()?;
+    let mut heuristic = HeuristicOptimizer::create(ctx.required_prop().clone())?;
     let expression = heuristic.optimize(expression)?;
     let mut cascades = CascadesOptimizer::create(ctx);
     cascades.optimize(expression)
@@ -16,25 +16,30 @@ use crate::sql::optimizer::property::NamedColumn;
 use crate::sql::optimizer::property::RequiredProperty;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::BindContext;
+use crate::sql::Metadata;
 
 pub struct OptimizeContext {
     required_prop: RequiredProperty,
     _output_named_columns: Vec<NamedColumn>,
+
+    /// Tables and columns of the query, and the statistics of the tables
+    metadata: Metadata,
 }
 
 impl OptimizeContext {
-    #[allow(dead_code)]
     pub fn create(
         required_prop: RequiredProperty,
         _output_named_columns: Vec<NamedColumn>,
+        metadata: Metadata,
     ) -> Self {
         OptimizeContext {
             required_prop,
             _output_named_columns,
+            metadata,
         }
     }
 
-    pub fn create_with_bind_context(bind_context: &BindContext) -> Self {
+    pub fn create_with_bind_context(bind_context: &BindContext, metadata: Metadata) -> Self {
         let _output_named_columns: Vec<NamedColumn> = bind_context
             .all_column_bindings()
             .iter()
@@ -50,10 +55,15 @@ impl OptimizeContext {
         OptimizeContext {
             required_prop,
             _output_named_columns,
+            metadata,
         }
     }
 
     pub fn required_prop(&self) -> &RequiredProperty {
         &self.required_prop
     }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
 }
@@ -14,8 +14,17 @@
 
 use common_exception::Result;
 
+use crate::sql::optimizer::rule::rule_associate_join::RuleAssociateJoin;
+use crate::sql::optimizer::rule::rule_commute_join::RuleCommuteJoin;
+use crate::sql::optimizer::rule::rule_implement_aggregate::RuleImplementAggregate;
+use crate::sql::optimizer::rule::rule_implement_filter::RuleImplementFilter;
 use crate::sql::optimizer::rule::rule_implement_get::RuleImplementGet;
+use crate::sql::optimizer::rule::rule_implement_inner_join::RuleImplementInnerJoin;
 use crate::sql::optimizer::rule::rule_implement_project::RuleImplementProject;
+use crate::sql::optimizer::rule::rule_merge_filter::RuleMergeFilter;
+use crate::sql::optimizer::rule::rule_push_down_aggregate_join::RulePushDownAggregateJoin;
+use crate::sql::optimizer::rule::rule_push_down_filter_join::RulePushDownFilterJoin;
+use crate::sql::optimizer::rule::rule_push_down_filter_project::RulePushDownFilterProject;
 use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RulePtr;
 
@@ -30,6 +39,15 @@ impl RuleFactory {
         match id {
             RuleID::ImplementGet => Ok(Box::new(RuleImplementGet::create())),
             RuleID::ImplementProject => Ok(Box::new(RuleImplementProject::create())),
+            RuleID::ImplementFilter => Ok(Box::new(RuleImplementFilter::create())),
+            RuleID::ImplementInnerJoin => Ok(Box::new(RuleImplementInnerJoin::create())),
+            RuleID::ImplementAggregate => Ok(Box::new(RuleImplementAggregate::create())),
+            RuleID::PushDownFilterJoin => Ok(Box::new(RulePushDownFilterJoin::create())),
+            RuleID::PushDownFilterProject => Ok(Box::new(RulePushDownFilterProject::create())),
+            RuleID::MergeFilter => Ok(Box::new(RuleMergeFilter::create())),
+            RuleID::CommuteJoin => Ok(Box::new(RuleCommuteJoin::create())),
+            RuleID::AssociateJoin => Ok(Box::new(RuleAssociateJoin::create())),
+            RuleID::PushDownAggregateJoin => Ok(Box::new(RulePushDownAggregateJoin::create())),
         }
     }
 }
@@ -17,8 +17,17 @@ use common_exception::Result;
 use crate::sql::optimizer::SExpr;
 
 mod factory;
+mod rule_associate_join;
+mod rule_commute_join;
+mod rule_implement_aggregate;
+mod rule_implement_filter;
 mod rule_implement_get;
+mod rule_implement_inner_join;
 mod rule_implement_project;
+mod rule_merge_filter;
+mod rule_push_down_aggregate_join;
+mod rule_push_down_filter_join;
+mod rule_push_down_filter_project;
 mod rule_set;
 mod transform_state;
 
@@ -40,6 +49,15 @@ pub trait Rule {
 pub enum RuleID {
     ImplementGet,
     ImplementProject,
+    ImplementFilter,
+    ImplementInnerJoin,
+    ImplementAggregate,
+    PushDownFilterJoin,
+    PushDownFilterProject,
+    MergeFilter,
+    CommuteJoin,
+    AssociateJoin,
+    PushDownAggregateJoin,
 }
 
 impl RuleID {
@@ -47,6 +65,15 @@ impl RuleID {
         match self {
             RuleID::ImplementGet => "ImplementGet",
             RuleID::ImplementProject => "ImplementProject",
+            RuleID::ImplementFilter => "ImplementFilter",
+            RuleID::ImplementInnerJoin => "ImplementInnerJoin",
+            RuleID::ImplementAggregate => "ImplementAggregate",
+            RuleID::PushDownFilterJoin => "PushDownFilterJoin",
+            RuleID::PushDownFilterProject => "PushDownFilterProject",
+            RuleID::MergeFilter => "MergeFilter",
+            RuleID::CommuteJoin => "CommuteJoin",
+            RuleID::AssociateJoin => "AssociateJoin",
+            RuleID::PushDownAggregateJoin => "PushDownAggregateJoin",
         }
     }
 
@@ -57,6 +84,15 @@ impl RuleID {
         match self {
             RuleID::ImplementGet => 0,
             RuleID::ImplementProject => 1,
+            RuleID::ImplementFilter => 2,
+            RuleID::ImplementInnerJoin => 3,
+            RuleID::ImplementAggregate => 4,
+            RuleID::PushDownFilterJoin => 5,
+            RuleID::PushDownFilterProject => 6,
+            RuleID::MergeFilter => 7,
+            RuleID::CommuteJoin => 8,
+            RuleID::AssociateJoin => 9,
+            RuleID::PushDownAggregateJoin => 10,
         }
     }
 }
@@ -0,0 +1,126 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalInnerJoin;
+use crate::sql::Plan;
+
+/// Rotate a left-deep inner join: (A ⋈ B) ⋈ C => A ⋈ (B ⋈ C)
+///
+/// Together with `CommuteJoin`, all the join orders of inner joins can be enumerated. The rule
+/// doesn't fire if B and C cannot be joined with any condition, to avoid cross products.
+pub struct RuleAssociateJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleAssociateJoin {
+    pub fn create() -> Self {
+        RuleAssociateJoin {
+            id: RuleID::AssociateJoin,
+            pattern: SExpr::create_binary(
+                Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+                SExpr::create_binary(
+                    Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                ),
+                SExpr::create_leaf(Rc::new(Plan::Pattern)),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleAssociateJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let up_join = LogicalInnerJoin::from_plan((*expression.plan()).clone())?;
+        let down_join_expr = &expression.children()[0];
+        let down_join = LogicalInnerJoin::from_plan((*down_join_expr.plan()).clone())?;
+        let a = &down_join_expr.children()[0];
+        let b = &down_join_expr.children()[1];
+        let c = &expression.children()[1];
+
+        let a_columns = state.relational_prop(a).output_columns().clone();
+        let b_columns = state.relational_prop(b).output_columns().clone();
+        let c_columns = state.relational_prop(c).output_columns().clone();
+        let bc_columns: ColumnSet = b_columns.union(&c_columns).cloned().collect();
+
+        let conditions = up_join
+            .left_conditions
+            .into_iter()
+            .zip(up_join.right_conditions.into_iter())
+            .chain(
+                down_join
+                    .left_conditions
+                    .into_iter()
+                    .zip(down_join.right_conditions.into_iter()),
+            );
+
+        let mut new_up_join = LogicalInnerJoin::default();
+        let mut new_down_join = LogicalInnerJoin::default();
+        for (left, right) in conditions {
+            let left_used = left.used_columns();
+            let right_used = right.used_columns();
+            if left_used.is_subset(&b_columns) && right_used.is_subset(&c_columns) {
+                new_down_join.left_conditions.push(left);
+                new_down_join.right_conditions.push(right);
+            } else if left_used.is_subset(&c_columns) && right_used.is_subset(&b_columns) {
+                new_down_join.left_conditions.push(right);
+                new_down_join.right_conditions.push(left);
+            } else if left_used.is_subset(&a_columns) && right_used.is_subset(&bc_columns) {
+                new_up_join.left_conditions.push(left);
+                new_up_join.right_conditions.push(right);
+            } else if left_used.is_subset(&bc_columns) && right_used.is_subset(&a_columns) {
+                new_up_join.left_conditions.push(right);
+                new_up_join.right_conditions.push(left);
+            } else {
+                // The condition cannot be evaluated by either of the new joins
+                return Ok(());
+            }
+        }
+
+        if new_down_join.left_conditions.is_empty() {
+            return Ok(());
+        }
+
+        let result = SExpr::create_binary(
+            Rc::new(Plan::LogicalInnerJoin(new_up_join)),
+            a.clone(),
+            SExpr::create_binary(
+                Rc::new(Plan::LogicalInnerJoin(new_down_join)),
+                b.clone(),
+                c.clone(),
+            ),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalAggregate;
+use crate::sql::PhysicalAggregate;
+use crate::sql::Plan;
+
+pub struct RuleImplementAggregate {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementAggregate {
+    pub fn create() -> Self {
+        RuleImplementAggregate {
+            id: RuleID::ImplementAggregate,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalAggregate(LogicalAggregate::default())),
+                SExpr::create_leaf(Rc::new(Plan::Pattern)),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleImplementAggregate {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let aggregate = LogicalAggregate::from_plan((*expression.plan()).clone())?;
+        let physical =
+            PhysicalAggregate::create(aggregate.group_items, aggregate.aggregate_functions);
+        let result = SExpr::create(
+            Rc::new(Plan::PhysicalAggregate(physical)),
+            expression.children().clone(),
+            None,
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
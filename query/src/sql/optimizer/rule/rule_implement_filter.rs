@@ -0,0 +1,65 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalFilter;
+use crate::sql::PhysicalFilter;
+use crate::sql::Plan;
+
+pub struct RuleImplementFilter {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementFilter {
+    pub fn create() -> Self {
+        RuleImplementFilter {
+            id: RuleID::ImplementFilter,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::default())),
+                SExpr::create_leaf(Rc::new(Plan::Pattern)),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleImplementFilter {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let filter = LogicalFilter::from_plan((*expression.plan()).clone())?;
+        let physical = PhysicalFilter::create(filter.predicates);
+        let result = SExpr::create(
+            Rc::new(Plan::PhysicalFilter(physical)),
+            expression.children().clone(),
+            None,
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
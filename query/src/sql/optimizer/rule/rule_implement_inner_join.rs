@@ -0,0 +1,67 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalInnerJoin;
+use crate::sql::PhysicalHashJoin;
+use crate::sql::Plan;
+
+pub struct RuleImplementInnerJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementInnerJoin {
+    pub fn create() -> Self {
+        RuleImplementInnerJoin {
+            id: RuleID::ImplementInnerJoin,
+            pattern: SExpr::create_binary(
+                Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+                SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                SExpr::create_leaf(Rc::new(Plan::Pattern)),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleImplementInnerJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let join = LogicalInnerJoin::from_plan((*expression.plan()).clone())?;
+        // Build hash table with the right child, the alternative is generated by `CommuteJoin`.
+        let physical = PhysicalHashJoin::create(join.right_conditions, join.left_conditions);
+        let result = SExpr::create(
+            Rc::new(Plan::PhysicalHashJoin(physical)),
+            expression.children().clone(),
+            None,
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::LogicalFilter;
+use crate::sql::Plan;
+
+/// Merge adjacent filters into a single one.
+pub struct RuleMergeFilter {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleMergeFilter {
+    pub fn create() -> Self {
+        RuleMergeFilter {
+            id: RuleID::MergeFilter,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::default())),
+                SExpr::create_unary(
+                    Rc::new(Plan::LogicalFilter(LogicalFilter::default())),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleMergeFilter {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let up_filter = LogicalFilter::from_plan((*expression.plan()).clone())?;
+        let down_filter_expr = &expression.children()[0];
+        let down_filter = LogicalFilter::from_plan((*down_filter_expr.plan()).clone())?;
+
+        let predicates = down_filter
+            .predicates
+            .into_iter()
+            .chain(up_filter.predicates.into_iter())
+            .collect();
+        let result = SExpr::create_unary(
+            Rc::new(Plan::LogicalFilter(LogicalFilter::create(predicates))),
+            down_filter_expr.children()[0].clone(),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
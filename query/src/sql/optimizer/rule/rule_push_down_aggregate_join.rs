@@ -0,0 +1,179 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::SExpr;
+use crate::sql::AggregateFunction;
+use crate::sql::BoundVariable;
+use crate::sql::LogicalAggregate;
+use crate::sql::LogicalInnerJoin;
+use crate::sql::Plan;
+use crate::sql::ProjectItem;
+use crate::sql::ScalarExpr;
+
+/// Eager aggregation, pre-aggregate the left child of an inner join before joining:
+///
+/// Aggregate(G, F(x))                     Aggregate(G, F'(p))
+///  \                                      \
+///   Join(l = r)              =>            Join(l = r)
+///   |   \                                  |   \
+///   L    R                                 |    R
+///                                          Aggregate(G ∩ L ∪ {l}, p = F(x))
+///                                           \
+///                                            L
+///
+/// It's applicable if all the aggregate functions are decomposable (`sum`, `count`, `min` and
+/// `max`) and only reference columns of L, and the aggregation has group items. Without group
+/// items an empty join still produces one row, where `count` must be 0 instead of the NULL
+/// `sum` of no partial counts. The partial results reuse the column indices of the aggregate
+/// functions, so an aggregate function reading its own output is recognized as the final phase
+/// and won't be pushed down again.
+pub struct RulePushDownAggregateJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RulePushDownAggregateJoin {
+    pub fn create() -> Self {
+        RulePushDownAggregateJoin {
+            id: RuleID::PushDownAggregateJoin,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalAggregate(LogicalAggregate::default())),
+                SExpr::create_binary(
+                    Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RulePushDownAggregateJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let aggregate = LogicalAggregate::from_plan((*expression.plan()).clone())?;
+        let join_expr = &expression.children()[0];
+        let join = LogicalInnerJoin::from_plan((*join_expr.plan()).clone())?;
+        let left = &join_expr.children()[0];
+        let right = &join_expr.children()[1];
+        let left_columns = state.relational_prop(left).output_columns().clone();
+        let right_columns = state.relational_prop(right).output_columns().clone();
+        let aggregate_columns: ColumnSet = aggregate
+            .aggregate_functions
+            .iter()
+            .map(|item| item.index)
+            .collect();
+
+        if aggregate.aggregate_functions.is_empty() || aggregate.group_items.is_empty() {
+            return Ok(());
+        }
+
+        let mut final_functions = Vec::with_capacity(aggregate.aggregate_functions.len());
+        for item in aggregate.aggregate_functions.iter() {
+            let func = match &item.expr {
+                ScalarExpr::AggregateFunction(func) => func,
+                _ => return Ok(()),
+            };
+            let used_columns = func.used_columns();
+            if func.distinct
+                || !used_columns.is_subset(&left_columns)
+                || !used_columns.is_disjoint(&aggregate_columns)
+            {
+                return Ok(());
+            }
+            let final_func_name = match func.func_name.to_lowercase().as_str() {
+                "sum" | "count" => "sum",
+                "min" => "min",
+                "max" => "max",
+                _ => return Ok(()),
+            };
+            final_functions.push(ProjectItem {
+                index: item.index,
+                expr: ScalarExpr::AggregateFunction(AggregateFunction {
+                    func_name: final_func_name.to_string(),
+                    distinct: false,
+                    args: vec![ScalarExpr::BoundVariable(BoundVariable {
+                        index: item.index,
+                        data_type: func.data_type.clone(),
+                        nullable: func.nullable,
+                    })],
+                    data_type: func.data_type.clone(),
+                    nullable: func.nullable,
+                }),
+            });
+        }
+
+        // Group by the group items from the left side and the join keys of the left side, these
+        // columns are passed through the partial aggregation.
+        let mut partial_group_items: Vec<ProjectItem> = vec![];
+        for scalar in aggregate
+            .group_items
+            .iter()
+            .map(|item| &item.expr)
+            .chain(join.left_conditions.iter())
+        {
+            let used_columns = scalar.used_columns();
+            if used_columns.is_subset(&right_columns) {
+                continue;
+            }
+            match scalar {
+                ScalarExpr::BoundVariable(variable) if left_columns.contains(&variable.index) => {
+                    if !partial_group_items
+                        .iter()
+                        .any(|item| item.index == variable.index)
+                    {
+                        partial_group_items.push(ProjectItem {
+                            index: variable.index,
+                            expr: scalar.clone(),
+                        });
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+
+        let partial_aggregate =
+            LogicalAggregate::create(partial_group_items, aggregate.aggregate_functions.clone());
+        let final_aggregate = LogicalAggregate::create(aggregate.group_items, final_functions);
+        let result = SExpr::create_unary(
+            Rc::new(Plan::LogicalAggregate(final_aggregate)),
+            SExpr::create_binary(
+                Rc::new(Plan::LogicalInnerJoin(join)),
+                SExpr::create_unary(
+                    Rc::new(Plan::LogicalAggregate(partial_aggregate)),
+                    left.clone(),
+                ),
+                right.clone(),
+            ),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
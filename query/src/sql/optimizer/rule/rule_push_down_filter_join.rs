@@ -0,0 +1,158 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::SExpr;
+use crate::sql::AndExpr;
+use crate::sql::ComparisonExpr;
+use crate::sql::ComparisonOp;
+use crate::sql::LogicalFilter;
+use crate::sql::LogicalInnerJoin;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+
+/// Push the conjuncts of a filter through an inner join:
+/// - predicates only referencing one side are pushed down to that side
+/// - equalities between both sides become join conditions
+/// - the others stay above the join
+pub struct RulePushDownFilterJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RulePushDownFilterJoin {
+    pub fn create() -> Self {
+        RulePushDownFilterJoin {
+            id: RuleID::PushDownFilterJoin,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::default())),
+                SExpr::create_binary(
+                    Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RulePushDownFilterJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let filter = LogicalFilter::from_plan((*expression.plan()).clone())?;
+        let join_expr = &expression.children()[0];
+        let mut join = LogicalInnerJoin::from_plan((*join_expr.plan()).clone())?;
+        let left = &join_expr.children()[0];
+        let right = &join_expr.children()[1];
+        let left_prop = state.relational_prop(left);
+        let right_prop = state.relational_prop(right);
+
+        let predicates: Vec<ScalarExpr> = filter
+            .predicates
+            .iter()
+            .flat_map(AndExpr::split_conjunctions)
+            .collect();
+        let predicate_count = predicates.len();
+
+        let mut left_predicates = vec![];
+        let mut right_predicates = vec![];
+        let mut remaining_predicates = vec![];
+        for predicate in predicates {
+            let used_columns = predicate.used_columns();
+            if used_columns.is_subset(left_prop.output_columns()) {
+                left_predicates.push(predicate);
+            } else if used_columns.is_subset(right_prop.output_columns()) {
+                right_predicates.push(predicate);
+            } else if let Some((left_condition, right_condition)) =
+                split_equi_condition(&predicate, &left_prop, &right_prop)
+            {
+                join.left_conditions.push(left_condition);
+                join.right_conditions.push(right_condition);
+            } else {
+                remaining_predicates.push(predicate);
+            }
+        }
+
+        if remaining_predicates.len() == predicate_count {
+            // Nothing can be pushed down
+            return Ok(());
+        }
+
+        let left_child = build_filter(left_predicates, left.clone());
+        let right_child = build_filter(right_predicates, right.clone());
+        let join_expr = SExpr::create_binary(
+            Rc::new(Plan::LogicalInnerJoin(join)),
+            left_child,
+            right_child,
+        );
+        state.add_result(build_filter(remaining_predicates, join_expr));
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
+
+fn build_filter(predicates: Vec<ScalarExpr>, child: SExpr) -> SExpr {
+    if predicates.is_empty() {
+        child
+    } else {
+        SExpr::create_unary(
+            Rc::new(Plan::LogicalFilter(LogicalFilter::create(predicates))),
+            child,
+        )
+    }
+}
+
+/// Split `l = r` into a pair of join conditions if `l` and `r` come from different sides.
+fn split_equi_condition(
+    predicate: &ScalarExpr,
+    left_prop: &RelationalProperty,
+    right_prop: &RelationalProperty,
+) -> Option<(ScalarExpr, ScalarExpr)> {
+    match predicate {
+        ScalarExpr::ComparisonExpr(ComparisonExpr {
+            op: ComparisonOp::Equal,
+            left,
+            right,
+        }) => {
+            let left_used = left.used_columns();
+            let right_used = right.used_columns();
+            if left_used.is_subset(left_prop.output_columns())
+                && right_used.is_subset(right_prop.output_columns())
+            {
+                Some((*left.clone(), *right.clone()))
+            } else if left_used.is_subset(right_prop.output_columns())
+                && right_used.is_subset(left_prop.output_columns())
+            {
+                Some((*right.clone(), *left.clone()))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
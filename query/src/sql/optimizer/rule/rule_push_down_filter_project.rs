@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::rc::Rc;
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::AndExpr;
+use crate::sql::LogicalFilter;
+use crate::sql::LogicalProject;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+
+/// Push the predicates of a filter which only reference the input of a projection below the
+/// projection.
+pub struct RulePushDownFilterProject {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RulePushDownFilterProject {
+    pub fn create() -> Self {
+        RulePushDownFilterProject {
+            id: RuleID::PushDownFilterProject,
+            pattern: SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::default())),
+                SExpr::create_unary(
+                    Rc::new(Plan::LogicalProject(LogicalProject::default())),
+                    SExpr::create_leaf(Rc::new(Plan::Pattern)),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RulePushDownFilterProject {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let filter = LogicalFilter::from_plan((*expression.plan()).clone())?;
+        let project_expr = &expression.children()[0];
+        let input = &project_expr.children()[0];
+        let input_prop = state.relational_prop(input);
+
+        let (pushed_predicates, remaining_predicates): (Vec<ScalarExpr>, Vec<ScalarExpr>) = filter
+            .predicates
+            .iter()
+            .flat_map(AndExpr::split_conjunctions)
+            .partition(|predicate| {
+                predicate
+                    .used_columns()
+                    .is_subset(input_prop.output_columns())
+            });
+        if pushed_predicates.is_empty() {
+            return Ok(());
+        }
+
+        let mut result = SExpr::create_unary(
+            project_expr.plan(),
+            SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::create(
+                    pushed_predicates,
+                ))),
+                input.clone(),
+            ),
+        );
+        if !remaining_predicates.is_empty() {
+            result = SExpr::create_unary(
+                Rc::new(Plan::LogicalFilter(LogicalFilter::create(
+                    remaining_predicates,
+                ))),
+                result,
+            );
+        }
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -12,15 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::sql::optimizer::memo::Memo;
+use crate::sql::optimizer::property::RelationalProperty;
 use crate::sql::optimizer::s_expr::SExpr;
 
-pub struct TransformState {
+pub struct TransformState<'a> {
     results: Vec<SExpr>,
+
+    /// The `Memo` which the transformed expression is extracted from, None if the expression is
+    /// a standalone `SExpr`.
+    memo: Option<&'a Memo>,
 }
 
-impl TransformState {
+impl<'a> TransformState<'a> {
     pub fn create() -> Self {
-        TransformState { results: vec![] }
+        TransformState {
+            results: vec![],
+            memo: None,
+        }
+    }
+
+    pub fn create_with_memo(memo: &'a Memo) -> Self {
+        TransformState {
+            results: vec![],
+            memo: Some(memo),
+        }
     }
 
     pub fn add_result(&mut self, result: SExpr) {
@@ -30,4 +46,24 @@ impl TransformState {
     pub fn results(&self) -> &Vec<SExpr> {
         &self.results
     }
+
+    /// Relational property of an expression, leaves extracted from `Memo` take the property of
+    /// their original groups.
+    pub fn relational_prop(&self, expression: &SExpr) -> RelationalProperty {
+        if let (Some(memo), Some(group_index)) = (self.memo, expression.original_group()) {
+            if let Some(prop) = memo.group(group_index).relational_prop() {
+                return prop.clone();
+            }
+        }
+
+        let children: Vec<RelationalProperty> = expression
+            .children()
+            .iter()
+            .map(|child| self.relational_prop(child))
+            .collect();
+        expression
+            .plan()
+            .compute_relational_prop(&children)
+            .unwrap_or_default()
+    }
 }
@@ -73,7 +73,7 @@ impl SExpr {
     pub fn match_pattern(&self, pattern: &SExpr) -> bool {
         if !pattern.plan().kind_eq(&Plan::Pattern) {
             // Pattern is plan
-            if self.plan().kind_eq(&pattern.plan()) {
+            if !self.plan().kind_eq(&pattern.plan()) {
                 return false;
             }
 
@@ -95,7 +95,12 @@ impl SExpr {
 
     pub fn compute_relational_prop(&self) -> RelationalProperty {
         if self.plan.is_logical() {
-            self.plan.compute_relational_prop(self).unwrap()
+            let children: Vec<RelationalProperty> = self
+                .children
+                .iter()
+                .map(|child| child.compute_relational_prop())
+                .collect();
+            self.plan.compute_relational_prop(&children).unwrap()
         } else {
             RelationalProperty::default()
         }
@@ -25,6 +25,7 @@ use common_planners::SelectPlan;
 use crate::sessions::QueryContext;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::JoinRelation;
 use crate::sql::statements::QueryAnalyzeState;
 use crate::sql::statements::QueryRelation;
 use crate::sql::DfHint;
@@ -76,7 +77,8 @@ impl PlanParser {
         let group_by = Self::build_group_by_plan(filter, data)?;
         let before_order = Self::build_before_order(group_by, data)?;
         let having = Self::build_having_plan(before_order, data)?;
-        let order_by = Self::build_order_by_plan(having, data)?;
+        let window = Self::build_window_plan(having, data)?;
+        let order_by = Self::build_order_by_plan(window, data)?;
         let projection = Self::build_projection_plan(order_by, data)?;
         let limit = Self::build_limit_plan(projection, data)?;
 
@@ -86,10 +88,34 @@ impl PlanParser {
     }
 
     fn build_from_plan(data: &QueryAnalyzeState) -> Result<PlanNode> {
-        match &data.relation {
+        Self::build_relation_plan(&data.relation)
+    }
+
+    fn build_relation_plan(relation: &QueryRelation) -> Result<PlanNode> {
+        match relation {
             QueryRelation::None => Err(ErrorCode::LogicalError("Not from in select query")),
             QueryRelation::Nested(data) => Self::build_query_plan(data),
             QueryRelation::FromTable(plan) => Ok(PlanNode::ReadSource(plan.as_ref().clone())),
+            QueryRelation::Join(join) => Self::build_join_plan(join),
+        }
+    }
+
+    fn build_join_plan(join: &JoinRelation) -> Result<PlanNode> {
+        let left = Self::build_join_side_plan(&join.left, &join.left_projection)?;
+        let right = Self::build_join_side_plan(&join.right, &join.right_projection)?;
+        PlanBuilder::from(&left)
+            .join(&right, join.join_type, join.condition.clone())?
+            .build()
+    }
+
+    fn build_join_side_plan(
+        relation: &QueryRelation,
+        projection: &Option<Vec<Expression>>,
+    ) -> Result<PlanNode> {
+        let plan = Self::build_relation_plan(relation)?;
+        match projection {
+            None => Ok(plan),
+            Some(exprs) => PlanBuilder::from(&plan).project(exprs)?.build(),
         }
     }
 
@@ -171,6 +197,28 @@ impl PlanParser {
         }
     }
 
+    fn build_window_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
+        fn is_all_column(exprs: &[Expression]) -> bool {
+            exprs
+                .iter()
+                .all(|expr| matches!(expr, Expression::Column(_)))
+        }
+
+        let mut builder = PlanBuilder::from(&plan);
+        for window_expression in &data.window_expressions {
+            builder = builder.window_func(window_expression.clone())?;
+        }
+
+        match data.after_window_expressions.is_empty() {
+            true => builder.build(),
+            // if all expression is column expression expression, we skip this expression
+            false if is_all_column(&data.after_window_expressions) => builder.build(),
+            false => builder
+                .expression(&data.after_window_expressions, "After Window")?
+                .build(),
+        }
+    }
+
     fn build_order_by_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
         match data.order_by_expressions.is_empty() {
             true => Ok(plan),
@@ -120,7 +120,10 @@ impl Binder {
                     table.as_str(),
                 )
                 .await?;
-                let table_index = self.metadata.add_base_table(database, table_meta.clone());
+                let statistics = table_meta.statistics(self.context.clone()).await?;
+                let table_index =
+                    self.metadata
+                        .add_base_table(database, table_meta.clone(), statistics);
 
                 for field in table_meta.schema().fields() {
                     self.metadata.add_column(
@@ -18,6 +18,7 @@ use common_datavalues2::prelude::*;
 
 use crate::sql::common::IndexType;
 use crate::storages::Table;
+use crate::storages::TableStatistics;
 
 #[derive(Clone)]
 pub struct TableEntry {
@@ -26,6 +27,7 @@ pub struct TableEntry {
     pub database: String,
 
     pub table: Arc<dyn Table>,
+    pub statistics: Option<TableStatistics>,
 }
 
 impl TableEntry {
@@ -34,12 +36,14 @@ impl TableEntry {
         name: String,
         database: String,
         table_meta: Arc<dyn Table>,
+        statistics: Option<TableStatistics>,
     ) -> Self {
         TableEntry {
             index,
             name,
             database,
             table: table_meta,
+            statistics,
         }
     }
 }
@@ -151,7 +155,12 @@ impl Metadata {
         column_index
     }
 
-    pub fn add_base_table(&mut self, database: String, table_meta: Arc<dyn Table>) -> IndexType {
+    pub fn add_base_table(
+        &mut self,
+        database: String,
+        table_meta: Arc<dyn Table>,
+        statistics: Option<TableStatistics>,
+    ) -> IndexType {
         let table_name = table_meta.name().to_string();
         let table_index = self.next_table_index();
         let table_entry = TableEntry {
@@ -159,6 +168,7 @@ impl Metadata {
             name: table_name,
             database,
             table: table_meta,
+            statistics,
         };
         self.tables.push(table_entry);
         table_index
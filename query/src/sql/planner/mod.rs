@@ -58,7 +58,10 @@ impl Planner {
         let bind_result = binder.bind(&stmts[0]).await?;
 
         // Step 3: optimize the SExpr with optimizers, and generate optimized physical SExpr
-        let optimize_context = OptimizeContext::create_with_bind_context(&bind_result.bind_context);
+        let optimize_context = OptimizeContext::create_with_bind_context(
+            &bind_result.bind_context,
+            bind_result.metadata.clone(),
+        );
         let optimized_expr = optimize(bind_result.s_expr().clone(), optimize_context)?;
 
         // Step 4: build executable Pipeline with SExpr
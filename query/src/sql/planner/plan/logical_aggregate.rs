@@ -0,0 +1,61 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::LogicalPlan;
+use crate::sql::Plan;
+use crate::sql::ProjectItem;
+
+/// Group the input by `group_items` and evaluate `aggregate_functions` for each group.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct LogicalAggregate {
+    pub group_items: Vec<ProjectItem>,
+    pub aggregate_functions: Vec<ProjectItem>,
+}
+
+impl LogicalAggregate {
+    pub fn create(group_items: Vec<ProjectItem>, aggregate_functions: Vec<ProjectItem>) -> Self {
+        LogicalAggregate {
+            group_items,
+            aggregate_functions,
+        }
+    }
+
+    pub fn from_plan(plan: Plan) -> Result<Self> {
+        match plan {
+            Plan::LogicalAggregate(aggregate) => Ok(aggregate),
+            _ => Err(ErrorCode::LogicalError("Invalid downcast")),
+        }
+    }
+}
+
+impl LogicalPlan for LogicalAggregate {
+    fn compute_relational_prop(&self, _children: &[RelationalProperty]) -> RelationalProperty {
+        let output_columns: ColumnSet = self
+            .group_items
+            .iter()
+            .chain(self.aggregate_functions.iter())
+            .map(|item| item.index)
+            .collect();
+        RelationalProperty::create(output_columns)
+    }
+
+    fn as_plan(&self) -> Plan {
+        Plan::LogicalAggregate(self.clone())
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::LogicalPlan;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+
+/// Filter the input with a conjunction of `predicates`.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct LogicalFilter {
+    pub predicates: Vec<ScalarExpr>,
+}
+
+impl LogicalFilter {
+    pub fn create(predicates: Vec<ScalarExpr>) -> Self {
+        LogicalFilter { predicates }
+    }
+
+    pub fn from_plan(plan: Plan) -> Result<Self> {
+        match plan {
+            Plan::LogicalFilter(filter) => Ok(filter),
+            _ => Err(ErrorCode::LogicalError("Invalid downcast")),
+        }
+    }
+}
+
+impl LogicalPlan for LogicalFilter {
+    fn compute_relational_prop(&self, children: &[RelationalProperty]) -> RelationalProperty {
+        children[0].clone()
+    }
+
+    fn as_plan(&self) -> Plan {
+        Plan::LogicalFilter(self.clone())
+    }
+}
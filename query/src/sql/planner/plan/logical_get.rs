@@ -17,7 +17,6 @@ use common_exception::Result;
 
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::RelationalProperty;
-use crate::sql::optimizer::SExpr;
 use crate::sql::IndexType;
 use crate::sql::LogicalPlan;
 use crate::sql::Plan;
@@ -38,7 +37,7 @@ impl LogicalGet {
 }
 
 impl LogicalPlan for LogicalGet {
-    fn compute_relational_prop(&self, _expression: &SExpr) -> RelationalProperty {
+    fn compute_relational_prop(&self, _children: &[RelationalProperty]) -> RelationalProperty {
         let output_columns: ColumnSet = self.columns.iter().cloned().collect();
         RelationalProperty::create(output_columns)
     }
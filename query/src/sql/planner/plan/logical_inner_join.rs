@@ -0,0 +1,69 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::LogicalPlan;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+
+/// Inner equi-join, the i-th condition of the left child equals to the i-th condition of the
+/// right child. A join without any condition is a cross join.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct LogicalInnerJoin {
+    pub left_conditions: Vec<ScalarExpr>,
+    pub right_conditions: Vec<ScalarExpr>,
+}
+
+impl LogicalInnerJoin {
+    pub fn create(left_conditions: Vec<ScalarExpr>, right_conditions: Vec<ScalarExpr>) -> Self {
+        LogicalInnerJoin {
+            left_conditions,
+            right_conditions,
+        }
+    }
+
+    pub fn from_plan(plan: Plan) -> Result<Self> {
+        match plan {
+            Plan::LogicalInnerJoin(join) => Ok(join),
+            _ => Err(ErrorCode::LogicalError("Invalid downcast")),
+        }
+    }
+
+    /// Join with the children swapped.
+    pub fn commute(&self) -> Self {
+        LogicalInnerJoin {
+            left_conditions: self.right_conditions.clone(),
+            right_conditions: self.left_conditions.clone(),
+        }
+    }
+}
+
+impl LogicalPlan for LogicalInnerJoin {
+    fn compute_relational_prop(&self, children: &[RelationalProperty]) -> RelationalProperty {
+        let output_columns: ColumnSet = children[0]
+            .output_columns()
+            .union(children[1].output_columns())
+            .cloned()
+            .collect();
+        RelationalProperty::create(output_columns)
+    }
+
+    fn as_plan(&self) -> Plan {
+        Plan::LogicalInnerJoin(self.clone())
+    }
+}
@@ -12,14 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod logical_aggregate;
+mod logical_filter;
 mod logical_get;
+mod logical_inner_join;
 mod logical_project;
+mod physical_aggregate;
+mod physical_filter;
+mod physical_hash_join;
 mod physical_project;
 mod physical_scan;
 
+pub use logical_aggregate::LogicalAggregate;
+pub use logical_filter::LogicalFilter;
 pub use logical_get::LogicalGet;
+pub use logical_inner_join::LogicalInnerJoin;
 pub use logical_project::LogicalProject;
 pub use logical_project::ProjectItem;
+pub use physical_aggregate::PhysicalAggregate;
+pub use physical_filter::PhysicalFilter;
+pub use physical_hash_join::PhysicalHashJoin;
 pub use physical_project::PhysicalProject;
 pub use physical_scan::PhysicalScan;
 
@@ -29,7 +41,8 @@ use crate::sql::optimizer::RequiredProperty;
 use crate::sql::optimizer::SExpr;
 
 pub trait LogicalPlan {
-    fn compute_relational_prop(&self, expression: &SExpr) -> RelationalProperty;
+    /// Derive the relational property with the relational properties of the children.
+    fn compute_relational_prop(&self, children: &[RelationalProperty]) -> RelationalProperty;
 
     fn as_plan(&self) -> Plan;
 }
@@ -48,10 +61,16 @@ pub enum Plan {
     // Logical operators
     LogicalGet(LogicalGet),
     LogicalProject(LogicalProject),
+    LogicalFilter(LogicalFilter),
+    LogicalInnerJoin(LogicalInnerJoin),
+    LogicalAggregate(LogicalAggregate),
 
     // Physical operators
     PhysicalScan(PhysicalScan),
     PhysicalProject(PhysicalProject),
+    PhysicalFilter(PhysicalFilter),
+    PhysicalHashJoin(PhysicalHashJoin),
+    PhysicalAggregate(PhysicalAggregate),
 
     // Pattern
     Pattern,
@@ -64,17 +83,34 @@ impl Plan {
     }
 
     pub fn is_logical(&self) -> bool {
-        matches!(self, Plan::LogicalGet(_) | Plan::LogicalProject(_))
+        matches!(
+            self,
+            Plan::LogicalGet(_)
+                | Plan::LogicalProject(_)
+                | Plan::LogicalFilter(_)
+                | Plan::LogicalInnerJoin(_)
+                | Plan::LogicalAggregate(_)
+        )
     }
 
     pub fn is_physical(&self) -> bool {
-        matches!(self, Plan::PhysicalScan(_) | Plan::PhysicalProject(_))
+        matches!(
+            self,
+            Plan::PhysicalScan(_)
+                | Plan::PhysicalProject(_)
+                | Plan::PhysicalFilter(_)
+                | Plan::PhysicalHashJoin(_)
+                | Plan::PhysicalAggregate(_)
+        )
     }
 
     pub fn as_logical_plan(&self) -> Option<&dyn LogicalPlan> {
         match self {
             Plan::LogicalGet(plan) => Some(plan),
             Plan::LogicalProject(plan) => Some(plan),
+            Plan::LogicalFilter(plan) => Some(plan),
+            Plan::LogicalInnerJoin(plan) => Some(plan),
+            Plan::LogicalAggregate(plan) => Some(plan),
             _ => None,
         }
     }
@@ -83,12 +119,18 @@ impl Plan {
         match self {
             Plan::PhysicalScan(plan) => Some(plan),
             Plan::PhysicalProject(plan) => Some(plan),
+            Plan::PhysicalFilter(plan) => Some(plan),
+            Plan::PhysicalHashJoin(plan) => Some(plan),
+            Plan::PhysicalAggregate(plan) => Some(plan),
             _ => None,
         }
     }
 
-    pub fn compute_relational_prop(&self, expression: &SExpr) -> Option<RelationalProperty> {
+    pub fn compute_relational_prop(
+        &self,
+        children: &[RelationalProperty],
+    ) -> Option<RelationalProperty> {
         self.as_logical_plan()
-            .map(|plan| plan.compute_relational_prop(expression))
+            .map(|plan| plan.compute_relational_prop(children))
     }
 }
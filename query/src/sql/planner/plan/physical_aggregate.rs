@@ -0,0 +1,60 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::optimizer::SExpr;
+use crate::sql::PhysicalPlan;
+use crate::sql::Plan;
+use crate::sql::ProjectItem;
+
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct PhysicalAggregate {
+    pub group_items: Vec<ProjectItem>,
+    pub aggregate_functions: Vec<ProjectItem>,
+}
+
+impl PhysicalAggregate {
+    pub fn create(group_items: Vec<ProjectItem>, aggregate_functions: Vec<ProjectItem>) -> Self {
+        PhysicalAggregate {
+            group_items,
+            aggregate_functions,
+        }
+    }
+}
+
+impl PhysicalPlan for PhysicalAggregate {
+    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
+        PhysicalProperty::default()
+    }
+
+    /// Columns produced by the aggregation itself are not required from the input.
+    fn compute_required_prop(&self, _input_prop: &RequiredProperty) -> RequiredProperty {
+        let mut required_columns = ColumnSet::new();
+        for item in self
+            .group_items
+            .iter()
+            .chain(self.aggregate_functions.iter())
+        {
+            let used_columns = item.expr.used_columns();
+            required_columns = required_columns.union(&used_columns).cloned().collect();
+        }
+        RequiredProperty::create(required_columns)
+    }
+
+    fn as_plan(&self) -> Plan {
+        Plan::PhysicalAggregate(self.clone())
+    }
+}
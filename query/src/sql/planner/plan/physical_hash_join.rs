@@ -0,0 +1,56 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::optimizer::SExpr;
+use crate::sql::PhysicalPlan;
+use crate::sql::Plan;
+use crate::sql::ScalarExpr;
+
+/// Hash join which builds the hash table with the right child and probes it with the left child.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct PhysicalHashJoin {
+    pub build_keys: Vec<ScalarExpr>,
+    pub probe_keys: Vec<ScalarExpr>,
+}
+
+impl PhysicalHashJoin {
+    pub fn create(build_keys: Vec<ScalarExpr>, probe_keys: Vec<ScalarExpr>) -> Self {
+        PhysicalHashJoin {
+            build_keys,
+            probe_keys,
+        }
+    }
+}
+
+impl PhysicalPlan for PhysicalHashJoin {
+    fn compute_physical_prop(&self, _expression: &SExpr) -> PhysicalProperty {
+        PhysicalProperty::default()
+    }
+
+    fn compute_required_prop(&self, input_prop: &RequiredProperty) -> RequiredProperty {
+        let mut required_columns: ColumnSet = input_prop.required_columns().clone();
+        for key in self.build_keys.iter().chain(self.probe_keys.iter()) {
+            let used_columns = key.used_columns();
+            required_columns = required_columns.union(&used_columns).cloned().collect();
+        }
+        RequiredProperty::create(required_columns)
+    }
+
+    fn as_plan(&self) -> Plan {
+        Plan::PhysicalHashJoin(self.clone())
+    }
+}
@@ -22,24 +22,41 @@ use crate::sql::IndexType;
 #[derive(Clone, Debug, PartialEq)]
 pub enum ScalarExpr {
     BoundVariable(BoundVariable),
+    ConstantExpr(ConstantExpr),
+    ComparisonExpr(ComparisonExpr),
+    AndExpr(AndExpr),
+    AggregateFunction(AggregateFunction),
 }
 
 impl ScalarExpr {
     pub fn data_type(&self) -> Result<DataTypePtr> {
         match self {
             ScalarExpr::BoundVariable(BoundVariable { data_type, .. }) => Ok(data_type.clone()),
+            ScalarExpr::ConstantExpr(ConstantExpr { data_type, .. }) => Ok(data_type.clone()),
+            ScalarExpr::ComparisonExpr(_) | ScalarExpr::AndExpr(_) => Ok(BooleanType::arc()),
+            ScalarExpr::AggregateFunction(AggregateFunction { data_type, .. }) => {
+                Ok(data_type.clone())
+            }
         }
     }
 
     pub fn nullable(&self) -> bool {
         match self {
             ScalarExpr::BoundVariable(BoundVariable { nullable, .. }) => *nullable,
+            ScalarExpr::ConstantExpr(ConstantExpr { value, .. }) => value.is_null(),
+            ScalarExpr::ComparisonExpr(ComparisonExpr { left, right, .. })
+            | ScalarExpr::AndExpr(AndExpr { left, right }) => left.nullable() || right.nullable(),
+            ScalarExpr::AggregateFunction(AggregateFunction { nullable, .. }) => *nullable,
         }
     }
 
     pub fn used_columns(&self) -> ColumnSet {
         match self {
             ScalarExpr::BoundVariable(scalar) => scalar.used_columns(),
+            ScalarExpr::ConstantExpr(_) => ColumnSet::new(),
+            ScalarExpr::ComparisonExpr(scalar) => scalar.used_columns(),
+            ScalarExpr::AndExpr(scalar) => scalar.used_columns(),
+            ScalarExpr::AggregateFunction(scalar) => scalar.used_columns(),
         }
     }
 }
@@ -56,3 +73,105 @@ impl BoundVariable {
         ColumnSet::from([self.index])
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstantExpr {
+    pub value: DataValue,
+    pub data_type: DataTypePtr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Equal,
+    NotEqual,
+    GT,
+    GTE,
+    LT,
+    LTE,
+}
+
+impl ComparisonOp {
+    pub fn to_func_name(&self) -> &'static str {
+        match self {
+            ComparisonOp::Equal => "=",
+            ComparisonOp::NotEqual => "<>",
+            ComparisonOp::GT => ">",
+            ComparisonOp::GTE => ">=",
+            ComparisonOp::LT => "<",
+            ComparisonOp::LTE => "<=",
+        }
+    }
+
+    /// The operator to use after swapping the operands, e.g. `a < b` is `b > a`.
+    pub fn reverse(&self) -> Self {
+        match self {
+            ComparisonOp::Equal => ComparisonOp::Equal,
+            ComparisonOp::NotEqual => ComparisonOp::NotEqual,
+            ComparisonOp::GT => ComparisonOp::LT,
+            ComparisonOp::GTE => ComparisonOp::LTE,
+            ComparisonOp::LT => ComparisonOp::GT,
+            ComparisonOp::LTE => ComparisonOp::GTE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonExpr {
+    pub op: ComparisonOp,
+    pub left: Box<ScalarExpr>,
+    pub right: Box<ScalarExpr>,
+}
+
+impl ComparisonExpr {
+    pub fn used_columns(&self) -> ColumnSet {
+        let left = self.left.used_columns();
+        let right = self.right.used_columns();
+        left.union(&right).cloned().collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AndExpr {
+    pub left: Box<ScalarExpr>,
+    pub right: Box<ScalarExpr>,
+}
+
+impl AndExpr {
+    pub fn used_columns(&self) -> ColumnSet {
+        let left = self.left.used_columns();
+        let right = self.right.used_columns();
+        left.union(&right).cloned().collect()
+    }
+
+    /// Split a conjunction into its conjuncts, e.g. `a AND (b AND c)` into `[a, b, c]`.
+    pub fn split_conjunctions(scalar: &ScalarExpr) -> Vec<ScalarExpr> {
+        match scalar {
+            ScalarExpr::AndExpr(AndExpr { left, right }) => {
+                let mut result = Self::split_conjunctions(left);
+                result.extend(Self::split_conjunctions(right));
+                result
+            }
+            _ => vec![scalar.clone()],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregateFunction {
+    pub func_name: String,
+    pub distinct: bool,
+    pub args: Vec<ScalarExpr>,
+
+    pub data_type: DataTypePtr,
+    pub nullable: bool,
+}
+
+impl AggregateFunction {
+    pub fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for arg in self.args.iter() {
+            result = result.union(&arg.used_columns()).cloned().collect();
+        }
+        result
+    }
+}
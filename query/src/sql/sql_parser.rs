@@ -17,6 +17,7 @@
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::time::Duration;
 use std::time::Instant;
 
 use common_exception::ErrorCode;
@@ -33,6 +34,7 @@ use metrics::histogram;
 use serde::Deserialize;
 use sqlparser::ast::BinaryOperator;
 use sqlparser::ast::ColumnDef;
+use sqlparser::ast::ColumnOption;
 use sqlparser::ast::ColumnOptionDef;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
@@ -52,20 +54,26 @@ use sqlparser::tokenizer::Word;
 
 use super::statements::DfCopy;
 use super::statements::DfDescribeStage;
+use crate::sql::statements::DfAlterTable;
+use crate::sql::statements::DfAlterTableAction;
 use crate::sql::statements::DfAlterUDF;
 use crate::sql::statements::DfAlterUser;
+use crate::sql::statements::DfAnalyzeTable;
 use crate::sql::statements::DfAuthOption;
 use crate::sql::statements::DfCreateDatabase;
 use crate::sql::statements::DfCreateStage;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfCreateUDF;
 use crate::sql::statements::DfCreateUser;
+use crate::sql::statements::DfCreateView;
+use crate::sql::statements::DfDeleteStatement;
 use crate::sql::statements::DfDescribeTable;
 use crate::sql::statements::DfDropDatabase;
 use crate::sql::statements::DfDropStage;
 use crate::sql::statements::DfDropTable;
 use crate::sql::statements::DfDropUDF;
 use crate::sql::statements::DfDropUser;
+use crate::sql::statements::DfDropView;
 use crate::sql::statements::DfExplain;
 use crate::sql::statements::DfGrantObject;
 use crate::sql::statements::DfGrantStatement;
@@ -73,6 +81,7 @@ use crate::sql::statements::DfInsertStatement;
 use crate::sql::statements::DfKillStatement;
 use crate::sql::statements::DfOptimizeTable;
 use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::DfRenameTable;
 use crate::sql::statements::DfRevokeStatement;
 use crate::sql::statements::DfSetVariable;
 use crate::sql::statements::DfShowCreateDatabase;
@@ -87,10 +96,12 @@ use crate::sql::statements::DfShowSettings;
 use crate::sql::statements::DfShowTables;
 use crate::sql::statements::DfShowUsers;
 use crate::sql::statements::DfTruncateTable;
+use crate::sql::statements::DfUpdateStatement;
 use crate::sql::statements::DfUseDatabase;
 use crate::sql::statements::DfUseTenant;
 use crate::sql::DfHint;
 use crate::sql::DfStatement;
+use crate::storages::fuse::MIN_ORPHAN_RETENTION_SECS;
 
 // Use `Parser::expected` instead, if possible
 macro_rules! parser_err {
@@ -114,7 +125,7 @@ impl<'a> DfParser<'a> {
     /// Parse the specified tokens with dialect
     pub fn new_with_dialect(sql: &str, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = Self::rewrite_time_travel(tokenizer.tokenize()?);
 
         Ok(DfParser {
             parser: Parser::new(tokens, dialect),
@@ -234,8 +245,14 @@ impl<'a> DfParser<'a> {
                         }
                     }
                     Keyword::TRUNCATE => self.parse_truncate(),
+                    Keyword::RENAME => {
+                        self.parser.next_token();
+                        self.parse_rename()
+                    }
                     Keyword::SET => self.parse_set(),
                     Keyword::INSERT => self.parse_insert(),
+                    Keyword::DELETE => self.parse_delete(),
+                    Keyword::UPDATE => self.parse_update(),
                     Keyword::SELECT | Keyword::WITH | Keyword::VALUES => self.parse_query(),
                     Keyword::GRANT => {
                         self.parser.next_token();
@@ -249,6 +266,7 @@ impl<'a> DfParser<'a> {
                         self.parser.next_token();
                         self.parse_copy()
                     }
+                    _ if w.value.to_uppercase() == "ANALYZE" => self.parse_analyze(),
                     Keyword::NoKeyword => match w.value.to_uppercase().as_str() {
                         // Use database
                         "USE" => self.parse_use_database(),
@@ -321,6 +339,41 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_delete(&mut self) -> Result<DfStatement, ParserError> {
+        // syntax: "DELETE FROM t [WHERE expr]"
+        self.parser.next_token();
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let name = self.parser.parse_object_name()?;
+        let selection = match self.parser.parse_keyword(Keyword::WHERE) {
+            true => Some(self.parser.parse_expr()?),
+            false => None,
+        };
+
+        Ok(DfStatement::Delete(DfDeleteStatement { name, selection }))
+    }
+
+    fn parse_update(&mut self) -> Result<DfStatement, ParserError> {
+        // syntax: "UPDATE t SET col = expr [, col = expr ...] [WHERE expr]"
+        self.parser.next_token();
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::SET)?;
+        let assignments = self.parser.parse_comma_separated(|parser| {
+            let column = parser.parse_identifier()?;
+            parser.expect_token(&Token::Eq)?;
+            Ok((column, parser.parse_expr()?))
+        })?;
+        let selection = match self.parser.parse_keyword(Keyword::WHERE) {
+            true => Some(self.parser.parse_expr()?),
+            false => None,
+        };
+
+        Ok(DfStatement::Update(DfUpdateStatement {
+            name,
+            assignments,
+            selection,
+        }))
+    }
+
     /// Parse an SQL EXPLAIN statement.
     pub fn parse_explain(&mut self) -> Result<DfStatement, ParserError> {
         // Parser is at the token immediately after EXPLAIN
@@ -521,6 +574,9 @@ impl<'a> DfParser<'a> {
                 }
             } else if let Some(option) = self.parser.parse_optional_column_option()? {
                 options.push(ColumnOptionDef { name: None, option });
+            } else if self.consume_token("CODEC") {
+                let option = self.parse_column_codec()?;
+                options.push(ColumnOptionDef { name: None, option });
             } else {
                 break;
             };
@@ -533,6 +589,23 @@ impl<'a> DfParser<'a> {
         })
     }
 
+    // syntax: "CODEC(codec [, codec ...])", the keyword CODEC is already consumed.
+    fn parse_column_codec(&mut self) -> Result<ColumnOption, ParserError> {
+        self.parser.expect_token(&Token::LParen)?;
+        let mut tokens = vec![Token::make_word("CODEC", None), Token::LParen];
+        loop {
+            let codec = self.parser.parse_identifier()?;
+            tokens.push(Token::make_word(&codec.value, None));
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+            tokens.push(Token::Comma);
+        }
+        self.parser.expect_token(&Token::RParen)?;
+        tokens.push(Token::RParen);
+        Ok(ColumnOption::DialectSpecific(tokens))
+    }
+
     fn parse_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => {
@@ -542,6 +615,11 @@ impl<'a> DfParser<'a> {
                 } else {
                     match w.keyword {
                         Keyword::TABLE => self.parse_create_table(),
+                        Keyword::VIEW => self.parse_create_view(false),
+                        Keyword::MATERIALIZED => {
+                            self.parser.expect_keyword(Keyword::VIEW)?;
+                            self.parse_create_view(true)
+                        }
                         Keyword::DATABASE => self.parse_create_database(),
                         Keyword::USER => self.parse_create_user(),
                         Keyword::FUNCTION => self.parse_create_udf(),
@@ -558,7 +636,8 @@ impl<'a> DfParser<'a> {
             Token::Word(w) => match w.keyword {
                 Keyword::USER => self.parse_alter_user(),
                 Keyword::FUNCTION => self.parse_alter_udf(),
-                _ => self.expected("keyword USER or FUNCTION", Token::Word(w)),
+                Keyword::TABLE => self.parse_alter_table(),
+                _ => self.expected("keyword USER, FUNCTION or TABLE", Token::Word(w)),
             },
             unexpected => self.expected("alter statement", unexpected),
         }
@@ -605,6 +684,7 @@ impl<'a> DfParser<'a> {
                     match w.keyword {
                         Keyword::DATABASE => self.parse_drop_database(),
                         Keyword::TABLE => self.parse_drop_table(),
+                        Keyword::VIEW => self.parse_drop_view(),
                         Keyword::USER => self.parse_drop_user(),
                         Keyword::FUNCTION => self.parse_drop_udf(),
                         _ => self.expected("drop statement", Token::Word(w)),
@@ -641,6 +721,76 @@ impl<'a> DfParser<'a> {
         Ok(DfStatement::DropTable(drop))
     }
 
+    /// Create view or materialized view.
+    fn parse_create_view(&mut self, materialized: bool) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::AS)?;
+        let query = Box::new(self.parser.parse_query()?);
+
+        let create = DfCreateView {
+            if_not_exists,
+            materialized,
+            name,
+            query,
+        };
+
+        Ok(DfStatement::CreateView(create))
+    }
+
+    /// Drop view.
+    fn parse_drop_view(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::DropView(DfDropView { if_exists, name }))
+    }
+
+    fn parse_alter_table(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let table_name = self.parser.parse_object_name()?;
+
+        let action = if self.parser.parse_keyword(Keyword::ADD) {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            DfAlterTableAction::AddColumn(self.parse_column_def()?)
+        } else if self.parser.parse_keyword(Keyword::DROP) {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            DfAlterTableAction::DropColumn(self.parser.parse_identifier()?)
+        } else if self.parser.parse_keyword(Keyword::RENAME) {
+            if self.parser.parse_keyword(Keyword::COLUMN) {
+                let old_name = self.parser.parse_identifier()?;
+                self.parser.expect_keyword(Keyword::TO)?;
+                let new_name = self.parser.parse_identifier()?;
+                DfAlterTableAction::RenameColumn { old_name, new_name }
+            } else {
+                self.parser.expect_keyword(Keyword::TO)?;
+                DfAlterTableAction::RenameTable(self.parser.parse_object_name()?)
+            }
+        } else {
+            return self.expected("ADD, DROP or RENAME", self.parser.peek_token());
+        };
+
+        let alter = DfAlterTable {
+            if_exists,
+            name: table_name,
+            action,
+        };
+
+        Ok(DfStatement::AlterTable(alter))
+    }
+
+    /// Rename table.
+    fn parse_rename(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::TO)?;
+        let new_name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::RenameTable(DfRenameTable { name, new_name }))
+    }
+
     // Parse 'sudo ...'.
     fn parse_sudo_command(&mut self) -> Result<DfStatement, ParserError> {
         self.parser.next_token();
@@ -1032,6 +1182,7 @@ impl<'a> DfParser<'a> {
         }
 
         let engine = self.parse_table_engine()?;
+        let cluster_keys = self.parse_cluster_keys()?;
 
         // parse table options: https://dev.mysql.com/doc/refman/8.0/en/create-table.html
         let options = self.parse_options()?;
@@ -1054,6 +1205,7 @@ impl<'a> DfParser<'a> {
             name: table_name,
             columns,
             engine,
+            cluster_keys,
             options,
             like: table_like,
             query,
@@ -1091,6 +1243,19 @@ impl<'a> DfParser<'a> {
         Ok(self.parser.next_token().to_string())
     }
 
+    // syntax: "CLUSTER BY (expr [, expr ...])"
+    fn parse_cluster_keys(&mut self) -> Result<Vec<Expr>, ParserError> {
+        if !self.consume_token("CLUSTER") {
+            return Ok(vec![]);
+        }
+
+        self.parser.expect_keyword(Keyword::BY)?;
+        self.parser.expect_token(&Token::LParen)?;
+        let cluster_keys = self.parser.parse_comma_separated(Parser::parse_expr)?;
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(cluster_keys)
+    }
+
     fn parse_show_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
@@ -1322,7 +1487,8 @@ impl<'a> DfParser<'a> {
     }
 
     fn parse_optimize(&mut self) -> Result<DfStatement, ParserError> {
-        // syntax: "optimize TABLE t [purge | compact | all]",  default action is "purge"
+        // syntax: "optimize TABLE t [purge [orphans [older than n <unit>]] | compact | all]",
+        // default action is "purge"
         self.expect_token("OPTIMIZE")?;
         self.parser.expect_keyword(Keyword::TABLE)?;
         let object_name = self.parser.parse_object_name()?;
@@ -1330,6 +1496,7 @@ impl<'a> DfParser<'a> {
             Token::EOF => Ok(Optimization::PURGE),
             Token::Word(w) => match w.keyword {
                 Keyword::ALL => Ok(Optimization::ALL),
+                Keyword::PURGE if self.consume_token("ORPHANS") => Ok(Optimization::PURGE_ORPHANS),
                 Keyword::PURGE => Ok(Optimization::PURGE),
                 Keyword::NoKeyword if w.value.to_uppercase().as_str() == "COMPACT" => {
                     Ok(Optimization::COMPACT)
@@ -1339,12 +1506,167 @@ impl<'a> DfParser<'a> {
             t => self.expected("Nothing, or one of PURGE, COMPACT, ALL", t),
         }?;
 
+        let orphans_older_than =
+            if operation == Optimization::PURGE_ORPHANS && self.consume_token("OLDER") {
+                self.expect_token("THAN")?;
+                let retention = self.parse_retention()?;
+                if retention.as_secs() < MIN_ORPHAN_RETENTION_SECS {
+                    return parser_err!(format!(
+                        "retention of orphans must be at least {} seconds, found: {} seconds",
+                        MIN_ORPHAN_RETENTION_SECS,
+                        retention.as_secs()
+                    ));
+                }
+                Some(retention)
+            } else {
+                None
+            };
+
         Ok(DfStatement::OptimizeTable(DfOptimizeTable {
             name: object_name,
             operation,
+            orphans_older_than,
         }))
     }
 
+    // syntax: "n {SECOND[S] | MINUTE[S] | HOUR[S] | DAY[S]}"
+    fn parse_retention(&mut self) -> Result<Duration, ParserError> {
+        let n = self.parser.parse_literal_uint()?;
+        let unit_secs = match self.parser.next_token() {
+            Token::Word(w) => match w.value.to_uppercase().trim_end_matches('S') {
+                "SECOND" => 1,
+                "MINUTE" => 60,
+                "HOUR" => 60 * 60,
+                "DAY" => 24 * 60 * 60,
+                _ => return self.expected("one of SECONDS, MINUTES, HOURS, DAYS", Token::Word(w)),
+            },
+            t => return self.expected("one of SECONDS, MINUTES, HOURS, DAYS", t),
+        };
+        match n.checked_mul(unit_secs) {
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => parser_err!(format!("retention {} is too large", n)),
+        }
+    }
+
+    fn parse_analyze(&mut self) -> Result<DfStatement, ParserError> {
+        // syntax: "ANALYZE TABLE t"
+        self.expect_token("ANALYZE")?;
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let object_name = self.parser.parse_object_name()?;
+        Ok(DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name: object_name,
+        }))
+    }
+
+    /// Rewrites the time travel clauses of table references, i.e. `AT (SNAPSHOT => '<id>')` and
+    /// `AT (TIMESTAMP => <expr>)`, into table hints `WITH (SNAPSHOT = '<id>')` which sqlparser
+    /// understands. The alias of the table, if any, is moved in front of the hints.
+    fn rewrite_time_travel(tokens: Vec<Token>) -> Vec<Token> {
+        let mut rewritten = Vec::with_capacity(tokens.len());
+        let mut idx = 0;
+        while idx < tokens.len() {
+            match Self::time_travel_hints(&tokens, idx) {
+                None => {
+                    rewritten.push(tokens[idx].clone());
+                    idx += 1;
+                }
+                Some((hints, clause_end)) => {
+                    let alias_end = Self::table_alias_end(&tokens, clause_end);
+                    rewritten.extend_from_slice(&tokens[clause_end..alias_end]);
+                    rewritten.push(Token::Whitespace(Whitespace::Space));
+                    rewritten.extend(hints);
+                    idx = alias_end;
+                }
+            }
+        }
+        rewritten
+    }
+
+    fn time_travel_hints(tokens: &[Token], start: usize) -> Option<(Vec<Token>, usize)> {
+        match &tokens[start] {
+            Token::Word(w) if w.quote_style.is_none() && w.value.eq_ignore_ascii_case("AT") => {}
+            _ => return None,
+        }
+
+        let lparen = Self::next_significant_token(tokens, start + 1)?;
+        if tokens[lparen] != Token::LParen {
+            return None;
+        }
+
+        let kind_idx = Self::next_significant_token(tokens, lparen + 1)?;
+        let kind = match &tokens[kind_idx] {
+            Token::Word(w)
+                if w.quote_style.is_none()
+                    && (w.value.eq_ignore_ascii_case("SNAPSHOT")
+                        || w.value.eq_ignore_ascii_case("TIMESTAMP")) =>
+            {
+                w.value.to_uppercase()
+            }
+            _ => return None,
+        };
+
+        let arrow = Self::next_significant_token(tokens, kind_idx + 1)?;
+        if tokens[arrow] != Token::RArrow {
+            return None;
+        }
+
+        let mut depth = 0;
+        let mut rparen = None;
+        for (idx, token) in tokens.iter().enumerate().skip(arrow + 1) {
+            match token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 0 => {
+                    rparen = Some(idx);
+                    break;
+                }
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+        }
+        let rparen = rparen?;
+
+        let mut hints = vec![
+            Token::make_keyword("WITH"),
+            Token::LParen,
+            Token::Word(Word {
+                value: kind,
+                quote_style: None,
+                keyword: Keyword::NoKeyword,
+            }),
+            Token::Eq,
+        ];
+        hints.extend_from_slice(&tokens[arrow + 1..rparen]);
+        hints.push(Token::RParen);
+        Some((hints, rparen + 1))
+    }
+
+    fn table_alias_end(tokens: &[Token], start: usize) -> usize {
+        let next = match Self::next_significant_token(tokens, start) {
+            None => return start,
+            Some(next) => next,
+        };
+
+        match &tokens[next] {
+            Token::Word(w) if w.keyword == Keyword::AS => {
+                match Self::next_significant_token(tokens, next + 1) {
+                    Some(alias) if matches!(tokens[alias], Token::Word(_)) => alias + 1,
+                    _ => start,
+                }
+            }
+            Token::Word(w) if w.keyword == Keyword::NoKeyword => next + 1,
+            _ => start,
+        }
+    }
+
+    fn next_significant_token(tokens: &[Token], start: usize) -> Option<usize> {
+        tokens
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, token)| !matches!(token, Token::Whitespace(_)))
+            .map(|(idx, _)| idx)
+    }
+
     fn consume_token(&mut self, expected: &str) -> bool {
         if self.parser.peek_token().to_string().to_uppercase() == *expected.to_uppercase() {
             self.parser.next_token();
@@ -21,25 +21,31 @@ use nom::IResult;
 
 use super::statements::DfCopy;
 use super::statements::DfDescribeStage;
+use crate::sql::statements::DfAlterTable;
 use crate::sql::statements::DfAlterUDF;
 use crate::sql::statements::DfAlterUser;
+use crate::sql::statements::DfAnalyzeTable;
 use crate::sql::statements::DfCreateDatabase;
 use crate::sql::statements::DfCreateStage;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfCreateUDF;
 use crate::sql::statements::DfCreateUser;
+use crate::sql::statements::DfCreateView;
+use crate::sql::statements::DfDeleteStatement;
 use crate::sql::statements::DfDescribeTable;
 use crate::sql::statements::DfDropDatabase;
 use crate::sql::statements::DfDropStage;
 use crate::sql::statements::DfDropTable;
 use crate::sql::statements::DfDropUDF;
 use crate::sql::statements::DfDropUser;
+use crate::sql::statements::DfDropView;
 use crate::sql::statements::DfExplain;
 use crate::sql::statements::DfGrantStatement;
 use crate::sql::statements::DfInsertStatement;
 use crate::sql::statements::DfKillStatement;
 use crate::sql::statements::DfOptimizeTable;
 use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::DfRenameTable;
 use crate::sql::statements::DfRevokeStatement;
 use crate::sql::statements::DfSetVariable;
 use crate::sql::statements::DfShowCreateDatabase;
@@ -54,6 +60,7 @@ use crate::sql::statements::DfShowSettings;
 use crate::sql::statements::DfShowTables;
 use crate::sql::statements::DfShowUsers;
 use crate::sql::statements::DfTruncateTable;
+use crate::sql::statements::DfUpdateStatement;
 use crate::sql::statements::DfUseDatabase;
 use crate::sql::statements::DfUseTenant;
 
@@ -79,8 +86,15 @@ pub enum DfStatement {
     DescribeTable(DfDescribeTable),
     DescribeStage(DfDescribeStage),
     DropTable(DfDropTable),
+    AlterTable(DfAlterTable),
+    RenameTable(DfRenameTable),
     TruncateTable(DfTruncateTable),
     OptimizeTable(DfOptimizeTable),
+    AnalyzeTable(DfAnalyzeTable),
+
+    // Views.
+    CreateView(DfCreateView),
+    DropView(DfDropView),
 
     // Settings.
     ShowSettings(DfShowSettings),
@@ -103,6 +117,10 @@ pub enum DfStatement {
     // Insert
     InsertQuery(DfInsertStatement),
 
+    // Delete and update
+    Delete(DfDeleteStatement),
+    Update(DfUpdateStatement),
+
     // User
     CreateUser(DfCreateUser),
     AlterUser(DfAlterUser),
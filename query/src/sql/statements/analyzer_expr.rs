@@ -27,6 +27,10 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_functions::is_builtin_function;
+use common_functions::windows::WindowFrame;
+use common_functions::windows::WindowFrameBound;
+use common_functions::windows::WindowFrameUnits;
+use common_functions::windows::WindowFunctionFactory;
 use common_planners::Expression;
 use sqlparser::ast::Expr;
 use sqlparser::ast::FunctionArgExpr;
@@ -34,6 +38,7 @@ use sqlparser::ast::Ident;
 use sqlparser::ast::Query;
 use sqlparser::ast::UnaryOperator;
 use sqlparser::ast::Value;
+use sqlparser::ast::WindowSpec;
 
 use crate::functions::ContextFunction;
 use crate::sessions::QueryContext;
@@ -125,21 +130,33 @@ impl ExpressionAnalyzer {
     }
 
     fn analyze_function(&self, info: &FunctionExprInfo, args: &mut Vec<Expression>) -> Result<()> {
-        let mut arguments = Vec::with_capacity(info.args_count);
-        for _ in 0..info.args_count {
-            match args.pop() {
-                None => {
-                    return Err(ErrorCode::LogicalError("It's a bug."));
-                }
-                Some(arg) => {
-                    arguments.insert(0, arg);
-                }
-            }
+        if let Some(window_info) = &info.over {
+            // The partition by and order by expressions are pushed after the arguments.
+            let order_by = Self::pop_arguments(args, window_info.order_by.len())?;
+            let partition_by = Self::pop_arguments(args, window_info.partition_by_count)?;
+            let arguments = Self::pop_arguments(args, info.args_count)?;
+
+            args.push(self.window_function(
+                info,
+                window_info,
+                arguments,
+                partition_by,
+                order_by,
+            )?);
+            return Ok(());
         }
 
+        let arguments = Self::pop_arguments(args, info.args_count)?;
+
         args.push(
             match AggregateFunctionFactory::instance().check(&info.name) {
                 true => self.aggr_function(info, &arguments),
+                false if WindowFunctionFactory::instance().check(&info.name) => {
+                    Err(ErrorCode::SyntaxException(format!(
+                        "Window function {} requires an OVER clause",
+                        info.name
+                    )))
+                }
                 false => match info.kind {
                     OperatorKind::Unary => Self::unary_function(info, &arguments),
                     OperatorKind::Binary => Self::binary_function(info, &arguments),
@@ -150,6 +167,22 @@ impl ExpressionAnalyzer {
         Ok(())
     }
 
+    fn pop_arguments(args: &mut Vec<Expression>, count: usize) -> Result<Vec<Expression>> {
+        let mut arguments = Vec::with_capacity(count);
+        for _ in 0..count {
+            match args.pop() {
+                None => {
+                    return Err(ErrorCode::LogicalError("It's a bug."));
+                }
+                Some(arg) => {
+                    arguments.insert(0, arg);
+                }
+            }
+        }
+
+        Ok(arguments)
+    }
+
     fn unary_function(info: &FunctionExprInfo, args: &[Expression]) -> Result<Expression> {
         match args.is_empty() {
             true => Err(ErrorCode::LogicalError("Unary operator must be one child.")),
@@ -198,7 +231,7 @@ impl ExpressionAnalyzer {
         }
     }
 
-    fn aggr_function(&self, info: &FunctionExprInfo, args: &[Expression]) -> Result<Expression> {
+    fn function_parameters(info: &FunctionExprInfo) -> Result<Vec<DataValue>> {
         let mut parameters = Vec::with_capacity(info.parameters.len());
 
         for parameter in &info.parameters {
@@ -215,6 +248,12 @@ impl ExpressionAnalyzer {
             };
         }
 
+        Ok(parameters)
+    }
+
+    fn aggr_function(&self, info: &FunctionExprInfo, args: &[Expression]) -> Result<Expression> {
+        let parameters = Self::function_parameters(info)?;
+
         if info.name.eq_ignore_ascii_case("count")
             && !args.is_empty()
             && matches!(args[0], Expression::Wildcard)
@@ -235,6 +274,58 @@ impl ExpressionAnalyzer {
         }
     }
 
+    fn window_function(
+        &self,
+        info: &FunctionExprInfo,
+        window_info: &WindowSpecInfo,
+        mut args: Vec<Expression>,
+        partition_by: Vec<Expression>,
+        order_by: Vec<Expression>,
+    ) -> Result<Expression> {
+        if !WindowFunctionFactory::instance().check(&info.name)
+            && !AggregateFunctionFactory::instance().check(&info.name)
+        {
+            return Err(ErrorCode::SyntaxException(format!(
+                "{} is not a window function or an aggregate function",
+                info.name
+            )));
+        }
+
+        if info.distinct {
+            return Err(ErrorCode::SyntaxException(format!(
+                "DISTINCT is not supported in window function {}",
+                info.name
+            )));
+        }
+
+        if info.name.eq_ignore_ascii_case("count")
+            && !args.is_empty()
+            && matches!(args[0], Expression::Wildcard)
+        {
+            args = vec![common_planners::lit(0i64)];
+        }
+
+        let order_by = order_by
+            .into_iter()
+            .zip(window_info.order_by.iter())
+            .map(|(expr, (asc, nulls_first))| Expression::Sort {
+                expr: Box::new(expr.clone()),
+                asc: *asc,
+                nulls_first: *nulls_first,
+                origin_expr: Box::new(expr),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Expression::WindowFunction {
+            op: info.name.clone(),
+            params: Self::function_parameters(info)?,
+            args,
+            partition_by,
+            order_by,
+            window_frame: window_info.window_frame,
+        })
+    }
+
     fn analyze_identifier(&self, ident: &Ident, arguments: &mut Vec<Expression>) -> Result<()> {
         let column_name = ident.clone().value;
         arguments.push(Expression::Column(column_name));
@@ -370,6 +461,64 @@ struct FunctionExprInfo {
     args_count: usize,
     kind: OperatorKind,
     parameters: Vec<Value>,
+    over: Option<WindowSpecInfo>,
+}
+
+struct WindowSpecInfo {
+    partition_by_count: usize,
+    // (asc, nulls_first) of each order by expression
+    order_by: Vec<(bool, bool)>,
+    window_frame: Option<WindowFrame>,
+}
+
+impl WindowSpecInfo {
+    fn try_create(window_spec: &WindowSpec) -> Result<WindowSpecInfo> {
+        let order_by = window_spec
+            .order_by
+            .iter()
+            .map(|order_by| {
+                let asc = order_by.asc.unwrap_or(true);
+                (asc, order_by.nulls_first.unwrap_or(asc))
+            })
+            .collect::<Vec<_>>();
+
+        let window_frame = match &window_spec.window_frame {
+            None => None,
+            Some(window_frame) => {
+                let units = match window_frame.units {
+                    sqlparser::ast::WindowFrameUnits::Rows => WindowFrameUnits::Rows,
+                    sqlparser::ast::WindowFrameUnits::Range => WindowFrameUnits::Range,
+                    sqlparser::ast::WindowFrameUnits::Groups => {
+                        return Err(ErrorCode::SyntaxException(
+                            "Unsupported window frame units: GROUPS",
+                        ));
+                    }
+                };
+
+                let start_bound = Self::frame_bound(&window_frame.start_bound);
+                let end_bound = match &window_frame.end_bound {
+                    None => WindowFrameBound::CurrentRow,
+                    Some(end_bound) => Self::frame_bound(end_bound),
+                };
+
+                Some(WindowFrame::create(units, start_bound, end_bound)?)
+            }
+        };
+
+        Ok(WindowSpecInfo {
+            partition_by_count: window_spec.partition_by.len(),
+            order_by,
+            window_frame,
+        })
+    }
+
+    fn frame_bound(bound: &sqlparser::ast::WindowFrameBound) -> WindowFrameBound {
+        match bound {
+            sqlparser::ast::WindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            sqlparser::ast::WindowFrameBound::Preceding(n) => WindowFrameBound::Preceding(*n),
+            sqlparser::ast::WindowFrameBound::Following(n) => WindowFrameBound::Following(*n),
+        }
+    }
 }
 
 struct InListInfo {
@@ -398,6 +547,7 @@ impl ExprRPNItem {
             args_count,
             kind: OperatorKind::Other,
             parameters: Vec::new(),
+            over: None,
         })
     }
 
@@ -408,6 +558,7 @@ impl ExprRPNItem {
             args_count: 2,
             kind: OperatorKind::Binary,
             parameters: Vec::new(),
+            over: None,
         })
     }
 
@@ -418,6 +569,7 @@ impl ExprRPNItem {
             args_count: 1,
             kind: OperatorKind::Unary,
             parameters: Vec::new(),
+            over: None,
         })
     }
 }
@@ -483,6 +635,10 @@ impl ExprRPNBuilder {
                     args_count: function.args.len(),
                     kind: OperatorKind::Other,
                     parameters: function.params.to_owned(),
+                    over: match &function.over {
+                        None => None,
+                        Some(window_spec) => Some(WindowSpecInfo::try_create(window_spec)?),
+                    },
                 }));
             }
             Expr::Cast { data_type, .. } => {
@@ -21,6 +21,7 @@ use common_datavalues2::DataSchemaRef;
 use common_exception::Result;
 use common_planners::ExplainType;
 use common_planners::Expression;
+use common_planners::JoinType;
 use common_planners::PlanNode;
 use common_planners::ReadDataSourcePlan;
 
@@ -39,6 +40,19 @@ pub enum QueryRelation {
     None,
     FromTable(Box<ReadDataSourcePlan>),
     Nested(Box<QueryAnalyzeState>),
+    Join(Box<JoinRelation>),
+}
+
+#[derive(Clone)]
+pub struct JoinRelation {
+    pub join_type: JoinType,
+    pub condition: Option<Expression>,
+    pub left: QueryRelation,
+    // Rename the columns of left relation to the names of joined schema.
+    pub left_projection: Option<Vec<Expression>>,
+    pub right: QueryRelation,
+    // Rename the columns of right relation to the names of joined schema.
+    pub right_projection: Option<Vec<Expression>>,
 }
 
 #[derive(Clone)]
@@ -54,6 +68,11 @@ pub struct QueryAnalyzeState {
     pub aggregate_expressions: Vec<Expression>,
     pub before_group_by_expressions: Vec<Expression>,
 
+    // window functions evaluated after the before projection(order by) expression plan
+    pub window_expressions: Vec<Expression>,
+    // expressions over the window function results, evaluated before projection or order by
+    pub after_window_expressions: Vec<Expression>,
+
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 
@@ -68,6 +87,18 @@ impl QueryAnalyzeState {
         }
     }
 
+    pub fn add_window_expression(&mut self, expr: &Expression) {
+        if !self.window_expressions.contains(expr) {
+            self.window_expressions.push(expr.clone());
+        }
+    }
+
+    pub fn add_after_window_expression(&mut self, expr: &Expression) {
+        if !self.after_window_expressions.contains(expr) {
+            self.after_window_expressions.push(expr.clone());
+        }
+    }
+
     pub fn add_before_group_expression(&mut self, expr: &Expression) {
         if !self.before_group_by_expressions.contains(expr) {
             self.before_group_by_expressions.push(expr.clone());
@@ -86,6 +117,8 @@ impl Default for QueryAnalyzeState {
             group_by_expressions: vec![],
             aggregate_expressions: vec![],
             before_group_by_expressions: vec![],
+            window_expressions: vec![],
+            after_window_expressions: vec![],
             limit: None,
             offset: None,
             relation: QueryRelation::None,
@@ -125,6 +158,14 @@ impl Debug for QueryAnalyzeState {
             debug_struct.field("having", predicate);
         }
 
+        if !self.window_expressions.is_empty() {
+            debug_struct.field("window", &self.window_expressions);
+        }
+
+        if !self.after_window_expressions.is_empty() {
+            debug_struct.field("after_window", &self.after_window_expressions);
+        }
+
         if !self.order_by_expressions.is_empty() {
             debug_struct.field("order_by", &self.order_by_expressions);
         }
@@ -156,8 +197,13 @@ impl AnalyzableStatement for DfStatement {
             DfStatement::DescribeTable(v) => v.analyze(ctx).await,
             DfStatement::DescribeStage(v) => v.analyze(ctx).await,
             DfStatement::DropTable(v) => v.analyze(ctx).await,
+            DfStatement::AlterTable(v) => v.analyze(ctx).await,
+            DfStatement::RenameTable(v) => v.analyze(ctx).await,
             DfStatement::TruncateTable(v) => v.analyze(ctx).await,
             DfStatement::OptimizeTable(v) => v.analyze(ctx).await,
+            DfStatement::AnalyzeTable(v) => v.analyze(ctx).await,
+            DfStatement::CreateView(v) => v.analyze(ctx).await,
+            DfStatement::DropView(v) => v.analyze(ctx).await,
             DfStatement::UseDatabase(v) => v.analyze(ctx).await,
             DfStatement::UseTenant(v) => v.analyze(ctx).await,
             DfStatement::ShowCreateTable(v) => v.analyze(ctx).await,
@@ -168,6 +214,8 @@ impl AnalyzableStatement for DfStatement {
             DfStatement::ShowGrants(v) => v.analyze(ctx).await,
             DfStatement::KillStatement(v) => v.analyze(ctx).await,
             DfStatement::InsertQuery(v) => v.analyze(ctx).await,
+            DfStatement::Delete(v) => v.analyze(ctx).await,
+            DfStatement::Update(v) => v.analyze(ctx).await,
             DfStatement::SetVariable(v) => v.analyze(ctx).await,
             DfStatement::CreateUser(v) => v.analyze(ctx).await,
             DfStatement::AlterUser(v) => v.analyze(ctx).await,
@@ -17,14 +17,18 @@ pub mod query;
 mod analyzer_expr;
 mod analyzer_statement;
 mod analyzer_value_expr;
+mod statement_alter_table;
 mod statement_alter_udf;
 mod statement_alter_user;
+mod statement_analyze_table;
 mod statement_copy;
 mod statement_create_database;
 mod statement_create_stage;
 mod statement_create_table;
 mod statement_create_udf;
 mod statement_create_user;
+mod statement_create_view;
+mod statement_delete;
 mod statement_describe_stage;
 mod statement_describe_table;
 mod statement_drop_database;
@@ -32,11 +36,13 @@ mod statement_drop_stage;
 mod statement_drop_table;
 mod statement_drop_udf;
 mod statement_drop_user;
+mod statement_drop_view;
 mod statement_explain;
 mod statement_grant;
 mod statement_insert;
 mod statement_kill;
 mod statement_optimize_table;
+mod statement_rename_table;
 mod statement_revoke;
 mod statement_select;
 mod statement_select_convert;
@@ -53,16 +59,21 @@ mod statement_show_settings;
 mod statement_show_tables;
 mod statement_show_users;
 mod statement_truncate_table;
+mod statement_update;
 mod statement_use_database;
 mod statement_use_tenant;
 
 pub use analyzer_statement::AnalyzableStatement;
 pub use analyzer_statement::AnalyzedResult;
+pub use analyzer_statement::JoinRelation;
 pub use analyzer_statement::QueryAnalyzeState;
 pub use analyzer_statement::QueryRelation;
 pub use query::QueryASTIR;
+pub use statement_alter_table::DfAlterTable;
+pub use statement_alter_table::DfAlterTableAction;
 pub use statement_alter_udf::DfAlterUDF;
 pub use statement_alter_user::DfAlterUser;
+pub use statement_analyze_table::DfAnalyzeTable;
 pub use statement_copy::DfCopy;
 pub use statement_create_database::DfCreateDatabase;
 pub use statement_create_stage::DfCreateStage;
@@ -70,6 +81,8 @@ pub use statement_create_table::DfCreateTable;
 pub use statement_create_udf::DfCreateUDF;
 pub use statement_create_user::DfAuthOption;
 pub use statement_create_user::DfCreateUser;
+pub use statement_create_view::DfCreateView;
+pub use statement_delete::DfDeleteStatement;
 pub use statement_describe_stage::DfDescribeStage;
 pub use statement_describe_table::DfDescribeTable;
 pub use statement_drop_database::DfDropDatabase;
@@ -77,12 +90,14 @@ pub use statement_drop_stage::DfDropStage;
 pub use statement_drop_table::DfDropTable;
 pub use statement_drop_udf::DfDropUDF;
 pub use statement_drop_user::DfDropUser;
+pub use statement_drop_view::DfDropView;
 pub use statement_explain::DfExplain;
 pub use statement_grant::DfGrantObject;
 pub use statement_grant::DfGrantStatement;
 pub use statement_insert::DfInsertStatement;
 pub use statement_kill::DfKillStatement;
 pub use statement_optimize_table::DfOptimizeTable;
+pub use statement_rename_table::DfRenameTable;
 pub use statement_revoke::DfRevokeStatement;
 pub use statement_select::DfQueryStatement;
 pub use statement_set_variable::DfSetVariable;
@@ -98,5 +113,6 @@ pub use statement_show_settings::DfShowSettings;
 pub use statement_show_tables::DfShowTables;
 pub use statement_show_users::DfShowUsers;
 pub use statement_truncate_table::DfTruncateTable;
+pub use statement_update::DfUpdateStatement;
 pub use statement_use_database::DfUseDatabase;
 pub use statement_use_tenant::DfUseTenant;
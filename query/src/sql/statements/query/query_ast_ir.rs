@@ -24,6 +24,7 @@ pub struct QueryASTIR {
     pub group_by_expressions: Vec<Expression>,
     pub having_predicate: Option<Expression>,
     pub aggregate_expressions: Vec<Expression>,
+    pub window_expressions: Vec<Expression>,
     pub order_by_expressions: Vec<Expression>,
     pub projection_expressions: Vec<Expression>,
     pub limit: Option<usize>,
@@ -43,6 +44,7 @@ pub trait QueryASTIRVisitor<Data> {
         Self::visit_group_by(&mut ir.group_by_expressions, data)?;
         Self::visit_order_by(&mut ir.order_by_expressions, data)?;
         Self::visit_aggregates(&mut ir.aggregate_expressions, data)?;
+        Self::visit_windows(&mut ir.window_expressions, data)?;
         Self::visit_projection(&mut ir.projection_expressions, data)?;
         Ok(())
     }
@@ -73,6 +75,18 @@ pub trait QueryASTIRVisitor<Data> {
 
                 Ok(())
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for arg in args.iter_mut().chain(partition_by).chain(order_by) {
+                    Self::visit_recursive_expr(arg, data)?;
+                }
+
+                Ok(())
+            }
             Expression::Sort {
                 expr, origin_expr, ..
             } => {
@@ -108,6 +122,14 @@ pub trait QueryASTIRVisitor<Data> {
         Ok(())
     }
 
+    fn visit_windows(exprs: &mut Vec<Expression>, data: &mut Data) -> Result<()> {
+        for expr in exprs {
+            Self::visit_recursive_expr(expr, data)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_order_by(exprs: &mut Vec<Expression>, data: &mut Data) -> Result<()> {
         for expr in exprs {
             Self::visit_recursive_expr(expr, data)?;
@@ -145,6 +167,10 @@ impl Debug for QueryASTIR {
             debug_struct.field("aggregate", &self.aggregate_expressions);
         }
 
+        if !self.window_expressions.is_empty() {
+            debug_struct.field("window", &self.window_expressions);
+        }
+
         if !self.order_by_expressions.is_empty() {
             debug_struct.field("order by", &self.order_by_expressions);
         }
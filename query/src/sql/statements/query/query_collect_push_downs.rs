@@ -17,8 +17,10 @@ use std::collections::HashSet;
 use common_exception::Result;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::RequireColumnsVisitor;
 
 use crate::sql::statements::query::query_ast_ir::QueryASTIRVisitor;
+use crate::sql::statements::query::JoinedColumnDesc;
 use crate::sql::statements::query::JoinedSchema;
 use crate::sql::statements::query::JoinedTableDesc;
 use crate::sql::statements::QueryASTIR;
@@ -55,17 +57,40 @@ impl QueryCollectPushDowns {
             require_filters: vec![],
         };
         QueryCollectPushDowns::visit(ir, &mut push_downs_data)?;
+
+        for mut condition in schema.get_join_conditions() {
+            Self::visit_recursive_expr(&mut condition, &mut push_downs_data)?;
+        }
+
         push_downs_data.collect_push_downs(schema)
     }
 
     fn collect_push_downs(mut self, schema: &mut JoinedSchema) -> Result<()> {
-        for index in 0..schema.get_tables_desc().len() {
+        let tables_num = schema.get_tables_desc().len();
+        let nullable_tables = schema.get_nullable_tables();
+        let conjunctions = match tables_num {
+            1 => vec![],
+            _ => self.collect_conjunctions(),
+        };
+
+        for index in 0..tables_num {
             let table_desc = &schema.get_tables_desc()[index];
-            let projection = self.collect_table_require_columns(table_desc);
+            let filters = match tables_num {
+                1 => self.require_filters.clone(),
+                // The filters of the tables which may be filled with NULL can't be pushed down.
+                _ if nullable_tables.contains(&index) => vec![],
+                _ => Self::collect_table_filters(table_desc, &conjunctions)?,
+            };
+
+            let mut projection = self.collect_table_require_columns(table_desc);
+            if projection.is_empty() {
+                // Rows are still required for join, e.g. SELECT t1.a FROM t1, t2.
+                projection = Self::collect_table_smallest_column(table_desc);
+            }
 
             schema.set_table_push_downs(index, Extras {
                 projection: Some(projection),
-                filters: self.require_filters.clone(),
+                filters,
                 limit: None,
                 order_by: vec![],
             });
@@ -74,6 +99,60 @@ impl QueryCollectPushDowns {
         Ok(())
     }
 
+    fn collect_conjunctions(&self) -> Vec<Expression> {
+        fn split(expr: &Expression, res: &mut Vec<Expression>) {
+            match expr {
+                Expression::BinaryExpression { left, op, right } if op.to_lowercase() == "and" => {
+                    split(left, res);
+                    split(right, res);
+                }
+                _ => res.push(expr.clone()),
+            }
+        }
+
+        let mut conjunctions = vec![];
+        for filter in &self.require_filters {
+            split(filter, &mut conjunctions);
+        }
+        conjunctions
+    }
+
+    // Only the conjunctions which refer to the table columns can be pushed down to the table.
+    fn collect_table_filters(
+        table_desc: &JoinedTableDesc,
+        conjunctions: &[Expression],
+    ) -> Result<Vec<Expression>> {
+        let table_columns = table_desc
+            .get_columns_desc()
+            .iter()
+            .map(|column_desc| Self::column_name(table_desc, column_desc))
+            .collect::<HashSet<_>>();
+
+        let mut filters: Option<Expression> = None;
+        for conjunction in conjunctions {
+            let columns = RequireColumnsVisitor::collect_columns_from_expr(conjunction)?;
+            if !columns.is_empty() && columns.is_subset(&table_columns) {
+                filters = Some(match filters {
+                    None => conjunction.clone(),
+                    Some(filters) => filters.and(conjunction.clone()),
+                });
+            }
+        }
+
+        Ok(filters.into_iter().collect())
+    }
+
+    fn column_name(table_desc: &JoinedTableDesc, column_desc: &JoinedColumnDesc) -> String {
+        match column_desc.is_ambiguity {
+            true => format!(
+                "{}.{}",
+                table_desc.get_name_parts().join("."),
+                column_desc.short_name
+            ),
+            false => column_desc.short_name.clone(),
+        }
+    }
+
     fn collect_table_require_columns(&mut self, table_desc: &JoinedTableDesc) -> Vec<usize> {
         match self.require_columns.is_empty() {
             true => Self::collect_table_smallest_column(table_desc),
@@ -102,14 +181,7 @@ impl QueryCollectPushDowns {
         let mut table_require_columns = Vec::new();
         let columns_desc = table_desc.get_columns_desc();
         for (column_index, column_desc) in columns_desc.iter().enumerate() {
-            let column_name = match column_desc.is_ambiguity {
-                true => format!(
-                    "{}.{}",
-                    table_desc.get_name_parts().join("."),
-                    column_desc.short_name
-                ),
-                false => column_desc.short_name.clone(),
-            };
+            let column_name = Self::column_name(table_desc, column_desc);
 
             if self.require_columns.remove(&column_name) {
                 // Require this column.
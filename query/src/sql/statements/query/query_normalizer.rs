@@ -19,6 +19,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::extract_aliases;
 use common_planners::find_aggregate_exprs_in_expr;
+use common_planners::find_window_exprs_in_expr;
 use common_planners::resolve_aliases_to_exprs;
 use common_planners::Expression;
 use sqlparser::ast::Expr;
@@ -36,7 +37,7 @@ pub struct QueryNormalizer {
     aliases_map: HashMap<String, Expression>,
 }
 
-/// Replace alias in query and collect aggregate functions
+/// Replace alias in query and collect aggregate functions and window functions
 impl QueryNormalizer {
     fn create(ctx: Arc<QueryContext>) -> QueryNormalizer {
         QueryNormalizer {
@@ -47,6 +48,7 @@ impl QueryNormalizer {
                 group_by_expressions: vec![],
                 having_predicate: None,
                 aggregate_expressions: vec![],
+                window_expressions: vec![],
                 order_by_expressions: vec![],
                 projection_expressions: vec![],
                 limit: None,
@@ -103,6 +105,7 @@ impl QueryNormalizer {
 
         for projection_expression in &projection_expressions {
             self.add_aggregate_function(projection_expression)?;
+            self.add_window_function(projection_expression)?;
         }
 
         self.query_ast_ir.projection_expressions = projection_expressions;
@@ -133,6 +136,7 @@ impl QueryNormalizer {
             let expression = self.resolve_aliases(&order_by_expr.expr).await?;
 
             self.add_aggregate_function(&expression)?;
+            self.add_window_function(&expression)?;
             self.query_ast_ir
                 .order_by_expressions
                 .push(Expression::Sort {
@@ -226,4 +230,14 @@ impl QueryNormalizer {
 
         Ok(())
     }
+
+    fn add_window_function(&mut self, expr: &Expression) -> Result<()> {
+        for window_expr in find_window_exprs_in_expr(expr) {
+            if !self.query_ast_ir.window_expressions.contains(&window_expr) {
+                self.query_ast_ir.window_expressions.push(window_expr);
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -82,6 +82,17 @@ impl QualifiedRewriter {
         QualifiedRewriter::visit(ir, &mut rewriter)
     }
 
+    pub fn rewrite_join_conditions(
+        schema: &mut JoinedSchema,
+        ctx: Arc<QueryContext>,
+    ) -> Result<()> {
+        let mut rewriter = QualifiedRewriter {
+            tables_schema: schema.clone(),
+            ctx,
+        };
+        schema.rewrite_join_conditions(|expr| Self::visit_recursive_expr(expr, &mut rewriter))
+    }
+
     fn expand_wildcard(&self, columns_expression: &mut Vec<Expression>) {
         for table_desc in self.tables_schema.get_tables_desc() {
             for column_desc in table_desc.get_columns_desc() {
@@ -165,6 +176,11 @@ impl QualifiedRewriter {
         let current_database = self.ctx.get_current_database();
         for table_desc in self.tables_schema.get_tables_desc() {
             let name_parts = table_desc.get_name_parts();
+            if name_parts.is_empty() {
+                // Table function or subquery without alias can't be referenced by name.
+                continue;
+            }
+
             if Self::first_diff_pos(ref_names, name_parts) == name_parts.len() {
                 // alias.column or database.table.column
                 return Some((name_parts.len(), table_desc.clone()));
@@ -14,6 +14,7 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
@@ -21,7 +22,9 @@ use std::sync::Arc;
 use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::JoinType;
 
 use crate::sql::statements::QueryAnalyzeState;
 use crate::storages::Table;
@@ -32,6 +35,91 @@ pub struct JoinedSchema {
     short_name_columns: HashMap<String, JoinedColumnDesc>,
     // Reference by full name, short name may be ambiguous.
     tables_long_name_columns: Vec<JoinedTableDesc>,
+    // Short names referenced by more than one column.
+    ambiguity_names: HashSet<String>,
+    // How the tables are joined, leaves are the positions in tables_long_name_columns.
+    relation: JoinedRelation,
+}
+
+#[derive(Clone, Debug)]
+pub enum JoinedRelation {
+    Table(usize),
+    Join {
+        join_type: JoinType,
+        condition: JoinedCondition,
+        left: Box<JoinedRelation>,
+        right: Box<JoinedRelation>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub enum JoinedCondition {
+    None,
+    On(Expression),
+    /// Pairs of (left table position, right table position, column short name).
+    Using(Vec<(usize, usize, String)>),
+}
+
+pub enum JoinedConstraint {
+    None,
+    On(Expression),
+    Using(Vec<String>),
+    Natural,
+}
+
+impl JoinedRelation {
+    fn shift(&self, offset: usize) -> JoinedRelation {
+        match self {
+            JoinedRelation::Table(pos) => JoinedRelation::Table(pos + offset),
+            JoinedRelation::Join {
+                join_type,
+                condition,
+                left,
+                right,
+            } => JoinedRelation::Join {
+                join_type: *join_type,
+                condition: match condition {
+                    JoinedCondition::Using(columns) => JoinedCondition::Using(
+                        columns
+                            .iter()
+                            .map(|(l, r, name)| (l + offset, r + offset, name.clone()))
+                            .collect(),
+                    ),
+                    other => other.clone(),
+                },
+                left: Box::new(left.shift(offset)),
+                right: Box::new(right.shift(offset)),
+            },
+        }
+    }
+
+    fn collect_tables(&self, tables: &mut Vec<usize>) {
+        match self {
+            JoinedRelation::Table(pos) => tables.push(*pos),
+            JoinedRelation::Join { left, right, .. } => {
+                left.collect_tables(tables);
+                right.collect_tables(tables);
+            }
+        }
+    }
+
+    fn collect_nullable_tables(&self, nullable: bool, tables: &mut HashSet<usize>) {
+        match self {
+            JoinedRelation::Table(pos) if nullable => {
+                tables.insert(*pos);
+            }
+            JoinedRelation::Table(_) => {}
+            JoinedRelation::Join {
+                join_type,
+                left,
+                right,
+                ..
+            } => {
+                left.collect_nullable_tables(nullable || join_type.is_left_nullable(), tables);
+                right.collect_nullable_tables(nullable || join_type.is_right_nullable(), tables);
+            }
+        }
+    }
 }
 
 impl JoinedSchema {
@@ -39,6 +127,8 @@ impl JoinedSchema {
         JoinedSchema {
             short_name_columns: HashMap::new(),
             tables_long_name_columns: Vec::new(),
+            ambiguity_names: HashSet::new(),
+            relation: JoinedRelation::Table(0),
         }
     }
 
@@ -75,6 +165,8 @@ impl JoinedSchema {
         Ok(JoinedSchema {
             short_name_columns,
             tables_long_name_columns: vec![table_desc],
+            ambiguity_names: HashSet::new(),
+            relation: JoinedRelation::Table(0),
         })
     }
 
@@ -93,6 +185,96 @@ impl JoinedSchema {
         }
     }
 
+    pub fn get_relation(&self) -> &JoinedRelation {
+        &self.relation
+    }
+
+    /// The name of the table column in the joined schema.
+    pub fn get_column_name(&self, table_pos: usize, short_name: &str) -> String {
+        let table_desc = &self.tables_long_name_columns[table_pos];
+        for column_desc in table_desc.get_columns_desc() {
+            if column_desc.short_name == short_name && column_desc.is_ambiguity {
+                let prefix = table_desc.get_name_parts().join(".");
+                return format!("{}.{}", prefix, short_name);
+            }
+        }
+
+        short_name.to_string()
+    }
+
+    /// The positions of the tables whose rows may be filled with NULL by outer joins.
+    pub fn get_nullable_tables(&self) -> HashSet<usize> {
+        let mut tables = HashSet::new();
+        self.relation.collect_nullable_tables(false, &mut tables);
+        tables
+    }
+
+    pub fn get_join_conditions(&self) -> Vec<Expression> {
+        fn collect(relation: &JoinedRelation, schema: &JoinedSchema, res: &mut Vec<Expression>) {
+            if let JoinedRelation::Join {
+                condition,
+                left,
+                right,
+                ..
+            } = relation
+            {
+                collect(left, schema, res);
+                collect(right, schema, res);
+                if let Some(condition) = schema.get_join_condition(condition) {
+                    res.push(condition);
+                }
+            }
+        }
+
+        let mut conditions = vec![];
+        collect(&self.relation, self, &mut conditions);
+        conditions
+    }
+
+    /// Resolve the join condition to an expression on the joined schema.
+    pub fn get_join_condition(&self, condition: &JoinedCondition) -> Option<Expression> {
+        match condition {
+            JoinedCondition::None => None,
+            JoinedCondition::On(expr) => Some(expr.clone()),
+            JoinedCondition::Using(columns) => {
+                let mut res: Option<Expression> = None;
+                for (left_pos, right_pos, name) in columns {
+                    let left = Expression::Column(self.get_column_name(*left_pos, name));
+                    let right = Expression::Column(self.get_column_name(*right_pos, name));
+                    let equal = left.eq(right);
+                    res = Some(match res {
+                        None => equal,
+                        Some(res) => res.and(equal),
+                    });
+                }
+                res
+            }
+        }
+    }
+
+    pub fn rewrite_join_conditions<F>(&mut self, mut f: F) -> Result<()>
+    where F: FnMut(&mut Expression) -> Result<()> {
+        fn rewrite<F>(relation: &mut JoinedRelation, f: &mut F) -> Result<()>
+        where F: FnMut(&mut Expression) -> Result<()> {
+            if let JoinedRelation::Join {
+                condition,
+                left,
+                right,
+                ..
+            } = relation
+            {
+                rewrite(left, f)?;
+                rewrite(right, f)?;
+                if let JoinedCondition::On(expr) = condition {
+                    f(expr)?;
+                }
+            }
+            Ok(())
+        }
+
+        rewrite(&mut self.relation, &mut f)
+    }
+
     pub fn take_tables_desc(self) -> Vec<JoinedTableDesc> {
         self.tables_long_name_columns
     }
@@ -121,8 +303,140 @@ impl JoinedSchema {
         Arc::new(DataSchema::new(fields))
     }
 
-    pub fn join(&self, _joined_schema: &JoinedSchema) -> Result<Arc<JoinedSchema>> {
-        unimplemented!("")
+    pub fn join(
+        &self,
+        right: &JoinedSchema,
+        join_type: JoinType,
+        constraint: JoinedConstraint,
+    ) -> Result<JoinedSchema> {
+        let offset = self.tables_long_name_columns.len();
+        let mut tables_desc = self.tables_long_name_columns.clone();
+
+        for right_table in &right.tables_long_name_columns {
+            let name_parts = right_table.get_name_parts();
+            for left_table in &self.tables_long_name_columns {
+                if left_table.get_name_parts() == name_parts {
+                    return Err(match name_parts.is_empty() {
+                        true => ErrorCode::SyntaxException(
+                            "Every derived table or table function in JOIN must have its own alias",
+                        ),
+                        false => ErrorCode::SyntaxException(format!(
+                            "Not unique table/alias: '{}'",
+                            name_parts.join(".")
+                        )),
+                    });
+                }
+            }
+
+            tables_desc.push(right_table.clone());
+        }
+
+        // Columns of the outer side of outer join may be filled with NULL.
+        if join_type.is_left_nullable() {
+            tables_desc[..offset]
+                .iter_mut()
+                .for_each(JoinedTableDesc::wrap_nullable_columns);
+        }
+
+        if join_type.is_right_nullable() {
+            tables_desc[offset..]
+                .iter_mut()
+                .for_each(JoinedTableDesc::wrap_nullable_columns);
+        }
+
+        let using_columns = match &constraint {
+            JoinedConstraint::Using(names) => names.clone(),
+            JoinedConstraint::Natural => {
+                let mut names = vec![];
+                for table_desc in &self.tables_long_name_columns {
+                    for column_desc in table_desc.get_columns_desc() {
+                        let name = &column_desc.short_name;
+                        if !column_desc.is_ambiguity && right.contains_column(name) {
+                            names.push(name.clone());
+                        }
+                    }
+                }
+                names
+            }
+            _ => vec![],
+        };
+
+        // The column of right side in USING is only referenced by full name.
+        let mut using = Vec::with_capacity(using_columns.len());
+        for name in &using_columns {
+            let left_pos = self.position_of_column(name)?;
+            let right_pos = right.position_of_column(name)? + offset;
+            tables_desc[right_pos].set_ambiguity(name);
+            using.push((left_pos, right_pos, name.clone()));
+        }
+
+        let mut ambiguity_names = self.ambiguity_names.clone();
+        ambiguity_names.extend(right.ambiguity_names.iter().cloned());
+        for name in right.short_name_columns.keys() {
+            if using_columns.contains(name) {
+                continue;
+            }
+
+            if self.short_name_columns.contains_key(name) || self.ambiguity_names.contains(name) {
+                ambiguity_names.insert(name.clone());
+            }
+        }
+
+        for name in self.short_name_columns.keys() {
+            if right.ambiguity_names.contains(name) {
+                ambiguity_names.insert(name.clone());
+            }
+        }
+
+        let mut short_name_columns = HashMap::new();
+        for table_desc in tables_desc.iter_mut() {
+            for name in &ambiguity_names {
+                table_desc.set_ambiguity(name);
+            }
+
+            for column_desc in table_desc.get_columns_desc() {
+                if !column_desc.is_ambiguity {
+                    short_name_columns.insert(column_desc.short_name.clone(), column_desc.clone());
+                }
+            }
+        }
+
+        let condition = match constraint {
+            JoinedConstraint::On(expr) => JoinedCondition::On(expr),
+            JoinedConstraint::Using(_) | JoinedConstraint::Natural if !using.is_empty() => {
+                JoinedCondition::Using(using)
+            }
+            _ => JoinedCondition::None,
+        };
+
+        Ok(JoinedSchema {
+            short_name_columns,
+            tables_long_name_columns: tables_desc,
+            ambiguity_names,
+            relation: JoinedRelation::Join {
+                join_type,
+                condition,
+                left: Box::new(self.relation.clone()),
+                right: Box::new(right.relation.shift(offset)),
+            },
+        })
+    }
+
+    fn position_of_column(&self, name: &str) -> Result<usize> {
+        if self.contains_column(name) {
+            for (pos, table_desc) in self.tables_long_name_columns.iter().enumerate() {
+                for column_desc in table_desc.get_columns_desc() {
+                    if column_desc.short_name == name && !column_desc.is_ambiguity {
+                        return Ok(pos);
+                    }
+                }
+            }
+        }
+
+        Err(ErrorCode::UnknownColumn(format!(
+            "Unknown or ambiguous column {} in USING clause",
+            name
+        )))
     }
 }
 
@@ -218,6 +532,28 @@ impl JoinedTableDesc {
             JoinedTableDesc::Subquery { columns_desc, .. } => columns_desc,
         }
     }
+
+    fn get_columns_desc_mut(&mut self) -> &mut [JoinedColumnDesc] {
+        match self {
+            JoinedTableDesc::Table { columns_desc, .. } => columns_desc,
+            JoinedTableDesc::Subquery { columns_desc, .. } => columns_desc,
+        }
+    }
+
+    fn set_ambiguity(&mut self, name: &str) {
+        for column_desc in self.get_columns_desc_mut() {
+            if column_desc.short_name == name {
+                column_desc.is_ambiguity = true;
+            }
+        }
+    }
+
+    fn wrap_nullable_columns(&mut self) {
+        for column_desc in self.get_columns_desc_mut() {
+            column_desc.data_type = wrap_nullable(&column_desc.data_type);
+            column_desc.nullable = true;
+        }
+    }
 }
 
 #[derive(Clone)]
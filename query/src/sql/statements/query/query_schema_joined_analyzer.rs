@@ -14,24 +14,44 @@
 
 use std::sync::Arc;
 
+use chrono::TimeZone;
+use chrono::Utc;
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::Expression;
+use common_planners::JoinType;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::Expr;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
+use sqlparser::ast::JoinConstraint;
 use sqlparser::ast::JoinOperator;
 use sqlparser::ast::ObjectName;
 use sqlparser::ast::Query;
 use sqlparser::ast::TableAlias;
 use sqlparser::ast::TableFactor;
 use sqlparser::ast::TableWithJoins;
+use sqlparser::ast::Value;
 
 use crate::catalogs::Catalog;
+use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
+use crate::sql::statements::query::query_schema_joined::JoinedConstraint;
 use crate::sql::statements::query::query_schema_joined::JoinedSchema;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 use crate::sql::statements::DfQueryStatement;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+use crate::storages::view::ViewTable;
+use crate::storages::view::VIEW_ENGINE;
+use crate::storages::NavigationPoint;
+use crate::storages::Table;
 
 pub struct JoinedSchemaAnalyzer {
     ctx: Arc<QueryContext>,
@@ -49,8 +69,17 @@ impl JoinedSchemaAnalyzer {
         let rpn = RelationRPNBuilder::build(&query.from)?;
         for rpn_item in &rpn {
             match rpn_item {
-                RelationRPNItem::Join(_) => {
-                    return Err(ErrorCode::UnImplement("Unimplemented SELECT JOIN yet."));
+                RelationRPNItem::Join(v) => {
+                    if analyzed_tables.len() < 2 {
+                        return Err(ErrorCode::LogicalError(
+                            "Logical error: this is relation rpn bug.",
+                        ));
+                    }
+
+                    let right = analyzed_tables.pop().unwrap();
+                    let left = analyzed_tables.pop().unwrap();
+                    let (join_type, constraint) = self.join_operator(v).await?;
+                    analyzed_tables.push(left.join(&right, join_type, constraint)?);
                 }
                 RelationRPNItem::Table(v) => {
                     let schema = self.table(v);
@@ -76,6 +105,43 @@ impl JoinedSchemaAnalyzer {
         Ok(analyzed_tables.remove(0))
     }
 
+    async fn join_operator(&self, op: &JoinOperator) -> Result<(JoinType, JoinedConstraint)> {
+        let (join_type, constraint) = match op {
+            JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+            JoinOperator::LeftOuter(constraint) => (JoinType::Left, constraint),
+            JoinOperator::RightOuter(constraint) => (JoinType::Right, constraint),
+            JoinOperator::FullOuter(constraint) => (JoinType::Full, constraint),
+            JoinOperator::CrossJoin => return Ok((JoinType::Cross, JoinedConstraint::None)),
+            _ => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Unsupported join operator: {:?}",
+                    op
+                )));
+            }
+        };
+
+        let constraint = match constraint {
+            JoinConstraint::On(expr) => {
+                let analyzer = ExpressionAnalyzer::create(self.ctx.clone());
+                JoinedConstraint::On(analyzer.analyze(expr).await?)
+            }
+            JoinConstraint::Using(idents) => {
+                JoinedConstraint::Using(idents.iter().map(|ident| ident.value.clone()).collect())
+            }
+            JoinConstraint::Natural => JoinedConstraint::Natural,
+            JoinConstraint::None => JoinedConstraint::None,
+        };
+
+        match (join_type, &constraint) {
+            (JoinType::Inner, JoinedConstraint::None) => Ok((JoinType::Cross, constraint)),
+            (_, JoinedConstraint::None) => Err(ErrorCode::SyntaxException(format!(
+                "{} JOIN must have ON or USING condition",
+                join_type
+            ))),
+            _ => Ok((join_type, constraint)),
+        }
+    }
+
     async fn subquery(&self, v: &DerivedRPNItem) -> Result<JoinedSchema> {
         let subquery = &(*v.subquery);
         let subquery = DfQueryStatement::try_from(subquery.clone())?;
@@ -96,7 +162,20 @@ impl JoinedSchemaAnalyzer {
     async fn table(&self, item: &TableRPNItem) -> Result<JoinedSchema> {
         // TODO(Winter): await query_context.get_table
         let (database, table) = self.resolve_table(&item.name)?;
-        let read_table = self.ctx.get_table(&database, &table).await?;
+        let mut read_table = self.ctx.get_table(&database, &table).await?;
+        if read_table.engine().eq_ignore_ascii_case(VIEW_ENGINE) {
+            if item.navigation.is_some() {
+                return Err(ErrorCode::SyntaxException(
+                    "Time travel of view is unsupported.",
+                ));
+            }
+            return self.view(database, table, read_table, &item.alias).await;
+        }
+
+        if let Some(navigation) = &item.navigation {
+            let point = self.navigation_point(navigation).await?;
+            read_table = read_table.navigate_to(self.ctx.clone(), &point).await?;
+        }
 
         match &item.alias {
             None => {
@@ -110,6 +189,93 @@ impl JoinedSchemaAnalyzer {
         }
     }
 
+    // The query of the view is expanded inline, as a subquery analyzed in the context of the
+    // caller, thus the tables the view refers to are accessed with the privileges of the caller.
+    async fn view(
+        &self,
+        database: String,
+        name: String,
+        view: Arc<dyn Table>,
+        alias: &Option<TableAlias>,
+    ) -> Result<JoinedSchema> {
+        self.ctx.get_current_session().validate_privilege(
+            &GrantObject::Table(database.clone(), name.clone()),
+            UserPrivilegeType::Select,
+        )?;
+
+        let view = view.as_any().downcast_ref::<ViewTable>().ok_or_else(|| {
+            ErrorCode::LogicalError(format!("Logical error, {} is not a view.", name))
+        })?;
+
+        let (mut statements, _) = DfParser::parse_sql(view.query()?)?;
+        let subquery = match statements.pop() {
+            Some(DfStatement::Query(subquery)) if statements.is_empty() => subquery,
+            _ => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Logical error, the query of view {} must be a single SELECT.",
+                    name
+                )));
+            }
+        };
+
+        let name_prefix = match alias {
+            None => vec![database, name],
+            Some(alias) => vec![alias.name.value.clone()],
+        };
+
+        match subquery.analyze(self.ctx.clone()).await? {
+            AnalyzedResult::SelectQuery(state) => JoinedSchema::from_subquery(state, name_prefix),
+            _ => Err(ErrorCode::LogicalError(
+                "Logical error, view analyzed data must be SelectQuery, it's a bug.",
+            )),
+        }
+    }
+
+    async fn navigation_point(&self, item: &NavigationRPNItem) -> Result<NavigationPoint> {
+        match item {
+            NavigationRPNItem::Snapshot(Expr::Value(Value::SingleQuotedString(snapshot_id))) => {
+                Ok(NavigationPoint::SnapshotID(snapshot_id.clone()))
+            }
+            NavigationRPNItem::Snapshot(expr) => Err(ErrorCode::SyntaxException(format!(
+                "Snapshot id of time travel must be a string literal, but got {}",
+                expr
+            ))),
+            NavigationRPNItem::Timestamp(expr) => {
+                let analyzer = ExpressionAnalyzer::create(self.ctx.clone());
+                let expression = analyzer.analyze(expr).await?;
+                let micros = Self::eval_timestamp(expression)?;
+                Ok(NavigationPoint::TimePoint(Utc.timestamp(
+                    (micros / 1_000_000) as i64,
+                    (micros % 1_000_000 * 1_000) as u32,
+                )))
+            }
+        }
+    }
+
+    // Evaluates the (constant) expression as timestamp, in microseconds.
+    fn eval_timestamp(expr: Expression) -> Result<u64> {
+        let input_fields = vec![DataField::new("_dummy", u8::to_data_type())];
+        let input_schema = DataSchemaRefExt::create(input_fields);
+
+        let expr = Expression::Cast {
+            expr: Box::new(expr),
+            data_type: DateTime64Type::arc(6, None),
+            is_nullable: false,
+        };
+        let output_schema = DataSchemaRefExt::create(vec![expr.to_data_field(&input_schema)?]);
+        let executor = ExpressionExecutor::try_create(
+            "time travel point executor",
+            input_schema.clone(),
+            output_schema,
+            vec![expr],
+            false,
+        )?;
+
+        let dummy_block = DataBlock::create(input_schema, vec![Series::from_data(vec![1u8])]);
+        let executed_block = executor.execute(&dummy_block)?;
+        executed_block.column(0).get_checked(0)?.as_u64()
+    }
+
     async fn table_function(&self, item: &TableFunctionRPNItem) -> Result<JoinedSchema> {
         if item.name.0.len() >= 2 {
             return Result::Err(ErrorCode::BadArguments(
@@ -151,9 +317,15 @@ impl JoinedSchemaAnalyzer {
     }
 }
 
+enum NavigationRPNItem {
+    Snapshot(Expr),
+    Timestamp(Expr),
+}
+
 struct TableRPNItem {
     name: ObjectName,
     alias: Option<TableAlias>,
+    navigation: Option<NavigationRPNItem>,
 }
 
 struct DerivedRPNItem {
@@ -193,6 +365,7 @@ impl RelationRPNBuilder {
         self.rpn.push(RelationRPNItem::Table(TableRPNItem {
             name: ObjectName(vec![Ident::new("system"), Ident::new("one")]),
             alias: None,
+            navigation: None,
         }));
     }
 
@@ -233,14 +406,12 @@ impl RelationRPNBuilder {
                 alias,
                 with_hints,
             } => {
-                if !with_hints.is_empty() {
-                    return Err(ErrorCode::SyntaxException(
-                        "MSSQL-specific `WITH (...)` hints is unsupported.",
-                    ));
-                }
-
+                let navigation = Self::navigation(with_hints)?;
                 match args.is_empty() {
-                    true => self.visit_table(name, alias),
+                    true => self.visit_table(name, alias, navigation),
+                    false if navigation.is_some() => Err(ErrorCode::SyntaxException(
+                        "Time travel of table function is unsupported.",
+                    )),
                     false => self.visit_table_function(name, args, alias),
                 }
             }
@@ -266,10 +437,42 @@ impl RelationRPNBuilder {
         }
     }
 
-    fn visit_table(&mut self, name: &ObjectName, alias: &Option<TableAlias>) -> Result<()> {
+    // The time travel clause `AT (SNAPSHOT => ..)` is rewritten into hints `WITH (SNAPSHOT = ..)`
+    // by the parser, other hints are unsupported.
+    fn navigation(with_hints: &[Expr]) -> Result<Option<NavigationRPNItem>> {
+        match with_hints {
+            [] => Ok(None),
+            [Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            }] => match left.as_ref() {
+                Expr::Identifier(ident) if ident.value == "SNAPSHOT" => {
+                    Ok(Some(NavigationRPNItem::Snapshot(right.as_ref().clone())))
+                }
+                Expr::Identifier(ident) if ident.value == "TIMESTAMP" => {
+                    Ok(Some(NavigationRPNItem::Timestamp(right.as_ref().clone())))
+                }
+                _ => Err(ErrorCode::SyntaxException(
+                    "MSSQL-specific `WITH (...)` hints is unsupported.",
+                )),
+            },
+            _ => Err(ErrorCode::SyntaxException(
+                "MSSQL-specific `WITH (...)` hints is unsupported.",
+            )),
+        }
+    }
+
+    fn visit_table(
+        &mut self,
+        name: &ObjectName,
+        alias: &Option<TableAlias>,
+        navigation: Option<NavigationRPNItem>,
+    ) -> Result<()> {
         self.rpn.push(RelationRPNItem::Table(TableRPNItem {
             name: name.clone(),
             alias: alias.clone(),
+            navigation,
         }));
         Ok(())
     }
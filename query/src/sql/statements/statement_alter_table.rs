@@ -0,0 +1,119 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AlterTableAction;
+use common_planners::AlterTablePlan;
+use common_planners::PlanNode;
+use common_planners::RenameTablePlan;
+use common_tracing::tracing;
+use sqlparser::ast::ColumnDef;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+
+use super::analyzer_expr::ExpressionAnalyzer;
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfCreateTable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfAlterTableAction {
+    AddColumn(ColumnDef),
+    DropColumn(Ident),
+    RenameColumn { old_name: Ident, new_name: Ident },
+    RenameTable(ObjectName),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAlterTable {
+    pub if_exists: bool,
+    pub name: ObjectName,
+    pub action: DfAlterTableAction,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfAlterTable {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let if_exists = self.if_exists;
+        let tenant = ctx.get_tenant();
+        let (db, table) = Self::resolve_table(ctx.clone(), &self.name)?;
+
+        let action = match &self.action {
+            DfAlterTableAction::AddColumn(column) => {
+                if DfCreateTable::column_codecs(column).is_some() {
+                    return Err(ErrorCode::UnImplement(
+                        "CODEC is not supported by ALTER TABLE ADD COLUMN",
+                    ));
+                }
+                let expr_analyzer = ExpressionAnalyzer::create(ctx.clone());
+                let field = DfCreateTable::column_field(&expr_analyzer, column).await?;
+                AlterTableAction::AddColumn(field)
+            }
+            DfAlterTableAction::DropColumn(name) => {
+                AlterTableAction::DropColumn(name.value.clone())
+            }
+            DfAlterTableAction::RenameColumn { old_name, new_name } => {
+                AlterTableAction::RenameColumn {
+                    old_name: old_name.value.clone(),
+                    new_name: new_name.value.clone(),
+                }
+            }
+            DfAlterTableAction::RenameTable(new_name) => {
+                let (new_db, new_table) = Self::resolve_table(ctx, new_name)?;
+                return Ok(AnalyzedResult::SimpleQuery(Box::new(
+                    PlanNode::RenameTable(RenameTablePlan {
+                        if_exists,
+                        tenant,
+                        db,
+                        table,
+                        new_db,
+                        new_table,
+                    }),
+                )));
+            }
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::AlterTable(
+            AlterTablePlan {
+                if_exists,
+                tenant,
+                db,
+                table,
+                action,
+            },
+        ))))
+    }
+}
+
+impl DfAlterTable {
+    pub(crate) fn resolve_table(
+        ctx: Arc<QueryContext>,
+        table_name: &ObjectName,
+    ) -> Result<(String, String)> {
+        let idents = &table_name.0;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Alter table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Alter table name must be [`db`].`table`",
+            )),
+        }
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -26,7 +27,9 @@ use common_planners::PlanNode;
 use common_tracing::tracing;
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOption;
+use sqlparser::ast::Expr;
 use sqlparser::ast::ObjectName;
+use sqlparser::tokenizer::Token;
 
 use super::analyzer_expr::ExpressionAnalyzer;
 use crate::sessions::QueryContext;
@@ -36,6 +39,9 @@ use crate::sql::statements::DfQueryStatement;
 use crate::sql::DfStatement;
 use crate::sql::PlanParser;
 use crate::sql::SQLCommon;
+use crate::storages::fuse::io::parse_column_codecs;
+use crate::storages::fuse::io::BlockWriteOptions;
+use crate::storages::fuse::TBL_OPT_KEY_COLUMN_CODECS;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateTable {
@@ -44,6 +50,8 @@ pub struct DfCreateTable {
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
     pub engine: String,
+    /// Expressions of "cluster by", the blocks of the table are sorted by them.
+    pub cluster_keys: Vec<Expr>,
     pub options: HashMap<String, String>,
 
     // The table name after "create .. like" statement.
@@ -66,7 +74,7 @@ impl AnalyzableStatement for DfCreateTable {
             // CTAS
             Some(query_statement) => {
                 let statements = vec![DfStatement::Query(query_statement.clone())];
-                let select_plan = PlanParser::build_plan(statements, ctx).await?;
+                let select_plan = PlanParser::build_plan(statements, ctx.clone()).await?;
 
                 // The schema contains two parts: create table (if specified) and select.
                 let mut fields = table_meta.schema.fields().to_vec();
@@ -86,6 +94,14 @@ impl AnalyzableStatement for DfCreateTable {
             None => None,
         };
 
+        // The cluster keys may refer to the columns of the query in CTAS.
+        table_meta.cluster_keys = self.table_cluster_keys(ctx, &table_meta.schema).await?;
+
+        // So are the codecs of the columns.
+        if self.engine.eq_ignore_ascii_case("FUSE") {
+            BlockWriteOptions::try_create(&table_meta.options)?.validate(&table_meta.schema)?;
+        }
+
         Ok(AnalyzedResult::SimpleQuery(Box::new(
             PlanNode::CreateTable(CreateTablePlan {
                 if_not_exists,
@@ -118,11 +134,88 @@ impl DfCreateTable {
         Ok(TableMeta {
             schema,
             engine,
-            options: self.options.clone(),
+            options: self.table_options()?,
             ..Default::default()
         })
     }
 
+    /// The options of the table, the codecs of the columns are merged into the option `COLUMN_CODECS`.
+    fn table_options(&self) -> Result<HashMap<String, String>> {
+        let mut options = HashMap::with_capacity(self.options.len());
+        let mut column_codecs = BTreeMap::new();
+        for (key, value) in &self.options {
+            if key.eq_ignore_ascii_case(TBL_OPT_KEY_COLUMN_CODECS) {
+                column_codecs.extend(parse_column_codecs(value)?);
+            } else {
+                options.insert(key.clone(), value.clone());
+            }
+        }
+
+        for column in &self.columns {
+            if let Some(codecs) = Self::column_codecs(column) {
+                column_codecs.insert(column.name.value.clone(), codecs);
+            }
+        }
+
+        if !column_codecs.is_empty() {
+            if !self.engine.eq_ignore_ascii_case("FUSE") {
+                return Err(ErrorCode::UnImplement(format!(
+                    "CODEC is not supported by the table engine {}",
+                    self.engine
+                )));
+            }
+            options.insert(
+                TBL_OPT_KEY_COLUMN_CODECS.to_string(),
+                serde_json::to_string(&column_codecs)?,
+            );
+        }
+        Ok(options)
+    }
+
+    /// The names of the codecs in `CODEC(...)` of the column, if there is.
+    pub(crate) fn column_codecs(column: &ColumnDef) -> Option<Vec<String>> {
+        column.options.iter().find_map(|opt| match &opt.option {
+            ColumnOption::DialectSpecific(tokens) => {
+                let mut words = tokens.iter().filter_map(|token| match token {
+                    Token::Word(word) => Some(word.value.clone()),
+                    _ => None,
+                });
+                match words.next() {
+                    Some(keyword) if keyword.eq_ignore_ascii_case("CODEC") => Some(words.collect()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
+
+    async fn table_cluster_keys(
+        &self,
+        ctx: Arc<QueryContext>,
+        schema: &DataSchemaRef,
+    ) -> Result<Option<String>> {
+        if self.cluster_keys.is_empty() {
+            return Ok(None);
+        }
+
+        if !self.engine.eq_ignore_ascii_case("FUSE") {
+            return Err(ErrorCode::UnImplement(format!(
+                "CLUSTER BY is not supported by the table engine {}",
+                self.engine
+            )));
+        }
+
+        let expr_analyzer = ExpressionAnalyzer::create(ctx);
+        let mut cluster_keys = Vec::with_capacity(self.cluster_keys.len());
+        for expr in &self.cluster_keys {
+            let expr = expr_analyzer.analyze(expr).await?;
+            // Make sure the columns referenced by the key exist.
+            expr.to_data_field(schema)?;
+            cluster_keys.push(expr);
+        }
+        Ok(Some(serde_json::to_string(&cluster_keys)?))
+    }
+
     async fn table_schema(&self, ctx: Arc<QueryContext>) -> Result<DataSchemaRef> {
         match &self.like {
             // For create table like statement, for example 'CREATE TABLE test2 LIKE db1.test1',
@@ -141,33 +234,39 @@ impl DfCreateTable {
                 let mut fields = Vec::with_capacity(self.columns.len());
 
                 for column in &self.columns {
-                    let mut nullable = true;
-                    let mut default_expr = None;
-                    for opt in &column.options {
-                        match &opt.option {
-                            ColumnOption::NotNull => {
-                                nullable = false;
-                            }
-                            ColumnOption::Default(expr) => {
-                                let expr = expr_analyzer.analyze(expr).await?;
-                                default_expr = Some(serde_json::to_vec(&expr)?);
-                            }
-                            _ => {}
-                        }
-                    }
-                    let field = SQLCommon::make_data_type(&column.data_type).map(|data_type| {
-                        if nullable {
-                            DataField::new_nullable(&column.name.value, data_type)
-                                .with_default_expr(default_expr)
-                        } else {
-                            DataField::new(&column.name.value, data_type)
-                                .with_default_expr(default_expr)
-                        }
-                    })?;
-                    fields.push(field);
+                    fields.push(Self::column_field(&expr_analyzer, column).await?);
                 }
                 Ok(DataSchemaRefExt::create(fields))
             }
         }
     }
+
+    /// Converts the definition of a column to the field of the table schema.
+    pub(crate) async fn column_field(
+        expr_analyzer: &ExpressionAnalyzer,
+        column: &ColumnDef,
+    ) -> Result<DataField> {
+        let mut nullable = true;
+        let mut default_expr = None;
+        for opt in &column.options {
+            match &opt.option {
+                ColumnOption::NotNull => {
+                    nullable = false;
+                }
+                ColumnOption::Default(expr) => {
+                    let expr = expr_analyzer.analyze(expr).await?;
+                    default_expr = Some(serde_json::to_vec(&expr)?);
+                }
+                _ => {}
+            }
+        }
+        SQLCommon::make_data_type(&column.data_type).map(|data_type| {
+            if nullable {
+                DataField::new_nullable(&column.name.value, data_type)
+                    .with_default_expr(default_expr)
+            } else {
+                DataField::new(&column.name.value, data_type).with_default_expr(default_expr)
+            }
+        })
+    }
 }
@@ -0,0 +1,262 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableMeta;
+use common_planners::AggregatorPartialPlan;
+use common_planners::CreateTablePlan;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::PlanVisitor;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+use sqlparser::ast::SelectItem;
+use sqlparser::ast::SetExpr;
+use sqlparser::ast::TableFactor;
+use sqlparser::ast::TableWithJoins;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfQueryStatement;
+use crate::sql::DfStatement;
+use crate::sql::PlanParser;
+use crate::storages::fuse::is_fuse_table;
+use crate::storages::view::MATERIALIZED_VIEW_QUERY;
+use crate::storages::view::MATERIALIZED_VIEW_SOURCE;
+use crate::storages::view::QUERY;
+use crate::storages::view::VIEW_ENGINE;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateView {
+    pub if_not_exists: bool,
+    pub materialized: bool,
+    /// View name
+    pub name: ObjectName,
+    pub query: Box<Query>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfCreateView {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let if_not_exists = self.if_not_exists;
+        let tenant = ctx.get_tenant();
+        let (db, view) = self.resolve_view(ctx.clone())?;
+
+        // The table names are qualified with the current database, so that the view
+        // refers to the same tables whatever the current database of its users is.
+        let mut query = self.query.as_ref().clone();
+        TableNameQualifier::qualify_query(&ctx.get_current_database(), &mut query);
+        let subquery = query.to_string();
+
+        // The materialized view only reads the new blocks of its source table.
+        let source = match self.materialized {
+            true => Some(Self::materialized_view_source(ctx.clone(), &query).await?),
+            false => None,
+        };
+
+        // Make sure the query is valid, and take the schema of it as the schema of the view.
+        let statement = DfQueryStatement::try_from(query)?;
+        let statements = vec![DfStatement::Query(Box::new(statement))];
+        let select_plan = PlanParser::build_plan(statements, ctx).await?;
+        if self.materialized {
+            MergeableAggregatesChecker.visit_plan_node(&select_plan)?;
+        }
+
+        let mut options = HashMap::new();
+        let engine = match source {
+            Some(source) => {
+                options.insert(MATERIALIZED_VIEW_QUERY.to_string(), subquery);
+                options.insert(MATERIALIZED_VIEW_SOURCE.to_string(), source.to_string());
+                "FUSE"
+            }
+            None => {
+                options.insert(QUERY.to_string(), subquery);
+                VIEW_ENGINE
+            }
+        };
+        let table_meta = TableMeta {
+            schema: select_plan.schema(),
+            engine: engine.to_string(),
+            options,
+            ..Default::default()
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(
+            PlanNode::CreateTable(CreateTablePlan {
+                if_not_exists,
+                tenant,
+                db,
+                table: view,
+                table_meta,
+                as_select: None,
+            }),
+        )))
+    }
+}
+
+impl DfCreateView {
+    /// Returns the table id of the source table of the materialized view, the query must read a
+    /// single FUSE table without joins and limits, so that it can run over the new blocks only.
+    async fn materialized_view_source(ctx: Arc<QueryContext>, query: &Query) -> Result<u64> {
+        let unsupported = |reason: &str| {
+            Err(ErrorCode::SyntaxException(format!(
+                "Unsupported query of materialized view, {}: {}",
+                reason, query
+            )))
+        };
+
+        if query.limit.is_some() || query.offset.is_some() || query.fetch.is_some() {
+            return unsupported("LIMIT is not allowed");
+        }
+
+        let select = match &query.body {
+            SetExpr::Select(select) => select,
+            _ => return unsupported("expects a SELECT"),
+        };
+
+        // The groups of an insertion are merged with the groups of the others by the readers
+        // of the view, filtering them on their partial aggregates is wrong.
+        if select.having.is_some() {
+            return unsupported("HAVING is not allowed");
+        }
+
+        let name = match &select.from[..] {
+            [TableWithJoins {
+                relation: TableFactor::Table { name, args, .. },
+                joins,
+            }] if args.is_empty() && joins.is_empty() => name,
+            _ => return unsupported("expects a single source table without joins"),
+        };
+
+        // The table names of the query are qualified already.
+        let (db, table) = match &name.0[..] {
+            [db, table] => (&db.value, &table.value),
+            _ => return unsupported("expects a source table [`db`].`table`"),
+        };
+
+        let source = ctx.get_table(db, table).await?;
+        if !is_fuse_table(source.as_ref()) {
+            return unsupported("the source table must be a FUSE table");
+        }
+        Ok(source.get_id())
+    }
+
+    fn resolve_view(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let idents = &self.name.0;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Create view name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Create view name must be [`db`].`view`",
+            )),
+        }
+    }
+}
+
+struct TableNameQualifier;
+
+impl TableNameQualifier {
+    fn qualify_query(database: &str, query: &mut Query) {
+        if let SetExpr::Select(select) = &mut query.body {
+            for table_with_joins in &mut select.from {
+                Self::qualify_joins(database, table_with_joins);
+            }
+
+            for item in &mut select.projection {
+                match item {
+                    SelectItem::UnnamedExpr(expr) => Self::qualify_expr(database, expr),
+                    SelectItem::ExprWithAlias { expr, .. } => Self::qualify_expr(database, expr),
+                    _ => {}
+                }
+            }
+
+            if let Some(selection) = &mut select.selection {
+                Self::qualify_expr(database, selection);
+            }
+
+            if let Some(having) = &mut select.having {
+                Self::qualify_expr(database, having);
+            }
+        }
+    }
+
+    fn qualify_joins(database: &str, table_with_joins: &mut TableWithJoins) {
+        Self::qualify_table_factor(database, &mut table_with_joins.relation);
+        for join in &mut table_with_joins.joins {
+            Self::qualify_table_factor(database, &mut join.relation);
+        }
+    }
+
+    fn qualify_table_factor(database: &str, factor: &mut TableFactor) {
+        match factor {
+            // Table functions are not qualified.
+            TableFactor::Table { name, args, .. } if args.is_empty() && name.0.len() == 1 => {
+                name.0.insert(0, Ident::new(database));
+            }
+            TableFactor::Derived { subquery, .. } => Self::qualify_query(database, subquery),
+            TableFactor::NestedJoin(joins) => Self::qualify_joins(database, joins),
+            _ => {}
+        }
+    }
+
+    // Qualifies the tables of the subqueries in the expression.
+    fn qualify_expr(database: &str, expr: &mut Expr) {
+        match expr {
+            Expr::Exists(subquery) => Self::qualify_query(database, subquery),
+            Expr::Subquery(subquery) => Self::qualify_query(database, subquery),
+            Expr::Nested(expr) => Self::qualify_expr(database, expr),
+            Expr::UnaryOp { expr, .. } => Self::qualify_expr(database, expr),
+            Expr::BinaryOp { left, right, .. } => {
+                Self::qualify_expr(database, left);
+                Self::qualify_expr(database, right);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The view holds the aggregates of every insertion into the source table, its readers merge
+/// them into the aggregates of all the rows, e.g. the `sum` of the partial `count`s. Aggregate
+/// functions whose partial results can't be merged so, e.g. `avg` and `uniq`, are rejected.
+struct MergeableAggregatesChecker;
+
+impl PlanVisitor for MergeableAggregatesChecker {
+    fn visit_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        for expr in &plan.aggr_expr {
+            if let Expression::AggregateFunction { op, distinct, .. } = expr {
+                let mergeable = !*distinct
+                    && matches!(op.to_lowercase().as_str(), "sum" | "count" | "min" | "max");
+                if !mergeable {
+                    return Err(ErrorCode::SyntaxException(format!(
+                        "Unsupported query of materialized view, the partial results of {} can not be merged, only sum, count, min and max are allowed",
+                        expr.column_name()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues2::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::find_aggregate_exprs_in_expr;
+use common_planners::DeletePlan;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::RewriteHelper;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::storages::Table;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDeleteStatement {
+    pub name: ObjectName,
+    pub selection: Option<Expr>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfDeleteStatement {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database_name, table_name) = self.resolve_table(&ctx)?;
+        let table = ctx.get_table(&database_name, &table_name).await?;
+        let schema = table.schema();
+
+        let selection = match &self.selection {
+            None => None,
+            Some(expr) => Some(Self::analyze_expr(ctx.clone(), expr, &schema, "DELETE").await?),
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Delete(
+            DeletePlan {
+                database_name,
+                table_name,
+                table_id: table.get_id(),
+                selection,
+            },
+        ))))
+    }
+}
+
+impl DfDeleteStatement {
+    fn resolve_table(&self, ctx: &QueryContext) -> Result<(String, String)> {
+        let DfDeleteStatement {
+            name: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Delete table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Delete table name must be [`db`].`table`",
+            )),
+        }
+    }
+
+    /// Analyzes an expression evaluated on each row of the table, i.e. the WHERE of DELETE and UPDATE,
+    /// or the new values of UPDATE.
+    pub(crate) async fn analyze_expr(
+        ctx: Arc<QueryContext>,
+        expr: &Expr,
+        schema: &DataSchemaRef,
+        statement: &str,
+    ) -> Result<Expression> {
+        let expression = ExpressionAnalyzer::create(ctx).analyze(expr).await?;
+
+        if !find_aggregate_exprs_in_expr(&expression).is_empty() {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Aggregate functions are not allowed in {}",
+                statement
+            )));
+        }
+
+        if !RewriteHelper::collect_exprs_sub_queries(&[expression.clone()])?.is_empty() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Subqueries are not supported in {}",
+                statement
+            )));
+        }
+
+        // Make sure all the columns exist.
+        expression.to_data_type(schema)?;
+        Ok(expression)
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::DropTablePlan;
+use common_planners::PlanNode;
+use common_tracing::tracing;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::storages::view::is_materialized_view;
+use crate::storages::view::VIEW_ENGINE;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropView {
+    pub if_exists: bool,
+    pub name: ObjectName,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfDropView {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let if_exists = self.if_exists;
+        let tenant = ctx.get_tenant();
+        let (db, view) = self.resolve_view(ctx.clone())?;
+
+        // A table that is not a view can not be dropped by DROP VIEW.
+        match ctx.get_table(&db, &view).await {
+            Ok(table)
+                if !table.engine().eq_ignore_ascii_case(VIEW_ENGINE)
+                    && !is_materialized_view(table.get_table_info()) =>
+            {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{}.{} is not a view, use DROP TABLE instead",
+                    db, view
+                )));
+            }
+            Err(e) if !if_exists || e.code() != ErrorCode::UnknownTable("").code() => {
+                return Err(e);
+            }
+            _ => {}
+        }
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::DropTable(
+            DropTablePlan {
+                if_exists,
+                tenant,
+                db,
+                table: view,
+            },
+        ))))
+    }
+}
+
+impl DfDropView {
+    fn resolve_view(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let idents = &self.name.0;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Drop view name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Drop view name must be [`db`].`view`",
+            )),
+        }
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -30,6 +31,7 @@ use crate::sql::statements::AnalyzedResult;
 pub struct DfOptimizeTable {
     pub name: ObjectName,
     pub operation: Optimization,
+    pub orphans_older_than: Option<Duration>,
 }
 
 #[async_trait::async_trait]
@@ -41,6 +43,7 @@ impl AnalyzableStatement for DfOptimizeTable {
             database,
             table,
             operation: self.operation,
+            orphans_older_than: self.orphans_older_than,
         };
         Ok(AnalyzedResult::SimpleQuery(Box::new(
             PlanNode::OptimizeTable(plan_node),
@@ -15,12 +15,15 @@
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues2::DataField;
 use common_datavalues2::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::expand_aggregate_arg_exprs;
+use common_planners::expand_window_arg_exprs;
 use common_planners::find_aggregate_exprs;
 use common_planners::find_aggregate_exprs_in_expr;
+use common_planners::find_window_exprs_in_expr;
 use common_planners::rebase_expr;
 use common_planners::Expression;
 use common_tracing::tracing;
@@ -32,6 +35,7 @@ use sqlparser::ast::TableWithJoins;
 
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_statement::QueryAnalyzeState;
+use crate::sql::statements::query::JoinedRelation;
 use crate::sql::statements::query::JoinedSchema;
 use crate::sql::statements::query::JoinedSchemaAnalyzer;
 use crate::sql::statements::query::JoinedTableDesc;
@@ -41,6 +45,7 @@ use crate::sql::statements::query::QueryCollectPushDowns;
 use crate::sql::statements::query::QueryNormalizer;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::JoinRelation;
 use crate::sql::statements::QueryRelation;
 use crate::storages::ToReadDataSourcePlan;
 
@@ -66,6 +71,7 @@ impl AnalyzableStatement for DfQueryStatement {
         let mut ir = QueryNormalizer::normalize(ctx.clone(), self).await?;
 
         QualifiedRewriter::rewrite(&joined_schema, ctx.clone(), &mut ir)?;
+        QualifiedRewriter::rewrite_join_conditions(&mut joined_schema, ctx.clone())?;
 
         QueryCollectPushDowns::collect_extras(&mut ir, &mut joined_schema)?;
 
@@ -87,18 +93,46 @@ impl DfQueryStatement {
 
         if let Some(predicate) = &ir.filter_predicate {
             Self::verify_no_aggregate(predicate, "filter")?;
+            Self::verify_no_window(predicate, "filter")?;
             analyze_state.filter = Some(predicate.clone());
         }
 
-        Self::analyze_projection(&ir.projection_expressions, &mut analyze_state)?;
+        Self::analyze_window(&ir.window_expressions, &mut analyze_state)?;
+        Self::analyze_projection(
+            &ir.projection_expressions,
+            &ir.window_expressions,
+            &mut analyze_state,
+        )?;
 
         // Allow `SELECT name FROM system.databases HAVING name = 'xxx'`
         if let Some(predicate) = &ir.having_predicate {
+            Self::verify_no_window(predicate, "having")?;
             analyze_state.having = Some(rebase_expr(predicate, &analyze_state.expressions)?);
         }
 
         for item in &ir.order_by_expressions {
             match item {
+                Expression::Sort {
+                    expr,
+                    asc,
+                    nulls_first,
+                    origin_expr,
+                } if !find_window_exprs_in_expr(expr).is_empty() => {
+                    // Sort by the window function results, which are computed after expressions.
+                    let window_exprs = &ir.window_expressions;
+                    let after_window_expr = rebase_expr(expr, window_exprs)?;
+                    let after_window_expr =
+                        rebase_expr(&after_window_expr, &analyze_state.expressions)?;
+                    analyze_state.add_after_window_expression(&after_window_expr);
+
+                    let base_exprs = std::slice::from_ref(expr.as_ref());
+                    analyze_state.order_by_expressions.push(Expression::Sort {
+                        expr: Box::new(rebase_expr(expr, base_exprs)?),
+                        asc: *asc,
+                        nulls_first: *nulls_first,
+                        origin_expr: Box::new(rebase_expr(origin_expr, base_exprs)?),
+                    });
+                }
                 Expression::Sort {
                     expr,
                     asc,
@@ -134,7 +168,17 @@ impl DfQueryStatement {
 
             analyze_state.expressions = expressions;
 
+            let mut after_window_expressions =
+                Vec::with_capacity(analyze_state.after_window_expressions.len());
+            for expression in &analyze_state.after_window_expressions {
+                let expression = rebase_expr(expression, &ir.aggregate_expressions)?;
+                after_window_expressions.push(rebase_expr(&expression, &ir.group_by_expressions)?);
+            }
+
+            analyze_state.after_window_expressions = after_window_expressions;
+
             for group_expression in &ir.group_by_expressions {
+                Self::verify_no_window(group_expression, "group by")?;
                 analyze_state.add_before_group_expression(group_expression);
                 let base_exprs = &analyze_state.before_group_by_expressions;
                 analyze_state
@@ -166,20 +210,65 @@ impl DfQueryStatement {
         Ok(())
     }
 
-    fn analyze_projection(exprs: &[Expression], state: &mut QueryAnalyzeState) -> Result<()> {
+    fn analyze_window(exprs: &[Expression], state: &mut QueryAnalyzeState) -> Result<()> {
+        let window_functions_args = expand_window_arg_exprs(exprs);
+
+        for window_function_arg in &window_functions_args {
+            Self::verify_no_window(window_function_arg, "window function arguments")?;
+            state.add_expression(window_function_arg);
+        }
+
+        for window_expression in exprs {
+            let base_exprs = &state.expressions;
+            let window_expression = rebase_expr(window_expression, base_exprs)?;
+            state.add_window_expression(&window_expression);
+        }
+
+        Ok(())
+    }
+
+    fn analyze_projection(
+        exprs: &[Expression],
+        window_exprs: &[Expression],
+        state: &mut QueryAnalyzeState,
+    ) -> Result<()> {
         for item in exprs {
-            match item {
-                Expression::Alias(_, expr) => state.add_expression(expr),
-                _ => state.add_expression(item),
+            let expr = match item {
+                Expression::Alias(_, expr) => expr.as_ref(),
+                _ => item,
+            };
+
+            match find_window_exprs_in_expr(expr).is_empty() {
+                true => {
+                    state.add_expression(expr);
+                    let rebased_expr = rebase_expr(item, &state.expressions)?;
+                    state.projection_expressions.push(rebased_expr);
+                }
+                false => {
+                    // The window function results are only available after the expressions
+                    let after_window_expr = rebase_expr(expr, window_exprs)?;
+                    let after_window_expr = rebase_expr(&after_window_expr, &state.expressions)?;
+                    state.add_after_window_expression(&after_window_expr);
+
+                    let rebased_expr = rebase_expr(item, std::slice::from_ref(expr))?;
+                    state.projection_expressions.push(rebased_expr);
+                }
             }
-
-            let rebased_expr = rebase_expr(item, &state.expressions)?;
-            state.projection_expressions.push(rebased_expr);
         }
 
         Ok(())
     }
 
+    fn verify_no_window(expr: &Expression, info: &str) -> Result<()> {
+        match find_window_exprs_in_expr(expr).is_empty() {
+            true => Ok(()),
+            false => Err(ErrorCode::SyntaxException(format!(
+                "{} cannot contain window functions",
+                info
+            ))),
+        }
+    }
+
     fn verify_no_aggregate(expr: &Expression, info: &str) -> Result<()> {
         match find_aggregate_exprs_in_expr(expr).is_empty() {
             true => Ok(()),
@@ -201,29 +290,119 @@ impl DfQueryStatement {
         let dry_run_res = Self::verify_with_dry_run(&schema, &state)?;
         state.finalize_schema = dry_run_res.schema().clone();
 
-        let mut tables_desc = schema.take_tables_desc();
-
-        if tables_desc.len() != 1 {
-            return Err(ErrorCode::UnImplement("Select join unimplemented yet."));
+        if schema.get_tables_desc().len() != 1 {
+            state.relation = Self::join_relation(schema, ctx).await?;
+            return Ok(AnalyzedResult::SelectQuery(Box::new(state)));
         }
 
-        match tables_desc.remove(0) {
+        let mut tables_desc = schema.take_tables_desc();
+        state.relation = Self::table_relation(tables_desc.remove(0), ctx).await?;
+        Ok(AnalyzedResult::SelectQuery(Box::new(state)))
+    }
+
+    async fn table_relation(
+        table_desc: JoinedTableDesc,
+        ctx: Arc<QueryContext>,
+    ) -> Result<QueryRelation> {
+        match table_desc {
             JoinedTableDesc::Table {
                 table, push_downs, ..
             } => {
                 let source_plan = table.read_plan(ctx.clone(), push_downs).await?;
-                state.relation = QueryRelation::FromTable(Box::new(source_plan));
+                Ok(QueryRelation::FromTable(Box::new(source_plan)))
             }
             JoinedTableDesc::Subquery {
                 state: subquery_state,
                 ..
             } => {
                 // TODO: maybe need reanalyze subquery.
-                state.relation = QueryRelation::Nested(subquery_state);
+                Ok(QueryRelation::Nested(subquery_state))
             }
         }
+    }
 
-        Ok(AnalyzedResult::SelectQuery(Box::new(state)))
+    async fn join_relation(schema: JoinedSchema, ctx: Arc<QueryContext>) -> Result<QueryRelation> {
+        let tables_desc = schema.get_tables_desc().to_vec();
+        let mut relations = Vec::with_capacity(tables_desc.len());
+
+        for (table_pos, table_desc) in tables_desc.into_iter().enumerate() {
+            let relation = Self::table_relation(table_desc, ctx.clone()).await?;
+            let relation_schema = match &relation {
+                QueryRelation::FromTable(plan) => plan.schema(),
+                QueryRelation::Nested(state) => state.finalize_schema.clone(),
+                _ => {
+                    return Err(ErrorCode::LogicalError(
+                        "Logical error: join relation must be table or subquery, it's a bug.",
+                    ));
+                }
+            };
+
+            // Rename the ambiguous columns to the full names in the joined schema.
+            let mut renamed = false;
+            let mut projection = Vec::with_capacity(relation_schema.fields().len());
+            for field in relation_schema.fields() {
+                let short_name = field.name();
+                let column_name = schema.get_column_name(table_pos, short_name);
+                match &column_name == short_name {
+                    true => projection.push(Expression::Column(column_name)),
+                    false => {
+                        renamed = true;
+                        let column = Box::new(Expression::Column(short_name.clone()));
+                        projection.push(Expression::Alias(column_name, column));
+                    }
+                }
+            }
+
+            relations.push(Some((relation, renamed.then(|| projection))));
+        }
+
+        Self::build_join_relation(&schema, schema.get_relation(), &mut relations)
+    }
+
+    fn build_join_relation(
+        schema: &JoinedSchema,
+        joined: &JoinedRelation,
+        relations: &mut Vec<Option<(QueryRelation, Option<Vec<Expression>>)>>,
+    ) -> Result<QueryRelation> {
+        match joined {
+            JoinedRelation::Table(_) => Err(ErrorCode::LogicalError(
+                "Logical error: join relation must have two sides, it's a bug.",
+            )),
+            JoinedRelation::Join {
+                join_type,
+                condition,
+                left,
+                right,
+            } => {
+                let (left, left_projection) = Self::build_join_side(schema, left, relations)?;
+                let (right, right_projection) = Self::build_join_side(schema, right, relations)?;
+
+                Ok(QueryRelation::Join(Box::new(JoinRelation {
+                    join_type: *join_type,
+                    condition: schema.get_join_condition(condition),
+                    left,
+                    left_projection,
+                    right,
+                    right_projection,
+                })))
+            }
+        }
+    }
+
+    fn build_join_side(
+        schema: &JoinedSchema,
+        side: &JoinedRelation,
+        relations: &mut Vec<Option<(QueryRelation, Option<Vec<Expression>>)>>,
+    ) -> Result<(QueryRelation, Option<Vec<Expression>>)> {
+        match side {
+            JoinedRelation::Table(pos) => match relations[*pos].take() {
+                Some(relation) => Ok(relation),
+                None => Err(ErrorCode::LogicalError(
+                    "Logical error: table is joined twice, it's a bug.",
+                )),
+            },
+            _ => Ok((Self::build_join_relation(schema, side, relations)?, None)),
+        }
     }
 
     fn verify_with_dry_run(schema: &JoinedSchema, state: &QueryAnalyzeState) -> Result<DataBlock> {
@@ -268,6 +447,8 @@ impl DfQueryStatement {
             }
         }
 
+        // The expression plan merges its fields into the input fields.
+        let before_expressions_block = data_block.clone();
         if !state.expressions.is_empty() {
             match Self::dry_run_exprs(&state.expressions, &data_block) {
                 Ok(res) => {
@@ -288,6 +469,36 @@ impl DfQueryStatement {
             }
         }
 
+        if !state.window_expressions.is_empty() {
+            let mut fields = before_expressions_block.schema().fields().clone();
+            Self::merge_fields(&mut fields, data_block.schema().fields());
+
+            for window_expression in &state.window_expressions {
+                let schema = DataSchemaRefExt::create(fields.clone());
+                match window_expression.to_data_field(&schema) {
+                    Ok(field) => Self::merge_fields(&mut fields, &[field]),
+                    Err(cause) => {
+                        return Err(cause.add_message_back(" (while in select window)"));
+                    }
+                }
+            }
+
+            data_block = DataBlock::empty_with_schema(DataSchemaRefExt::create(fields));
+        }
+
+        if !state.after_window_expressions.is_empty() {
+            match Self::dry_run_exprs(&state.after_window_expressions, &data_block) {
+                Ok(res) => {
+                    let mut fields = data_block.schema().fields().clone();
+                    Self::merge_fields(&mut fields, res.schema().fields());
+                    data_block = DataBlock::empty_with_schema(DataSchemaRefExt::create(fields));
+                }
+                Err(cause) => {
+                    return Err(cause.add_message_back(" (while in select after window)"));
+                }
+            }
+        }
+
         if !state.order_by_expressions.is_empty() {
             if let Err(cause) = Self::dry_run_exprs(&state.order_by_expressions, &data_block) {
                 return Err(cause.add_message_back(" (while in select order by)"));
@@ -308,6 +519,14 @@ impl DfQueryStatement {
         Ok(data_block)
     }
 
+    fn merge_fields(fields: &mut Vec<DataField>, new_fields: &[DataField]) {
+        for new_field in new_fields {
+            if !fields.iter().any(|field| field.name() == new_field.name()) {
+                fields.push(new_field.clone());
+            }
+        }
+    }
+
     fn dry_run_expr(expr: &Expression, data: &DataBlock) -> Result<DataBlock> {
         let schema = data.schema();
         let data_field = expr.to_data_field(schema)?;
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::UpdatePlan;
+use common_tracing::tracing;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfDeleteStatement;
+use crate::storages::Table;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfUpdateStatement {
+    pub name: ObjectName,
+    pub assignments: Vec<(Ident, Expr)>,
+    pub selection: Option<Expr>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfUpdateStatement {
+    #[tracing::instrument(level = "debug", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (database_name, table_name) = self.resolve_table(&ctx)?;
+        let table = ctx.get_table(&database_name, &table_name).await?;
+        let schema = table.schema();
+
+        let mut columns = HashSet::with_capacity(self.assignments.len());
+        let mut assignments = Vec::with_capacity(self.assignments.len());
+        for (ident, value) in &self.assignments {
+            let name = ident.value.clone();
+            let field = schema.field_with_name(&name)?;
+            if !columns.insert(name.clone()) {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Multiple assignments to the same column '{}'",
+                    name
+                )));
+            }
+
+            let expr =
+                DfDeleteStatement::analyze_expr(ctx.clone(), value, &schema, "UPDATE").await?;
+            let expr = if &expr.to_data_type(&schema)? != field.data_type() {
+                Expression::Cast {
+                    expr: Box::new(expr),
+                    data_type: field.data_type().clone(),
+                    is_nullable: field.is_nullable(),
+                }
+            } else {
+                expr
+            };
+            assignments.push((name, expr));
+        }
+
+        let selection = match &self.selection {
+            None => None,
+            Some(expr) => {
+                Some(DfDeleteStatement::analyze_expr(ctx.clone(), expr, &schema, "UPDATE").await?)
+            }
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(Box::new(PlanNode::Update(
+            UpdatePlan {
+                database_name,
+                table_name,
+                table_id: table.get_id(),
+                assignments,
+                selection,
+            },
+        ))))
+    }
+}
+
+impl DfUpdateStatement {
+    fn resolve_table(&self, ctx: &QueryContext) -> Result<(String, String)> {
+        let DfUpdateStatement {
+            name: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Update table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Update table name must be [`db`].`table`",
+            )),
+        }
+    }
+}
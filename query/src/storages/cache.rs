@@ -12,18 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_tracing::tracing;
+
 use crate::configs::QueryConfig;
 use crate::storages::fuse::cache;
+use crate::storages::fuse::cache::BlockDataCache;
 use crate::storages::fuse::cache::MemoryCache;
+use crate::storages::fuse::io::AnalyzedStatisticsCache;
 use crate::storages::fuse::io::BlockMetaCache;
+use crate::storages::fuse::io::BloomFilterIndexCache;
 use crate::storages::fuse::io::SegmentInfoCache;
 use crate::storages::fuse::io::TableSnapshotCache;
 
 /// Where all the caches reside
 pub struct CacheManager {
     table_snapshot_cache: Option<TableSnapshotCache>,
+    analyzed_statistics_cache: Option<AnalyzedStatisticsCache>,
     segment_info_cache: Option<SegmentInfoCache>,
     block_meta_cache: Option<BlockMetaCache>,
+    bloom_filter_index_cache: Option<BloomFilterIndexCache>,
+    block_data_cache: Option<BlockDataCache>,
     cluster_id: String,
     tenant_id: String,
 }
@@ -36,19 +44,30 @@ impl CacheManager {
         if !config.table_cache_enabled {
             Self {
                 table_snapshot_cache: None,
+                analyzed_statistics_cache: None,
                 segment_info_cache: None,
                 block_meta_cache: None,
+                bloom_filter_index_cache: None,
+                block_data_cache: None,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }
         } else {
             let table_snapshot_cache = Self::with_capacity(config.table_cache_snapshot_count);
+            // at most one for each snapshot
+            let analyzed_statistics_cache = Self::with_capacity(config.table_cache_snapshot_count);
             let segment_info_cache = Self::with_capacity(config.table_cache_segment_count);
             let block_meta_cache = Self::with_capacity(config.table_cache_block_meta_count);
+            // at most one for each block
+            let bloom_filter_index_cache = Self::with_capacity(config.table_cache_block_meta_count);
+            let block_data_cache = Self::new_block_data_cache(config);
             Self {
                 table_snapshot_cache,
+                analyzed_statistics_cache,
                 segment_info_cache,
                 block_meta_cache,
+                bloom_filter_index_cache,
+                block_data_cache,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }
@@ -59,6 +78,10 @@ impl CacheManager {
         self.table_snapshot_cache.clone()
     }
 
+    pub fn get_analyzed_statistics_cache(&self) -> Option<AnalyzedStatisticsCache> {
+        self.analyzed_statistics_cache.clone()
+    }
+
     pub fn get_table_segment_cache(&self) -> Option<SegmentInfoCache> {
         self.segment_info_cache.clone()
     }
@@ -67,6 +90,14 @@ impl CacheManager {
         self.block_meta_cache.clone()
     }
 
+    pub fn get_bloom_filter_index_cache(&self) -> Option<BloomFilterIndexCache> {
+        self.bloom_filter_index_cache.clone()
+    }
+
+    pub fn get_block_data_cache(&self) -> Option<BlockDataCache> {
+        self.block_data_cache.clone()
+    }
+
     pub fn get_tenant_id(&self) -> &str {
         self.tenant_id.as_str()
     }
@@ -75,6 +106,32 @@ impl CacheManager {
         self.cluster_id.as_str()
     }
 
+    // The disk cache is disabled (with an error logged) if it can not be created,
+    // queries still work without it.
+    fn new_block_data_cache(config: &QueryConfig) -> Option<BlockDataCache> {
+        if config.table_disk_cache_mb_size == 0 {
+            return None;
+        }
+
+        let capacity = config.table_disk_cache_mb_size * 1024 * 1024;
+        match BlockDataCache::try_create(
+            &config.table_disk_cache_root,
+            capacity,
+            &config.tenant_id,
+            &config.cluster_id,
+        ) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                tracing::error!(
+                    "failed to create the disk cache at {}, disk cache is disabled: {}",
+                    config.table_disk_cache_root,
+                    e
+                );
+                None
+            }
+        }
+    }
+
     fn with_capacity<T>(capacity: u64) -> Option<MemoryCache<T>> {
         if capacity > 0 {
             Some(cache::new_memory_cache(capacity))
@@ -0,0 +1,171 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_cache::LruDiskCache;
+use common_dal2::Operator;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_tracing::tracing;
+use futures::AsyncReadExt;
+use uuid::Uuid;
+
+use crate::storages::fuse::cache::metrics::DiskCacheDeferMetrics;
+use crate::storages::fuse::cache::metrics::TenantLabel;
+
+/// The directory under the root of the cache, where the ranges are written before they are
+/// moved into the cache.
+const TEMP_DIR: &str = "_tmp";
+
+/// Caches the byte ranges of block files on local disk, e.g. the column chunks of blocks.
+///
+/// Block files are immutable, thus a range is identified by the path of the file and
+/// the offset and length of the range, and never needs to be invalidated.
+///
+/// The lock of the cache is only held while the index is looked up or updated, the files are
+/// read and written by the blocking threads of tokio without it.
+#[derive(Clone)]
+pub struct BlockDataCache {
+    cache: Arc<RwLock<LruDiskCache>>,
+    temp_dir: PathBuf,
+    tenant_id: String,
+    cluster_id: String,
+}
+
+impl BlockDataCache {
+    /// Creates a cache of `capacity` bytes, stores the files in `root`.
+    ///
+    /// Files left in `root` by previous runs are kept, and evicted the first.
+    pub fn try_create(
+        root: &str,
+        capacity: u64,
+        tenant_id: impl Into<String>,
+        cluster_id: impl Into<String>,
+    ) -> Result<Self> {
+        // the ranges which were being written by a previous run are not complete
+        let temp_dir = Path::new(root).join(TEMP_DIR);
+        if temp_dir.exists() {
+            std::fs::remove_dir_all(&temp_dir)?;
+        }
+        let cache = LruDiskCache::new(root, capacity)?;
+        std::fs::create_dir_all(&temp_dir)?;
+        Ok(Self {
+            cache: Arc::new(RwLock::new(cache)),
+            temp_dir,
+            tenant_id: tenant_id.into(),
+            cluster_id: cluster_id.into(),
+        })
+    }
+
+    /// Reads `len` bytes at `offset` of the file at `path`, from the local disk if cached,
+    /// otherwise from the storage, and populates the cache.
+    pub async fn read_range(
+        &self,
+        data_accessor: &Operator,
+        path: &str,
+        offset: u64,
+        len: u64,
+    ) -> Result<Vec<u8>> {
+        let mut metrics = DiskCacheDeferMetrics {
+            tenant_label: TenantLabel {
+                tenant_id: &self.tenant_id,
+                cluster_id: &self.cluster_id,
+            },
+            cache_hit: false,
+            read_bytes: len,
+        };
+
+        // the key is a path relative to the root of the cache
+        let key = format!("{}_{}_{}", path.trim_start_matches('/'), offset, len);
+        if let Some(bytes) = self.get(&key, len).await {
+            metrics.cache_hit = true;
+            return Ok(bytes);
+        }
+
+        let mut reader = data_accessor
+            .read(path)
+            .offset(offset)
+            .size(len)
+            .run()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+        let mut bytes = Vec::with_capacity(len as usize);
+        reader.read_to_end(&mut bytes).await?;
+
+        self.insert(&key, bytes).await
+    }
+
+    /// Returns the number of cached ranges.
+    pub fn len(&self) -> usize {
+        self.cache.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.read().is_empty()
+    }
+
+    async fn get(&self, key: &str, len: u64) -> Option<Vec<u8>> {
+        // an opened file stays readable even if it's evicted while being read
+        let mut file = self.cache.write().get_file(key).ok()?;
+        let bytes = tokio::task::spawn_blocking(move || {
+            let mut bytes = Vec::with_capacity(len as usize);
+            file.read_to_end(&mut bytes).map(|_| bytes)
+        })
+        .await;
+
+        match bytes {
+            Ok(Ok(bytes)) if bytes.len() as u64 == len => Some(bytes),
+            // the cached file is broken, reads it from the storage again
+            _ => {
+                let _ = self.cache.write().remove(key);
+                None
+            }
+        }
+    }
+
+    /// Writes the bytes into a temporary file, and moves it into the cache, returns the bytes.
+    ///
+    /// Failing to populate the cache does not fail the read.
+    async fn insert(&self, key: &str, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        let temp_path = self.temp_dir.join(Uuid::new_v4().simple().to_string());
+        let (bytes, written) = tokio::task::spawn_blocking(move || {
+            let written = std::fs::write(&temp_path, &bytes).map(|_| temp_path);
+            (bytes, written)
+        })
+        .await
+        .map_err(|e| ErrorCode::TokioError(e.to_string()))?;
+
+        let temp_path = match written {
+            Ok(temp_path) => temp_path,
+            Err(e) => {
+                tracing::warn!("failed to cache {} in disk cache: {}", key, e);
+                return Ok(bytes);
+            }
+        };
+
+        let inserted = self.cache.write().insert_file(key, &temp_path);
+        if let Err(e) = inserted {
+            tracing::warn!("failed to cache {} in disk cache: {}", key, e);
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        Ok(bytes)
+    }
+}
@@ -57,3 +57,41 @@ impl Drop for CacheDeferMetrics<'_> {
         }
     }
 }
+
+const DISK_CACHE_READ_BYTES_FROM_REMOTE: &str = "disk_cache_read_bytes_from_remote";
+const DISK_CACHE_READ_BYTES_FROM_LOCAL: &str = "disk_cache_read_bytes_from_local";
+const DISK_CACHE_ACCESS_COUNT: &str = "disk_cache_access_count";
+const DISK_CACHE_ACCESS_HIT_COUNT: &str = "disk_cache_access_hit_count";
+
+/// Metrics of the disk cache of block data, which are measured by the bytes read.
+pub struct DiskCacheDeferMetrics<'a> {
+    pub tenant_label: TenantLabel<'a>,
+    pub cache_hit: bool,
+    pub read_bytes: u64,
+}
+
+impl Drop for DiskCacheDeferMetrics<'_> {
+    fn drop(&mut self) {
+        let label = &self.tenant_label;
+        let tenant_id = label.tenant_id;
+        let cluster_id = label.cluster_id;
+
+        label_counter(DISK_CACHE_ACCESS_COUNT, tenant_id, cluster_id);
+        if self.cache_hit {
+            label_counter(DISK_CACHE_ACCESS_HIT_COUNT, tenant_id, cluster_id);
+            label_counter_with_val(
+                DISK_CACHE_READ_BYTES_FROM_LOCAL,
+                self.read_bytes,
+                tenant_id,
+                cluster_id,
+            );
+        } else {
+            label_counter_with_val(
+                DISK_CACHE_READ_BYTES_FROM_REMOTE,
+                self.read_bytes,
+                tenant_id,
+                cluster_id,
+            );
+        }
+    }
+}
@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod disk_cache;
 mod local_cache;
 mod metrics;
 
+pub use disk_cache::BlockDataCache;
 pub use local_cache::new_memory_cache;
 pub use local_cache::CachedReader;
 pub use local_cache::HasTenantLabel;
@@ -19,12 +19,21 @@ pub const TBL_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD: &str = "BLOCK_SIZE_THRESHOLD"
 
 pub const TBL_OPT_KEY_BLOCK_PER_SEGMENT: &str = "BLOCK_PER_SEGMENT";
 pub const TBL_OPT_KEY_ROW_PER_BLOCK: &str = "ROW_PER_BLOCK";
+pub const TBL_OPT_KEY_COMPRESSION: &str = "COMPRESSION";
+pub const TBL_OPT_KEY_COLUMN_CODECS: &str = "COLUMN_CODECS";
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
+pub const FUSE_TBL_BLOOM_FILTER_INDEX_PREFIX: &str = "_i";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
+pub const FUSE_TBL_STATISTICS_PREFIX: &str = "_ts";
 
 pub const DEFAULT_CHUNK_BLOCK_NUM: usize = 1000;
 
 pub const DEFAULT_BLOCK_PER_SEGMENT: usize = 1000;
 pub const DEFAULT_ROW_PER_BLOCK: usize = 1000 * 1000;
 pub const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
+
+// orphan files younger than it might belong to insertions in progress
+pub const DEFAULT_ORPHAN_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+// a shorter retention risks removing the files of the insertions not committed yet
+pub const MIN_ORPHAN_RETENTION_SECS: u64 = 60 * 60;
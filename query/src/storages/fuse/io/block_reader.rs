@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
 
 use common_arrow::arrow::datatypes::DataType as ArrowType;
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
@@ -26,14 +31,21 @@ use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_tracing::tracing;
 use common_tracing::tracing::debug_span;
 use common_tracing::tracing::Instrument;
 use futures::io::BufReader;
+use futures::io::Cursor;
+use futures::AsyncRead;
+use futures::AsyncSeek;
 use futures::StreamExt;
 use futures::TryStreamExt;
 
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::storages::fuse::cache::BlockDataCache;
 use crate::storages::fuse::io::meta_readers::BlockMetaReader;
+use crate::storages::fuse::meta::ColumnId;
 
 pub struct BlockReader {
     data_accessor: Operator,
@@ -42,9 +54,11 @@ pub struct BlockReader {
     table_schema: DataSchemaRef,
     arrow_table_schema: ArrowSchema,
     projection: Vec<usize>,
+    col_mapping: Option<Vec<Option<ColumnId>>>,
     file_len: u64,
     read_buffer_size: u64,
     metadata_reader: BlockMetaReader,
+    data_cache: Option<BlockDataCache>,
 }
 
 impl BlockReader {
@@ -66,12 +80,29 @@ impl BlockReader {
             table_schema,
             arrow_table_schema,
             projection,
+            col_mapping: None,
             file_len,
             read_buffer_size,
             metadata_reader: reader,
+            data_cache: None,
         }
     }
 
+    /// Sets the positions of the table columns in the block file, for the blocks written
+    /// before the schema of the table changed.
+    #[must_use]
+    pub fn with_col_mapping(mut self, col_mapping: Option<Vec<Option<ColumnId>>>) -> Self {
+        self.col_mapping = col_mapping;
+        self
+    }
+
+    /// Reads the column chunks through the local disk cache, if there is one.
+    #[must_use]
+    pub fn with_data_cache(mut self, data_cache: Option<BlockDataCache>) -> Self {
+        self.data_cache = data_cache;
+        self
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&mut self) -> Result<DataBlock> {
         let block_meta = &self.metadata_reader.read(self.path.as_str()).await?;
@@ -88,12 +119,25 @@ impl BlockReader {
             &metadata.row_groups[0]
         };
 
-        let col_num = self.projection.len();
-        let cols = self
-            .projection
-            .clone()
-            .into_iter()
-            .map(|idx| (row_group.column(idx).clone(), idx));
+        // the columns absent in the block file are filled with their default values
+        let mut present = Vec::with_capacity(self.projection.len());
+        let mut absent = Vec::new();
+        for (pos, idx) in self.projection.iter().enumerate() {
+            let physical_idx = match &self.col_mapping {
+                None => Some(*idx),
+                Some(mapping) => mapping.get(*idx).cloned().flatten().map(|v| v as usize),
+            };
+            match physical_idx {
+                Some(physical_idx) if physical_idx < row_group.columns().len() => {
+                    present.push((row_group.column(physical_idx).clone(), *idx))
+                }
+                _ => absent.push((pos, *idx)),
+            }
+        }
+
+        let col_num = present.len();
+        let num_rows = row_group.num_rows() as usize;
+        let cols = present.into_iter();
 
         let fields = self.table_schema.fields();
         let arrow_fields = self.arrow_table_schema.fields();
@@ -103,31 +147,54 @@ impl BlockReader {
         let stream = futures::stream::iter(cols).map(|(col_meta, idx)| {
             let data_accessor = self.data_accessor.clone();
             let path = self.path.clone();
+            let data_cache = self.data_cache.clone();
             async move {
-                let reader = SeekableReader::new(data_accessor, path.as_str(), stream_len);
-                let reader = BufReader::with_capacity(read_buffer_size as usize, reader);
-                let data_type = fields[idx].data_type();
-                let arrow_type = arrow_fields[idx].data_type();
-                Self::read_column(reader, &col_meta, data_type.clone(), arrow_type.clone()).await
+                let data_type = fields[idx].data_type().clone();
+                let arrow_type = arrow_fields[idx].data_type().clone();
+                match data_cache {
+                    Some(cache) => {
+                        let (offset, len) = col_meta.byte_range();
+                        let bytes = cache
+                            .read_range(&data_accessor, path.as_str(), offset, len)
+                            .await?;
+                        let reader = ColumnChunkCursor::new(offset, bytes);
+                        Self::read_column(reader, &col_meta, data_type, arrow_type).await
+                    }
+                    None => {
+                        let reader = SeekableReader::new(data_accessor, path.as_str(), stream_len);
+                        let reader = BufReader::with_capacity(read_buffer_size as usize, reader);
+                        Self::read_column(reader, &col_meta, data_type, arrow_type).await
+                    }
+                }
             }
             .instrument(debug_span!("block_reader_read_column").or_current())
         });
 
         // TODO configuration of the buffer size
         let buffer_size = 10;
-        let n = std::cmp::min(buffer_size, col_num);
-        let data_cols = stream.buffered(n).try_collect().await?;
+        let n = std::cmp::max(1, std::cmp::min(buffer_size, col_num));
+        let mut data_cols: Vec<ColumnRef> = stream.buffered(n).try_collect().await?;
+
+        for (pos, idx) in absent {
+            let field = &fields[idx];
+            let value = column_default_value(field)?;
+            let column = field.data_type().create_constant_column(&value, num_rows)?;
+            data_cols.insert(pos, column);
+        }
 
         let block = DataBlock::create(self.block_schema.clone(), data_cols);
         Ok(block)
     }
 
-    async fn read_column(
-        mut reader: BufReader<SeekableReader>,
+    async fn read_column<R>(
+        mut reader: R,
         column_chunk_meta: &ColumnChunkMetaData,
         data_type: DataTypePtr,
         arrow_type: ArrowType,
-    ) -> Result<ColumnRef> {
+    ) -> Result<ColumnRef>
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send,
+    {
         let col_pages = get_page_stream(
             column_chunk_meta,
             &mut reader,
@@ -152,3 +219,81 @@ impl BlockReader {
         }
     }
 }
+
+/// The bytes of one column chunk, addressed by their offsets in the block file.
+struct ColumnChunkCursor {
+    offset: u64,
+    inner: Cursor<Vec<u8>>,
+}
+
+impl ColumnChunkCursor {
+    fn new(offset: u64, bytes: Vec<u8>) -> Self {
+        Self {
+            offset,
+            inner: Cursor::new(bytes),
+        }
+    }
+}
+
+impl AsyncRead for ColumnChunkCursor {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for ColumnChunkCursor {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let offset = self.offset;
+        let pos = match pos {
+            SeekFrom::Start(p) if p < offset => {
+                return Poll::Ready(Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("seek to {} before the column chunk at {}", p, offset),
+                )));
+            }
+            SeekFrom::Start(p) => SeekFrom::Start(p - offset),
+            other => other,
+        };
+        Pin::new(&mut self.inner)
+            .poll_seek(cx, pos)
+            .map_ok(|p| p + offset)
+    }
+}
+
+/// Evaluates the default value of a column, for the rows written before the column is added.
+pub fn column_default_value(field: &DataField) -> Result<DataValue> {
+    let expr = match field.default_expr() {
+        None => return Ok(field.data_type().default_value()),
+        Some(expr) => serde_json::from_slice::<Expression>(expr)?,
+    };
+
+    let expr = Expression::Alias(
+        field.name().to_string(),
+        Box::new(Expression::Cast {
+            expr: Box::new(expr),
+            data_type: field.data_type().clone(),
+            is_nullable: field.is_nullable(),
+        }),
+    );
+
+    // the default expression refers to no column, evaluate it against a block of one row
+    let dummy_schema = DataSchemaRefExt::create(vec![DataField::new("_dummy", u8::to_data_type())]);
+    let dummy_block = DataBlock::create(dummy_schema.clone(), vec![Series::from_data(vec![0u8])]);
+    let executor = ExpressionExecutor::try_create(
+        "column default value executor",
+        dummy_schema,
+        DataSchemaRefExt::create(vec![field.clone()]),
+        vec![expr],
+        true,
+    )?;
+    let block = executor.execute(&dummy_block)?;
+    Ok(block.column(0).get(0))
+}
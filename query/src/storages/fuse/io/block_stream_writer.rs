@@ -26,7 +26,9 @@ use futures::StreamExt;
 use futures::TryStreamExt;
 
 use crate::storages::fuse::io::block_writer;
-use crate::storages::fuse::io::locations::gen_block_location;
+use crate::storages::fuse::io::BlockWriteOptions;
+use crate::storages::fuse::io::ClusterKeySorter;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::Statistics;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
@@ -40,15 +42,22 @@ pub struct BlockStreamWriter {
     data_schema: Arc<DataSchema>,
     number_of_blocks_accumulated: usize,
     statistics_accumulator: Option<StatisticsAccumulator>,
+    cluster_key_sorter: ClusterKeySorter,
+    write_options: BlockWriteOptions,
+    location_generator: TableMetaLocationGenerator,
 }
 
 impl BlockStreamWriter {
+    #[allow(clippy::too_many_arguments)]
     pub async fn write_block_stream(
         data_accessor: Operator,
         block_stream: SendableDataBlockStream,
         data_schema: Arc<DataSchema>,
         row_per_block: usize,
         block_per_segment: usize,
+        cluster_key_sorter: ClusterKeySorter,
+        write_options: BlockWriteOptions,
+        location_generator: TableMetaLocationGenerator,
     ) -> SegmentInfoStream {
         // filter out empty blocks
         let block_stream =
@@ -62,9 +71,12 @@ impl BlockStreamWriter {
             .map_ok(|vs| futures::stream::iter(vs.into_iter().map(Ok)))
             .try_flatten();
 
-        // Write out the blocks.
+        // Write out the blocks, which are sorted by the cluster keys if there are.
         // And transform the stream of DataBlocks into Stream of SegmentInfo at the same time.
-        let block_writer = BlockStreamWriter::new(block_per_segment, data_accessor, data_schema);
+        let block_writer = BlockStreamWriter::new(block_per_segment, data_accessor, data_schema)
+            .with_cluster_key_sorter(cluster_key_sorter)
+            .with_write_options(write_options)
+            .with_location_generator(location_generator);
         let segments = Self::transform(Box::pin(block_stream), block_writer);
 
         Box::pin(segments)
@@ -81,9 +93,30 @@ impl BlockStreamWriter {
             data_schema,
             number_of_blocks_accumulated: 0,
             statistics_accumulator: None,
+            cluster_key_sorter: ClusterKeySorter::default(),
+            write_options: BlockWriteOptions::default(),
+            location_generator: TableMetaLocationGenerator::default(),
         }
     }
 
+    pub fn with_cluster_key_sorter(mut self, cluster_key_sorter: ClusterKeySorter) -> Self {
+        self.cluster_key_sorter = cluster_key_sorter;
+        self
+    }
+
+    pub fn with_write_options(mut self, write_options: BlockWriteOptions) -> Self {
+        self.write_options = write_options;
+        self
+    }
+
+    pub fn with_location_generator(
+        mut self,
+        location_generator: TableMetaLocationGenerator,
+    ) -> Self {
+        self.location_generator = location_generator;
+        self
+    }
+
     /// Transforms a stream of S to a stream of T
     ///
     /// It's more like [Stream::filter_map] than [Stream::map] in the sense
@@ -113,13 +146,28 @@ impl BlockStreamWriter {
     }
 
     pub async fn write_block(&mut self, block: DataBlock) -> Result<Option<SegmentInfo>> {
+        let (block, cluster_stats) = self.cluster_key_sorter.sort(block)?;
+        let bloom_filter_index_location = block_writer::write_bloom_filter_index(
+            &block,
+            self.data_accessor.clone(),
+            &self.location_generator,
+        )
+        .await?;
         let mut acc = self.statistics_accumulator.take().unwrap_or_default();
-        let partial_acc = acc.begin(&block)?;
+        let partial_acc = acc
+            .begin(&block)?
+            .with_cluster_stats(cluster_stats)
+            .with_bloom_filter_index_location(bloom_filter_index_location);
         let schema = block.schema().to_arrow();
-        let location = gen_block_location();
-        let file_size =
-            block_writer::write_block(&schema, block, self.data_accessor.clone(), &location)
-                .await?;
+        let location = self.location_generator.gen_block_location();
+        let file_size = block_writer::write_block(
+            &schema,
+            block,
+            self.data_accessor.clone(),
+            &location,
+            &self.write_options,
+        )
+        .await?;
         acc = partial_acc.end(file_size, location);
         self.number_of_blocks_accumulated += 1;
         if self.number_of_blocks_accumulated >= self.num_block_threshold {
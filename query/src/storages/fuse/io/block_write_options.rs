@@ -0,0 +1,163 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::io::parquet::write::Compression;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::TBL_OPT_KEY_COLUMN_CODECS;
+use crate::storages::fuse::TBL_OPT_KEY_COMPRESSION;
+
+/// The codec of one column of the block files, declared by `CODEC(...)` on the column.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColumnCodec {
+    /// Overrides the compression of the table if set.
+    pub compression: Option<Compression>,
+    /// Writes the column as a dictionary page and the RLE encoded keys.
+    pub dictionary: bool,
+}
+
+impl ColumnCodec {
+    pub fn try_create(codecs: &[String]) -> Result<Self> {
+        let mut codec = ColumnCodec::default();
+        for name in codecs {
+            if name.eq_ignore_ascii_case("DICTIONARY") {
+                codec.dictionary = true;
+                continue;
+            }
+
+            if codec.compression.is_some() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "At most one compression can be specified in CODEC({})",
+                    codecs.join(", ")
+                )));
+            }
+            codec.compression = Some(parse_compression(name)?);
+        }
+        Ok(codec)
+    }
+}
+
+/// How the blocks of a FUSE table are encoded and compressed.
+///
+/// It is taken from the table options `COMPRESSION` and `COLUMN_CODECS`, so the blocks written
+/// by insertions, mutations and `OPTIMIZE` are all encoded in the same way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockWriteOptions {
+    compression: Compression,
+    column_codecs: HashMap<String, ColumnCodec>,
+}
+
+impl Default for BlockWriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: Compression::Lz4,
+            column_codecs: HashMap::new(),
+        }
+    }
+}
+
+impl BlockWriteOptions {
+    pub fn try_create(options: &HashMap<String, String>) -> Result<Self> {
+        let mut write_options = Self::default();
+        for (key, value) in options {
+            if key.eq_ignore_ascii_case(TBL_OPT_KEY_COMPRESSION) {
+                write_options.compression = parse_compression(value)?;
+            } else if key.eq_ignore_ascii_case(TBL_OPT_KEY_COLUMN_CODECS) {
+                for (column, codecs) in parse_column_codecs(value)? {
+                    let codec = ColumnCodec::try_create(&codecs)?;
+                    write_options.column_codecs.insert(column, codec);
+                }
+            }
+        }
+        Ok(write_options)
+    }
+
+    /// Makes sure the codecs refer to the columns of the table, and the dictionary encoding
+    /// is only applied to the String columns.
+    pub fn validate(&self, schema: &DataSchemaRef) -> Result<()> {
+        for (column, codec) in &self.column_codecs {
+            let field = schema.field_with_name(column)?;
+            let data_type = remove_nullable(field.data_type());
+            if codec.dictionary && !data_type.data_type_id().is_string() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "DICTIONARY encoding is only supported by String columns, but column {} is {}",
+                    column,
+                    data_type.name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The compression of the table, used by the columns without their own codecs.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub fn column_compression(&self, column: &str) -> Compression {
+        self.column_codecs
+            .get(column)
+            .and_then(|codec| codec.compression)
+            .unwrap_or(self.compression)
+    }
+
+    /// Whether the column is dictionary encoded, the columns of the other types than
+    /// strings (which might be changed by `ALTER TABLE`) are always written plainly.
+    pub fn is_dictionary_encoded(&self, field: &ArrowField) -> bool {
+        let is_string = matches!(
+            &field.data_type,
+            ArrowDataType::Utf8
+                | ArrowDataType::LargeUtf8
+                | ArrowDataType::Binary
+                | ArrowDataType::LargeBinary
+        );
+        is_string
+            && self
+                .column_codecs
+                .get(&field.name)
+                .map(|codec| codec.dictionary)
+                .unwrap_or(false)
+    }
+}
+
+/// Parses the value of the table option `COLUMN_CODECS`, e.g. `{"c1":["DICTIONARY","ZSTD"]}`.
+pub fn parse_column_codecs(value: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    serde_json::from_str(value).map_err(|e| {
+        ErrorCode::BadArguments(format!(
+            "Invalid value of table option {}: {}, {}",
+            TBL_OPT_KEY_COLUMN_CODECS, value, e
+        ))
+    })
+}
+
+fn parse_compression(name: &str) -> Result<Compression> {
+    match name.to_lowercase().as_str() {
+        "none" | "uncompressed" => Ok(Compression::Uncompressed),
+        "lz4" => Ok(Compression::Lz4),
+        "zstd" => Ok(Compression::Zstd),
+        "snappy" => Ok(Compression::Snappy),
+        "gzip" => Ok(Compression::Gzip),
+        _ => Err(ErrorCode::BadArguments(format!(
+            "Unknown compression {}, expecting one of NONE, LZ4, ZSTD, SNAPPY and GZIP",
+            name
+        ))),
+    }
+}
@@ -13,50 +13,158 @@
 //  limitations under the License.
 //
 
+use std::io::Write;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::compute::cast::cast;
 use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::IntegerType;
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::error::ArrowError;
 use common_arrow::arrow::io::parquet::write::WriteOptions;
 use common_arrow::arrow::io::parquet::write::*;
 use common_arrow::arrow::record_batch::RecordBatch;
 use common_arrow::parquet::encoding::Encoding;
+use common_arrow::parquet::error::ParquetError;
+use common_base::tokio;
+use common_base::tokio::sync::mpsc;
 use common_dal2::Operator;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::io::Cursor;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::io::BlockWriteOptions;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::index::BloomFilterIndexer;
+
+/// The size of the chunks of the parquet file, which are sent to the storage while the rest of
+/// the file is being serialized.
+const WRITE_CHUNK_SIZE: usize = 1024 * 1024;
 
 pub async fn write_block(
     arrow_schema: &ArrowSchema,
     block: DataBlock,
     data_accessor: Operator,
     location: &str,
+    write_options: &BlockWriteOptions,
+) -> Result<u64> {
+    let arrow_schema = arrow_schema.clone();
+    let write_options = write_options.clone();
+    let batch = RecordBatch::try_from(block)?;
+
+    // The parquet file is serialized by a blocking task and uploaded chunk by chunk at the same
+    // time, so the whole file is never buffered in memory.
+    let (tx, rx) = mpsc::channel(2);
+    let serializer = tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkWriter::new(tx);
+        let res = serialize_block(&arrow_schema, batch, &write_options, &mut writer);
+        if let Err(cause) = &res {
+            // fails the upload, instead of leaving a truncated file
+            writer.abort(cause);
+        }
+        (res, writer.upload_closed)
+    });
+
+    let chunks = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    });
+    let reader = Box::pin(chunks).into_async_read();
+    let uploaded = data_accessor
+        .write_stream(location)
+        .run(Box::new(reader))
+        .await;
+
+    // The reader is dropped once the upload returns, so the serializer never blocks here.
+    let (serialized, upload_closed) = serializer
+        .await
+        .map_err(|e| ErrorCode::TokioError(e.to_string()))?;
+    match uploaded {
+        Ok(_) => serialized,
+        // the serialization is aborted by the failed upload
+        Err(e) if upload_closed => Err(ErrorCode::DalTransportError(e.to_string())),
+        // the upload is failed by the serialization, whose error is kept
+        Err(e) => Err(serialized
+            .err()
+            .unwrap_or_else(|| ErrorCode::DalTransportError(e.to_string()))),
+    }
+}
+
+/// Builds the bloom filters of the block and writes them into an index file, returns the
+/// location of the index, or None if no column of the block has a bloom filter.
+pub async fn write_bloom_filter_index(
+    block: &DataBlock,
+    data_accessor: Operator,
+    location_generator: &TableMetaLocationGenerator,
+) -> Result<Option<String>> {
+    if block.num_rows() == 0 {
+        return Ok(None);
+    }
+
+    let filters = BloomFilterIndexer::build(&[block.clone()])?;
+    if filters.is_empty() {
+        return Ok(None);
+    }
+
+    let location = location_generator.gen_bloom_filter_index_location();
+    let bytes = serde_json::to_vec(&filters)?;
+    data_accessor
+        .write(&location, bytes.len() as u64)
+        .run(Box::new(Cursor::new(bytes)))
+        .await
+        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+    Ok(Some(location))
+}
+
+fn serialize_block(
+    arrow_schema: &ArrowSchema,
+    batch: RecordBatch,
+    write_options: &BlockWriteOptions,
+    writer: &mut ChunkWriter,
 ) -> Result<u64> {
     let options = WriteOptions {
         write_statistics: true,
-        compression: Compression::Lz4, // let's begin with lz4
+        compression: write_options.compression(),
         version: Version::V2,
     };
-    let batch = RecordBatch::try_from(block)?;
-    let encodings: Vec<_> = arrow_schema
+    let parquet_schema = to_parquet_schema(arrow_schema)?;
+
+    // The columns are compressed and encoded individually according to their codecs,
+    // which is why the row group is assembled here instead of by `RowGroupIterator`.
+    let mut columns = Vec::with_capacity(arrow_schema.fields().len());
+    for ((field, array), descriptor) in arrow_schema
         .fields()
         .iter()
-        .map(|f| col_encoding(&f.data_type))
-        .collect();
-
-    let iter = vec![Ok(batch)];
-    let row_groups = RowGroupIterator::try_new(iter.into_iter(), arrow_schema, options, encodings)?;
-    let parquet_schema = row_groups.parquet_schema().clone();
-
-    // PutObject in S3 need to know the content-length in advance
-    // multipart upload may intimidate this, but let's fit things together first
-    // see issue #xxx
+        .zip(batch.columns().iter())
+        .zip(parquet_schema.columns().iter())
+    {
+        let options = WriteOptions {
+            compression: write_options.column_compression(&field.name),
+            ..options
+        };
+        let (array, encoding) = if write_options.is_dictionary_encoded(field) {
+            let dict_type =
+                ArrowDataType::Dictionary(IntegerType::UInt32, Box::new(field.data_type.clone()));
+            let array: Arc<dyn Array> = cast(array.as_ref(), &dict_type)?.into();
+            (array, Encoding::RleDictionary)
+        } else {
+            (array.clone(), col_encoding(&field.data_type))
+        };
 
-    use bytes::BufMut;
-    // we need a configuration of block size threshold here
-    let mut writer = Vec::with_capacity(100 * 1024 * 1024).writer();
+        let pages = array_to_pages(array, descriptor.clone(), options, encoding)?;
+        let encoded_pages = DynIter::new(pages.map(|page| Ok::<_, ParquetError>(page?)));
+        let compressed_pages =
+            Compressor::new(encoded_pages, options.compression, vec![]).map_err(ArrowError::from);
+        columns.push(Ok::<_, ArrowError>(DynStreamingIterator::new(
+            compressed_pages,
+        )));
+    }
+    let row_groups = std::iter::once(Ok::<_, ArrowError>(DynIter::new(columns.into_iter())));
 
     let len = common_arrow::parquet::write::write_file(
-        &mut writer,
+        writer,
         row_groups,
         parquet_schema,
         options,
@@ -64,19 +172,64 @@ pub async fn write_block(
         None,
     )
     .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
-
-    let parquet = writer.into_inner();
-    let stream_len = parquet.len();
-
-    data_accessor
-        .write(location, stream_len as u64)
-        .run(Box::new(Cursor::new(parquet)))
-        .await
+    writer
+        .flush()
         .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
 
     Ok(len)
 }
 
+/// Sends the bytes written into it to the storage in chunks of [WRITE_CHUNK_SIZE].
+struct ChunkWriter {
+    tx: mpsc::Sender<std::io::Result<Vec<u8>>>,
+    buf: Vec<u8>,
+    // the upload stops reading the chunks, e.g. it's failed
+    upload_closed: bool,
+}
+
+impl ChunkWriter {
+    fn new(tx: mpsc::Sender<std::io::Result<Vec<u8>>>) -> Self {
+        Self {
+            tx,
+            buf: Vec::with_capacity(WRITE_CHUNK_SIZE),
+            upload_closed: false,
+        }
+    }
+
+    fn send_chunk(&mut self) -> std::io::Result<()> {
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(WRITE_CHUNK_SIZE));
+        self.tx.blocking_send(Ok(chunk)).map_err(|_| {
+            self.upload_closed = true;
+            std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "the upload of the block is aborted",
+            )
+        })
+    }
+
+    fn abort(&mut self, cause: &ErrorCode) {
+        let err = std::io::Error::new(std::io::ErrorKind::Other, cause.to_string());
+        let _ = self.tx.blocking_send(Err(err));
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= WRITE_CHUNK_SIZE {
+            self.send_chunk()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        self.send_chunk()
+    }
+}
+
 fn col_encoding(_data_type: &ArrowDataType) -> Encoding {
     // Although encoding does work, parquet2 has not implemented decoding of DeltaLengthByteArray yet, we fallback to Plain
     // From parquet2: Decoding "DeltaLengthByteArray"-encoded required V2 pages is not yet implemented for Binary.
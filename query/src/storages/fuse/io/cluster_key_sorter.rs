@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::storages::fuse::meta::ClusterStatistics;
+
+/// Sorts the blocks of a clustered table by the cluster keys before they are written.
+///
+/// Blocks are kept as they are if the table has no cluster keys.
+#[derive(Default)]
+pub struct ClusterKeySorter {
+    schema: Option<DataSchemaRef>,
+    executor: Option<ExpressionExecutor>,
+    key_fields: Vec<DataField>,
+}
+
+impl ClusterKeySorter {
+    pub fn try_create(schema: DataSchemaRef, cluster_keys: &[Expression]) -> Result<Self> {
+        if cluster_keys.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut exprs = Vec::with_capacity(cluster_keys.len());
+        let mut key_fields = Vec::with_capacity(cluster_keys.len());
+        for (idx, key) in cluster_keys.iter().enumerate() {
+            // the keys are evaluated into hidden columns, which never collide with the table columns
+            let name = format!("_cluster_key_{}", idx);
+            let field = key.to_data_field(&schema)?;
+            key_fields.push(DataField::new(&name, field.data_type().clone()));
+            exprs.push(Expression::Alias(name, Box::new(key.clone())));
+        }
+
+        let executor = ExpressionExecutor::try_create(
+            "cluster key expression executor",
+            schema.clone(),
+            DataSchemaRefExt::create(key_fields.clone()),
+            exprs,
+            true,
+        )?;
+        executor.validate()?;
+
+        Ok(ClusterKeySorter {
+            schema: Some(schema),
+            executor: Some(executor),
+            key_fields,
+        })
+    }
+
+    /// Returns the sorted block, and the range of the cluster keys of it.
+    pub fn sort(&self, block: DataBlock) -> Result<(DataBlock, Option<ClusterStatistics>)> {
+        let (schema, executor) = match (&self.schema, &self.executor) {
+            (Some(schema), Some(executor)) if block.num_rows() > 0 => (schema, executor),
+            _ => return Ok((block, None)),
+        };
+
+        let keys = executor.execute(&block)?;
+        let mut block_with_keys = block;
+        for (idx, field) in self.key_fields.iter().enumerate() {
+            block_with_keys =
+                block_with_keys.add_column(keys.column(idx).clone(), field.clone())?;
+        }
+
+        let sort_columns_descriptions = self
+            .key_fields
+            .iter()
+            .map(|field| SortColumnDescription {
+                column_name: field.name().clone(),
+                asc: true,
+                nulls_first: true,
+            })
+            .collect::<Vec<_>>();
+        let sorted = DataBlock::sort_block(&block_with_keys, &sort_columns_descriptions, None)?;
+
+        let last = sorted.num_rows() - 1;
+        let mut min = Vec::with_capacity(self.key_fields.len());
+        let mut max = Vec::with_capacity(self.key_fields.len());
+        for field in &self.key_fields {
+            let column = sorted.try_column_by_name(field.name())?;
+            min.push(column.get_checked(0)?);
+            max.push(column.get_checked(last)?);
+        }
+
+        let sorted = sorted.resort(schema.clone())?;
+        Ok((sorted, Some(ClusterStatistics { min, max })))
+    }
+}
@@ -16,19 +16,83 @@
 use uuid::Uuid;
 
 use crate::storages::fuse::constants::FUSE_TBL_BLOCK_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_BLOOM_FILTER_INDEX_PREFIX;
 use crate::storages::fuse::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::storages::fuse::constants::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::storages::fuse::constants::FUSE_TBL_STATISTICS_PREFIX;
 
-pub fn gen_block_location() -> String {
-    let part_uuid = Uuid::new_v4().simple().to_string() + ".parquet";
-    format!("{}/{}", FUSE_TBL_BLOCK_PREFIX, part_uuid)
+/// Generates the locations of the objects (blocks, bloom filter indexes, segments, snapshots,
+/// statistics) of a table.
+///
+/// All the objects are placed under the `prefix` of the table, so that the files which
+/// belong to a table can be enumerated by listing the prefix. An empty prefix stands for
+/// the legacy layout, in which the objects of all the tables share the same root.
+#[derive(Clone, Debug, Default)]
+pub struct TableMetaLocationGenerator {
+    prefix: String,
 }
 
-pub fn gen_segment_info_location() -> String {
-    let segment_uuid = Uuid::new_v4().simple().to_string();
-    format!("{}/{}", FUSE_TBL_SEGMENT_PREFIX, segment_uuid)
+impl TableMetaLocationGenerator {
+    pub fn with_prefix(prefix: String) -> Self {
+        Self { prefix }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn gen_block_location(&self) -> String {
+        let part_uuid = Uuid::new_v4().simple().to_string() + ".parquet";
+        self.location_of(FUSE_TBL_BLOCK_PREFIX, &part_uuid)
+    }
+
+    pub fn gen_bloom_filter_index_location(&self) -> String {
+        let index_uuid = Uuid::new_v4().simple().to_string();
+        self.location_of(FUSE_TBL_BLOOM_FILTER_INDEX_PREFIX, &index_uuid)
+    }
+
+    pub fn gen_segment_info_location(&self) -> String {
+        let segment_uuid = Uuid::new_v4().simple().to_string();
+        self.location_of(FUSE_TBL_SEGMENT_PREFIX, &segment_uuid)
+    }
+
+    pub fn snapshot_location_from_uuid(&self, id: &Uuid) -> String {
+        self.location_of(FUSE_TBL_SNAPSHOT_PREFIX, &id.simple().to_string())
+    }
+
+    pub fn gen_table_statistics_location(&self) -> String {
+        let statistics_uuid = Uuid::new_v4().simple().to_string();
+        self.location_of(FUSE_TBL_STATISTICS_PREFIX, &statistics_uuid)
+    }
+
+    fn location_of(&self, kind_prefix: &str, name: &str) -> String {
+        if self.prefix.is_empty() {
+            format!("{}/{}", kind_prefix, name)
+        } else {
+            format!("{}/{}/{}", self.prefix, kind_prefix, name)
+        }
+    }
+}
+
+/// Location of the snapshot `id`, which is the previous snapshot of the one stored at `location`.
+///
+/// Snapshots of a table are kept in the same directory, thus the directory is derived from
+/// `location`.
+pub fn prev_snapshot_location(location: &str, id: &Uuid) -> String {
+    match location.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, id.simple()),
+        None => TableMetaLocationGenerator::default().snapshot_location_from_uuid(id),
+    }
 }
 
-pub fn snapshot_location(id: &Uuid) -> String {
-    format!("{}/{}", FUSE_TBL_SNAPSHOT_PREFIX, id.simple())
+/// Location of the snapshot stored at `location` in the legacy layout, i.e. without the
+/// table prefix, or None if `location` is already of the legacy layout.
+pub fn legacy_snapshot_location(location: &str) -> Option<String> {
+    let legacy_dir = format!("{}/", FUSE_TBL_SNAPSHOT_PREFIX);
+    if location.starts_with(&legacy_dir) {
+        return None;
+    }
+    location
+        .rsplit_once('/')
+        .map(|(_, name)| format!("{}{}", legacy_dir, name))
 }
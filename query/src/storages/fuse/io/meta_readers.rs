@@ -31,9 +31,12 @@ use crate::storages::fuse::cache::HasTenantLabel;
 use crate::storages::fuse::cache::Loader;
 use crate::storages::fuse::cache::MemoryCache;
 use crate::storages::fuse::cache::TenantLabel;
-use crate::storages::fuse::io::snapshot_location;
+use crate::storages::fuse::io::legacy_snapshot_location;
+use crate::storages::fuse::io::prev_snapshot_location;
+use crate::storages::fuse::meta::AnalyzedStatistics;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::index::BlockBloomFilters;
 
 /// Provider of [BufReader]
 ///
@@ -58,9 +61,13 @@ impl BlockMeta {
 pub type SegmentInfoCache = MemoryCache<SegmentInfo>;
 pub type TableSnapshotCache = MemoryCache<TableSnapshot>;
 pub type BlockMetaCache = MemoryCache<BlockMeta>;
+pub type AnalyzedStatisticsCache = MemoryCache<AnalyzedStatistics>;
+pub type BloomFilterIndexCache = MemoryCache<BlockBloomFilters>;
 
 pub type SegmentInfoReader<'a> = CachedReader<SegmentInfo, &'a QueryContext>;
 pub type TableSnapshotReader<'a> = CachedReader<TableSnapshot, &'a QueryContext>;
+pub type AnalyzedStatisticsReader<'a> = CachedReader<AnalyzedStatistics, &'a QueryContext>;
+pub type BloomFilterIndexReader<'a> = CachedReader<BlockBloomFilters, &'a QueryContext>;
 
 /// A sugar type of BlockMeta reader
 ///
@@ -89,6 +96,24 @@ impl MetaReaders {
         )
     }
 
+    pub fn analyzed_statistics_reader(ctx: &QueryContext) -> AnalyzedStatisticsReader {
+        AnalyzedStatisticsReader::new(
+            ctx.get_storage_cache_manager()
+                .get_analyzed_statistics_cache(),
+            ctx,
+            "ANALYZED_STATISTICS_CACHE".to_owned(),
+        )
+    }
+
+    pub fn bloom_filter_index_reader(ctx: &QueryContext) -> BloomFilterIndexReader {
+        BloomFilterIndexReader::new(
+            ctx.get_storage_cache_manager()
+                .get_bloom_filter_index_cache(),
+            ctx,
+            "BLOOM_FILTER_INDEX_CACHE".to_owned(),
+        )
+    }
+
     pub fn block_meta_reader(ctx: Arc<QueryContext>) -> BlockMetaReader {
         BlockMetaReader::new(
             ctx.get_storage_cache_manager().get_block_meta_cache(),
@@ -103,20 +128,42 @@ impl<'a> TableSnapshotReader<'a> {
         &self,
         latest_snapshot_location: Option<&String>,
     ) -> Result<Vec<Arc<TableSnapshot>>> {
+        let history = self
+            .read_snapshot_history_with_locations(latest_snapshot_location)
+            .await?;
+        Ok(history.into_iter().map(|(_, snapshot)| snapshot).collect())
+    }
+
+    /// Walks back the snapshot chain, latest first, and returns the snapshots along with
+    /// their locations.
+    pub async fn read_snapshot_history_with_locations(
+        &self,
+        latest_snapshot_location: Option<&String>,
+    ) -> Result<Vec<(String, Arc<TableSnapshot>)>> {
         let mut snapshots = vec![];
         let mut current_snapshot_location = latest_snapshot_location.cloned();
         while let Some(loc) = current_snapshot_location {
-            let r = self.read(loc).await;
-            let snapshot = match r {
-                Ok(s) => s,
+            let r = match self.read(&loc).await {
+                // tables created before the objects were placed under the table prefix,
+                // may still refer to snapshots of the legacy layout
+                Err(e) if e.code() == ErrorCode::dal_path_not_found_code() => {
+                    match legacy_snapshot_location(&loc) {
+                        Some(legacy) => self.read(&legacy).await.map(|s| (legacy, s)),
+                        None => Err(e),
+                    }
+                }
+                r => r.map(|s| (loc, s)),
+            };
+            let (loc, snapshot) = match r {
+                Ok(v) => v,
                 Err(e) if e.code() == ErrorCode::dal_path_not_found_code() => {
                     break;
                 }
                 Err(e) => return Err(e),
             };
             let prev = snapshot.prev_snapshot_id;
-            snapshots.push(snapshot);
-            current_snapshot_location = prev.map(|id| snapshot_location(&id));
+            current_snapshot_location = prev.map(|id| prev_snapshot_location(&loc, &id));
+            snapshots.push((loc, snapshot));
         }
         Ok(snapshots)
     }
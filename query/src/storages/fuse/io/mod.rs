@@ -14,17 +14,31 @@
 
 mod block_reader;
 mod block_stream_writer;
+mod block_write_options;
 mod block_writer;
+mod cluster_key_sorter;
 mod locations;
 mod meta_readers;
 
+pub use block_reader::column_default_value;
 pub use block_reader::BlockReader;
 pub use block_stream_writer::BlockRegulator;
 pub use block_stream_writer::BlockStreamWriter;
 pub use block_stream_writer::SegmentInfoStream;
-pub use locations::gen_segment_info_location;
-pub use locations::snapshot_location;
+pub use block_write_options::parse_column_codecs;
+pub use block_write_options::BlockWriteOptions;
+pub use block_write_options::ColumnCodec;
+pub use block_writer::write_block;
+pub use block_writer::write_bloom_filter_index;
+pub use cluster_key_sorter::ClusterKeySorter;
+pub use locations::legacy_snapshot_location;
+pub use locations::prev_snapshot_location;
+pub use locations::TableMetaLocationGenerator;
+pub use meta_readers::AnalyzedStatisticsCache;
+pub use meta_readers::AnalyzedStatisticsReader;
 pub use meta_readers::BlockMetaCache;
+pub use meta_readers::BloomFilterIndexCache;
+pub use meta_readers::BloomFilterIndexReader;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoCache;
 pub use meta_readers::SegmentInfoReader;
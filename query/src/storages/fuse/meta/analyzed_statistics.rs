@@ -0,0 +1,40 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::SnapshotId;
+use crate::storages::index::ColumnDistribution;
+
+/// Statistics collected by `ANALYZE TABLE`, referenced by the snapshots committed since then.
+///
+/// They are not maintained by the later insertions or mutations, until the table is analyzed again.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AnalyzedStatistics {
+    /// id of the snapshot being analyzed
+    pub snapshot_id: SnapshotId,
+
+    /// The time at which the table is analyzed
+    pub timestamp: DateTime<Utc>,
+
+    pub row_count: u64,
+
+    pub col_distributions: HashMap<ColumnId, ColumnDistribution>,
+}
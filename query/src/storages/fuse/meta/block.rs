@@ -15,6 +15,8 @@
 
 use std::collections::HashMap;
 
+use common_datavalues2::DataValue;
+
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::index::ColumnStatistics;
 
@@ -26,9 +28,30 @@ pub struct BlockMeta {
     pub block_size: u64,
     pub file_size: u64,
     pub col_stats: HashMap<ColumnId, ColumnStatistics>,
+    /// Location of the bloom filter index of the block, which is kept apart so that the
+    /// segments stay small. Absent if no column of the block has a bloom filter.
+    #[serde(default)]
+    pub bloom_filter_index_location: Option<String>,
+    /// Range of the cluster keys, absent if the table is not clustered
+    #[serde(default)]
+    pub cluster_stats: Option<ClusterStatistics>,
+    /// Positions of the table columns in the block file, `None` for the columns added after the
+    /// block was written. Absent if the block is laid out as the table schema.
+    #[serde(default)]
+    pub col_mapping: Option<Vec<Option<ColumnId>>>,
     pub location: BlockLocation,
 }
 
+/// The smallest and the largest values of the cluster keys in a block.
+///
+/// The rows of a block are sorted by the cluster keys, they are the keys of the first
+/// and the last row.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ClusterStatistics {
+    pub min: Vec<DataValue>,
+    pub max: Vec<DataValue>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct BlockLocation {
     pub path: String,
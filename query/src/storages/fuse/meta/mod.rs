@@ -13,12 +13,15 @@
 //  limitations under the License.
 //
 
+mod analyzed_statistics;
 mod block;
 mod segment;
 mod snapshot;
 
+pub use analyzed_statistics::AnalyzedStatistics;
 pub use block::BlockLocation;
 pub use block::BlockMeta;
+pub use block::ClusterStatistics;
 pub use segment::SegmentInfo;
 pub use snapshot::ColumnId;
 pub use snapshot::Location;
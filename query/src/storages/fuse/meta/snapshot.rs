@@ -14,6 +14,8 @@
 
 use std::collections::HashMap;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_datavalues2::DataSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -34,6 +36,12 @@ pub struct TableSnapshot {
 
     pub prev_snapshot_id: Option<SnapshotId>,
 
+    /// The time at which the snapshot is committed
+    ///
+    /// Snapshots generated by previous versions do not have it.
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+
     /// For each snapshot, we keep a schema for it (in case of schema evolution)
     pub schema: DataSchema,
 
@@ -42,9 +50,13 @@ pub struct TableSnapshot {
 
     /// Pointers to SegmentInfos (may be of different format)
     ///
-    /// We rely on background merge tasks to keep merging segments, so that
+    /// We rely on compaction (`OPTIMIZE TABLE .. COMPACT`) to keep merging segments, so that
     /// this the size of this vector could be kept reasonable
     pub segments: Vec<Location>,
+
+    /// Location of the statistics collected by the latest `ANALYZE TABLE`, if any
+    #[serde(default)]
+    pub analyzed_statistics_location: Option<Location>,
 }
 
 impl TableSnapshot {
@@ -23,6 +23,10 @@ mod table;
 mod table_functions;
 
 pub use constants::*;
+pub use table::is_fuse_table;
 pub use table::FuseTable;
+pub use table_functions::ClusteringInformation;
+pub use table_functions::ClusteringInformationTable;
 pub use table_functions::FuseHistoryTable;
+pub use table_functions::FUSE_FUNC_CLUSTERING;
 pub use table_functions::FUSE_FUNC_HIST;
@@ -0,0 +1,312 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UpdateTableMetaReq;
+use common_planners::AlterTableAction;
+use common_planners::AlterTablePlan;
+use common_planners::Expression;
+use common_planners::RewriteHelper;
+use futures::io::Cursor;
+use uuid::Uuid;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io;
+use crate::storages::fuse::io::column_default_value;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::meta::Statistics;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::statistics;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::TBL_OPT_KEY_COLUMN_CODECS;
+use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::storages::index::ColumnStatistics;
+
+impl FuseTable {
+    /// Changes the schema of the table, without touching the block files.
+    ///
+    /// Statistics of blocks are keyed by the positions of columns, so (unless the positions are
+    /// kept, e.g. renaming a column) the segments of the current snapshot are rewritten: the
+    /// statistics are remapped to the new positions, and each block records in `col_mapping`
+    /// where the columns of the new schema are located in its file. Columns that are absent
+    /// from a block are filled with their default values while reading.
+    pub async fn do_alter(&self, ctx: Arc<QueryContext>, plan: AlterTablePlan) -> Result<()> {
+        let schema = self.table_info.schema();
+        let (new_schema, positions) = self.evolve_schema(schema.as_ref(), &plan.action)?;
+
+        let mut new_table_meta = self.table_info.meta.clone();
+        new_table_meta.schema = new_schema.clone();
+        Self::evolve_column_codecs(&mut new_table_meta.options, &plan.action)?;
+
+        if let Some(prev_snapshot) = self.read_table_snapshot(ctx.as_ref()).await? {
+            let location_generator = self.meta_location_generator();
+            let new_snapshot = Self::evolve_snapshot(
+                ctx.as_ref(),
+                &location_generator,
+                &prev_snapshot,
+                &new_schema,
+                &positions,
+            )
+            .await?;
+            let new_snapshot_loc =
+                location_generator.snapshot_location_from_uuid(&new_snapshot.snapshot_id);
+            let da = ctx.get_storage_accessor().await?;
+            let bytes = serde_json::to_vec(&new_snapshot)?;
+            da.write(&new_snapshot_loc, bytes.len() as u64)
+                .run(Box::new(Cursor::new(bytes)))
+                .await
+                .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+            new_table_meta
+                .options
+                .insert(TBL_OPT_KEY_SNAPSHOT_LOC.to_string(), new_snapshot_loc);
+        }
+
+        ctx.get_catalog()
+            .update_table_meta(UpdateTableMetaReq::new(
+                &self.table_info.ident,
+                new_table_meta,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the new schema, and for each column of it, the position of the column in the
+    /// current schema (None for the added column).
+    fn evolve_schema(
+        &self,
+        schema: &DataSchema,
+        action: &AlterTableAction,
+    ) -> Result<(DataSchemaRef, Vec<Option<usize>>)> {
+        let mut fields = schema.fields().clone();
+        let mut positions = (0..fields.len()).map(Some).collect::<Vec<_>>();
+        match action {
+            AlterTableAction::AddColumn(field) => {
+                if schema.has_field(field.name()) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column {} already exists",
+                        field.name()
+                    )));
+                }
+                fields.push(field.clone());
+                positions.push(None);
+            }
+            AlterTableAction::DropColumn(name) => {
+                let index = self.altered_column_index(schema, name)?;
+                if fields.len() == 1 {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Can not drop column {}, the only column of the table",
+                        name
+                    )));
+                }
+                fields.remove(index);
+                positions.remove(index);
+            }
+            AlterTableAction::RenameColumn { old_name, new_name } => {
+                let index = self.altered_column_index(schema, old_name)?;
+                if schema.has_field(new_name) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column {} already exists",
+                        new_name
+                    )));
+                }
+                let field = &fields[index];
+                fields[index] = DataField::new(new_name, field.data_type().clone())
+                    .with_default_expr(field.default_expr().clone());
+            }
+        }
+        Ok((DataSchemaRefExt::create(fields), positions))
+    }
+
+    /// Keeps the codecs of the columns (the table option `COLUMN_CODECS`) in step with the
+    /// dropped or renamed column.
+    fn evolve_column_codecs(
+        options: &mut HashMap<String, String>,
+        action: &AlterTableAction,
+    ) -> Result<()> {
+        let key = match options
+            .keys()
+            .find(|key| key.eq_ignore_ascii_case(TBL_OPT_KEY_COLUMN_CODECS))
+        {
+            Some(key) => key.clone(),
+            None => return Ok(()),
+        };
+
+        let mut column_codecs = io::parse_column_codecs(&options[&key])?;
+        match action {
+            AlterTableAction::AddColumn(_) => return Ok(()),
+            AlterTableAction::DropColumn(name) => {
+                column_codecs.remove(name);
+            }
+            AlterTableAction::RenameColumn { old_name, new_name } => {
+                if let Some(codecs) = column_codecs.remove(old_name) {
+                    column_codecs.insert(new_name.clone(), codecs);
+                }
+            }
+        }
+        options.insert(key, serde_json::to_string(&column_codecs)?);
+        Ok(())
+    }
+
+    /// Position of the column to be dropped or renamed, which must not be used by the cluster keys.
+    fn altered_column_index(&self, schema: &DataSchema, name: &str) -> Result<usize> {
+        let index = schema
+            .fields()
+            .iter()
+            .position(|f| f.name() == name)
+            .ok_or_else(|| ErrorCode::UnknownColumn(format!("Unknown column {}", name)))?;
+
+        for cluster_key in self.cluster_keys()? {
+            for column in RewriteHelper::expression_plan_columns(&cluster_key)? {
+                if matches!(&column, Expression::Column(column_name) if column_name == name) {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column {} is used by the cluster keys of the table",
+                        name
+                    )));
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    async fn evolve_snapshot(
+        ctx: &QueryContext,
+        location_generator: &TableMetaLocationGenerator,
+        prev_snapshot: &TableSnapshot,
+        new_schema: &DataSchemaRef,
+        positions: &[Option<usize>],
+    ) -> Result<TableSnapshot> {
+        let positions_kept = positions.len() == prev_snapshot.schema.fields().len()
+            && positions.iter().enumerate().all(|(i, p)| *p == Some(i));
+
+        let (segments, summary) = if positions_kept {
+            (
+                prev_snapshot.segments.clone(),
+                prev_snapshot.summary.clone(),
+            )
+        } else {
+            let default_values = new_schema
+                .fields()
+                .iter()
+                .zip(positions)
+                .map(|(field, position)| match position {
+                    Some(_) => Ok(DataValue::Null),
+                    None => column_default_value(field),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let da = ctx.get_storage_accessor().await?;
+            let segment_reader = MetaReaders::segment_info_reader(ctx);
+            let mut segments = Vec::with_capacity(prev_snapshot.segments.len());
+            let mut summary = Statistics::default();
+            for segment_location in &prev_snapshot.segments {
+                let segment = segment_reader.read(segment_location).await?;
+                let blocks = segment
+                    .blocks
+                    .iter()
+                    .map(|block_meta| {
+                        Self::evolve_block_meta(block_meta, positions, &default_values)
+                    })
+                    .collect::<Vec<_>>();
+                let new_segment = SegmentInfo {
+                    summary: statistics::reduce_block_metas(&blocks, new_schema.as_ref())?,
+                    blocks,
+                };
+                summary = statistics::merge_statistics(
+                    new_schema.as_ref(),
+                    &summary,
+                    &new_segment.summary,
+                )?;
+
+                let new_segment_location = location_generator.gen_segment_info_location();
+                let bytes = serde_json::to_vec(&new_segment)?;
+                da.write(&new_segment_location, bytes.len() as u64)
+                    .run(Box::new(Cursor::new(bytes)))
+                    .await
+                    .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+                segments.push(new_segment_location);
+            }
+            (segments, summary)
+        };
+
+        Ok(TableSnapshot {
+            snapshot_id: Uuid::new_v4(),
+            prev_snapshot_id: Some(prev_snapshot.snapshot_id),
+            timestamp: Some(Utc::now()),
+            schema: new_schema.as_ref().clone(),
+            summary,
+            segments,
+            // the statistics are keyed by the column ids of the previous schema
+            analyzed_statistics_location: None,
+        })
+    }
+
+    fn evolve_block_meta(
+        block_meta: &BlockMeta,
+        positions: &[Option<usize>],
+        default_values: &[DataValue],
+    ) -> BlockMeta {
+        let mut col_stats = HashMap::with_capacity(positions.len());
+        let mut col_mapping = Vec::with_capacity(positions.len());
+        for (index, position) in positions.iter().enumerate() {
+            let column_id = index as ColumnId;
+            match position {
+                Some(prev_index) => {
+                    let prev_id = *prev_index as ColumnId;
+                    if let Some(stats) = block_meta.col_stats.get(&prev_id) {
+                        col_stats.insert(column_id, stats.clone());
+                    }
+                    let physical_id = match &block_meta.col_mapping {
+                        None => Some(prev_id),
+                        Some(mapping) => mapping.get(*prev_index).cloned().flatten(),
+                    };
+                    col_mapping.push(physical_id);
+                }
+                None => {
+                    // the column is absent from the block, all its values are the default one
+                    let value = default_values[index].clone();
+                    let null_count = if value.is_null() {
+                        block_meta.row_count
+                    } else {
+                        0
+                    };
+                    col_stats.insert(column_id, ColumnStatistics {
+                        min: value.clone(),
+                        max: value,
+                        null_count,
+                        in_memory_size: 0,
+                    });
+                    col_mapping.push(None);
+                }
+            }
+        }
+
+        BlockMeta {
+            col_stats,
+            col_mapping: Some(col_mapping),
+            ..block_meta.clone()
+        }
+    }
+}
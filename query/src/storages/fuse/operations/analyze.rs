@@ -0,0 +1,197 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues2::ColumnRef;
+use common_datavalues2::DataField;
+use common_datavalues2::DataSchemaRefExt;
+use common_datavalues2::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::io::Cursor;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::AnalyzedStatistics;
+use crate::storages::fuse::meta::ColumnId;
+use crate::storages::fuse::operations::AnalyzeOperationLogEntry;
+use crate::storages::fuse::operations::TableOperation;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::ColumnDistribution;
+use crate::storages::index::Histogram;
+use crate::storages::index::HyperLogLog;
+use crate::storages::index::DEFAULT_HISTOGRAM_BUCKETS;
+
+/// The max number of values of a column sampled to build its histogram.
+const HISTOGRAM_SAMPLE_SIZE: usize = 10000;
+
+impl FuseTable {
+    /// Collects the NDV sketches and the histograms of all the columns of the current snapshot,
+    /// and commits a new snapshot which refers to them.
+    ///
+    /// The blocks are read one by one, the sketches of the blocks are merged, and the histograms
+    /// are built from a bounded sample of the values.
+    pub async fn do_analyze(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        // 1. collect the distribution of each column, block by block
+        let da = ctx.get_storage_accessor().await?;
+        let read_buffer_size = ctx.get_settings().get_storage_read_buffer_size()?;
+        let schema = self.table_info.schema();
+        let projection = (0..schema.fields().len()).collect::<Vec<usize>>();
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut collectors = schema
+            .fields()
+            .iter()
+            .map(|_| DistributionCollector::default())
+            .collect::<Vec<_>>();
+        let mut rng = StdRng::from_entropy();
+        let mut num_blocks = 0;
+        for segment_location in &snapshot.segments {
+            let segment_info = segment_reader.read(segment_location).await?;
+            for block_meta in &segment_info.blocks {
+                let mut block_reader = BlockReader::new(
+                    da.clone(),
+                    block_meta.location.path.clone(),
+                    schema.clone(),
+                    projection.clone(),
+                    block_meta.file_size,
+                    read_buffer_size,
+                    MetaReaders::block_meta_reader(ctx.clone()),
+                )
+                .with_col_mapping(block_meta.col_mapping.clone());
+                let block = block_reader.read().await.map_err(|e| {
+                    ErrorCode::ParquetError(format!(
+                        "fail to read block {}, {}",
+                        block_meta.location.path, e
+                    ))
+                })?;
+                for (idx, collector) in collectors.iter_mut().enumerate() {
+                    collector.add_column(block.column(idx), &mut rng)?;
+                }
+                num_blocks += 1;
+            }
+        }
+
+        let mut col_distributions = HashMap::with_capacity(schema.fields().len());
+        if num_blocks > 0 {
+            for (idx, (field, collector)) in schema.fields().iter().zip(collectors).enumerate() {
+                col_distributions.insert(idx as ColumnId, collector.finish(field));
+            }
+        }
+
+        // 2. persist the statistics, and attach them to a new snapshot
+        let statistics = AnalyzedStatistics {
+            snapshot_id: snapshot.snapshot_id,
+            timestamp: Utc::now(),
+            row_count: snapshot.summary.row_count,
+            col_distributions,
+        };
+        let statistics_location = self
+            .meta_location_generator()
+            .gen_table_statistics_location();
+        let bytes = serde_json::to_vec(&statistics)?;
+        da.write(&statistics_location, bytes.len() as u64)
+            .run(Box::new(Cursor::new(bytes)))
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+
+        let log_entry = AnalyzeOperationLogEntry {
+            statistics_location,
+            schema: snapshot.schema.clone(),
+        };
+        self.do_commit(ctx, TableOperation::Analyze(log_entry))
+            .await
+    }
+}
+
+/// Collects the distribution of a column block by block, keeping at most
+/// `HISTOGRAM_SAMPLE_SIZE` of its values in memory.
+#[derive(Default)]
+struct DistributionCollector {
+    sketch: HyperLogLog,
+    null_count: u64,
+    num_values: u64,
+    // a uniform sample of the non-null values, by reservoir sampling
+    sample: Vec<DataValue>,
+}
+
+impl DistributionCollector {
+    fn add_column(&mut self, column: &ColumnRef, rng: &mut impl Rng) -> Result<()> {
+        let mut sketch = HyperLogLog::new();
+        for value in column.to_values() {
+            if value.is_null() {
+                self.null_count += 1;
+                continue;
+            }
+
+            sketch.add(&value);
+            self.num_values += 1;
+            if self.sample.len() < HISTOGRAM_SAMPLE_SIZE {
+                self.sample.push(value);
+            } else {
+                let idx = rng.gen_range(0..self.num_values) as usize;
+                if idx < HISTOGRAM_SAMPLE_SIZE {
+                    self.sample[idx] = value;
+                }
+            }
+        }
+        self.sketch.merge(&sketch)
+    }
+
+    fn finish(self, field: &DataField) -> ColumnDistribution {
+        // columns of types which can not be sorted, e.g. arrays, get no histogram
+        let histogram = match Self::sort_sample(field, &self.sample) {
+            Ok(values) => {
+                Histogram::from_sorted_sample(&values, self.num_values, DEFAULT_HISTOGRAM_BUCKETS)
+            }
+            Err(_) => Histogram::default(),
+        };
+
+        ColumnDistribution {
+            ndv: self.sketch.count(),
+            null_count: self.null_count,
+            sketch: self.sketch,
+            histogram,
+        }
+    }
+
+    fn sort_sample(field: &DataField, sample: &[DataValue]) -> Result<Vec<DataValue>> {
+        if sample.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let column = field.data_type().create_column(sample)?;
+        let block = DataBlock::create(DataSchemaRefExt::create(vec![field.clone()]), vec![column]);
+        let sort_column = SortColumnDescription {
+            column_name: field.name().clone(),
+            asc: true,
+            nulls_first: true,
+        };
+        let sorted = DataBlock::sort_block(&block, &[sort_column], None)?;
+        Ok(sorted.column(0).to_values())
+    }
+}
@@ -24,9 +24,9 @@ use futures::io::Cursor;
 use futures::StreamExt;
 
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io;
 use crate::storages::fuse::io::BlockStreamWriter;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::operations::StorageQuota;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
@@ -48,7 +48,16 @@ impl FuseTable {
         let block_per_seg =
             self.get_option(TBL_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
 
+        // rejects the appending if the storage quota is already used up
+        let storage_quota = StorageQuota::try_create(&ctx).await?;
+        if let Some(quota) = &storage_quota {
+            quota.check(0)?;
+        }
+
         let da = ctx.get_storage_accessor().await?;
+        let cluster_key_sorter = self.cluster_key_sorter()?;
+        let write_options = self.block_write_options()?;
+        let location_generator = self.meta_location_generator();
 
         let mut segment_stream = BlockStreamWriter::write_block_stream(
             da.clone(),
@@ -56,14 +65,23 @@ impl FuseTable {
             self.table_info.schema().clone(),
             rows_per_block,
             block_per_seg,
+            cluster_key_sorter,
+            write_options,
+            location_generator.clone(),
         )
         .await;
 
         let log_entries = stream! {
+            let mut appended_bytes = 0;
             while let Some(segment) = segment_stream.next().await {
                 let log_entry_res = match segment {
                     Ok(seg) => {
-                        let seg_loc = io::gen_segment_info_location();
+                        // the blocks written before the quota is exceeded are left uncommitted
+                        appended_bytes += seg.summary.compressed_byte_size;
+                        if let Some(quota) = &storage_quota {
+                            quota.check(appended_bytes)?;
+                        }
+                        let seg_loc = location_generator.gen_segment_info_location();
                         let bytes = serde_json::to_vec(&seg)?;
                         da.write(&seg_loc, bytes.len() as u64)
                         .run(Box::new(Cursor::new(bytes)))
@@ -80,7 +98,7 @@ impl FuseTable {
         Ok(Box::pin(log_entries))
     }
 
-    fn get_option<T: FromStr>(&self, opt_key: &str, default: T) -> T {
+    pub(crate) fn get_option<T: FromStr>(&self, opt_key: &str, default: T) -> T {
         self.table_info
             .options()
             .get(opt_key)
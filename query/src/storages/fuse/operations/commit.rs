@@ -13,12 +13,15 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use backoff::backoff::Backoff;
 use backoff::ExponentialBackoffBuilder;
+use chrono::Utc;
 use common_datavalues2::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -32,23 +35,21 @@ use uuid::Uuid;
 
 use crate::catalogs::Catalog;
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io;
+use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::Statistics;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::operations::AnalyzeOperationLogEntry;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
-use crate::storages::fuse::operations::TableOperationLog;
+use crate::storages::fuse::operations::CompactionOperationLogEntry;
+use crate::storages::fuse::operations::MutationOperationLogEntry;
+use crate::storages::fuse::operations::TableOperation;
 use crate::storages::fuse::statistics;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::storages::Table;
 
 impl FuseTable {
-    pub async fn do_commit(
-        &self,
-        ctx: Arc<QueryContext>,
-        operation_log: TableOperationLog,
-        overwrite: bool,
-    ) -> Result<()> {
+    pub async fn do_commit(&self, ctx: Arc<QueryContext>, operation: TableOperation) -> Result<()> {
         let tid = self.table_info.ident.table_id;
 
         let mut tbl = self;
@@ -82,10 +83,7 @@ impl FuseTable {
             .build();
 
         loop {
-            match tbl
-                .try_commit(ctx.as_ref(), &operation_log, overwrite)
-                .await
-            {
+            match tbl.try_commit(ctx.as_ref(), &operation).await {
                 Ok(_) => break Ok(()),
                 Err(e) if e.code() == ErrorCode::table_version_mismatched_code() => {
                     match backoff.next_backoff() {
@@ -134,35 +132,54 @@ impl FuseTable {
     }
 
     #[inline]
-    pub async fn try_commit(
-        &self,
-        ctx: &QueryContext,
-        operation_log: &TableOperationLog,
-        overwrite: bool,
-    ) -> Result<()> {
+    pub async fn try_commit(&self, ctx: &QueryContext, operation: &TableOperation) -> Result<()> {
         let prev = self.read_table_snapshot(ctx).await?;
+        let prev_bytes = prev.as_ref().map_or(0, |v| v.summary.compressed_byte_size);
         let schema = self.table_info.meta.schema.as_ref().clone();
-        let (segments, summary) = Self::merge_append_operations(&schema, operation_log)?;
-        let rows_written = summary.row_count;
-        let new_snapshot = if overwrite {
-            TableSnapshot {
-                snapshot_id: Uuid::new_v4(),
-                prev_snapshot_id: prev.as_ref().map(|v| v.snapshot_id),
-                schema,
-                summary,
-                segments,
+        let (new_snapshot, rows_written) = match operation {
+            TableOperation::Append { log, overwrite } => {
+                let (segments, summary) = Self::merge_append_operations(&schema, log)?;
+                let rows_written = summary.row_count;
+                let new_snapshot = if *overwrite {
+                    TableSnapshot {
+                        snapshot_id: Uuid::new_v4(),
+                        prev_snapshot_id: prev.as_ref().map(|v| v.snapshot_id),
+                        timestamp: Some(Utc::now()),
+                        schema,
+                        summary,
+                        segments,
+                        analyzed_statistics_location: None,
+                    }
+                } else {
+                    Self::merge_table_operations(
+                        self.table_info.meta.schema.as_ref(),
+                        prev,
+                        segments,
+                        summary,
+                    )?
+                };
+                (new_snapshot, rows_written)
+            }
+            TableOperation::Mutation(log_entry) => {
+                let new_snapshot =
+                    Self::merge_mutation_operation(ctx, &schema, prev, log_entry).await?;
+                (new_snapshot, log_entry.rows_written)
+            }
+            TableOperation::Compaction(log_entry) => {
+                let new_snapshot =
+                    Self::merge_compaction_operation(ctx, &schema, prev, log_entry).await?;
+                (new_snapshot, 0)
+            }
+            TableOperation::Analyze(log_entry) => {
+                let new_snapshot = Self::merge_analyze_operation(prev, log_entry)?;
+                (new_snapshot, 0)
             }
-        } else {
-            Self::merge_table_operations(
-                self.table_info.meta.schema.as_ref(),
-                prev,
-                segments,
-                summary,
-            )?
         };
 
         let uuid = new_snapshot.snapshot_id;
-        let snapshot_loc = io::snapshot_location(&uuid);
+        let snapshot_loc = self
+            .meta_location_generator()
+            .snapshot_location_from_uuid(&uuid);
         let bytes = serde_json::to_vec(&new_snapshot)?;
         let da = ctx.get_storage_accessor().await?;
         da.write(&snapshot_loc, bytes.len() as u64)
@@ -170,7 +187,10 @@ impl FuseTable {
             .await
             .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
 
+        let new_bytes = new_snapshot.summary.compressed_byte_size;
         Self::commit_to_meta_server(ctx, &self.get_table_info().ident, snapshot_loc).await?;
+        ctx.get_storage_usage_cache()
+            .update(&ctx.get_tenant(), prev_bytes, new_bytes);
         ctx.get_dal_context().inc_write_rows(rows_written as usize);
         Ok(())
     }
@@ -189,6 +209,9 @@ impl FuseTable {
             statistics
         };
         let prev_snapshot_id = previous.as_ref().map(|v| v.snapshot_id);
+        let analyzed_statistics_location = previous
+            .as_ref()
+            .and_then(|v| v.analyzed_statistics_location.clone());
 
         // 2. merge segment locations with previous snapshot, if any
         if let Some(snapshot) = &previous {
@@ -199,13 +222,162 @@ impl FuseTable {
         let new_snapshot = TableSnapshot {
             snapshot_id: Uuid::new_v4(),
             prev_snapshot_id,
+            timestamp: Some(Utc::now()),
             schema: schema.clone(),
             summary: stats,
             segments: new_segments,
+            analyzed_statistics_location,
         };
         Ok(new_snapshot)
     }
 
+    // Replaces the segments rewritten by the mutation, the segments appended since the mutation
+    // began are kept. If any of the replaced segments is gone, the table has been mutated by others
+    // concurrently, and the mutation can not be committed; neither can it if the table has been
+    // altered, the rewritten blocks are in the column layout of the schema before.
+    async fn merge_mutation_operation(
+        ctx: &QueryContext,
+        schema: &DataSchema,
+        previous: Option<Arc<TableSnapshot>>,
+        log_entry: &MutationOperationLogEntry,
+    ) -> Result<TableSnapshot> {
+        let mut replaced_segments = log_entry
+            .replaced_segments
+            .iter()
+            .map(|(from, to)| (from, to))
+            .collect::<HashMap<_, _>>();
+
+        if matches!(&previous, Some(snapshot) if snapshot.schema != log_entry.schema) {
+            return Err(ErrorCode::OCCRetryFailure(
+                "can not commit the mutation, the table has been altered concurrently",
+            ));
+        }
+
+        let mut segments = vec![];
+        let prev_snapshot_id = previous.as_ref().map(|v| v.snapshot_id);
+        let analyzed_statistics_location = previous
+            .as_ref()
+            .and_then(|v| v.analyzed_statistics_location.clone());
+        if let Some(snapshot) = &previous {
+            for location in &snapshot.segments {
+                match replaced_segments.remove(location) {
+                    None => segments.push(location.clone()),
+                    Some(Some(new_location)) => segments.push(new_location.clone()),
+                    Some(None) => {}
+                }
+            }
+        }
+
+        if !replaced_segments.is_empty() {
+            return Err(ErrorCode::OCCRetryFailure(
+                "can not commit the mutation, the table has been changed by others concurrently",
+            ));
+        }
+
+        // recompute the summary, since the statistics of the replaced segments can not be subtracted
+        let summary = Self::summarize_segments(ctx, schema, &segments).await?;
+
+        Ok(TableSnapshot {
+            snapshot_id: Uuid::new_v4(),
+            prev_snapshot_id,
+            timestamp: Some(Utc::now()),
+            schema: schema.clone(),
+            summary,
+            segments,
+            analyzed_statistics_location,
+        })
+    }
+
+    // The merged segments are replaced by the new segments, at the position of the first merged
+    // one; segments appended since the compaction began are kept. If any of the merged segments
+    // is gone, the table has been mutated by others concurrently, and the compaction is aborted.
+    async fn merge_compaction_operation(
+        ctx: &QueryContext,
+        schema: &DataSchema,
+        previous: Option<Arc<TableSnapshot>>,
+        log_entry: &CompactionOperationLogEntry,
+    ) -> Result<TableSnapshot> {
+        let mut merged_segments = log_entry.merged_segments.iter().collect::<HashSet<_>>();
+
+        let mut segments = vec![];
+        let mut new_segments = Some(&log_entry.new_segments);
+        let prev_snapshot_id = previous.as_ref().map(|v| v.snapshot_id);
+        let analyzed_statistics_location = previous
+            .as_ref()
+            .and_then(|v| v.analyzed_statistics_location.clone());
+        if let Some(snapshot) = &previous {
+            for location in &snapshot.segments {
+                if merged_segments.remove(location) {
+                    if let Some(new_segments) = new_segments.take() {
+                        segments.extend(new_segments.iter().cloned());
+                    }
+                } else {
+                    segments.push(location.clone());
+                }
+            }
+        }
+
+        if !merged_segments.is_empty() {
+            return Err(ErrorCode::OCCRetryFailure(
+                "can not commit the compaction, the table has been changed by others concurrently",
+            ));
+        }
+
+        let summary = Self::summarize_segments(ctx, schema, &segments).await?;
+
+        Ok(TableSnapshot {
+            snapshot_id: Uuid::new_v4(),
+            prev_snapshot_id,
+            timestamp: Some(Utc::now()),
+            schema: schema.clone(),
+            summary,
+            segments,
+            analyzed_statistics_location,
+        })
+    }
+
+    // The data is left as it is, only the statistics are attached. Data appended or mutated since
+    // the analysis began is kept, the statistics are just as stale as they are after a later
+    // insertion; but they can not be applied to a different schema.
+    fn merge_analyze_operation(
+        previous: Option<Arc<TableSnapshot>>,
+        log_entry: &AnalyzeOperationLogEntry,
+    ) -> Result<TableSnapshot> {
+        let snapshot = previous.ok_or_else(|| {
+            ErrorCode::LogicalError("can not commit the statistics of a table without snapshot")
+        })?;
+
+        if snapshot.schema != log_entry.schema {
+            return Err(ErrorCode::OCCRetryFailure(
+                "can not commit the statistics, the table has been altered concurrently",
+            ));
+        }
+
+        Ok(TableSnapshot {
+            snapshot_id: Uuid::new_v4(),
+            prev_snapshot_id: Some(snapshot.snapshot_id),
+            timestamp: Some(Utc::now()),
+            schema: snapshot.schema.clone(),
+            summary: snapshot.summary.clone(),
+            segments: snapshot.segments.clone(),
+            analyzed_statistics_location: Some(log_entry.statistics_location.clone()),
+        })
+    }
+
+    async fn summarize_segments(
+        ctx: &QueryContext,
+        schema: &DataSchema,
+        segments: &[String],
+    ) -> Result<Statistics> {
+        let reader = MetaReaders::segment_info_reader(ctx);
+        let mut summary = Statistics::default();
+        for location in segments {
+            let segment_info = reader.read(location).await?;
+            summary = statistics::merge_statistics(schema, &summary, &segment_info.summary)?;
+        }
+        Ok(summary)
+    }
+
     async fn commit_to_meta_server(
         ctx: &QueryContext,
         tbl_id: &TableIdent,
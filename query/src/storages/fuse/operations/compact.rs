@@ -0,0 +1,190 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::io::Cursor;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::BlockLocation;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::operations::CompactionOperationLogEntry;
+use crate::storages::fuse::operations::TableOperation;
+use crate::storages::fuse::statistics;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::TBL_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
+use crate::storages::fuse::TBL_OPT_KEY_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::TBL_OPT_KEY_ROW_PER_BLOCK;
+
+impl FuseTable {
+    /// Merges the undersized blocks, and the segments which are not full, of the current snapshot.
+    ///
+    /// Blocks are considered undersized if both the number of rows and the in-memory size of
+    /// them are less than the thresholds (table options `ROW_PER_BLOCK` and `BLOCK_SIZE_THRESHOLD`).
+    pub async fn do_compact(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let row_per_block = self.get_option(TBL_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let block_per_seg =
+            self.get_option(TBL_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+        let block_size_threshold = self.get_option(
+            TBL_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD,
+            DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+        );
+        let is_undersized = |block_meta: &BlockMeta| {
+            (block_meta.row_count as usize) < row_per_block
+                && (block_meta.block_size as usize) < block_size_threshold
+        };
+
+        // 1. pick the segments to be merged, segments that are full of well-sized blocks are kept
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut merged_segments = vec![];
+        let mut kept_blocks = vec![];
+        let mut undersized_blocks = vec![];
+        for segment_location in &snapshot.segments {
+            let segment_info = segment_reader.read(segment_location).await?;
+            let has_undersized = segment_info.blocks.iter().any(is_undersized);
+            if !has_undersized && segment_info.blocks.len() >= block_per_seg {
+                continue;
+            }
+
+            merged_segments.push(segment_location.clone());
+            for block_meta in &segment_info.blocks {
+                if is_undersized(block_meta) {
+                    undersized_blocks.push(block_meta.clone());
+                } else {
+                    kept_blocks.push(block_meta.clone());
+                }
+            }
+        }
+
+        // short cut, nothing could be merged
+        if merged_segments.len() <= 1 && undersized_blocks.len() <= 1 {
+            return Ok(());
+        }
+
+        // 2. merge the undersized blocks
+        let da = ctx.get_storage_accessor().await?;
+        let read_buffer_size = ctx.get_settings().get_storage_read_buffer_size()?;
+        let schema = self.table_info.schema();
+        let projection = (0..schema.fields().len()).collect::<Vec<usize>>();
+        let arrow_schema = schema.to_arrow();
+        let cluster_key_sorter = self.cluster_key_sorter()?;
+        let write_options = self.block_write_options()?;
+        let location_generator = self.meta_location_generator();
+
+        let mut blocks = kept_blocks;
+        let mut accumulated = vec![];
+        let mut accumulated_rows = 0;
+        let mut accumulated_size = 0;
+        let num_undersized = undersized_blocks.len();
+        for (idx, block_meta) in undersized_blocks.iter().enumerate() {
+            let mut block_reader = BlockReader::new(
+                da.clone(),
+                block_meta.location.path.clone(),
+                schema.clone(),
+                projection.clone(),
+                block_meta.file_size,
+                read_buffer_size,
+                MetaReaders::block_meta_reader(ctx.clone()),
+            )
+            .with_col_mapping(block_meta.col_mapping.clone());
+            let block = block_reader.read().await.map_err(|e| {
+                ErrorCode::ParquetError(format!(
+                    "fail to read block {}, {}",
+                    block_meta.location.path, e
+                ))
+            })?;
+            accumulated_rows += block.num_rows();
+            accumulated_size += block.memory_size();
+            accumulated.push(block);
+
+            let is_last = idx + 1 == num_undersized;
+            if accumulated_rows < row_per_block
+                && accumulated_size < block_size_threshold
+                && !is_last
+            {
+                continue;
+            }
+
+            let block = DataBlock::concat_blocks(&accumulated)?;
+            let (block, cluster_stats) = cluster_key_sorter.sort(block)?;
+            accumulated.clear();
+            accumulated_rows = 0;
+            accumulated_size = 0;
+
+            let row_count = block.num_rows() as u64;
+            let block_size = block.memory_size() as u64;
+            let col_stats = StatisticsAccumulator::acc_columns(&block)?;
+            let bloom_filter_index_location =
+                io::write_bloom_filter_index(&block, da.clone(), &location_generator).await?;
+            let location = location_generator.gen_block_location();
+            let file_size =
+                io::write_block(&arrow_schema, block, da.clone(), &location, &write_options)
+                    .await?;
+            blocks.push(BlockMeta {
+                row_count,
+                block_size,
+                file_size,
+                col_stats,
+                bloom_filter_index_location,
+                cluster_stats,
+                col_mapping: None,
+                location: BlockLocation {
+                    path: location,
+                    meta_size: 0,
+                },
+            });
+        }
+
+        // 3. pack the blocks into segments
+        let mut new_segments = Vec::with_capacity(blocks.len() / block_per_seg + 1);
+        for chunk in blocks.chunks(block_per_seg) {
+            let summary = statistics::reduce_block_metas(chunk, schema.as_ref())?;
+            let new_segment = SegmentInfo {
+                blocks: chunk.to_vec(),
+                summary,
+            };
+            let new_segment_location = location_generator.gen_segment_info_location();
+            let bytes = serde_json::to_vec(&new_segment)?;
+            da.write(&new_segment_location, bytes.len() as u64)
+                .run(Box::new(Cursor::new(bytes)))
+                .await
+                .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+            new_segments.push(new_segment_location);
+        }
+
+        let log_entry = CompactionOperationLogEntry {
+            merged_segments,
+            new_segments,
+        };
+        self.do_commit(ctx, TableOperation::Compaction(log_entry))
+            .await
+    }
+}
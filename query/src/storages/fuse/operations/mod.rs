@@ -12,15 +12,31 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter;
+mod analyze;
 mod append;
 mod commit;
+mod compact;
+mod delete;
+mod mutation;
+mod navigate;
 mod operation_log;
 mod optimize;
 mod part_info;
+mod purge_orphans;
+mod quota;
 mod read;
 mod read_partitions;
 mod truncate;
+mod update;
 
+pub use operation_log::AnalyzeOperationLogEntry;
 pub use operation_log::AppendOperationLogEntry;
+pub use operation_log::CompactionOperationLogEntry;
+pub use operation_log::MutationOperationLogEntry;
+pub use operation_log::TableOperation;
 pub use operation_log::TableOperationLog;
 pub use part_info::PartInfo;
+pub use quota::tenant_storage_usage;
+pub use quota::StorageQuota;
+pub use quota::StorageUsageCache;
@@ -0,0 +1,316 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Extras;
+use futures::io::Cursor;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::BlockLocation;
+use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::SegmentInfo;
+use crate::storages::fuse::operations::MutationOperationLogEntry;
+use crate::storages::fuse::operations::TableOperation;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics;
+use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::fuse::FuseTable;
+
+/// Rewrites the rows of a block which satisfy the selection.
+///
+/// Rows that match the selection are removed (delete), or replaced by the
+/// evaluated assignments (update); the other rows are kept as they are.
+pub struct BlockMutator {
+    schema: DataSchemaRef,
+    selection: Option<ExpressionExecutor>,
+    assignments: Option<ExpressionExecutor>,
+}
+
+impl BlockMutator {
+    pub fn try_create_delete(
+        schema: DataSchemaRef,
+        selection: &Option<Expression>,
+    ) -> Result<Self> {
+        Ok(BlockMutator {
+            selection: Self::selection_executor(&schema, selection)?,
+            assignments: None,
+            schema,
+        })
+    }
+
+    pub fn try_create_update(
+        schema: DataSchemaRef,
+        selection: &Option<Expression>,
+        assignments: &[(String, Expression)],
+    ) -> Result<Self> {
+        let exprs = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = field.name();
+                match assignments.iter().find(|(column, _)| column == name) {
+                    Some((_, value)) => Expression::Alias(name.clone(), Box::new(value.clone())),
+                    None => Expression::Column(name.clone()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let executor = ExpressionExecutor::try_create(
+            "update expression executor",
+            schema.clone(),
+            schema.clone(),
+            exprs,
+            true,
+        )?;
+        executor.validate()?;
+
+        Ok(BlockMutator {
+            selection: Self::selection_executor(&schema, selection)?,
+            assignments: Some(executor),
+            schema,
+        })
+    }
+
+    fn selection_executor(
+        schema: &DataSchemaRef,
+        selection: &Option<Expression>,
+    ) -> Result<Option<ExpressionExecutor>> {
+        match selection {
+            None => Ok(None),
+            Some(expr) => {
+                let expr_field = expr.to_data_field(schema)?;
+                let expr_schema = DataSchemaRefExt::create(vec![expr_field]);
+                let executor = ExpressionExecutor::try_create(
+                    "selection expression executor",
+                    schema.clone(),
+                    expr_schema,
+                    vec![expr.clone()],
+                    false,
+                )?;
+                executor.validate()?;
+                Ok(Some(executor))
+            }
+        }
+    }
+
+    /// Returns [None] if none of the rows is affected, otherwise the mutated block,
+    /// which might be empty if all the rows are deleted.
+    pub fn mutate(&self, block: &DataBlock) -> Result<Option<DataBlock>> {
+        let (matched, unmatched) = match &self.selection {
+            None => (block.clone(), None),
+            Some(executor) => {
+                let predicate = executor.execute(block)?;
+                let predicate =
+                    DataBlock::cast_to_nonull_boolean(predicate.column(0))?.convert_full_column();
+                let predicate: &BooleanColumn = Series::check_get(&predicate)?;
+                let values = predicate.values();
+
+                // the null count of the bitmap is the number of unset bits
+                if values.null_count() == values.len() {
+                    return Ok(None);
+                }
+
+                let negated: ColumnRef =
+                    Series::from_data(values.iter().map(|v| !v).collect::<Vec<_>>());
+                let selected: ColumnRef = Arc::new(predicate.clone());
+                (
+                    DataBlock::filter_block(block, &selected)?,
+                    Some(DataBlock::filter_block(block, &negated)?),
+                )
+            }
+        };
+
+        let mut blocks = Vec::with_capacity(2);
+        if let Some(unmatched) = unmatched {
+            if unmatched.num_rows() > 0 {
+                blocks.push(unmatched);
+            }
+        }
+        if let Some(executor) = &self.assignments {
+            if matched.num_rows() > 0 {
+                blocks.push(executor.execute(&matched)?);
+            }
+        }
+
+        if blocks.is_empty() {
+            Ok(Some(DataBlock::empty_with_schema(self.schema.clone())))
+        } else {
+            Ok(Some(DataBlock::concat_blocks(&blocks)?))
+        }
+    }
+}
+
+impl FuseTable {
+    /// Applies the mutator to the blocks which might satisfy the selection, and commits
+    /// the rewritten segments.
+    ///
+    /// Blocks are rewritten as a whole, blocks (and segments) that are not affected are
+    /// shared with the previous snapshot.
+    pub async fn do_mutation(
+        &self,
+        ctx: Arc<QueryContext>,
+        selection: &Option<Expression>,
+        mutator: BlockMutator,
+    ) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let schema = self.table_info.schema();
+        let push_downs = selection.as_ref().map(|expr| Extras {
+            filters: vec![expr.clone()],
+            ..Extras::default()
+        });
+        let candidates = BlockPruner::new(&snapshot)
+            .apply(schema.clone(), &push_downs, ctx.as_ref())
+            .await?
+            .into_iter()
+            .map(|block_meta| block_meta.location.path)
+            .collect::<HashSet<_>>();
+
+        if candidates.is_empty() {
+            return Ok(());
+        }
+
+        let da = ctx.get_storage_accessor().await?;
+        let read_buffer_size = ctx.get_settings().get_storage_read_buffer_size()?;
+        let projection = (0..schema.fields().len()).collect::<Vec<usize>>();
+        let arrow_schema = schema.to_arrow();
+        let cluster_key_sorter = self.cluster_key_sorter()?;
+        let write_options = self.block_write_options()?;
+        let location_generator = self.meta_location_generator();
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+
+        let mut replaced_segments = vec![];
+        let mut rows_written = 0;
+        for segment_location in &snapshot.segments {
+            let segment_info = segment_reader.read(segment_location).await?;
+            let mut blocks = Vec::with_capacity(segment_info.blocks.len());
+            let mut segment_mutated = false;
+
+            for block_meta in &segment_info.blocks {
+                if !candidates.contains(&block_meta.location.path) {
+                    blocks.push(block_meta.clone());
+                    continue;
+                }
+
+                let mut block_reader = BlockReader::new(
+                    da.clone(),
+                    block_meta.location.path.clone(),
+                    schema.clone(),
+                    projection.clone(),
+                    block_meta.file_size,
+                    read_buffer_size,
+                    MetaReaders::block_meta_reader(ctx.clone()),
+                )
+                .with_col_mapping(block_meta.col_mapping.clone());
+                let block = block_reader.read().await.map_err(|e| {
+                    ErrorCode::ParquetError(format!(
+                        "fail to read block {}, {}",
+                        block_meta.location.path, e
+                    ))
+                })?;
+
+                match mutator.mutate(&block)? {
+                    None => blocks.push(block_meta.clone()),
+                    Some(new_block) => {
+                        segment_mutated = true;
+                        if new_block.num_rows() == 0 {
+                            continue;
+                        }
+
+                        // the updated keys might break the order of the rows
+                        let (new_block, cluster_stats) = cluster_key_sorter.sort(new_block)?;
+                        let row_count = new_block.num_rows() as u64;
+                        let block_size = new_block.memory_size() as u64;
+                        let col_stats = StatisticsAccumulator::acc_columns(&new_block)?;
+                        let bloom_filter_index_location = io::write_bloom_filter_index(
+                            &new_block,
+                            da.clone(),
+                            &location_generator,
+                        )
+                        .await?;
+                        let location = location_generator.gen_block_location();
+                        let file_size = io::write_block(
+                            &arrow_schema,
+                            new_block,
+                            da.clone(),
+                            &location,
+                            &write_options,
+                        )
+                        .await?;
+                        rows_written += row_count;
+                        blocks.push(BlockMeta {
+                            row_count,
+                            block_size,
+                            file_size,
+                            col_stats,
+                            bloom_filter_index_location,
+                            cluster_stats,
+                            col_mapping: None,
+                            location: BlockLocation {
+                                path: location,
+                                meta_size: 0,
+                            },
+                        });
+                    }
+                }
+            }
+
+            if !segment_mutated {
+                continue;
+            }
+
+            if blocks.is_empty() {
+                replaced_segments.push((segment_location.clone(), None));
+                continue;
+            }
+
+            let summary = statistics::reduce_block_metas(&blocks, schema.as_ref())?;
+            let new_segment = SegmentInfo { blocks, summary };
+            let new_segment_location = location_generator.gen_segment_info_location();
+            let bytes = serde_json::to_vec(&new_segment)?;
+            da.write(&new_segment_location, bytes.len() as u64)
+                .run(Box::new(Cursor::new(bytes)))
+                .await
+                .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+            replaced_segments.push((segment_location.clone(), Some(new_segment_location)));
+        }
+
+        if replaced_segments.is_empty() {
+            return Ok(());
+        }
+
+        let log_entry = MutationOperationLogEntry {
+            replaced_segments,
+            rows_written,
+            schema: snapshot.schema.clone(),
+        };
+        self.do_commit(ctx, TableOperation::Mutation(log_entry))
+            .await
+    }
+}
@@ -0,0 +1,82 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use uuid::Uuid;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::storages::NavigationPoint;
+use crate::storages::Table;
+
+impl FuseTable {
+    /// Walks back the snapshot chain, and returns the table which points to the matched snapshot.
+    pub async fn do_navigate(
+        &self,
+        ctx: Arc<QueryContext>,
+        point: &NavigationPoint,
+    ) -> Result<Arc<dyn Table>> {
+        let snapshot_loc = self.snapshot_loc();
+        let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+        let snapshots = reader
+            .read_snapshot_history_with_locations(snapshot_loc.as_ref())
+            .await?;
+
+        let snapshot = match point {
+            NavigationPoint::SnapshotID(id) => {
+                let snapshot_id = Uuid::parse_str(id).map_err(|e| {
+                    ErrorCode::BadArguments(format!("invalid snapshot id '{}', {}", id, e))
+                })?;
+                Self::find_snapshot(&snapshots, |s| s.snapshot_id == snapshot_id)
+            }
+            // the history is ordered by commit time, latest first
+            NavigationPoint::TimePoint(time_point) => Self::find_snapshot(
+                &snapshots,
+                |s| matches!(s.timestamp, Some(t) if t <= *time_point),
+            ),
+        };
+
+        let (location, snapshot) = snapshot.ok_or_else(|| {
+            ErrorCode::TableHistoricalDataNotFound(format!(
+                "no historical data of table {} found at {:?}",
+                self.table_info.desc, point
+            ))
+        })?;
+
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = Arc::new(snapshot.schema.clone());
+        table_info
+            .meta
+            .options
+            .insert(TBL_OPT_KEY_SNAPSHOT_LOC.to_string(), location.clone());
+        Ok(Arc::new(FuseTable { table_info }))
+    }
+
+    fn find_snapshot<P>(
+        snapshots: &[(String, Arc<TableSnapshot>)],
+        predicate: P,
+    ) -> Option<&(String, Arc<TableSnapshot>)>
+    where
+        P: Fn(&TableSnapshot) -> bool,
+    {
+        snapshots.iter().find(|(_, s)| predicate(s))
+    }
+}
@@ -18,16 +18,28 @@ use std::convert::TryFrom;
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::Series;
 use common_datavalues2::prelude::SeriesFrom;
+use common_datavalues2::DataSchema;
 use common_datavalues2::DataSchemaRef;
 use common_datavalues2::DataValue;
 use common_exception::ErrorCode;
 
 use crate::storages::fuse::meta::SegmentInfo;
 
-// currently, only support append,
 pub type TableOperationLog = Vec<AppendOperationLogEntry>;
 
-// to be wrapped in enum
+/// Operations which can be committed to a table, see [FuseTable::do_commit]
+///
+/// [FuseTable::do_commit]: crate::storages::fuse::FuseTable::do_commit
+pub enum TableOperation {
+    Append {
+        log: TableOperationLog,
+        overwrite: bool,
+    },
+    Mutation(MutationOperationLogEntry),
+    Compaction(CompactionOperationLogEntry),
+    Analyze(AnalyzeOperationLogEntry),
+}
+
 pub struct AppendOperationLogEntry {
     pub segment_location: String,
     pub segment_info: SegmentInfo,
@@ -46,6 +58,32 @@ impl AppendOperationLogEntry {
     }
 }
 
+/// Segments rewritten by DELETE or UPDATE.
+pub struct MutationOperationLogEntry {
+    /// Pairs of (location of the segment being replaced, location of the new segment),
+    /// the new location is None if all the rows of the segment have been deleted.
+    pub replaced_segments: Vec<(String, Option<String>)>,
+    pub rows_written: u64,
+    /// The schema of the mutated snapshot, by which the blocks are rewritten
+    pub schema: DataSchema,
+}
+
+/// Segments merged by `OPTIMIZE TABLE .. COMPACT`.
+pub struct CompactionOperationLogEntry {
+    /// Locations of the segments being merged
+    pub merged_segments: Vec<String>,
+    /// Locations of the segments which take the place of the merged ones
+    pub new_segments: Vec<String>,
+}
+
+/// Statistics collected by `ANALYZE TABLE`.
+pub struct AnalyzeOperationLogEntry {
+    /// Location of the collected statistics
+    pub statistics_location: String,
+    /// The schema of the analyzed snapshot, by which the statistics are keyed
+    pub schema: DataSchema,
+}
+
 impl TryFrom<AppendOperationLogEntry> for DataBlock {
     type Error = common_exception::ErrorCode;
     fn try_from(value: AppendOperationLogEntry) -> std::result::Result<Self, Self::Error> {
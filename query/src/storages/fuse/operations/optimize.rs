@@ -22,7 +22,6 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io::snapshot_location;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
@@ -38,7 +37,9 @@ impl FuseTable {
         let tbl_info = self.get_table_info();
         let snapshot_loc = tbl_info.meta.options.get(TBL_OPT_KEY_SNAPSHOT_LOC);
         let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
-        let mut snapshots = reader.read_snapshot_history(snapshot_loc).await?;
+        let mut snapshots = reader
+            .read_snapshot_history_with_locations(snapshot_loc)
+            .await?;
 
         let min_history_len = if !keep_last_snapshot { 0 } else { 1 };
 
@@ -48,16 +49,19 @@ impl FuseTable {
         }
 
         let current_segments: HashSet<&String>;
+        let current_statistics: Option<&String>;
         let current_snapshot;
         if !keep_last_snapshot {
             // if truncate_all requested, gc root contains nothing;
             current_segments = HashSet::new();
+            current_statistics = None;
         } else {
-            current_snapshot = snapshots.remove(0);
+            current_snapshot = snapshots.remove(0).1;
             current_segments = HashSet::from_iter(&current_snapshot.segments);
+            current_statistics = current_snapshot.analyzed_statistics_location.as_ref();
         }
 
-        let prevs = snapshots.iter().fold(HashSet::new(), |mut acc, s| {
+        let prevs = snapshots.iter().fold(HashSet::new(), |mut acc, (_, s)| {
             acc.extend(&s.segments);
             acc
         });
@@ -71,15 +75,29 @@ impl FuseTable {
             self.blocks_of(current_segments.iter(), ctx.clone()).await?;
         let block_delta = prev_blocks.difference(&current_blocks);
 
+        // statistics (collected by ANALYZE TABLE) to be removed
+        let statistics_delta = snapshots
+            .iter()
+            .filter_map(|(_, s)| s.analyzed_statistics_location.as_ref())
+            .filter(|location| Some(*location) != current_statistics)
+            .collect::<HashSet<_>>();
+
         // NOTE: the following actions are NOT transactional yet
 
-        // 1. remove blocks
+        // 1. remove blocks (and their bloom filter indexes)
         for x in block_delta {
             self.remove_location(da.clone(), x).await?;
             if let Some(c) = ctx.get_storage_cache_manager().get_block_meta_cache() {
                 let cache = &mut *c.write().await;
                 cache.pop(x.as_str());
             }
+            if let Some(c) = ctx
+                .get_storage_cache_manager()
+                .get_bloom_filter_index_cache()
+            {
+                let cache = &mut *c.write().await;
+                cache.pop(x.as_str());
+            }
         }
 
         // 2. remove the segments
@@ -91,9 +109,20 @@ impl FuseTable {
             }
         }
 
-        // 3. remove the snapshots
-        for x in snapshots.iter().rev() {
-            let loc = snapshot_location(&x.snapshot_id);
+        // 3. remove the statistics
+        for x in statistics_delta {
+            self.remove_location(da.clone(), x.as_str()).await?;
+            if let Some(c) = ctx
+                .get_storage_cache_manager()
+                .get_analyzed_statistics_cache()
+            {
+                let cache = &mut *c.write().await;
+                cache.pop(x.as_str());
+            }
+        }
+
+        // 4. remove the snapshots
+        for (loc, _) in snapshots.iter().rev() {
             self.remove_location(da.clone(), loc.as_str()).await?;
             if let Some(c) = ctx.get_storage_cache_manager().get_table_snapshot_cache() {
                 let cache = &mut *c.write().await;
@@ -104,7 +133,9 @@ impl FuseTable {
         Ok(())
     }
 
-    async fn blocks_of(
+    /// Returns the locations of the blocks referenced by the given segments, along with the
+    /// locations of their bloom filter indexes.
+    pub(crate) async fn blocks_of(
         &self,
         locations: impl Iterator<Item = impl AsRef<str>>,
         ctx: Arc<QueryContext>,
@@ -115,12 +146,15 @@ impl FuseTable {
             let res = reader.read(location).await?;
             for block_meta in &res.blocks {
                 result.insert(block_meta.location.path.clone());
+                if let Some(index_location) = &block_meta.bloom_filter_index_location {
+                    result.insert(index_location.clone());
+                }
             }
         }
         Ok(result)
     }
 
-    async fn remove_location(
+    pub(crate) async fn remove_location(
         &self,
         data_accessor: Operator,
         location: impl AsRef<str>,
@@ -16,21 +16,33 @@
 use common_exception::ErrorCode;
 use common_exception::Result;
 
-/// Holds the location and length information of a given [Part].
+use crate::storages::fuse::meta::ColumnId;
+
+/// Holds the location, length and column mapping information of a given [Part].
 ///
 /// It is intended to be encoded as string, and assigned to the field `name` of a given [`Part`],
 /// then passed around to the `read` method of [FuseTable], where the name filed will be decoded back to [PartInfo]
 ///
+/// The column mapping is only encoded for the blocks written before the last schema change,
+/// see `BlockMeta::col_mapping`.
+///
 /// [Part]: common_planners::Part
 /// [FuseTable]: crate::storages::fuse::FuseTable
 ///
 #[derive(PartialEq, Debug)]
-pub struct PartInfo<'a>(&'a str, u64);
+pub struct PartInfo<'a>(&'a str, u64, Option<Vec<Option<ColumnId>>>);
 
 impl<'a> PartInfo<'a> {
     #[inline]
     pub fn new(location: &'a str, length: u64) -> Self {
-        Self(location, length)
+        Self(location, length, None)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn with_col_mapping(mut self, col_mapping: Option<Vec<Option<ColumnId>>>) -> Self {
+        self.2 = col_mapping;
+        self
     }
 
     #[inline]
@@ -43,12 +55,17 @@ impl<'a> PartInfo<'a> {
         self.1
     }
 
+    #[inline]
+    pub fn col_mapping(&self) -> Option<&[Option<ColumnId>]> {
+        self.2.as_deref()
+    }
+
     #[inline]
     pub fn decode(part_name: &'a str) -> Result<PartInfo> {
         let parts = part_name.split('-').collect::<Vec<_>>();
-        if parts.len() != 2 {
+        if parts.len() != 2 && parts.len() != 3 {
             return Err(ErrorCode::LogicalError(format!(
-                "invalid format of `Part.name` , expects 'name-length', got {}",
+                "invalid format of `Part.name` , expects 'name-length[-mapping]', got {}",
                 part_name
             )));
         }
@@ -59,11 +76,46 @@ impl<'a> PartInfo<'a> {
                 parts[1], e
             ))
         })?;
-        Ok(Self(part_location, part_len))
+        let col_mapping = match parts.get(2) {
+            None => None,
+            Some(mapping) => Some(Self::decode_col_mapping(mapping)?),
+        };
+        Ok(Self(part_location, part_len, col_mapping))
     }
 
     #[inline]
     pub fn encode(&self) -> String {
-        format!("{}-{}", self.0, self.1,)
+        match &self.2 {
+            None => format!("{}-{}", self.0, self.1,),
+            Some(mapping) => {
+                let mapping = mapping
+                    .iter()
+                    .map(|pos| match pos {
+                        Some(pos) => pos.to_string(),
+                        None => "_".to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}-{}-{}", self.0, self.1, mapping.join(","))
+            }
+        }
+    }
+
+    // the mapping is encoded as comma separated positions, '_' stands for an absent column
+    fn decode_col_mapping(mapping: &str) -> Result<Vec<Option<ColumnId>>> {
+        if mapping.is_empty() {
+            return Ok(vec![]);
+        }
+        mapping
+            .split(',')
+            .map(|pos| match pos {
+                "_" => Ok(None),
+                _ => pos.parse::<ColumnId>().map(Some).map_err(|e| {
+                    ErrorCode::LogicalError(format!(
+                        "invalid format of `Part.name` format, expects column positions, but got {}, {}",
+                        mapping, e
+                    ))
+                }),
+            })
+            .collect()
     }
 }
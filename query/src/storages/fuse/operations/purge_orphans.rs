@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use common_dal2::Object;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_ORPHAN_RETENTION_SECS;
+
+impl FuseTable {
+    /// Removes the files under the prefix of the table which are not referenced by any snapshot
+    /// of the history, e.g. blocks and segments left behind by failed or aborted insertions.
+    ///
+    /// Only files older than `older_than` are removed, so that the files of insertions which are
+    /// still in progress (written, but not committed yet) are kept. Files of the legacy layout,
+    /// which are shared by all the tables, are never touched.
+    pub async fn do_purge_orphans(
+        &self,
+        ctx: Arc<QueryContext>,
+        older_than: Option<Duration>,
+    ) -> Result<()> {
+        let older_than =
+            older_than.unwrap_or_else(|| Duration::from_secs(DEFAULT_ORPHAN_RETENTION_SECS));
+        let da = ctx.get_storage_accessor().await?;
+
+        // everything reachable from the snapshot chain must be kept
+        let reachable = self.reachable_locations(ctx.clone()).await?;
+
+        let prefix = format!("{}/", self.meta_location_generator().prefix());
+        let objects = da
+            .list(&prefix)
+            .recursive(true)
+            .run()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?
+            .try_collect::<Vec<Object>>()
+            .await
+            .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+
+        let now = SystemTime::now();
+        let is_expired = |object: &Object| match object.last_modified {
+            Some(t) => matches!(now.duration_since(t), Ok(age) if age >= older_than),
+            // the age is unknown, keep it to be safe
+            None => false,
+        };
+        let orphans = objects
+            .iter()
+            .filter(|&o| !o.is_dir() && !reachable.contains(&o.path) && is_expired(o))
+            .collect::<Vec<_>>();
+
+        for orphan in &orphans {
+            self.remove_location(da.clone(), &orphan.path).await?;
+        }
+
+        tracing::info!(
+            "purged {} orphan files of table {}",
+            orphans.len(),
+            self.table_info.desc
+        );
+        Ok(())
+    }
+
+    async fn reachable_locations(&self, ctx: Arc<QueryContext>) -> Result<HashSet<String>> {
+        let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+        let snapshots = reader
+            .read_snapshot_history_with_locations(self.snapshot_loc().as_ref())
+            .await?;
+
+        let mut segments = HashSet::new();
+        let mut reachable = HashSet::new();
+        for (location, snapshot) in snapshots {
+            reachable.insert(location);
+            reachable.extend(snapshot.analyzed_statistics_location.clone());
+            segments.extend(snapshot.segments.iter().cloned());
+        }
+        reachable.extend(self.blocks_of(segments.iter(), ctx).await?);
+        reachable.extend(segments);
+        Ok(reachable)
+    }
+}
@@ -0,0 +1,137 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::FuseTable;
+
+/// The storage quota of the current user, against the storage used by all the tables of the tenant.
+pub struct StorageQuota {
+    max_storage_in_bytes: u64,
+    used_storage_in_bytes: u64,
+}
+
+impl StorageQuota {
+    /// Returns None if the storage of the current user is unlimited.
+    pub async fn try_create(ctx: &QueryContext) -> Result<Option<StorageQuota>> {
+        let max_storage_in_bytes = ctx.get_current_user()?.quota.max_storage_in_bytes;
+        if max_storage_in_bytes == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(StorageQuota {
+            max_storage_in_bytes,
+            used_storage_in_bytes: tenant_storage_usage(ctx).await?,
+        }))
+    }
+
+    /// Fails if appending `appended_bytes` makes the storage usage exceed the quota.
+    pub fn check(&self, appended_bytes: u64) -> Result<()> {
+        let usage = self.used_storage_in_bytes + appended_bytes;
+        if usage > self.max_storage_in_bytes {
+            return Err(ErrorCode::StorageQuotaExceeded(format!(
+                "Storage quota exceeded, storage usage {} bytes, quota {} bytes",
+                usage, self.max_storage_in_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The total compressed size in bytes of the FUSE tables of the current tenant.
+///
+/// The usage is read from the snapshots of all the tables only when it's not cached yet.
+pub async fn tenant_storage_usage(ctx: &QueryContext) -> Result<u64> {
+    let tenant = ctx.get_tenant();
+    let cache = ctx.get_storage_usage_cache();
+    if let Some(usage) = cache.get(&tenant) {
+        return Ok(usage);
+    }
+
+    let catalog = ctx.get_catalog();
+    let mut usage = 0;
+    for db in catalog.list_databases(&tenant).await? {
+        for table in catalog.list_tables(&tenant, db.name()).await? {
+            if let Some(fuse_table) = table.as_any().downcast_ref::<FuseTable>() {
+                if let Some(snapshot) = fuse_table.read_table_snapshot(ctx).await? {
+                    usage += snapshot.summary.compressed_byte_size;
+                }
+            }
+        }
+    }
+
+    cache.set(&tenant, usage);
+    Ok(usage)
+}
+
+// The commits of the other query nodes are not seen by the cache, the usage is read again
+// from the snapshots once it is older than this.
+const STORAGE_USAGE_TTL: Duration = Duration::from_secs(60);
+
+struct CachedStorageUsage {
+    bytes: u64,
+    refreshed_at: Instant,
+}
+
+/// The storage usage of the tenants, kept up to date by the commits of this query node.
+#[derive(Default)]
+pub struct StorageUsageCache {
+    usages: RwLock<HashMap<String, CachedStorageUsage>>,
+}
+
+impl StorageUsageCache {
+    pub fn create() -> StorageUsageCache {
+        StorageUsageCache::default()
+    }
+
+    /// Returns None if the usage of the tenant is not cached or expired.
+    pub fn get(&self, tenant: &str) -> Option<u64> {
+        let usages = self.usages.read();
+        usages
+            .get(tenant)
+            .filter(|usage| usage.refreshed_at.elapsed() < STORAGE_USAGE_TTL)
+            .map(|usage| usage.bytes)
+    }
+
+    pub fn set(&self, tenant: &str, bytes: u64) {
+        let mut usages = self.usages.write();
+        usages.insert(tenant.to_string(), CachedStorageUsage {
+            bytes,
+            refreshed_at: Instant::now(),
+        });
+    }
+
+    /// Applies a commit replacing a snapshot of `prev_bytes` by a snapshot of `new_bytes`.
+    pub fn update(&self, tenant: &str, prev_bytes: u64, new_bytes: u64) {
+        let mut usages = self.usages.write();
+        if let Some(usage) = usages.get_mut(tenant) {
+            usage.bytes = (usage.bytes + new_bytes).saturating_sub(prev_bytes);
+        }
+    }
+
+    /// The usage is read again, e.g. after the tables are dropped.
+    pub fn invalidate(&self, tenant: &str) {
+        let mut usages = self.usages.write();
+        usages.remove(tenant);
+    }
+}
@@ -64,12 +64,14 @@ impl FuseTable {
         let part_stream = futures::stream::iter(iter);
 
         let read_buffer_size = ctx.get_settings().get_storage_read_buffer_size()?;
+        let data_cache = ctx.get_storage_cache_manager().get_block_data_cache();
         let stream = part_stream
             .map(move |part| {
                 let da = da.clone();
                 let table_schema = table_schema.clone();
                 let projection = projection.clone();
                 let reader = MetaReaders::block_meta_reader(ctx.clone());
+                let data_cache = data_cache.clone();
                 async move {
                     let part_info = PartInfo::decode(&part.name)?;
                     let part_location = part_info.location();
@@ -83,7 +85,9 @@ impl FuseTable {
                         part_len,
                         read_buffer_size,
                         reader,
-                    );
+                    )
+                    .with_col_mapping(part_info.col_mapping().map(|v| v.to_vec()))
+                    .with_data_cache(data_cache);
                     block_reader.read().await.map_err(|e| {
                         ErrorCode::ParquetError(format!(
                             "fail to read block {}, {}",
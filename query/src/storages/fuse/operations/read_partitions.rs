@@ -72,8 +72,9 @@ impl FuseTable {
         blocks_metas.iter().fold(
             (Statistics::default(), Partitions::default()),
             |(mut stats, mut parts), block_meta| {
-                let name =
-                    PartInfo::new(block_meta.location.path.as_str(), block_meta.file_size).encode();
+                let name = PartInfo::new(block_meta.location.path.as_str(), block_meta.file_size)
+                    .with_col_mapping(block_meta.col_mapping.clone())
+                    .encode();
                 parts.push(Part { name, version: 0 });
 
                 stats.read_rows += block_meta.row_count as usize;
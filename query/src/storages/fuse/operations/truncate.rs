@@ -15,6 +15,7 @@
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::UpsertTableOptionReq;
@@ -24,7 +25,6 @@ use uuid::Uuid;
 
 use crate::catalogs::Catalog;
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
@@ -38,11 +38,15 @@ impl FuseTable {
             let new_snapshot = TableSnapshot {
                 snapshot_id: Uuid::new_v4(),
                 prev_snapshot_id: Some(prev_id),
+                timestamp: Some(Utc::now()),
                 schema: prev_snapshot.schema.clone(),
                 summary: Default::default(),
                 segments: vec![],
+                analyzed_statistics_location: None,
             };
-            let new_snapshot_loc = io::snapshot_location(&new_snapshot.snapshot_id);
+            let new_snapshot_loc = self
+                .meta_location_generator()
+                .snapshot_location_from_uuid(&new_snapshot.snapshot_id);
             let da = ctx.get_storage_accessor().await?;
             let bytes = serde_json::to_vec(&new_snapshot)?;
             da.write(&new_snapshot_loc, bytes.len() as u64)
@@ -61,6 +65,11 @@ impl FuseTable {
                     new_snapshot_loc,
                 ))
                 .await?;
+            ctx.get_storage_usage_cache().update(
+                &ctx.get_tenant(),
+                prev_snapshot.summary.compressed_byte_size,
+                0,
+            );
         }
 
         Ok(())
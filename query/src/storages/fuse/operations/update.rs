@@ -0,0 +1,34 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::UpdatePlan;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::operations::mutation::BlockMutator;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    pub async fn do_update(&self, ctx: Arc<QueryContext>, plan: &UpdatePlan) -> Result<()> {
+        let mutator = BlockMutator::try_create_update(
+            self.table_info.schema(),
+            &plan.selection,
+            &plan.assignments,
+        )?;
+        self.do_mutation(ctx, &plan.selection, mutator).await
+    }
+}
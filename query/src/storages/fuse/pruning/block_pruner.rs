@@ -13,31 +13,35 @@
 //  limitations under the License.
 //
 
+use std::sync::Arc;
+
 use common_datavalues2::DataSchemaRef;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
 use common_tracing::tracing;
 use futures::StreamExt;
 use futures::TryStreamExt;
 
 use crate::sessions::QueryContext;
-use crate::storages::fuse::io::snapshot_location;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::meta::BlockMeta;
 use crate::storages::fuse::meta::SegmentInfo;
 use crate::storages::fuse::meta::TableSnapshot;
+use crate::storages::index::BlockBloomFilters;
 use crate::storages::index::BlockStatistics;
+use crate::storages::index::BloomFilterIndexer;
 use crate::storages::index::RangeFilter;
 
 pub struct BlockPruner {
-    table_snapshot_location: String,
+    segment_locations: Vec<String>,
 }
 
 type Pred = Box<dyn Fn(&BlockStatistics) -> Result<bool> + Send + Sync + Unpin>;
 impl BlockPruner {
     pub fn new(table_snapshot: &TableSnapshot) -> Self {
         Self {
-            table_snapshot_location: snapshot_location(&table_snapshot.snapshot_id),
+            segment_locations: table_snapshot.segments.clone(),
         }
     }
 
@@ -48,19 +52,24 @@ impl BlockPruner {
         push_down: &Option<Extras>,
         ctx: &QueryContext,
     ) -> Result<Vec<BlockMeta>> {
-        let block_pred: Pred = match push_down {
-            Some(exprs) if !exprs.filters.is_empty() => {
-                // for the time being, we only handle the first expr
-                let verifiable_expression = RangeFilter::try_create(&exprs.filters[0], schema)?;
+        // all the pushed down filters must hold, combine them into one conjunction
+        let filter = push_down
+            .as_ref()
+            .and_then(|exprs| exprs.filters.iter().cloned().reduce(|acc, e| acc.and(e)));
+
+        let block_pred: Pred = match &filter {
+            Some(expr) => {
+                let verifiable_expression = RangeFilter::try_create(expr, schema.clone())?;
                 Box::new(move |v: &BlockStatistics| verifiable_expression.eval(v))
             }
-            _ => Box::new(|_: &BlockStatistics| Ok(true)),
+            None => Box::new(|_: &BlockStatistics| Ok(true)),
         };
 
-        let reader = MetaReaders::table_snapshot_reader(ctx);
-        let snapshot = reader.read(self.table_snapshot_location.as_str()).await?;
-        let segment_num = snapshot.segments.len();
-        let segment_locs = snapshot.segments.clone();
+        // the bloom filter indexes are loaded only if they might prune some blocks
+        let bloom_filter = filter.filter(BloomFilterIndexer::is_applicable);
+
+        let segment_num = self.segment_locations.len();
+        let segment_locs = self.segment_locations.clone();
 
         if segment_locs.is_empty() {
             return Ok(vec![]);
@@ -70,7 +79,11 @@ impl BlockPruner {
             .map(|seg_loc| async {
                 let reader = MetaReaders::segment_info_reader(ctx);
                 let segment_info = reader.read(seg_loc).await?;
-                Self::filter_segment(segment_info.as_ref(), &block_pred)
+                let blocks = Self::filter_segment(segment_info.as_ref(), &block_pred)?;
+                match &bloom_filter {
+                    None => Ok(blocks),
+                    Some(expr) => Self::filter_blocks_by_bloom(blocks, expr, &schema, ctx).await,
+                }
             })
             // configuration of the max size of buffered futures
             .buffered(std::cmp::min(10, segment_num))
@@ -99,4 +112,45 @@ impl BlockPruner {
             Ok(vec![])
         }
     }
+
+    async fn filter_blocks_by_bloom(
+        blocks: Vec<BlockMeta>,
+        expr: &Expression,
+        schema: &DataSchemaRef,
+        ctx: &QueryContext,
+    ) -> Result<Vec<BlockMeta>> {
+        let reader = MetaReaders::bloom_filter_index_reader(ctx);
+        let mut result = Vec::with_capacity(blocks.len());
+        for block_meta in blocks {
+            if let Some(location) = &block_meta.bloom_filter_index_location {
+                let filters = Self::table_column_filters(&block_meta, reader.read(location).await?);
+                if !BloomFilterIndexer::create(schema.clone(), filters).maybe_true(expr)? {
+                    continue;
+                }
+            }
+            result.push(block_meta);
+        }
+        Ok(result)
+    }
+
+    // The index is keyed by the positions of the columns in the block file, which differ from
+    // the ones in the table schema once the table is altered.
+    fn table_column_filters(
+        block_meta: &BlockMeta,
+        filters: Arc<BlockBloomFilters>,
+    ) -> Arc<BlockBloomFilters> {
+        match &block_meta.col_mapping {
+            None => filters,
+            Some(mapping) => Arc::new(
+                mapping
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, physical_id)| {
+                        let filter = filters.get(physical_id.as_ref()?)?;
+                        Some((idx as u32, filter.clone()))
+                    })
+                    .collect(),
+            ),
+        }
+    }
 }
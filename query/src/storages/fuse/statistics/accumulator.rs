@@ -22,6 +22,7 @@ use common_functions::aggregates::eval_aggr;
 
 use crate::storages::fuse::meta::BlockLocation;
 use crate::storages::fuse::meta::BlockMeta;
+use crate::storages::fuse::meta::ClusterStatistics;
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::index::BlockStatistics;
 use crate::storages::index::ColumnStatistics;
@@ -55,6 +56,8 @@ impl StatisticsAccumulator {
             block_row_count: block.num_rows() as u64,
             block_size: block.memory_size() as u64,
             block_column_statistics: block_stats,
+            block_bloom_filter_index_location: None,
+            block_cluster_stats: None,
         })
     }
 
@@ -110,9 +113,21 @@ pub struct PartiallyAccumulated {
     block_row_count: u64,
     block_size: u64,
     block_column_statistics: HashMap<ColumnId, ColumnStatistics>,
+    block_bloom_filter_index_location: Option<String>,
+    block_cluster_stats: Option<ClusterStatistics>,
 }
 
 impl PartiallyAccumulated {
+    pub fn with_cluster_stats(mut self, cluster_stats: Option<ClusterStatistics>) -> Self {
+        self.block_cluster_stats = cluster_stats;
+        self
+    }
+
+    pub fn with_bloom_filter_index_location(mut self, location: Option<String>) -> Self {
+        self.block_bloom_filter_index_location = location;
+        self
+    }
+
     pub fn end(mut self, file_size: u64, location: String) -> StatisticsAccumulator {
         let mut stats = &mut self.accumulator;
         stats.file_size += file_size;
@@ -125,6 +140,9 @@ impl PartiallyAccumulated {
             block_size: self.block_size,
             file_size,
             col_stats: self.block_column_statistics,
+            bloom_filter_index_location: self.block_bloom_filter_index_location,
+            cluster_stats: self.block_cluster_stats,
+            col_mapping: None,
         };
         stats.blocks_metas.push(block_meta);
         self.accumulator
@@ -18,4 +18,5 @@ pub mod reducers;
 pub use accumulator::PartiallyAccumulated;
 pub use accumulator::StatisticsAccumulator;
 pub use reducers::merge_statistics;
+pub use reducers::reduce_block_metas;
 pub use reducers::reduce_block_stats;
@@ -23,6 +23,7 @@ use common_datavalues2::DataValue;
 use common_exception::Result;
 use common_functions::aggregates::eval_aggr;
 
+use crate::storages::fuse::meta::BlockMeta;
 use crate::storages::fuse::meta::ColumnId;
 use crate::storages::fuse::meta::Statistics;
 use crate::storages::index::BlockStatistics;
@@ -124,3 +125,18 @@ pub fn merge_statistics(schema: &DataSchema, l: &Statistics, r: &Statistics) ->
     };
     Ok(s)
 }
+
+pub fn reduce_block_metas(block_metas: &[BlockMeta], schema: &DataSchema) -> Result<Statistics> {
+    let col_stats = block_metas
+        .iter()
+        .map(|block_meta| &block_meta.col_stats)
+        .collect::<Vec<_>>();
+    let s = Statistics {
+        row_count: block_metas.iter().map(|v| v.row_count).sum(),
+        block_count: block_metas.len() as u64,
+        uncompressed_byte_size: block_metas.iter().map(|v| v.block_size).sum(),
+        compressed_byte_size: block_metas.iter().map(|v| v.file_size).sum(),
+        col_stats: reduce_block_stats(&col_stats, schema)?,
+    };
+    Ok(s)
+}
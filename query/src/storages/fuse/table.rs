@@ -15,29 +15,42 @@
 
 use std::any::Any;
 use std::any::TypeId;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
+use common_planners::AlterTablePlan;
+use common_planners::DeletePlan;
+use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::StreamExt;
 
 use crate::sessions::QueryContext;
+use crate::storages::fuse::io::BlockWriteOptions;
+use crate::storages::fuse::io::ClusterKeySorter;
 use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::meta::TableSnapshot;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::operations::TableOperation;
 use crate::storages::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::storages::NavigationPoint;
 use crate::storages::StorageContext;
 use crate::storages::StorageDescription;
 use crate::storages::Table;
+use crate::storages::TableStatistics;
 
 pub struct FuseTable {
     pub(crate) table_info: TableInfo,
@@ -92,6 +105,36 @@ impl Table for FuseTable {
         self.do_read(ctx, &plan.push_downs).await
     }
 
+    async fn statistics(&self, ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
+        let snapshot = self.read_table_snapshot(ctx.as_ref()).await?;
+        Ok(Some(match snapshot {
+            None => TableStatistics::default(),
+            Some(snapshot) => {
+                let column_distributions = match &snapshot.analyzed_statistics_location {
+                    None => HashMap::new(),
+                    Some(location) => {
+                        // the statistics are only a hint to the optimizer, missing is tolerated
+                        let reader = MetaReaders::analyzed_statistics_reader(ctx.as_ref());
+                        match reader.read(location).await {
+                            Ok(statistics) => statistics.col_distributions.clone(),
+                            Err(e) if e.code() == ErrorCode::dal_path_not_found_code() => {
+                                HashMap::new()
+                            }
+                            Err(e) => return Err(e),
+                        }
+                    }
+                };
+                TableStatistics {
+                    num_rows: snapshot.summary.row_count,
+                    data_size: snapshot.summary.uncompressed_byte_size,
+                    data_size_compressed: snapshot.summary.compressed_byte_size,
+                    column_statistics: snapshot.summary.col_stats.clone(),
+                    column_distributions,
+                }
+            }
+        }))
+    }
+
     #[tracing::instrument(level = "debug", name="fuse_table_append_data", skip(self, ctx, stream), fields(ctx.id = ctx.get_id().as_str()))]
     async fn append_data(
         &self,
@@ -118,7 +161,11 @@ impl Table for FuseTable {
             .iter()
             .map(AppendOperationLogEntry::try_from)
             .collect::<Result<Vec<AppendOperationLogEntry>>>()?;
-        self.do_commit(ctx, append_log_entries, overwrite).await
+        self.do_commit(ctx, TableOperation::Append {
+            log: append_log_entries,
+            overwrite,
+        })
+        .await
     }
 
     async fn truncate(
@@ -129,12 +176,69 @@ impl Table for FuseTable {
         self.do_truncate(ctx, truncate_plan).await
     }
 
+    async fn delete(&self, ctx: Arc<QueryContext>, delete_plan: DeletePlan) -> Result<()> {
+        self.do_delete(ctx, &delete_plan).await
+    }
+
+    async fn update(&self, ctx: Arc<QueryContext>, update_plan: UpdatePlan) -> Result<()> {
+        self.do_update(ctx, &update_plan).await
+    }
+
+    async fn alter(&self, ctx: Arc<QueryContext>, alter_plan: AlterTablePlan) -> Result<()> {
+        self.do_alter(ctx, alter_plan).await
+    }
+
     async fn optimize(&self, ctx: Arc<QueryContext>, keep_last_snapshot: bool) -> Result<()> {
         self.do_optimize(ctx, keep_last_snapshot).await
     }
+
+    async fn compact(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        self.do_compact(ctx).await
+    }
+
+    async fn purge_orphans(
+        &self,
+        ctx: Arc<QueryContext>,
+        older_than: Option<Duration>,
+    ) -> Result<()> {
+        self.do_purge_orphans(ctx, older_than).await
+    }
+
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<()> {
+        self.do_analyze(ctx).await
+    }
+
+    async fn navigate_to(
+        &self,
+        ctx: Arc<QueryContext>,
+        point: &NavigationPoint,
+    ) -> Result<Arc<dyn Table>> {
+        self.do_navigate(ctx, point).await
+    }
 }
 
 impl FuseTable {
+    /// The expressions of `CLUSTER BY`, empty if the table is not clustered.
+    pub fn cluster_keys(&self) -> Result<Vec<Expression>> {
+        match &self.table_info.meta.cluster_keys {
+            None => Ok(vec![]),
+            Some(cluster_keys) => Ok(serde_json::from_str(cluster_keys)?),
+        }
+    }
+
+    pub(crate) fn cluster_key_sorter(&self) -> Result<ClusterKeySorter> {
+        ClusterKeySorter::try_create(self.table_info.schema(), &self.cluster_keys()?)
+    }
+
+    pub(crate) fn block_write_options(&self) -> Result<BlockWriteOptions> {
+        BlockWriteOptions::try_create(self.table_info.options())
+    }
+
+    /// Objects of the table are placed under the prefix of its table id.
+    pub(crate) fn meta_location_generator(&self) -> TableMetaLocationGenerator {
+        TableMetaLocationGenerator::with_prefix(self.table_info.ident.table_id.to_string())
+    }
+
     pub(crate) fn snapshot_loc(&self) -> Option<String> {
         self.table_info
             .options()
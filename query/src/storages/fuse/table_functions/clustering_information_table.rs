@@ -0,0 +1,297 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+use common_planners::Expression;
+use common_planners::ReadDataSourcePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::meta::ClusterStatistics;
+use crate::storages::fuse::table_functions::table_arg_util::parse_func_history_args;
+use crate::storages::fuse::table_functions::table_arg_util::string_literal;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+use crate::table_functions::TableArgs;
+use crate::table_functions::TableFunction;
+
+pub const FUSE_FUNC_CLUSTERING: &str = "clustering_information";
+
+pub struct ClusteringInformationTable {
+    table_info: TableInfo,
+    arg_database_name: String,
+    arg_table_name: String,
+}
+
+impl ClusteringInformationTable {
+    pub fn create(
+        database_name: &str,
+        table_func_name: &str,
+        table_id: u64,
+        table_args: TableArgs,
+    ) -> Result<Arc<dyn TableFunction>> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("cluster_by_keys", Vu8::to_data_type()),
+            DataField::new("total_block_count", u64::to_data_type()),
+            DataField::new("total_constant_block_count", u64::to_data_type()),
+            DataField::new("average_overlaps", f64::to_data_type()),
+            DataField::new("average_depth", f64::to_data_type()),
+        ]);
+
+        let (arg_database_name, arg_table_name) = parse_func_history_args(&table_args)?;
+
+        let engine = FUSE_FUNC_CLUSTERING.to_owned();
+
+        let table_info = TableInfo {
+            ident: TableIdent::new(table_id, 0),
+            desc: format!("'{}'.'{}'", database_name, table_func_name),
+            name: table_func_name.to_string(),
+            meta: TableMeta {
+                schema,
+                engine,
+                ..Default::default()
+            },
+        };
+
+        Ok(Arc::new(ClusteringInformationTable {
+            table_info,
+            arg_database_name,
+            arg_table_name,
+        }))
+    }
+
+    fn clustering_to_block(
+        &self,
+        cluster_keys: &[Expression],
+        total_block_count: u64,
+        cluster_stats: &[ClusterStatistics],
+    ) -> DataBlock {
+        let cluster_by_keys = format!(
+            "({})",
+            cluster_keys
+                .iter()
+                .map(|key| format!("{:?}", key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let info = ClusteringInformation::from_cluster_stats(cluster_stats);
+
+        DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(vec![cluster_by_keys.into_bytes()]),
+            Series::from_data(vec![total_block_count]),
+            Series::from_data(vec![info.total_constant_block_count]),
+            Series::from_data(vec![info.average_overlaps]),
+            Series::from_data(vec![info.average_depth]),
+        ])
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ClusteringInformationTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn table_args(&self) -> Option<Vec<Expression>> {
+        Some(vec![
+            string_literal(self.arg_database_name.as_str()),
+            string_literal(self.arg_table_name.as_str()),
+        ])
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        _plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let tenant_id = ctx.get_tenant();
+        let tbl = ctx
+            .get_catalog()
+            .get_table(
+                tenant_id.as_str(),
+                self.arg_database_name.as_str(),
+                self.arg_table_name.as_str(),
+            )
+            .await?;
+
+        let tbl = tbl.as_any().downcast_ref::<FuseTable>().ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "expecting fuse table, but got table of engine type: {}",
+                tbl.get_table_info().meta.engine
+            ))
+        })?;
+
+        let cluster_keys = tbl.cluster_keys()?;
+        if cluster_keys.is_empty() {
+            return Err(ErrorCode::BadArguments(format!(
+                "table {}.{} has no cluster keys",
+                self.arg_database_name, self.arg_table_name
+            )));
+        }
+
+        let mut total_block_count = 0;
+        let mut cluster_stats = vec![];
+        if let Some(snapshot) = tbl.read_table_snapshot(ctx.as_ref()).await? {
+            let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+            for segment_location in &snapshot.segments {
+                let segment_info = segment_reader.read(segment_location).await?;
+                for block_meta in &segment_info.blocks {
+                    total_block_count += 1;
+                    // blocks written before the table was clustered have no cluster stats
+                    if let Some(stats) = &block_meta.cluster_stats {
+                        cluster_stats.push(stats.clone());
+                    }
+                }
+            }
+        }
+
+        let blocks =
+            vec![self.clustering_to_block(&cluster_keys, total_block_count, &cluster_stats)];
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema(),
+            None,
+            blocks,
+        )))
+    }
+}
+
+impl TableFunction for ClusteringInformationTable {
+    fn function_name(&self) -> &str {
+        self.name()
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
+
+/// How well the blocks are clustered, the smaller the overlaps and the depth, the better.
+///
+/// - overlaps of a block: the number of other blocks whose key ranges overlap with it.
+/// - depth of a point: the number of blocks whose key ranges contain it, the points are the
+///   bounds of the key ranges of all the blocks.
+#[derive(Debug, PartialEq)]
+pub struct ClusteringInformation {
+    pub total_constant_block_count: u64,
+    pub average_overlaps: f64,
+    pub average_depth: f64,
+}
+
+impl ClusteringInformation {
+    pub fn from_cluster_stats(cluster_stats: &[ClusterStatistics]) -> Self {
+        let total_constant_block_count = cluster_stats
+            .iter()
+            .filter(|stats| stats.min == stats.max)
+            .count() as u64;
+
+        if cluster_stats.is_empty() {
+            return ClusteringInformation {
+                total_constant_block_count,
+                average_overlaps: 0.0,
+                average_depth: 0.0,
+            };
+        }
+
+        let overlaps = |a: &ClusterStatistics, b: &ClusterStatistics| {
+            compare_keys(&a.max, &b.min) != Ordering::Less
+                && compare_keys(&b.max, &a.min) != Ordering::Less
+        };
+        let contains = |stats: &ClusterStatistics, point: &[DataValue]| {
+            compare_keys(&stats.min, point) != Ordering::Greater
+                && compare_keys(&stats.max, point) != Ordering::Less
+        };
+
+        let mut total_overlaps = 0;
+        for (idx, stats) in cluster_stats.iter().enumerate() {
+            total_overlaps += cluster_stats
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, other)| *other_idx != idx && overlaps(stats, other))
+                .count();
+        }
+
+        let mut points = cluster_stats
+            .iter()
+            .flat_map(|stats| [stats.min.as_slice(), stats.max.as_slice()])
+            .collect::<Vec<_>>();
+        points.sort_by(|a, b| compare_keys(a, b));
+        points.dedup_by(|a, b| compare_keys(a, b) == Ordering::Equal);
+
+        let total_depth = points
+            .iter()
+            .map(|point| {
+                cluster_stats
+                    .iter()
+                    .filter(|stats| contains(stats, point))
+                    .count()
+            })
+            .sum::<usize>();
+
+        ClusteringInformation {
+            total_constant_block_count,
+            average_overlaps: total_overlaps as f64 / cluster_stats.len() as f64,
+            average_depth: total_depth as f64 / points.len() as f64,
+        }
+    }
+}
+
+// Compares the cluster keys in the order they are sorted, i.e. ascending and nulls first.
+fn compare_keys(a: &[DataValue], b: &[DataValue]) -> Ordering {
+    for (a, b) in a.iter().zip(b.iter()) {
+        let ordering = compare_value(a, b);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn compare_value(a: &DataValue, b: &DataValue) -> Ordering {
+    match (a, b) {
+        (DataValue::Null, DataValue::Null) => Ordering::Equal,
+        (DataValue::Null, _) => Ordering::Less,
+        (_, DataValue::Null) => Ordering::Greater,
+        (DataValue::Boolean(a), DataValue::Boolean(b)) => a.cmp(b),
+        (DataValue::Int64(a), DataValue::Int64(b)) => a.cmp(b),
+        (DataValue::UInt64(a), DataValue::UInt64(b)) => a.cmp(b),
+        (DataValue::Int64(a), DataValue::UInt64(b)) => (*a as i128).cmp(&(*b as i128)),
+        (DataValue::UInt64(a), DataValue::Int64(b)) => (*a as i128).cmp(&(*b as i128)),
+        (DataValue::Float64(a), DataValue::Float64(b)) => {
+            a.partial_cmp(b).unwrap_or(Ordering::Equal)
+        }
+        (DataValue::String(a), DataValue::String(b)) => a.cmp(b),
+        (DataValue::Array(a), DataValue::Array(b)) => compare_keys(a, b),
+        (DataValue::Struct(a), DataValue::Struct(b)) => compare_keys(a, b),
+        _ => Ordering::Equal,
+    }
+}
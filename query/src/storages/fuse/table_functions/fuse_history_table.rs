@@ -63,6 +63,7 @@ impl FuseHistoryTable {
             DataField::new("row_count", u64::to_data_type()),
             DataField::new("bytes_uncompressed", u64::to_data_type()),
             DataField::new("bytes_compressed", u64::to_data_type()),
+            DataField::new_nullable("timestamp", DateTime64Type::arc(6, None)),
         ]);
 
         let (arg_database_name, arg_table_name) = parse_func_history_args(&table_args)?;
@@ -96,6 +97,7 @@ impl FuseHistoryTable {
         let mut row_count: Vec<u64> = Vec::with_capacity(len);
         let mut compressed: Vec<u64> = Vec::with_capacity(len);
         let mut uncompressed: Vec<u64> = Vec::with_capacity(len);
+        let mut timestamps: Vec<Option<u64>> = Vec::with_capacity(len);
         for s in snapshots {
             snapshot_ids.push(s.snapshot_id.simple().to_string().into_bytes());
             prev_snapshot_ids.push(
@@ -107,6 +109,7 @@ impl FuseHistoryTable {
             row_count.push(s.summary.row_count);
             compressed.push(s.summary.compressed_byte_size);
             uncompressed.push(s.summary.uncompressed_byte_size);
+            timestamps.push(s.timestamp.map(|t| (t.timestamp_nanos() / 1_000) as u64));
         }
 
         DataBlock::create(self.table_info.schema(), vec![
@@ -117,6 +120,7 @@ impl FuseHistoryTable {
             Series::from_data(row_count),
             Series::from_data(uncompressed),
             Series::from_data(compressed),
+            Series::from_data(timestamps),
         ])
     }
 }
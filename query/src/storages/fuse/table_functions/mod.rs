@@ -13,8 +13,12 @@
 //  limitations under the License.
 //
 
+mod clustering_information_table;
 mod fuse_history_table;
 mod table_arg_util;
 
+pub use clustering_information_table::ClusteringInformation;
+pub use clustering_information_table::ClusteringInformationTable;
+pub use clustering_information_table::FUSE_FUNC_CLUSTERING;
 pub use fuse_history_table::FuseHistoryTable;
 pub use fuse_history_table::FUSE_FUNC_HIST;
@@ -12,16 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use bincode;
-use bit_vec::BitVec;
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
-use common_tracing::tracing;
 
 use crate::storages::index::IndexSchemaVersion;
 
@@ -38,11 +36,13 @@ pub enum BloomFilterExprEvalResult {
     NotApplicable,
 }
 
-/// BloomFilterIndex represents multiple  bloom filters (per column) in data block.
+/// Bloom filters of a data block, keyed by the column index in the table schema.
 ///
-/// By default we create bloom filter per column for a parquet data file. For columns whose data_type
-/// are not applicable for a bloom filter, we skip the creation.
-/// That is to say, it is legal to have a BloomFilterBlock with zero columns.
+/// Columns whose data type is not applicable for a bloom filter have no entry,
+/// that is to say, it is legal to have an empty `BlockBloomFilters`.
+pub type BlockBloomFilters = HashMap<u32, BloomFilter>;
+
+/// BloomFilterIndexer evaluates predicates against the per column bloom filters of a data block.
 ///
 /// For example, for the data block as follows:
 ///```
@@ -51,24 +51,60 @@ pub enum BloomFilterExprEvalResult {
 ///         | "Bob"   |  30   |
 ///         +---------+-------+
 /// ```
-/// We will create bloom filter files
+/// We will create bloom filters
 ///```
 ///         +---Bloom(name)--+--Bloom(age)--+
 ///         |  123456789abcd |  ac2345bcd   |
 ///         +----------------+--------------+
 /// ```
 pub struct BloomFilterIndexer {
-    pub inner: DataBlock,
+    schema: DataSchemaRef,
+    filters: Arc<BlockBloomFilters>,
 }
 
 const BLOOM_FILTER_MAX_NUM_BITS: usize = 20480; // 2.5KB, maybe too big?
 const BLOOM_FILTER_DEFAULT_FALSE_POSITIVE_RATE: f64 = 0.05;
 
+// Filters whose estimated false positive rate exceeds this are not kept, a saturated
+// filter costs space in the index file but never prunes anything.
+const BLOOM_FILTER_MAX_FALSE_POSITIVE_RATE: f64 = 0.5;
+
 impl BloomFilterIndexer {
-    /// For every applicable column, we will create a bloom filter.
-    /// The bloom filter will be stored with field name 'Bloom(column_name)'
-    pub fn to_bloom_column_name(column_name: &str) -> String {
-        format!("Bloom({})", column_name)
+    pub fn create(schema: DataSchemaRef, filters: Arc<BlockBloomFilters>) -> Self {
+        Self { schema, filters }
+    }
+
+    /// Create an indexer with the bloom filters built from the input data.
+    ///
+    /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
+    pub fn from_data(blocks: &[DataBlock]) -> Result<Self> {
+        Self::from_data_and_seeds(blocks, Self::create_seeds())
+    }
+
+    /// Create an indexer with the bloom filters built from the input data and seeds.
+    ///
+    /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
+    pub fn from_data_and_seeds(blocks: &[DataBlock], seeds: [u64; 4]) -> Result<Self> {
+        let filters = Self::build_with_seeds(blocks, seeds)?;
+        Ok(Self::create(blocks[0].schema().clone(), Arc::new(filters)))
+    }
+
+    /// Returns whether the expression might be evaluated to false by bloom filters, that is,
+    /// whether it's worth loading the bloom filters to evaluate it.
+    pub fn is_applicable(expr: &Expression) -> bool {
+        match expr {
+            Expression::BinaryExpression { left, op, right } => match op.to_lowercase().as_str() {
+                "=" => matches!(
+                    (left.as_ref(), right.as_ref()),
+                    (Expression::Column(_), Expression::Literal { .. })
+                        | (Expression::Literal { .. }, Expression::Column(_))
+                ),
+                "and" => Self::is_applicable(left) || Self::is_applicable(right),
+                "or" => Self::is_applicable(left) && Self::is_applicable(right),
+                _ => false,
+            },
+            _ => false,
+        }
     }
 
     #[inline(always)]
@@ -80,75 +116,82 @@ impl BloomFilterIndexer {
         [seed0, seed1, seed2, seed3]
     }
 
-    /// Create a bloom filter block from input data.
+    /// Build the bloom filters of every applicable column from input data.
     ///
     /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
-    #[allow(dead_code)]
-    pub fn from_data(blocks: &[DataBlock]) -> Result<Self> {
+    pub fn build(blocks: &[DataBlock]) -> Result<BlockBloomFilters> {
         let seeds = Self::create_seeds();
-        Self::from_data_and_seeds(blocks, seeds)
+        Self::build_with_seeds(blocks, seeds)
     }
 
-    /// Create a bloom filter block from input data blocks and seeds.
+    /// Build the bloom filters of every applicable column from input data blocks and seeds.
     ///
     /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
-    pub fn from_data_and_seeds(blocks: &[DataBlock], seeds: [u64; 4]) -> Result<Self> {
+    pub fn build_with_seeds(blocks: &[DataBlock], seeds: [u64; 4]) -> Result<BlockBloomFilters> {
         if blocks.is_empty() {
             return Err(ErrorCode::BadArguments("data blocks is empty"));
         }
 
-        let total_num_rows = blocks.iter().map(|block| block.num_rows() as u64).sum();
-
-        let mut bloom_columns = vec![];
-        let mut bloom_fields = vec![];
-
+        let mut filters = BlockBloomFilters::new();
         let fields = blocks[0].schema().fields();
         for (i, field) in fields.iter().enumerate() {
-            if BloomFilter::is_supported_type(field.data_type()) {
-                // create field
-                let bloom_column_name = Self::to_bloom_column_name(field.name());
-                let bloom_field = DataField::new(&bloom_column_name, Vu8::to_data_type());
-                bloom_fields.push(bloom_field);
-
-                // create bloom filter per column
-                let mut bloom_filter = BloomFilter::with_rate_and_max_bits(
-                    total_num_rows,
-                    BLOOM_FILTER_DEFAULT_FALSE_POSITIVE_RATE,
-                    BLOOM_FILTER_MAX_NUM_BITS,
-                    seeds,
-                );
-
-                // ingest the same column data from all blocks
-                for block in blocks.iter() {
-                    let col = block.column(i);
-                    bloom_filter.add(col)?;
-                }
+            if !BloomFilter::is_supported_type(field.data_type()) {
+                continue;
+            }
+
+            let num_items = blocks
+                .iter()
+                .map(|block| {
+                    let column = block.column(i);
+                    let null_count = match column.validity() {
+                        (true, _) => column.len(),
+                        (false, Some(bitmap)) => bitmap.null_count(),
+                        (false, None) => 0,
+                    };
+                    (column.len() - null_count) as u64
+                })
+                .sum::<u64>();
+            if num_items == 0 {
+                continue;
+            }
+
+            // create bloom filter per column
+            let mut bloom_filter = BloomFilter::with_rate_and_max_bits(
+                num_items,
+                BLOOM_FILTER_DEFAULT_FALSE_POSITIVE_RATE,
+                BLOOM_FILTER_MAX_NUM_BITS,
+                seeds,
+            );
+
+            // ingest the same column data from all blocks
+            for block in blocks.iter() {
+                bloom_filter.add(block.column(i))?;
+            }
 
-                // create bloom filter column
-                let serialized_bytes = bloom_filter.to_vec()?;
-                let bloom_column =
-                    ColumnRef::Constant(DataValue::String(Some(serialized_bytes)), 1);
-                bloom_columns.push(bloom_column);
+            if bloom_filter.estimated_false_positive_rate() <= BLOOM_FILTER_MAX_FALSE_POSITIVE_RATE
+            {
+                filters.insert(i as u32, bloom_filter);
             }
         }
 
-        let schema = Arc::new(DataSchema::new(bloom_fields));
-        let block = DataBlock::create(schema, bloom_columns);
-        Ok(Self { inner: block })
+        Ok(filters)
     }
 
-    fn find(&self, column_name: &str, target: DataValue) -> Result<BloomFilterExprEvalResult> {
-        let bloom_column = Self::to_bloom_column_name(column_name);
-        if !self.inner.schema().has_field(&bloom_column)
-            || !BloomFilter::is_supported_value(&target)
-        {
-            // The column doesn't have bloom filter bitmap
+    fn find(&self, column_name: &str, target: &DataValue) -> Result<BloomFilterExprEvalResult> {
+        let bloom_filter = match self.schema.index_of(column_name) {
+            Ok(idx) => match self.filters.get(&(idx as u32)) {
+                Some(bloom_filter) => bloom_filter,
+                // The column doesn't have bloom filter bitmap
+                None => return Ok(BloomFilterExprEvalResult::NotApplicable),
+            },
+            Err(_) => return Ok(BloomFilterExprEvalResult::NotApplicable),
+        };
+
+        let data_type = self.schema.field_with_name(column_name)?.data_type();
+        if !BloomFilter::is_supported_value(data_type, target) {
             return Ok(BloomFilterExprEvalResult::NotApplicable);
         }
 
-        let val = self.inner.first(&bloom_column)?;
-        let bloom_bytes = val.as_string()?;
-        let bloom_filter = BloomFilter::from_vec(bloom_bytes.as_ref())?;
         if bloom_filter.find(target)? {
             Ok(BloomFilterExprEvalResult::Unknown)
         } else {
@@ -159,7 +202,6 @@ impl BloomFilterIndexer {
     /// Returns false when the expression must be false, otherwise true.
     /// The 'true' doesn't really mean the expression is true, but 'maybe true'.
     /// That is to say, you still need the load all data and run the execution.
-    #[allow(dead_code)]
     pub fn maybe_true(&self, expr: &Expression) -> Result<bool> {
         Ok(self.eval(expr)? != BloomFilterExprEvalResult::False)
     }
@@ -193,7 +235,7 @@ impl BloomFilterIndexer {
             // match the expression of 'column_name = literal constant'
             (Expression::Column(column), Expression::Literal { value, .. })
             | (Expression::Literal { value, .. }, Expression::Column(column)) => {
-                self.find(column, value.clone())
+                self.find(column, value)
             }
             _ => Ok(BloomFilterExprEvalResult::NotApplicable),
         }
@@ -246,12 +288,11 @@ impl BloomFilterIndexer {
     }
 
     /// Find and returns the bloom filter by name
-    pub fn try_get_bloom(&self, column_name: &str) -> Result<BloomFilter> {
-        let bloom_column = Self::to_bloom_column_name(column_name);
-        let val = self.inner.first(&bloom_column)?;
-        let bloom_bytes = val.as_string()?;
-        let bloom_filter = BloomFilter::from_vec(bloom_bytes.as_ref())?;
-        Ok(bloom_filter)
+    pub fn try_get_bloom(&self, column_name: &str) -> Result<&BloomFilter> {
+        let idx = self.schema.index_of(column_name)?;
+        self.filters.get(&(idx as u32)).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("Column {} has no bloom filter", column_name))
+        })
     }
 }
 
@@ -259,10 +300,13 @@ impl BloomFilterIndexer {
 ///
 /// Most ideas/implementations are ported from Clickhouse.
 /// https://github.com/ClickHouse/ClickHouse/blob/1bf375e2b761db5b99b0f403b90c412a530f4d5c/src/Interpreters/BloomFilter.cpp
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BloomFilter {
-    // Container for bitmap
-    container: BitVec,
+    // Container for bitmap, serialized as a base64 string to keep the segment meta compact.
+    #[serde(with = "bitmap_format")]
+    container: Vec<u64>,
+
+    num_bits: usize,
 
     // The number of hashes for bloom filter. We use double hashing and mix the result
     // to achieve k hashes. The value doesn't really mean the number of hashing we actually compute.
@@ -271,7 +315,7 @@ pub struct BloomFilter {
 
     version: IndexSchemaVersion,
 
-    // The seeding for hash function. For now we use Seahash lib, that needs 4 seeds.
+    // The seeding for the two hash functions, two seeds for each.
     seeds: [u64; 4],
 }
 
@@ -292,18 +336,19 @@ impl BloomFilter {
         max_num_bits: usize,
         seeds: [u64; 4],
     ) -> Self {
-        let mut num_bits = Self::optimal_num_bits(num_items, false_positive_rate);
+        let num_bits = Self::optimal_num_bits(num_items, false_positive_rate);
+        let num_bits = std::cmp::min(num_bits, max_num_bits);
         let num_hashes = Self::optimal_num_hashes(num_items, num_bits as u64);
 
-        num_bits = std::cmp::min(num_bits, max_num_bits);
-
         Self::with_size(num_bits, num_hashes, seeds)
     }
 
     /// Create a bloom filter instance with specified bitmap length and number of hashes
     pub fn with_size(num_bits: usize, num_hashes: usize, seeds: [u64; 4]) -> Self {
+        let num_bits = std::cmp::max(num_bits, 1);
         Self {
-            container: BitVec::from_elem(num_bits, false),
+            container: vec![0; (num_bits + 63) / 64],
+            num_bits,
             seeds,
             num_hashes,
             version: IndexSchemaVersion::V1,
@@ -319,9 +364,7 @@ impl BloomFilter {
     pub fn optimal_num_bits(num_items: u64, false_positive_rate: f64) -> usize {
         let power_of_ln2 = core::f32::consts::LN_2 as f64 * core::f32::consts::LN_2 as f64;
         let m = -(num_items as f64 * false_positive_rate.ln()) / power_of_ln2;
-        let num_bits = m.ceil() as usize;
-        tracing::info!("Bloom filter calculate optimal bits, num_bits: {}, num_items: {}, false_positive_rate: {}", num_bits, num_items, false_positive_rate);
-        num_bits
+        m.ceil() as usize
     }
 
     /// Calculate the number of hashes for the bloom filter from the formula:
@@ -332,17 +375,12 @@ impl BloomFilter {
     /// n: number of items
     pub fn optimal_num_hashes(num_items: u64, num_bits: u64) -> usize {
         let k = num_bits as f64 / num_items as f64 * core::f32::consts::LN_2 as f64;
-        let num_hashes = std::cmp::max(2, k.ceil() as usize); // at least two hashes
-        tracing::info!(
-            "Bloom filter calculate optimal hashes, num_hashes: {}",
-            num_hashes
-        );
-        num_hashes
+        std::cmp::max(2, k.ceil() as usize) // at least two hashes
     }
 
     /// Returns the number of bits of the bloom filter.
     pub fn num_bits(&self) -> usize {
-        self.container.len()
+        self.num_bits
     }
 
     /// Returns the number of hashes of the bloom filter.
@@ -351,10 +389,20 @@ impl BloomFilter {
     }
 
     /// Returns the reference of bitmap container
-    pub fn bitmap(&self) -> &BitVec<u32> {
+    pub fn bitmap(&self) -> &[u64] {
         &self.container
     }
 
+    /// Estimate the false positive rate from the ratio of bits set: (bits_set / m) ^ k
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let bits_set = self
+            .container
+            .iter()
+            .map(|word| word.count_ones() as u64)
+            .sum::<u64>();
+        (bits_set as f64 / self.num_bits as f64).powi(self.num_hashes as i32)
+    }
+
     /// Returns true if the bitmap contains the other's bitmap.
     /// Notice: this function doesn't do any schema check, but only bits comparison.
     pub fn contains(&self, other: &BloomFilter) -> bool {
@@ -362,8 +410,10 @@ impl BloomFilter {
             return false;
         }
 
-        let mut copy = self.container.clone();
-        !copy.or(other.bitmap())
+        self.container
+            .iter()
+            .zip(other.bitmap())
+            .all(|(word, other_word)| word | other_word == *word)
     }
 
     /// Clone and return an empty bloom filter with same number of bits, seeds and hashes.
@@ -381,57 +431,97 @@ impl BloomFilter {
     /// "Bloom filters support columns with the following (input) data types: byte, short, int,
     /// long, float, double, date, timestamp, and string."
     ///
-    /// Nulls are not added to the Bloom
-    /// filter, so any null related filter requires reading the data file. "
+    /// Float32 is left out, the values are widened to Float64 and would never match a
+    /// Float64 literal of the same text. Nulls are not added to the Bloom filter, so any
+    /// null related filter requires reading the data file.
     pub fn is_supported_type(data_type: &DataTypePtr) -> bool {
-        let data_type_id = data_type.data_type_id();
-        matches!(
-            data_type_id,
-            TypeID::UInt8
-                | TypeID::UInt16
-                | TypeID::UInt32
-                | TypeID::UInt64
-                | TypeID::Int8
-                | TypeID::Int16
-                | TypeID::Int32
-                | TypeID::Int64
-                | TypeID::Float32
-                | TypeID::Float64
-                | TypeID::Date16
-                | TypeID::Date32
-                | TypeID::DateTime32
-                | TypeID::DateTime64
-                | TypeID::Interval
-                | TypeID::String
+        let data_type_id = remove_nullable(data_type).data_type_id();
+        data_type_id.is_integer()
+            || data_type_id.is_date_or_date_time()
+            || data_type_id.is_interval()
+            || data_type_id.is_string()
+            || data_type_id == TypeID::Float64
+    }
+
+    /// Return whether the data value can be looked up in the bloom filter of a column
+    /// with the given data type. Nulls are not supported.
+    pub fn is_supported_value(data_type: &DataTypePtr, value: &DataValue) -> bool {
+        if !Self::is_supported_type(data_type) {
+            return false;
+        }
+
+        let data_type_id = remove_nullable(data_type).data_type_id();
+        match value {
+            DataValue::Int64(_) | DataValue::UInt64(_) => {
+                data_type_id.is_integer()
+                    || data_type_id.is_date_or_date_time()
+                    || data_type_id.is_interval()
+            }
+            DataValue::Float64(_) => data_type_id == TypeID::Float64,
+            DataValue::String(_) => data_type_id.is_string(),
+            _ => false,
+        }
+    }
+
+    // Seeded FNV-1a, finalized by the fmix64 step of murmur3 so that the low bits are well mixed.
+    // We don't rely on std hashers here: the bits are persisted, the hash must be stable.
+    #[inline(always)]
+    fn hash_bytes(seed0: u64, seed1: u64, bytes: &[u8]) -> u64 {
+        let mut hash = 0xcbf29ce484222325_u64 ^ seed0;
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+
+        hash ^= seed1 ^ bytes.len() as u64;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+        hash ^= hash >> 33;
+        hash
+    }
+
+    #[inline(always)]
+    fn hash_pair(&self, bytes: &[u8]) -> (u64, u64) {
+        (
+            Self::hash_bytes(self.seeds[0], self.seeds[1], bytes),
+            Self::hash_bytes(self.seeds[2], self.seeds[3], bytes),
         )
     }
 
-    /// Return whether the data value is supported by bloom filter.
-    /// Nulls are not supported.
-    pub fn is_supported_value(value: &DataValue) -> bool {
-        Self::is_supported_type(&value.data_type()) && !value.is_null()
+    // Integers are widened to i128 so that signed and unsigned values hash the same, and
+    // -0.0 is normalized so that it hashes the same as 0.0.
+    #[inline(always)]
+    fn hash_value(&self, value: &DataValue) -> Option<(u64, u64)> {
+        match value {
+            DataValue::Int64(v) => Some(self.hash_pair(&(*v as i128).to_le_bytes())),
+            DataValue::UInt64(v) => Some(self.hash_pair(&(*v as i128).to_le_bytes())),
+            DataValue::Float64(v) => {
+                let v = if *v == 0.0 { 0.0_f64 } else { *v };
+                Some(self.hash_pair(&v.to_bits().to_le_bytes()))
+            }
+            DataValue::String(v) => Some(self.hash_pair(v)),
+            _ => None,
+        }
     }
 
-    // Create hasher for bloom. Use seahash for now, may change to city hash.
     #[inline(always)]
-    fn create_hasher(&self) -> DFHasher {
-        let hasher =
-            SeaHasher::with_seeds(self.seeds[0], self.seeds[1], self.seeds[2], self.seeds[3]);
-        DFHasher::SeaHasher64(hasher, self.seeds)
+    fn bit_position(&self, hash1: u64, hash2: u64, i: usize) -> usize {
+        let h1 = std::num::Wrapping(hash1);
+        let h2 = std::num::Wrapping(hash2);
+        let index = std::num::Wrapping(i as u64);
+        let res = (h1 + index * h2 + index * index).0;
+        (res % self.num_bits as u64) as usize
     }
 
     #[inline(always)]
     // Set bits for bloom filter, ported from Clickhouse.
     // https://github.com/ClickHouse/ClickHouse/blob/1bf375e2b761db5b99b0f403b90c412a530f4d5c/src/Interpreters/BloomFilter.cpp#L67
-    fn set_bits(&mut self, hash1: &u64, hash2: &u64) {
-        let h1 = std::num::Wrapping(*hash1);
-        let h2 = std::num::Wrapping(*hash2);
-
+    fn set_bits(&mut self, hash1: u64, hash2: u64) {
         for i in 0..self.num_hashes {
-            let index = std::num::Wrapping(i as u64);
-            let res = (h1 + index * h2 + index * index).0;
-            let bit_pos = (res % self.container.len() as u64) as usize;
-            self.container.set(bit_pos, true);
+            let bit_pos = self.bit_position(hash1, hash2, i);
+            self.container[bit_pos / 64] |= 1 << (bit_pos % 64);
         }
     }
 
@@ -442,45 +532,39 @@ impl BloomFilter {
     pub fn add(&mut self, column: &ColumnRef) -> Result<()> {
         if !Self::is_supported_type(&column.data_type()) {
             return Err(ErrorCode::BadArguments(format!(
-                "Unsupported data type: {} ",
+                "Unsupported data type: {:?} ",
                 column.data_type()
             )));
         }
 
-        let series = column.to_minimal_array()?;
-
-        let hasher1 = self.create_hasher();
-        let hash1_arr = series.vec_hash(hasher1)?;
-
-        let hasher2 = self.create_hasher();
-        let hash2_arr = series.vec_hash(hasher2)?;
-
-        let validity = series.validity();
-        let no_null = validity == None || series.null_count() == 0;
-        let all_null = series.len() == series.null_count();
-
-        if all_null {
+        let column = column.convert_full_column();
+        let validity = match column.validity() {
+            (true, _) => return Ok(()),
+            (false, validity) => validity.cloned(),
+        };
+        let is_valid = |row: usize| validity.as_ref().map_or(true, |v| v.get_bit(row));
+
+        let inner = Series::remove_nullable(&column);
+        if inner.data_type_id().is_string() {
+            // fast path, avoid materializing a DataValue per row
+            let strings: &StringColumn = Series::check_get(&inner)?;
+            for (row, bytes) in strings.iter().enumerate() {
+                if is_valid(row) {
+                    let (hash1, hash2) = self.hash_pair(bytes);
+                    self.set_bits(hash1, hash2);
+                }
+            }
             return Ok(());
         }
 
-        if no_null {
-            hash1_arr
-                .into_no_null_iter()
-                .zip(hash2_arr.into_no_null_iter())
-                .for_each(|(h1, h2)| {
-                    self.set_bits(h1, h2);
-                });
-        } else {
-            let bitmap = validity.unwrap();
-            bitmap
-                .into_iter()
-                .zip(hash1_arr.into_no_null_iter())
-                .zip(hash2_arr.into_no_null_iter())
-                .for_each(|((valid, h1), h2)| {
-                    if valid {
-                        self.set_bits(h1, h2);
-                    }
-                });
+        for row in 0..inner.len() {
+            if !is_valid(row) {
+                continue;
+            }
+
+            if let Some((hash1, hash2)) = self.hash_value(&inner.get(row)) {
+                self.set_bits(hash1, hash2);
+            }
         }
         Ok(())
     }
@@ -494,42 +578,19 @@ impl BloomFilter {
     ///
     /// Example:
     /// ```
-    ///     let not_exist = BloomFilter::is_supported_value(data_value) && !bloom.find(data_value)?;
+    ///     let not_exist = BloomFilter::is_supported_value(data_type, data_value) && !bloom.find(data_value)?;
     ///
     /// ```
-    pub fn find(&self, val: DataValue) -> Result<bool> {
-        if !Self::is_supported_value(&val) {
-            return Err(ErrorCode::BadArguments(format!(
-                "Unsupported data value: {} ",
-                val
-            )));
-        }
-
-        let col = ColumnRef::Constant(val, 1);
-        let series = col.to_minimal_array()?;
-
-        let hasher1 = self.create_hasher();
-        let hash1_arr = series.vec_hash(hasher1)?;
-        let hash1 = hash1_arr.inner().value(0);
-
-        let hasher2 = self.create_hasher();
-        let hash2_arr = series.vec_hash(hasher2)?;
-        let hash2 = hash2_arr.inner().value(0);
-
-        let h1 = std::num::Wrapping(hash1);
-        let h2 = std::num::Wrapping(hash2);
-        for i in 0..self.num_hashes {
-            let index = std::num::Wrapping(i as u64);
-            let res = (h1 + index * h2 + index * index).0;
-            let bit_pos = (res % self.container.len() as u64) as usize;
-            // If any bit is not 1 in bloom filter, it means the data never ever showed up before.
-            // unwrap should be fine because 'bit_pos' was mod by container.len()
-            let is_bit_set = self.container.get(bit_pos).unwrap();
-            if !is_bit_set {
-                return Ok(false);
-            }
-        }
-        Ok(true)
+    pub fn find(&self, val: &DataValue) -> Result<bool> {
+        let (hash1, hash2) = self.hash_value(val).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("Unsupported data value: {:?} ", val))
+        })?;
+
+        // If any bit is not 1 in bloom filter, it means the data never ever showed up before.
+        Ok((0..self.num_hashes).all(|i| {
+            let bit_pos = self.bit_position(hash1, hash2, i);
+            self.container[bit_pos / 64] & (1 << (bit_pos % 64)) != 0
+        }))
     }
 
     /// Serialize the bloom filter to byte vector.
@@ -554,3 +615,32 @@ impl BloomFilter {
         }
     }
 }
+
+mod bitmap_format {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(words: &[u64], serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>();
+        serializer.serialize_str(&base64::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u64>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = base64::decode(encoded).map_err(serde::de::Error::custom)?;
+        if bytes.len() % 8 != 0 {
+            return Err(serde::de::Error::custom(
+                "invalid bloom filter bitmap length",
+            ));
+        }
+
+        Ok(bytes
+            .chunks(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect())
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues2::DataValue;
+
+use crate::storages::index::HyperLogLog;
+
+/// Number of buckets of the histograms collected by `ANALYZE TABLE`
+pub const DEFAULT_HISTOGRAM_BUCKETS: usize = 100;
+
+/// The distribution of the values of a column, collected by `ANALYZE TABLE`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ColumnDistribution {
+    /// Estimated number of distinct non-null values
+    pub ndv: u64,
+    pub null_count: u64,
+    pub sketch: HyperLogLog,
+    pub histogram: Histogram,
+}
+
+/// An equi-height histogram of the non-null values of a column.
+///
+/// Each bucket holds roughly the same number of values, equal values never span two buckets.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Histogram {
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct HistogramBucket {
+    pub lower_bound: DataValue,
+    pub upper_bound: DataValue,
+    pub num_values: u64,
+    pub num_distinct: u64,
+}
+
+impl Histogram {
+    /// Builds the histogram from the sorted non-null values of a column.
+    pub fn from_sorted_values(values: &[DataValue], num_buckets: usize) -> Histogram {
+        let len = values.len();
+        let height = ((len + num_buckets.max(1) - 1) / num_buckets.max(1)).max(1);
+
+        let mut buckets = vec![];
+        let mut start = 0;
+        while start < len {
+            let mut end = (start + height).min(len);
+            while end < len && values[end] == values[end - 1] {
+                end += 1;
+            }
+
+            let num_distinct = 1
+                + (start + 1..end)
+                    .filter(|idx| values[*idx] != values[*idx - 1])
+                    .count();
+            buckets.push(HistogramBucket {
+                lower_bound: values[start].clone(),
+                upper_bound: values[end - 1].clone(),
+                num_values: (end - start) as u64,
+                num_distinct: num_distinct as u64,
+            });
+            start = end;
+        }
+
+        Histogram { buckets }
+    }
+
+    /// Builds the histogram from a sorted sample of the non-null values of a column, the
+    /// numbers of values of the buckets are scaled up to the `num_values` of the column, while
+    /// the numbers of distinct values are those seen in the sample.
+    pub fn from_sorted_sample(
+        sample: &[DataValue],
+        num_values: u64,
+        num_buckets: usize,
+    ) -> Histogram {
+        let mut histogram = Self::from_sorted_values(sample, num_buckets);
+        if !sample.is_empty() && num_values > sample.len() as u64 {
+            let scale = num_values as f64 / sample.len() as f64;
+            for bucket in &mut histogram.buckets {
+                bucket.num_values = (bucket.num_values as f64 * scale).round() as u64;
+            }
+        }
+        histogram
+    }
+
+    pub fn num_values(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.num_values).sum()
+    }
+
+    /// Fraction of the values less than `value`, the values inside a bucket are assumed to be
+    /// distributed uniformly. None if the bounds of the buckets are not numeric.
+    pub fn fraction_below(&self, value: f64) -> Option<f64> {
+        let total = self.num_values();
+        if total == 0 {
+            return None;
+        }
+
+        let mut below = 0.0;
+        for bucket in &self.buckets {
+            let lower = bucket.lower_bound.as_f64().ok()?;
+            let upper = bucket.upper_bound.as_f64().ok()?;
+            if value > upper {
+                below += bucket.num_values as f64;
+                continue;
+            }
+            if value > lower {
+                below += bucket.num_values as f64 * (value - lower) / (upper - lower);
+            }
+            break;
+        }
+        Some(below / total as f64)
+    }
+}
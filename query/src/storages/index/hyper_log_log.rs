@@ -0,0 +1,148 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::Hasher;
+
+use common_datavalues2::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use twox_hash::XxHash64;
+
+/// Number of bits of the hash used to pick a register, 2^12 registers give a standard error
+/// of about 1.6%
+const PRECISION: u32 = 12;
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// A HyperLogLog sketch, estimates the number of distinct values added to it.
+///
+/// Sketches are mergeable, the sketch of a table is the union of the sketches of its blocks.
+/// The values are hashed with the seeded xxHash64, whose output is stable across builds and
+/// platforms, so the persisted sketches can be merged with the ones collected later.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        HyperLogLog {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+
+    /// Adds a value to the sketch, NULLs are not counted.
+    pub fn add(&mut self, value: &DataValue) {
+        if value.is_null() {
+            return;
+        }
+
+        let mut hasher = XxHash64::with_seed(0);
+        hash_value(value, &mut hasher);
+        self.add_hash(hasher.finish());
+    }
+
+    pub fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // the guard bit bounds the rank, if all the remaining bits are zero
+        let remaining = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<()> {
+        if self.registers.len() != other.registers.len() {
+            return Err(ErrorCode::LogicalError(format!(
+                "can not merge HyperLogLog sketches of {} and {} registers",
+                self.registers.len(),
+                other.registers.len()
+            )));
+        }
+
+        for (register, other) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *register = (*register).max(*other);
+        }
+        Ok(())
+    }
+
+    /// The estimated number of distinct values.
+    pub fn count(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-(*register as i32)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+
+        // small range correction, counts the empty registers instead (linear counting)
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as u64;
+        }
+        estimate.round() as u64
+    }
+}
+
+// Each value is written as a tag byte followed by its little-endian bytes, which never
+// depends on the layout of the types on the platform.
+fn hash_value(value: &DataValue, hasher: &mut XxHash64) {
+    match value {
+        DataValue::Null => hasher.write_u8(0),
+        DataValue::Boolean(v) => {
+            hasher.write_u8(1);
+            hasher.write_u8(*v as u8);
+        }
+        DataValue::Int64(v) => {
+            hasher.write_u8(2);
+            hasher.write(&v.to_le_bytes());
+        }
+        DataValue::UInt64(v) => {
+            hasher.write_u8(3);
+            hasher.write(&v.to_le_bytes());
+        }
+        DataValue::Float64(v) => {
+            hasher.write_u8(4);
+            hasher.write(&v.to_bits().to_le_bytes());
+        }
+        DataValue::String(v) => {
+            hasher.write_u8(5);
+            hasher.write(&(v.len() as u64).to_le_bytes());
+            hasher.write(v);
+        }
+        DataValue::Array(values) => {
+            hasher.write_u8(6);
+            hash_values(values, hasher);
+        }
+        DataValue::Struct(values) => {
+            hasher.write_u8(7);
+            hash_values(values, hasher);
+        }
+    }
+}
+
+fn hash_values(values: &[DataValue], hasher: &mut XxHash64) {
+    hasher.write(&(values.len() as u64).to_le_bytes());
+    for value in values {
+        hash_value(value, hasher);
+    }
+}
@@ -12,15 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// TODO: refactor bloom_filter into datavalues2
-// mod bloom_filter;
+mod bloom_filter;
+mod histogram;
+mod hyper_log_log;
 mod index_min_max;
 mod index_sparse;
 pub mod range_filter;
 
-// pub use bloom_filter::BloomFilter;
-// pub use bloom_filter::BloomFilterExprEvalResult;
-// pub use bloom_filter::BloomFilterIndexer;
+pub use bloom_filter::BlockBloomFilters;
+pub use bloom_filter::BloomFilter;
+pub use bloom_filter::BloomFilterExprEvalResult;
+pub use bloom_filter::BloomFilterIndexer;
+pub use histogram::ColumnDistribution;
+pub use histogram::Histogram;
+pub use histogram::HistogramBucket;
+pub use histogram::DEFAULT_HISTOGRAM_BUCKETS;
+pub use hyper_log_log::HyperLogLog;
 pub use index_min_max::MinMaxIndex;
 pub use index_sparse::SparseIndex;
 pub use index_sparse::SparseIndexValue;
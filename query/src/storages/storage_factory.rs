@@ -25,6 +25,8 @@ use crate::storages::fuse::FuseTable;
 use crate::storages::github::GithubTable;
 use crate::storages::memory::MemoryTable;
 use crate::storages::null::NullTable;
+use crate::storages::view::ViewTable;
+use crate::storages::view::VIEW_ENGINE;
 use crate::storages::StorageContext;
 use crate::storages::Table;
 
@@ -104,6 +106,12 @@ impl StorageFactory {
             descriptor: Arc::new(FuseTable::description),
         });
 
+        // Register VIEW table engine.
+        creators.insert(VIEW_ENGINE.to_string(), Storage {
+            creator: Arc::new(ViewTable::try_create),
+            descriptor: Arc::new(ViewTable::description),
+        });
+
         StorageFactory {
             storages: RwLock::new(creators),
         }
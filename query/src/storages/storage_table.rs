@@ -15,13 +15,18 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_datablocks::DataBlock;
 use common_datavalues2::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::MetaId;
 use common_meta_types::TableInfo;
+use common_planners::AlterTablePlan;
+use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::Part;
@@ -29,9 +34,12 @@ use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
 use common_streams::SendableDataBlockStream;
 
 use crate::sessions::QueryContext;
+use crate::storages::index::ColumnDistribution;
+use crate::storages::index::ColumnStatistics;
 
 #[async_trait::async_trait]
 pub trait Table: Sync + Send {
@@ -84,6 +92,11 @@ pub trait Table: Sync + Send {
         None
     }
 
+    /// The statistics of the table for the optimizer, None if the table keeps no statistics.
+    async fn statistics(&self, _ctx: Arc<QueryContext>) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
+
     // Read block data from the underling.
     async fn read(
         &self,
@@ -123,7 +136,94 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn delete(&self, _ctx: Arc<QueryContext>, _delete_plan: DeletePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "delete for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn update(&self, _ctx: Arc<QueryContext>, _update_plan: UpdatePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "update for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn alter(&self, _ctx: Arc<QueryContext>, _alter_plan: AlterTablePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "alter for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
     async fn optimize(&self, _ctx: Arc<QueryContext>, _keep_last_snapshot: bool) -> Result<()> {
         Ok(())
     }
+
+    async fn compact(&self, _ctx: Arc<QueryContext>) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "compact for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Removes the files of the table that are no longer referenced by it and older than
+    /// `older_than` (or the default retention of the engine if None).
+    async fn purge_orphans(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _older_than: Option<Duration>,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "purge orphans for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Collects the statistics of the columns, see [TableStatistics::column_distributions].
+    async fn analyze(&self, _ctx: Arc<QueryContext>) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "analyze for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Returns the table as of the given point of its history.
+    async fn navigate_to(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _point: &NavigationPoint,
+    ) -> Result<Arc<dyn Table>> {
+        Err(ErrorCode::UnImplement(format!(
+            "time travel for table {} is not supported, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+}
+
+/// Statistics of the whole table, used to estimate the cost of plans.
+#[derive(Clone, Debug, Default)]
+pub struct TableStatistics {
+    pub num_rows: u64,
+    pub data_size: u64,
+    pub data_size_compressed: u64,
+    /// Keyed by the position of the column in the schema of the table.
+    pub column_statistics: HashMap<u32, ColumnStatistics>,
+    /// Distributions of the columns, only available once the table is analyzed, keyed the same.
+    pub column_distributions: HashMap<u32, ColumnDistribution>,
+}
+
+/// A point of the history of a table, see [Table::navigate_to]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NavigationPoint {
+    SnapshotID(String),
+    TimePoint(DateTime<Utc>),
 }
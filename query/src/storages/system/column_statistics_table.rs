@@ -0,0 +1,150 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+use common_planners::ReadDataSourcePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::sessions::QueryContext;
+use crate::storages::index::Histogram;
+use crate::storages::Table;
+
+/// The statistics of the columns collected by `ANALYZE TABLE`, tables which are not analyzed
+/// are left out.
+pub struct ColumnStatisticsTable {
+    table_info: TableInfo,
+}
+
+impl ColumnStatisticsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("ndv", u64::to_data_type()),
+            DataField::new("null_count", u64::to_data_type()),
+            DataField::new("histogram_buckets", u64::to_data_type()),
+            DataField::new("histogram", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'column_statistics'".to_string(),
+            name: "column_statistics".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemColumnStatistics".to_string(),
+                ..Default::default()
+            },
+        };
+
+        Self { table_info }
+    }
+
+    /// Renders the buckets as `[lower_bound, upper_bound]: num_values`
+    fn display_histogram(histogram: &Histogram) -> String {
+        histogram
+            .buckets
+            .iter()
+            .map(|bucket| {
+                format!(
+                    "[{}, {}]: {}",
+                    bucket.lower_bound, bucket.upper_bound, bucket.num_values
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ColumnStatisticsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        _plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog();
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut database_names: Vec<Vec<u8>> = vec![];
+        let mut table_names: Vec<Vec<u8>> = vec![];
+        let mut names: Vec<Vec<u8>> = vec![];
+        let mut ndvs: Vec<u64> = vec![];
+        let mut null_counts: Vec<u64> = vec![];
+        let mut histogram_buckets: Vec<u64> = vec![];
+        let mut histograms: Vec<Vec<u8>> = vec![];
+        for database in databases {
+            for table in catalog
+                .list_tables(tenant.as_str(), database.name())
+                .await?
+            {
+                let statistics = match table.statistics(ctx.clone()).await? {
+                    Some(statistics) => statistics,
+                    None => continue,
+                };
+
+                let schema = table.schema();
+                for (position, field) in schema.fields().iter().enumerate() {
+                    if let Some(distribution) =
+                        statistics.column_distributions.get(&(position as u32))
+                    {
+                        database_names.push(database.name().as_bytes().to_vec());
+                        table_names.push(table.name().as_bytes().to_vec());
+                        names.push(field.name().as_bytes().to_vec());
+                        ndvs.push(distribution.ndv);
+                        null_counts.push(distribution.null_count);
+                        histogram_buckets.push(distribution.histogram.buckets.len() as u64);
+                        histograms
+                            .push(Self::display_histogram(&distribution.histogram).into_bytes());
+                    }
+                }
+            }
+        }
+
+        let block = DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(database_names),
+            Series::from_data(table_names),
+            Series::from_data(names),
+            Series::from_data(ndvs),
+            Series::from_data(null_counts),
+            Series::from_data(histogram_buckets),
+            Series::from_data(histograms),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema(),
+            None,
+            vec![block],
+        )))
+    }
+}
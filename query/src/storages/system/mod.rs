@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod clusters_table;
+mod column_statistics_table;
 mod columns_table;
 mod configs_table;
 mod contributors_table;
@@ -24,6 +25,7 @@ mod metrics_table;
 mod one_table;
 mod processes_table;
 mod query_log_table;
+mod quotas_table;
 mod settings_table;
 mod tables_table;
 mod tracing_table;
@@ -31,6 +33,7 @@ mod tracing_table_stream;
 mod users_table;
 
 pub use clusters_table::ClustersTable;
+pub use column_statistics_table::ColumnStatisticsTable;
 pub use columns_table::ColumnsTable;
 pub use configs_table::ConfigsTable;
 pub use contributors_table::ContributorsTable;
@@ -42,6 +45,7 @@ pub use metrics_table::MetricsTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
 pub use query_log_table::QueryLogTable;
+pub use quotas_table::QuotasTable;
 pub use settings_table::SettingsTable;
 pub use tables_table::TablesTable;
 pub use tracing_table::TracingTable;
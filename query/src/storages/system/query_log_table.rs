@@ -76,6 +76,7 @@ impl QueryLogTable {
             DataField::new("result_bytes", u64::to_data_type()),
             DataField::new("cpu_usage", u32::to_data_type()),
             DataField::new("memory_usage", u64::to_data_type()),
+            DataField::new("stage_retries", u64::to_data_type()),
             // Client.
             DataField::new("client_info", Vu8::to_data_type()),
             DataField::new("client_address", Vu8::to_data_type()),
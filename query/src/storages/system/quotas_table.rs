@@ -0,0 +1,104 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+use common_planners::ReadDataSourcePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::sessions::QueryContext;
+use crate::storages::fuse::operations::tenant_storage_usage;
+use crate::storages::Table;
+
+/// The quotas of the users, with the resources used against them.
+///
+/// The storage quota of a user limits the storage used by all the tables of the tenant.
+pub struct QuotasTable {
+    table_info: TableInfo,
+}
+
+impl QuotasTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("hostname", Vu8::to_data_type()),
+            DataField::new("max_cpu", u64::to_data_type()),
+            DataField::new("max_memory_in_bytes", u64::to_data_type()),
+            DataField::new("max_storage_in_bytes", u64::to_data_type()),
+            DataField::new("used_storage_in_bytes", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'quotas'".to_string(),
+            name: "quotas".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemQuotas".to_string(),
+                ..Default::default()
+            },
+        };
+        QuotasTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QuotasTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        _plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let tenant = ctx.get_tenant();
+        let users = ctx.get_user_manager().get_users(&tenant).await?;
+        let used_storage = tenant_storage_usage(&ctx).await?;
+
+        let names: Vec<&str> = users.iter().map(|x| x.name.as_str()).collect();
+        let hostnames: Vec<&str> = users.iter().map(|x| x.hostname.as_str()).collect();
+        let max_cpus: Vec<u64> = users.iter().map(|x| x.quota.max_cpu).collect();
+        let max_memories: Vec<u64> = users.iter().map(|x| x.quota.max_memory_in_bytes).collect();
+        let max_storages: Vec<u64> = users.iter().map(|x| x.quota.max_storage_in_bytes).collect();
+        let used_storages: Vec<u64> = vec![used_storage; users.len()];
+
+        let block = DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(hostnames),
+            Series::from_data(max_cpus),
+            Series::from_data(max_memories),
+            Series::from_data(max_storages),
+            Series::from_data(used_storages),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema(),
+            None,
+            vec![block],
+        )))
+    }
+}
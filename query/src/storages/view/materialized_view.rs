@@ -0,0 +1,89 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The table option of a materialized view that holds the query of it.
+pub const MATERIALIZED_VIEW_QUERY: &str = "materialized_view_query";
+
+/// The table option of a materialized view that holds the table id of its source table.
+pub const MATERIALIZED_VIEW_SOURCE: &str = "materialized_view_source";
+
+/// The table option of a source table that holds the materialized views over it, in json.
+pub const MATERIALIZED_VIEWS: &str = "materialized_views";
+
+/// A materialized view is a FUSE table holding the result of its query over the rows inserted
+/// into its source table since the view is created, the query runs over the new blocks of
+/// every insertion into the source table and the result is appended to the view.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MaterializedViewRef {
+    pub database: String,
+    pub table: String,
+}
+
+impl MaterializedViewRef {
+    /// The materialized views registered to the source table.
+    pub fn list(source_options: &HashMap<String, String>) -> Result<Vec<MaterializedViewRef>> {
+        match source_options.get(MATERIALIZED_VIEWS) {
+            None => Ok(vec![]),
+            Some(views) => serde_json::from_str(views).map_err(|e| {
+                ErrorCode::LogicalError(format!(
+                    "Logical error, invalid materialized views {}: {}",
+                    views, e
+                ))
+            }),
+        }
+    }
+
+    pub fn to_option_value(views: &[MaterializedViewRef]) -> Result<String> {
+        serde_json::to_string(views).map_err(|e| ErrorCode::LogicalError(e.to_string()))
+    }
+}
+
+pub fn is_materialized_view(table_info: &TableInfo) -> bool {
+    table_info.options().contains_key(MATERIALIZED_VIEW_QUERY)
+}
+
+/// The table id of the source table of the materialized view.
+pub fn materialized_view_source(table_info: &TableInfo) -> Result<u64> {
+    match table_info.options().get(MATERIALIZED_VIEW_SOURCE) {
+        Some(source) => source.parse::<u64>().map_err(|e| {
+            ErrorCode::LogicalError(format!(
+                "Logical error, invalid source {} of materialized view {}: {}",
+                source, table_info.name, e
+            ))
+        }),
+        None => Err(ErrorCode::LogicalError(format!(
+            "Logical error, the source of materialized view {} is missing",
+            table_info.name
+        ))),
+    }
+}
+
+/// The query of the materialized view.
+pub fn materialized_view_query(table_info: &TableInfo) -> Result<&str> {
+    match table_info.options().get(MATERIALIZED_VIEW_QUERY) {
+        Some(query) => Ok(query),
+        None => Err(ErrorCode::LogicalError(format!(
+            "Logical error, the query of materialized view {} is missing",
+            table_info.name
+        ))),
+    }
+}
@@ -66,6 +66,7 @@ table_engine_memory_enabled = true
 database_engine_github_enabled = true
 wait_timeout_mills = 5000
 max_query_log_size = 10000
+query_result_cache_count = 1024
 table_cache_enabled = false
 table_cache_snapshot_count = 256
 table_cache_segment_count = 10240
@@ -108,6 +109,11 @@ bucket = \"\"
 account = \"\"
 master_key = \"\"
 container = \"\"
+
+[storage.gcs]
+endpoint_url = \"\"
+bucket = \"\"
+credential = \"\"
 ";
 
     let tom_actual = toml::to_string(&actual).unwrap();
@@ -141,6 +147,9 @@ fn test_env_config() -> Result<()> {
     std::env::set_var("S3_STORAGE_SECRET_ACCESS_KEY", "us.key");
     std::env::set_var("S3_STORAGE_ENABLE_POD_IAM_POLICY", "true");
     std::env::set_var("S3_STORAGE_BUCKET", "us.bucket");
+    std::env::set_var("GCS_STORAGE_ENDPOINT_URL", "http://127.0.0.1:4443");
+    std::env::set_var("GCS_STORAGE_BUCKET", "gcs.bucket");
+    std::env::set_var("GCS_STORAGE_CREDENTIAL", "gcs.credential");
     std::env::set_var("QUERY_TABLE_ENGINE_CSV_ENABLED", "true");
     std::env::set_var("QUERY_TABLE_ENGINE_PARQUET_ENABLED", "true");
     std::env::set_var("QUERY_TABLE_ENGINE_MEMORY_ENABLED", "true");
@@ -174,6 +183,10 @@ fn test_env_config() -> Result<()> {
     assert!(configured.storage.s3.enable_pod_iam_policy);
     assert_eq!("us.bucket", configured.storage.s3.bucket);
 
+    assert_eq!("http://127.0.0.1:4443", configured.storage.gcs.endpoint_url);
+    assert_eq!("gcs.bucket", configured.storage.gcs.bucket);
+    assert_eq!("gcs.credential", configured.storage.gcs.credential);
+
     assert!(configured.query.table_engine_csv_enabled);
     assert!(configured.query.table_engine_parquet_enabled);
     assert!(configured.query.table_engine_memory_enabled);
@@ -208,6 +221,9 @@ fn test_env_config() -> Result<()> {
     std::env::remove_var("S3_STORAGE_SECRET_ACCESS_KEY");
     std::env::remove_var("S3_STORAGE_BUCKET");
     std::env::remove_var("S3_STORAGE_ENABLE_POD_IAM_POLICY");
+    std::env::remove_var("GCS_STORAGE_ENDPOINT_URL");
+    std::env::remove_var("GCS_STORAGE_BUCKET");
+    std::env::remove_var("GCS_STORAGE_CREDENTIAL");
     std::env::remove_var("QUERY_TABLE_ENGINE_CSV_ENABLED");
     std::env::remove_var("QUERY_TABLE_ENGINE_PARQUET_ENABLED");
     std::env::remove_var("QUERY_TABLE_ENGINE_MEMORY_ENABLED");
@@ -76,3 +76,70 @@ async fn test_select_interpreter() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_new_processor_framework() -> Result<()> {
+    common_tracing::init_default_ut_tracing();
+    let ctx = crate::tests::create_query_context()?;
+    ctx.get_settings().set_settings(
+        "enable_new_processor_framework".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+
+    {
+        let query = "select number from numbers_mt(10) where number > 5 order by number desc limit 2 offset 1";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 8      |",
+            "| 7      |",
+            "+--------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    {
+        let query =
+            "select count() as c, sum(number + 1) as s from numbers_mt(100) where number % 3 = 0";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+------+",
+            "| c  | s    |",
+            "+----+------+",
+            "| 34 | 1717 |",
+            "+----+------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    {
+        let query = "select number % 3 as k, count() as c from numbers_mt(10) group by k having c > 3 or k = 2";
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---+---+",
+            "| k | c |",
+            "+---+---+",
+            "| 0 | 4 |",
+            "| 2 | 3 |",
+            "+---+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
@@ -13,3 +13,4 @@
 // limitations under the License.
 
 mod plan_scheduler;
+mod plan_scheduler_query;
@@ -0,0 +1,73 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::interpreters::*;
+use databend_query::sql::*;
+use futures::TryStreamExt;
+
+use crate::tests::create_query_context_with_cluster;
+use crate::tests::ClusterDescriptor;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_schedule_query_with_failed_node() -> Result<()> {
+    let query = "SELECT SUM(number) FROM numbers(1000)";
+
+    // Reschedule the query on the local node only.
+    {
+        let ctx = create_query_context_with_cluster(
+            ClusterDescriptor::new()
+                .with_node("failed_node", "127.0.0.1:1")
+                .with_node("dummy_local", "127.0.0.1:9090")
+                .with_local_id("dummy_local"),
+        )?;
+
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        let expected = vec![
+            "+-------------+",
+            "| SUM(number) |",
+            "+-------------+",
+            "| 499500      |",
+            "+-------------+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        assert_eq!(ctx.get_stage_retries(), 1);
+        assert_eq!(ctx.get_cluster().get_nodes().len(), 1);
+    }
+
+    // No retry.
+    {
+        let ctx = create_query_context_with_cluster(
+            ClusterDescriptor::new()
+                .with_node("failed_node", "127.0.0.1:1")
+                .with_node("dummy_local", "127.0.0.1:9090")
+                .with_local_id("dummy_local"),
+        )?;
+        ctx.get_settings()
+            .set_settings("max_stage_retries".to_string(), "0".to_string(), false)?;
+
+        let plan = PlanParser::parse(ctx.clone(), query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        assert!(executor.execute(None).await.is_err());
+        assert_eq!(ctx.get_stage_retries(), 0);
+        assert_eq!(ctx.get_cluster().get_nodes().len(), 2);
+    }
+
+    Ok(())
+}
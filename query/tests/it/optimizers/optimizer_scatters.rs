@@ -14,6 +14,9 @@
 
 use common_base::tokio;
 use common_exception::Result;
+use common_planners::JoinPlan;
+use common_planners::PlanNode;
+use common_planners::StageKind;
 use databend_query::optimizers::Optimizer;
 use databend_query::optimizers::ScattersOptimizer;
 use databend_query::sql::PlanParser;
@@ -219,3 +222,119 @@ async fn test_scatter_optimizer() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scatter_optimizer_with_join() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect_left: &'static str,
+        expect_right: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Standalone join",
+            query: "SELECT * FROM numbers_local(10) AS a JOIN numbers_local(10) AS b ON a.number = b.number",
+            expect_left: "Local",
+            expect_right: "Local",
+        },
+        Test {
+            name: "Standalone join with cluster right input",
+            query: "SELECT * FROM numbers_local(10) AS a JOIN numbers(100000000) AS b ON a.number = b.number",
+            expect_left: "Local",
+            expect_right: "Convergent",
+        },
+        Test {
+            name: "Cluster join with small right input",
+            query: "SELECT * FROM numbers(100000000) AS a JOIN numbers(10) AS b ON a.number = b.number",
+            expect_left: "Local",
+            expect_right: "Broadcast",
+        },
+        Test {
+            name: "Cluster join with standalone right input",
+            query: "SELECT * FROM numbers(100000000) AS a LEFT JOIN numbers_local(10) AS b ON a.number = b.number",
+            expect_left: "Local",
+            expect_right: "Broadcast",
+        },
+        Test {
+            name: "Cluster cross join",
+            query: "SELECT * FROM numbers(100000000) AS a CROSS JOIN numbers(100000000) AS b",
+            expect_left: "Local",
+            expect_right: "Broadcast",
+        },
+        Test {
+            name: "Cluster join with large right input",
+            query: "SELECT * FROM numbers(100000000) AS a JOIN numbers(100000000) AS b ON a.number = b.number",
+            expect_left: "Normal",
+            expect_right: "Normal",
+        },
+        Test {
+            name: "Cluster right join",
+            query: "SELECT * FROM numbers(100000000) AS a RIGHT JOIN numbers(10) AS b ON a.number = b.number",
+            expect_left: "Normal",
+            expect_right: "Normal",
+        },
+        Test {
+            name: "Cluster right join with standalone right input",
+            query: "SELECT * FROM numbers(100000000) AS a RIGHT JOIN numbers_local(10) AS b ON a.number = b.number",
+            expect_left: "Convergent",
+            expect_right: "Local",
+        },
+    ];
+
+    fn find_join(plan: &PlanNode) -> Option<JoinPlan> {
+        match plan {
+            PlanNode::Join(join) => Some(join.clone()),
+            _ => plan.inputs().iter().find_map(|input| find_join(input)),
+        }
+    }
+
+    fn join_input_kind(input: &PlanNode) -> &'static str {
+        match input {
+            PlanNode::Stage(stage) => match stage.kind {
+                StageKind::Normal => "Normal",
+                StageKind::Expansive => "Expansive",
+                StageKind::Convergent => "Convergent",
+            },
+            PlanNode::Broadcast(_) => "Broadcast",
+            _ => "Local",
+        }
+    }
+
+    for test in tests {
+        let ctx = create_query_context_with_cluster(
+            ClusterDescriptor::new()
+                .with_node("Github", "www.github.com:9090")
+                .with_node("dummy_local", "127.0.0.1:9090")
+                .with_local_id("dummy_local"),
+        )?;
+
+        let plan = PlanParser::parse(ctx.clone(), test.query).await?;
+        let mut optimizer = ScattersOptimizer::create(ctx);
+        let optimized = optimizer.optimize(&plan)?;
+        let join = find_join(&optimized).unwrap();
+
+        assert_eq!(
+            test.expect_left,
+            join_input_kind(&join.left),
+            "{:#?}",
+            test.name
+        );
+        assert_eq!(
+            test.expect_right,
+            join_input_kind(&join.right),
+            "{:#?}",
+            test.name
+        );
+
+        if let PlanNode::Stage(stage) = join.left.as_ref() {
+            if stage.kind == StageKind::Normal {
+                let scatters_expr = format!("{:?}", stage.scatters_expr);
+                assert!(scatters_expr.starts_with("sipHash("), "{:#?}", test.name);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_base::tokio::sync::mpsc::channel;
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::pipelines::new::executor::PipelinePullingExecutor;
+use databend_query::pipelines::new::processors::port::OutputPort;
+use databend_query::pipelines::new::processors::SyncReceiverSource;
+use databend_query::pipelines::new::processors::TransformLimit;
+use databend_query::pipelines::new::NewPipe;
+use databend_query::pipelines::new::NewPipeline;
+use futures::TryStreamExt;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pulling_executor_with_limit() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("number", u64::to_data_type())]);
+    let (tx, rx) = channel(3);
+    for index in 0..3u64 {
+        let values = vec![index * 3, index * 3 + 1, index * 3 + 2];
+        let block = DataBlock::create(schema.clone(), vec![Series::from_data(values)]);
+        tx.send(Ok(block)).await.unwrap();
+    }
+    drop(tx);
+
+    let output = OutputPort::create();
+    let mut pipeline = NewPipeline::create();
+    pipeline.add_pipe(NewPipe::SimplePipe {
+        processors: vec![SyncReceiverSource::create(rx, output.clone())?],
+        inputs_port: vec![],
+        outputs_port: vec![output],
+    });
+    pipeline
+        .add_transform(|input, output| TransformLimit::try_create(Some(4), 1, input, output))?;
+
+    let executor = PipelinePullingExecutor::try_create(pipeline, 2)?;
+    let result = executor.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 1      |",
+        "| 2      |",
+        "| 3      |",
+        "| 4      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pulling_executor_with_error() -> Result<()> {
+    let (tx, rx) = channel(1);
+    tx.send(Err(ErrorCode::LogicalError("test error")))
+        .await
+        .unwrap();
+    drop(tx);
+
+    let output = OutputPort::create();
+    let mut pipeline = NewPipeline::create();
+    pipeline.add_pipe(NewPipe::SimplePipe {
+        processors: vec![SyncReceiverSource::create(rx, output.clone())?],
+        inputs_port: vec![],
+        outputs_port: vec![output],
+    });
+
+    let executor = PipelinePullingExecutor::try_create(pipeline, 2)?;
+    let result = executor.try_collect::<Vec<_>>().await;
+
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().message(), "test error");
+    Ok(())
+}
@@ -18,8 +18,11 @@ mod transform_expression;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
+mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
 mod transform_projection;
 mod transform_sort;
 mod transform_source;
+mod transform_spill;
+mod transform_window_func;
@@ -45,6 +45,7 @@ async fn test_transform_final_aggregator() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(AggregatorPartialTransform::try_create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
@@ -53,6 +54,7 @@ async fn test_transform_final_aggregator() -> Result<()> {
     pipeline.merge_processor()?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(AggregatorFinalTransform::try_create(
+            ctx.clone(),
             aggr_final.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
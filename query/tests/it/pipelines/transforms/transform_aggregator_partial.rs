@@ -42,6 +42,7 @@ async fn test_transform_partial_aggregator() -> Result<()> {
 
     pipeline.add_simple_transform(|| {
         Ok(Box::new(AggregatorPartialTransform::try_create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
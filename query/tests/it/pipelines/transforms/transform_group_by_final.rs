@@ -50,10 +50,12 @@ async fn test_transform_final_group_by() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
             group_exprs.to_vec(),
+            None,
         )))
     })?;
     pipeline.merge_processor()?;
@@ -61,11 +63,13 @@ async fn test_transform_final_group_by() -> Result<()> {
     let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByFinalTransform::create(
+            ctx.clone(),
             aggr_final.schema(),
             max_block_size,
             source_schema.clone(),
             aggr_exprs.to_vec(),
             group_exprs.to_vec(),
+            None,
         )))
     })?;
 
@@ -91,3 +95,85 @@ async fn test_transform_final_group_by() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_final_group_by_with_spill() -> Result<()> {
+    let ctx = crate::tests::create_query_context()?;
+    ctx.get_settings()
+        .set_settings("max_block_size".to_string(), "2".to_string(), false)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // sum(number), avg(number)
+    let aggr_exprs = &[sum(col("number")), avg(col("number"))];
+
+    let group_exprs = &[col("number")];
+    let aggr_partial = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .aggregate_partial(aggr_exprs, group_exprs)?
+        .build()?;
+
+    let aggr_final = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .aggregate_final(
+            test_source.number_schema_for_test()?,
+            aggr_exprs,
+            group_exprs,
+        )?
+        .build()?;
+
+    // Spill the partial states after every block, and the final input as soon as it arrives.
+    let temp_dir = tempfile::tempdir()?;
+    let spill = SpillConfig::create(1, temp_dir.path().to_path_buf());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(10)?;
+    let source_schema = test_source.number_schema_for_test()?;
+    pipeline.add_source(Arc::new(source))?;
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
+            aggr_partial.schema(),
+            source_schema.clone(),
+            aggr_exprs.to_vec(),
+            group_exprs.to_vec(),
+            Some(spill.clone()),
+        )))
+    })?;
+    pipeline.merge_processor()?;
+
+    let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(GroupByFinalTransform::create(
+            ctx.clone(),
+            aggr_final.schema(),
+            max_block_size,
+            source_schema.clone(),
+            aggr_exprs.to_vec(),
+            group_exprs.to_vec(),
+            Some(spill.clone()),
+        )))
+    })?;
+
+    // Result.
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    // SELECT SUM(number), AVG(number), number from numbers(10) group by number;
+    let expected = vec![
+        "+-------------+-------------+--------+",
+        "| sum(number) | avg(number) | number |",
+        "+-------------+-------------+--------+",
+        "| 0           | 0           | 0      |",
+        "| 1           | 1           | 1      |",
+        "| 2           | 2           | 2      |",
+        "| 3           | 3           | 3      |",
+        "| 4           | 4           | 4      |",
+        "| 5           | 5           | 5      |",
+        "| 6           | 6           | 6      |",
+        "| 7           | 7           | 7      |",
+        "| 8           | 8           | 8      |",
+        "| 9           | 9           | 9      |",
+        "+-------------+-------------+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -43,10 +43,12 @@ async fn test_transform_partial_group_by() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.clone(),
             group_exprs.clone(),
+            None,
         )))
     })?;
     pipeline.merge_processor()?;
@@ -0,0 +1,126 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use databend_query::pipelines::processors::*;
+use databend_query::pipelines::transforms::*;
+use futures::TryStreamExt;
+
+async fn execute_join(join_type: JoinType, on: Option<Expression>) -> Result<Vec<DataBlock>> {
+    let ctx = crate::tests::create_query_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(6)?;
+    pipeline.add_source(Arc::new(source))?;
+    pipeline.merge_processor()?;
+
+    let build_plan = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(4)?);
+    let build_plan = PlanBuilder::from(&build_plan)
+        .project(&[col("number").alias("b")])?
+        .build()?;
+
+    let probe_plan = PlanBuilder::create(test_source.number_schema_for_test()?).build()?;
+    if let PlanNode::Join(plan) = PlanBuilder::from(&probe_plan)
+        .join(&build_plan, join_type, on)?
+        .build()?
+    {
+        let puller = JoinHashTablePuller::create(
+            ctx.clone(),
+            plan.right.as_ref().clone(),
+            plan.right_keys.clone(),
+        );
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(HashJoinTransform::try_create(
+                ctx.clone(),
+                &plan,
+                puller.clone(),
+            )?))
+        })?;
+    }
+
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_inner() -> Result<()> {
+    let on = col("number").eq(col("b")).and(col("b").gt(lit(1u64)));
+    let result = execute_join(JoinType::Inner, Some(on)).await?;
+
+    let expected = vec![
+        "+--------+---+",
+        "| number | b |",
+        "+--------+---+",
+        "| 2      | 2 |",
+        "| 3      | 3 |",
+        "+--------+---+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_left() -> Result<()> {
+    let on = col("number").eq(col("b"));
+    let result = execute_join(JoinType::Left, Some(on)).await?;
+
+    let expected = vec![
+        "+--------+------+",
+        "| number | b    |",
+        "+--------+------+",
+        "| 0      | 0    |",
+        "| 1      | 1    |",
+        "| 2      | 2    |",
+        "| 3      | 3    |",
+        "| 4      | NULL |",
+        "| 5      | NULL |",
+        "+--------+------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_anti() -> Result<()> {
+    let on = col("number").eq(col("b"));
+    let result = execute_join(JoinType::LeftAnti, Some(on)).await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 4      |",
+        "| 5      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_cross() -> Result<()> {
+    let result = execute_join(JoinType::Cross, None).await?;
+    let rows: usize = result.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(24, rows);
+
+    Ok(())
+}
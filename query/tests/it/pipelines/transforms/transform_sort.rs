@@ -48,9 +48,11 @@ async fn test_transform_sort() -> Result<()> {
 
     pipeline.add_simple_transform(|| {
         Ok(Box::new(SortMergeTransform::try_create(
+            ctx.clone(),
             plan.schema(),
             sort_expression.to_vec(),
             None,
+            None,
         )?))
     })?;
 
@@ -58,9 +60,11 @@ async fn test_transform_sort() -> Result<()> {
         pipeline.merge_processor()?;
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                ctx.clone(),
                 plan.schema(),
                 sort_expression.to_vec(),
                 None,
+                None,
             )?))
         })?;
     }
@@ -89,3 +93,66 @@ async fn test_transform_sort() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_sort_with_spill() -> Result<()> {
+    let ctx = crate::tests::create_query_context()?;
+    ctx.get_settings()
+        .set_settings("max_block_size".to_string(), "2".to_string(), false)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // Pipeline.
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let a = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(a))?;
+
+    let sort_expression = &[sort("number", false, false)];
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .sort(sort_expression)?
+        .build()?;
+
+    // Spill every block into its own sorted run.
+    let temp_dir = tempfile::tempdir()?;
+    let spill = SpillConfig::create(1, temp_dir.path().to_path_buf());
+
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(SortPartialTransform::try_create(
+            plan.schema(),
+            sort_expression.to_vec(),
+            None,
+        )?))
+    })?;
+
+    pipeline.merge_processor()?;
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(SortMergeTransform::try_create(
+            ctx.clone(),
+            plan.schema(),
+            sort_expression.to_vec(),
+            None,
+            Some(spill.clone()),
+        )?))
+    })?;
+
+    // Result.
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 7      |",
+        "| 6      |",
+        "| 5      |",
+        "| 4      |",
+        "| 3      |",
+        "| 2      |",
+        "| 1      |",
+        "| 0      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
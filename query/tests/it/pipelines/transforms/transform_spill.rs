@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use databend_query::pipelines::transforms::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+fn number_block(values: Vec<u64>) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", u64::to_data_type())]);
+    DataBlock::create(schema, vec![Series::from_data(values)])
+}
+
+async fn sorted_run(config: &SpillConfig, blocks: Vec<Vec<u64>>) -> Result<SortedRun> {
+    let schema = number_block(vec![]).schema().clone();
+    let mut writer = SpillWriter::try_create(config, schema).await?;
+    for values in blocks {
+        writer.write(number_block(values)).await?;
+    }
+    Ok(Box::new(writer.into_reader().await?))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_spill_file_roundtrip() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let config = SpillConfig::create(1, temp_dir.path().to_path_buf());
+
+    let blocks = vec![
+        number_block(vec![1, 2, 3]),
+        number_block(vec![]),
+        number_block(vec![4]),
+    ];
+    let mut writer = SpillWriter::try_create(&config, blocks[0].schema().clone()).await?;
+    for block in blocks {
+        writer.write(block).await?;
+    }
+
+    // Empty blocks are not written.
+    assert_eq!(writer.num_blocks(), 2);
+    assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 1);
+
+    let reader = writer.into_reader().await?;
+    let result = blocking_stream(reader).try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+---+", //
+        "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &result);
+
+    // The spill file is removed once the reader is dropped.
+    assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_sorted_runs_merger() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let config = SpillConfig::create(1, temp_dir.path().to_path_buf());
+    let tests = vec![
+        (None, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+        (Some(4), vec![1, 2, 3, 4]),
+    ];
+
+    for (limit, expected) in tests {
+        let runs = vec![
+            sorted_run(&config, vec![vec![1, 4], vec![7, 10]]).await?,
+            sorted_run(&config, vec![vec![2, 3], vec![], vec![8]]).await?,
+            sorted_run(&config, vec![vec![5, 6, 9]]).await?,
+        ];
+
+        let descriptions = vec![SortColumnDescription {
+            column_name: "a".to_string(),
+            asc: true,
+            nulls_first: false,
+        }];
+
+        let merger = SortedRunsMerger::create(runs, descriptions, limit);
+        let blocks = blocking_stream(merger).try_collect::<Vec<_>>().await?;
+        let block = DataBlock::concat_blocks(&blocks)?;
+        let column: &UInt64Column = Series::check_get(block.column(0))?;
+        assert_eq!(column.values(), expected.as_slice());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use databend_query::pipelines::processors::*;
+use databend_query::pipelines::transforms::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+async fn execute_window_func(window_func: Expression) -> Result<Vec<(u64, u64)>> {
+    let ctx = crate::tests::create_query_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(6)?;
+    pipeline.add_source(Arc::new(source))?;
+    pipeline.merge_processor()?;
+
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .expression(&[modular(col("number"), lit(2))], "")?
+        .window_func(window_func.clone())?
+        .build()?;
+
+    if let PlanNode::WindowFunc(window_plan) = &plan {
+        if let PlanNode::Expression(expression_plan) = window_plan.input.as_ref() {
+            pipeline.add_simple_transform(|| {
+                Ok(Box::new(ExpressionTransform::try_create(
+                    expression_plan.input.schema(),
+                    expression_plan.schema.clone(),
+                    expression_plan.exprs.clone(),
+                )?))
+            })?;
+        }
+
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(WindowFuncTransform::create(
+                window_plan.window_func.clone(),
+                window_plan.schema(),
+                window_plan.input.schema(),
+            )))
+        })?;
+    }
+
+    let stream = pipeline.execute().await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let block = DataBlock::concat_blocks(&blocks)?;
+
+    let numbers = block.try_column_by_name("number")?;
+    let results = block.try_column_by_name(&window_func.column_name())?;
+    let mut rows = Vec::with_capacity(block.num_rows());
+    for row in 0..block.num_rows() {
+        rows.push((numbers.get_u64(row)?, results.get_u64(row)?));
+    }
+
+    rows.sort_unstable();
+    Ok(rows)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_window_row_number() -> Result<()> {
+    let window_func = Expression::WindowFunction {
+        op: "row_number".to_string(),
+        params: vec![],
+        args: vec![],
+        partition_by: vec![modular(col("number"), lit(2))],
+        order_by: vec![sort("number", false, false)],
+        window_frame: None,
+    };
+
+    let rows = execute_window_func(window_func).await?;
+    assert_eq!(vec![(0, 3), (1, 3), (2, 2), (3, 2), (4, 1), (5, 1)], rows);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_window_running_sum() -> Result<()> {
+    let window_func = Expression::WindowFunction {
+        op: "sum".to_string(),
+        params: vec![],
+        args: vec![col("number")],
+        partition_by: vec![modular(col("number"), lit(2))],
+        order_by: vec![sort("number", true, false)],
+        window_frame: None,
+    };
+
+    let rows = execute_window_func(window_func).await?;
+    assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 4), (4, 6), (5, 9)], rows);
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod query_ctx;
+mod query_result_cache;
 mod session;
 mod session_context;
 mod session_setting;
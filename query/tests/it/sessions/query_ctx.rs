@@ -13,9 +13,17 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_base::MemoryTracker;
+use common_base::RuntimeTracker;
+use common_base::TrySpawn;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::UserInfo;
 use databend_query::configs::DiskStorageConfig;
 use databend_query::configs::S3StorageConfig;
+use databend_query::pipelines::processors::Processor;
+use databend_query::pipelines::transforms::MemoryLimitStream;
+use futures::StreamExt;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_storage_accessor_s3() -> Result<()> {
@@ -54,3 +62,87 @@ async fn test_get_storage_accessor_fs() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_memory_limit() -> Result<()> {
+    let ctx = crate::tests::create_query_context()?;
+    ctx.get_settings()
+        .set_settings("max_memory_usage".to_string(), "1024".to_string(), false)?;
+
+    // no runtime, nothing is allocated by the query yet
+    ctx.check_memory_limit()?;
+
+    // the tracker of the threads of the query runtime
+    let tracker = ctx
+        .try_spawn(async { MemoryTracker::current() })?
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tracker.get_memory_limit(), 1024);
+
+    tracker.alloc_memory(1 << 20);
+    let err = ctx.check_memory_limit().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::MemoryLimitExceededCode());
+
+    tracker.dealloc_memory(1 << 20);
+    ctx.check_memory_limit()?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memory_limit_stream() -> Result<()> {
+    let ctx = crate::tests::create_query_context()?;
+    ctx.get_settings()
+        .set_settings("max_memory_usage".to_string(), "1024".to_string(), false)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(8)?;
+
+    let tracker = ctx
+        .try_spawn(async { MemoryTracker::current() })?
+        .await
+        .unwrap()
+        .unwrap();
+    let mut stream = MemoryLimitStream::create(ctx.clone(), source.execute().await?);
+    assert!(stream.next().await.unwrap().is_ok());
+
+    // the blocks are not pulled from the input any more once the limit is exceeded
+    tracker.alloc_memory(1 << 20);
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(err.code(), ErrorCode::MemoryLimitExceededCode());
+    tracker.dealloc_memory(1 << 20);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_user_memory_limit() -> Result<()> {
+    let sessions = crate::tests::SessionManagerBuilder::create().build()?;
+    let mut user = UserInfo::new_no_auth("test_user".to_string(), "%".to_string());
+    user.quota.max_memory_in_bytes = 1024;
+
+    let tracker = sessions.get_user_memory_tracker("test", &user);
+    assert_eq!(tracker.get_memory_limit(), 1024);
+
+    // the queries of a user share the tracker
+    let query_1 = RuntimeTracker::create_with_parent(Some(tracker.clone()));
+    let query_2 =
+        RuntimeTracker::create_with_parent(Some(sessions.get_user_memory_tracker("test", &user)));
+    query_1.get_memory_tracker().alloc_memory(512);
+    assert!(query_2.get_memory_tracker().check_memory_limit().is_ok());
+
+    query_2.get_memory_tracker().alloc_memory(1024);
+    assert_eq!(tracker.get_memory_usage(), 1536);
+    let err = query_1
+        .get_memory_tracker()
+        .check_memory_limit()
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::MemoryLimitExceededCode());
+
+    // the updated quota takes effect for the running queries
+    user.quota.max_memory_in_bytes = 0;
+    let _ = sessions.get_user_memory_tracker("test", &user);
+    assert!(query_1.get_memory_tracker().check_memory_limit().is_ok());
+
+    Ok(())
+}
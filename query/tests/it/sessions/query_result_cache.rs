@@ -0,0 +1,125 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use databend_query::sessions::normalize_query;
+use databend_query::sessions::QueryResultCache;
+use databend_query::sessions::QueryResultCacheKey;
+use databend_query::sessions::TableSnapshotRef;
+
+#[test]
+fn test_normalize_query() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "whitespaces",
+            query: "  select  a,\n\tb from   t  where a > 1 ",
+            expect: "select a, b from t where a > 1",
+        },
+        Test {
+            name: "trailing-semicolons",
+            query: "select a from t ; ;",
+            expect: "select a from t",
+        },
+        Test {
+            name: "quoted-strings",
+            query: "select  'a  b', \"c\td\" from t where s = 'x\\'  y'",
+            expect: "select 'a  b', \"c\td\" from t where s = 'x\\'  y'",
+        },
+        Test {
+            name: "quoted-identifiers",
+            query: "select `a  b`  from t",
+            expect: "select `a  b` from t",
+        },
+        Test {
+            name: "empty",
+            query: " ; ",
+            expect: "",
+        },
+    ];
+
+    for test in tests {
+        assert_eq!(normalize_query(test.query), test.expect, "{:#?}", test.name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_query_result_cache() -> Result<()> {
+    let conf = crate::tests::ConfigBuilder::create().config();
+    let cache = QueryResultCache::create(&conf.query);
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", i64::to_data_type())]);
+    let block = DataBlock::create(schema, vec![Series::from_data(vec![1i64, 2, 3])]);
+    let snapshot = |loc: Option<&str>| TableSnapshotRef {
+        table_id: 1,
+        snapshot_loc: loc.map(|loc| loc.to_string()),
+    };
+    let key = |loc: Option<&str>| QueryResultCacheKey {
+        key: "select a from t".to_string(),
+        snapshots: vec![snapshot(loc)],
+        views: vec![],
+    };
+
+    assert!(cache.get(&key(Some("_ss/1"))).is_none());
+
+    cache.put(key(Some("_ss/1")), vec![block.clone()]);
+    let blocks = cache.get(&key(Some("_ss/1"))).unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].num_rows(), 3);
+
+    // The table has a new snapshot, the stale result is evicted.
+    assert!(cache.get(&key(Some("_ss/2"))).is_none());
+    assert!(cache.is_empty());
+
+    // An empty table without any snapshot.
+    cache.put(key(None), vec![]);
+    assert_eq!(cache.get(&key(None)).unwrap().len(), 0);
+    assert!(cache.get(&key(Some("_ss/1"))).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_result_cache_redefined_view() -> Result<()> {
+    let conf = crate::tests::ConfigBuilder::create().config();
+    let cache = QueryResultCache::create(&conf.query);
+
+    let key = |view: TableIdent| QueryResultCacheKey {
+        key: "select a from v".to_string(),
+        snapshots: vec![TableSnapshotRef {
+            table_id: 1,
+            snapshot_loc: Some("_ss/1".to_string()),
+        }],
+        views: vec![view],
+    };
+
+    cache.put(key(TableIdent::new(2, 1)), vec![]);
+    assert!(cache.get(&key(TableIdent::new(2, 1))).is_some());
+
+    // The view is dropped and created again, with another definition over the same table.
+    assert!(cache.get(&key(TableIdent::new(3, 1))).is_none());
+    assert!(cache.is_empty());
+
+    Ok(())
+}
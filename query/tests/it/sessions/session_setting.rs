@@ -14,6 +14,7 @@
 
 use common_base::tokio;
 use common_exception::Result;
+use common_meta_types::UserInfo;
 use databend_query::sessions::Session;
 use databend_query::sessions::SessionManager;
 
@@ -41,3 +42,32 @@ async fn test_session_setting() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_setting_max_threads_capped_by_cpu_quota() -> Result<()> {
+    let conf = crate::tests::ConfigBuilder::create().config();
+
+    let session_manager = SessionManager::from_conf(conf.clone()).await.unwrap();
+
+    let session = Session::try_create(
+        conf.clone(),
+        String::from("test-001"),
+        String::from("test-type"),
+        session_manager,
+    )?;
+
+    let settings = session.get_settings();
+    settings.set_settings("max_threads".to_string(), "8".to_string(), false)?;
+
+    let mut user = UserInfo::new_no_auth("test".to_string(), "%".to_string());
+    user.quota.max_cpu = 2;
+    session.set_current_user(user.clone());
+    assert_eq!(settings.get_max_threads()?, 2);
+
+    // unlimited
+    user.quota.max_cpu = 0;
+    session.set_current_user(user);
+    assert_eq!(settings.get_max_threads()?, 8);
+
+    Ok(())
+}
@@ -0,0 +1,387 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use common_base::tokio;
+use common_datavalues2::prelude::*;
+use common_exception::Result;
+use databend_query::catalogs::Catalog;
+use databend_query::sql::optimizer::optimize;
+use databend_query::sql::optimizer::ColumnSet;
+use databend_query::sql::optimizer::OptimizeContext;
+use databend_query::sql::optimizer::RequiredProperty;
+use databend_query::sql::optimizer::SExpr;
+use databend_query::sql::AggregateFunction;
+use databend_query::sql::AndExpr;
+use databend_query::sql::BoundVariable;
+use databend_query::sql::ComparisonExpr;
+use databend_query::sql::ComparisonOp;
+use databend_query::sql::ConstantExpr;
+use databend_query::sql::IndexType;
+use databend_query::sql::LogicalAggregate;
+use databend_query::sql::LogicalFilter;
+use databend_query::sql::LogicalGet;
+use databend_query::sql::LogicalInnerJoin;
+use databend_query::sql::LogicalProject;
+use databend_query::sql::Metadata;
+use databend_query::sql::PhysicalFilter;
+use databend_query::sql::PhysicalHashJoin;
+use databend_query::sql::PhysicalProject;
+use databend_query::sql::PhysicalScan;
+use databend_query::sql::Plan;
+use databend_query::sql::ProjectItem;
+use databend_query::sql::ScalarExpr;
+use databend_query::storages::index::ColumnStatistics;
+use databend_query::storages::TableStatistics;
+
+use crate::tests::create_query_context;
+
+/// (name, min, max) of a column
+type ColumnDesc = (&'static str, i64, i64);
+
+/// Register tables with the given row counts and integer column ranges, the columns are indexed
+/// in order of registration.
+async fn create_metadata(tables: Vec<(u64, Vec<ColumnDesc>)>) -> Result<Metadata> {
+    let ctx = create_query_context()?;
+    let table = ctx
+        .get_catalog()
+        .get_table(ctx.get_tenant().as_str(), "system", "one")
+        .await?;
+
+    let mut metadata = Metadata::create();
+    for (num_rows, columns) in tables {
+        let column_statistics: HashMap<u32, ColumnStatistics> = columns
+            .iter()
+            .enumerate()
+            .map(|(position, (_, min, max))| {
+                (position as u32, ColumnStatistics {
+                    min: DataValue::Int64(*min),
+                    max: DataValue::Int64(*max),
+                    null_count: 0,
+                    in_memory_size: 0,
+                })
+            })
+            .collect();
+        let statistics = TableStatistics {
+            num_rows,
+            data_size: 0,
+            data_size_compressed: 0,
+            column_statistics,
+            column_distributions: HashMap::new(),
+        };
+        let table_index =
+            metadata.add_base_table("system".to_string(), table.clone(), Some(statistics));
+        for (name, _, _) in columns {
+            metadata.add_column(name.to_string(), i64::to_data_type(), false, table_index);
+        }
+    }
+    Ok(metadata)
+}
+
+fn column(index: IndexType) -> ScalarExpr {
+    ScalarExpr::BoundVariable(BoundVariable {
+        index,
+        data_type: i64::to_data_type(),
+        nullable: false,
+    })
+}
+
+fn compare(op: ComparisonOp, left: ScalarExpr, right: ScalarExpr) -> ScalarExpr {
+    ScalarExpr::ComparisonExpr(ComparisonExpr {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
+
+fn constant(value: i64) -> ScalarExpr {
+    ScalarExpr::ConstantExpr(ConstantExpr {
+        value: DataValue::Int64(value),
+        data_type: i64::to_data_type(),
+    })
+}
+
+fn get(table_index: IndexType, columns: &[IndexType]) -> SExpr {
+    SExpr::create_leaf(Rc::new(Plan::LogicalGet(LogicalGet {
+        table_index,
+        columns: columns.iter().cloned().collect(),
+    })))
+}
+
+fn scan(table_index: IndexType, columns: &[IndexType]) -> SExpr {
+    SExpr::create_leaf(Rc::new(Plan::PhysicalScan(PhysicalScan::create(
+        table_index,
+        columns.iter().cloned().collect(),
+    ))))
+}
+
+fn cross_join(left: SExpr, right: SExpr) -> SExpr {
+    SExpr::create_binary(
+        Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::default())),
+        left,
+        right,
+    )
+}
+
+fn hash_join(build_key: IndexType, probe_key: IndexType, probe: SExpr, build: SExpr) -> SExpr {
+    SExpr::create_binary(
+        Rc::new(Plan::PhysicalHashJoin(PhysicalHashJoin::create(
+            vec![column(build_key)],
+            vec![column(probe_key)],
+        ))),
+        probe,
+        build,
+    )
+}
+
+fn create_context(required: &[IndexType], metadata: Metadata) -> OptimizeContext {
+    let required_columns: ColumnSet = required.iter().cloned().collect();
+    OptimizeContext::create(RequiredProperty::create(required_columns), vec![], metadata)
+}
+
+#[tokio::test]
+async fn test_push_down_filter_and_prune_columns() -> Result<()> {
+    // t1(a, b) with 10 rows, t2(c, d, e) with 1000 rows
+    let metadata = create_metadata(vec![
+        (10, vec![("a", 0, 9), ("b", 0, 10)]),
+        (1000, vec![("c", 0, 999), ("d", 0, 999), ("e", 0, 999)]),
+    ])
+    .await?;
+
+    // SELECT a, d FROM t1, t2 WHERE a = c AND b > 5
+    let project_items = vec![
+        ProjectItem {
+            index: 0,
+            expr: column(0),
+        },
+        ProjectItem {
+            index: 3,
+            expr: column(3),
+        },
+    ];
+    let predicate = ScalarExpr::AndExpr(AndExpr {
+        left: Box::new(compare(ComparisonOp::Equal, column(0), column(2))),
+        right: Box::new(compare(ComparisonOp::GT, column(1), constant(5))),
+    });
+    let expr = SExpr::create_unary(
+        Rc::new(Plan::LogicalProject(LogicalProject::create(
+            project_items.clone(),
+        ))),
+        SExpr::create_unary(
+            Rc::new(Plan::LogicalFilter(LogicalFilter::create(vec![predicate]))),
+            cross_join(get(0, &[0, 1]), get(1, &[2, 3, 4])),
+        ),
+    );
+
+    let result = optimize(expr, create_context(&[0, 3], metadata))?;
+
+    // The filter on t1 is pushed down, the equality becomes the join condition, column `e` is
+    // pruned, and the filtered t1 is chosen as the build side.
+    let expected = SExpr::create_unary(
+        Rc::new(Plan::PhysicalProject(PhysicalProject::create(
+            project_items,
+        ))),
+        hash_join(
+            0,
+            2,
+            scan(1, &[2, 3]),
+            SExpr::create_unary(
+                Rc::new(Plan::PhysicalFilter(PhysicalFilter::create(vec![compare(
+                    ComparisonOp::GT,
+                    column(1),
+                    constant(5),
+                )]))),
+                scan(0, &[0, 1]),
+            ),
+        ),
+    );
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_reorder() -> Result<()> {
+    // t1(a) and t2(b) with 10000 rows, t3(c) with 10 rows
+    let metadata = create_metadata(vec![
+        (10000, vec![("a", 0, 9999)]),
+        (10000, vec![("b", 0, 9999)]),
+        (10, vec![("c", 0, 9)]),
+    ])
+    .await?;
+
+    // SELECT * FROM t1, t2, t3 WHERE a = b AND b = c
+    let predicates = vec![
+        compare(ComparisonOp::Equal, column(0), column(1)),
+        compare(ComparisonOp::Equal, column(1), column(2)),
+    ];
+    let expr = SExpr::create_unary(
+        Rc::new(Plan::LogicalFilter(LogicalFilter::create(predicates))),
+        cross_join(cross_join(get(0, &[0]), get(1, &[1])), get(2, &[2])),
+    );
+
+    let result = optimize(expr, create_context(&[0, 1, 2], metadata))?;
+
+    // Joining t2 with the small t3 first produces much less intermediate rows:
+    // t1 ⋈ (t2 ⋈ t3)
+    let expected = hash_join(
+        1,
+        0,
+        scan(0, &[0]),
+        hash_join(2, 1, scan(1, &[1]), scan(2, &[2])),
+    );
+    assert_eq!(result, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_push_down_aggregate() -> Result<()> {
+    // t1(k, x) with 100000 rows and 10 distinct keys, t2(k2, name) with 10 rows
+    let mut metadata = create_metadata(vec![
+        (100000, vec![("k", 0, 9), ("x", 0, 99999)]),
+        (10, vec![("k2", 0, 9), ("name", 0, 9)]),
+    ])
+    .await?;
+    let sum_index = metadata.add_derived_column("sum(x)".to_string(), i64::to_data_type(), false);
+
+    // SELECT name, sum(x) FROM t1, t2 WHERE k = k2 GROUP BY name
+    let sum = ProjectItem {
+        index: sum_index,
+        expr: ScalarExpr::AggregateFunction(AggregateFunction {
+            func_name: "sum".to_string(),
+            distinct: false,
+            args: vec![column(1)],
+            data_type: i64::to_data_type(),
+            nullable: false,
+        }),
+    };
+    let group_item = ProjectItem {
+        index: 3,
+        expr: column(3),
+    };
+    let expr = SExpr::create_unary(
+        Rc::new(Plan::LogicalAggregate(LogicalAggregate::create(
+            vec![group_item.clone()],
+            vec![sum.clone()],
+        ))),
+        SExpr::create_binary(
+            Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::create(
+                vec![column(0)],
+                vec![column(2)],
+            ))),
+            get(0, &[0, 1]),
+            get(1, &[2, 3]),
+        ),
+    );
+
+    let result = optimize(expr, create_context(&[3, sum_index], metadata))?;
+
+    // t1 is aggregated by the join key before joining, the final aggregation sums up the
+    // partial sums.
+    let final_aggregate = match result.plan().as_ref() {
+        Plan::PhysicalAggregate(aggregate) => aggregate.clone(),
+        plan => panic!("unexpected plan: {:?}", plan),
+    };
+    assert_eq!(final_aggregate.group_items, vec![group_item]);
+    assert_eq!(final_aggregate.aggregate_functions.len(), 1);
+    assert_eq!(
+        final_aggregate.aggregate_functions[0].expr.used_columns(),
+        ColumnSet::from([sum_index])
+    );
+
+    let join = &result.children()[0];
+    assert!(matches!(join.plan().as_ref(), Plan::PhysicalHashJoin(_)));
+    let partial_aggregates: Vec<&SExpr> = join
+        .children()
+        .iter()
+        .filter(|child| matches!(child.plan().as_ref(), Plan::PhysicalAggregate(_)))
+        .collect();
+    assert_eq!(partial_aggregates.len(), 1);
+    match partial_aggregates[0].plan().as_ref() {
+        Plan::PhysicalAggregate(aggregate) => {
+            let group_columns: Vec<IndexType> = aggregate
+                .group_items
+                .iter()
+                .map(|item| item.index)
+                .collect();
+            assert_eq!(group_columns, vec![0]);
+            assert_eq!(aggregate.aggregate_functions, vec![sum]);
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(partial_aggregates[0].children()[0], scan(0, &[0, 1]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_push_down_aggregate_without_group_items() -> Result<()> {
+    // t1(k, x) with 100000 rows and 10 distinct keys, t2(k2) with 10 rows
+    let mut metadata = create_metadata(vec![
+        (100000, vec![("k", 0, 9), ("x", 0, 99999)]),
+        (10, vec![("k2", 0, 9)]),
+    ])
+    .await?;
+    let count_index =
+        metadata.add_derived_column("count()".to_string(), u64::to_data_type(), false);
+
+    // SELECT count(*) FROM t1, t2 WHERE k = k2
+    let count = ProjectItem {
+        index: count_index,
+        expr: ScalarExpr::AggregateFunction(AggregateFunction {
+            func_name: "count".to_string(),
+            distinct: false,
+            args: vec![],
+            data_type: u64::to_data_type(),
+            nullable: false,
+        }),
+    };
+    let expr = SExpr::create_unary(
+        Rc::new(Plan::LogicalAggregate(LogicalAggregate::create(
+            vec![],
+            vec![count.clone()],
+        ))),
+        SExpr::create_binary(
+            Rc::new(Plan::LogicalInnerJoin(LogicalInnerJoin::create(
+                vec![column(0)],
+                vec![column(2)],
+            ))),
+            get(0, &[0, 1]),
+            get(1, &[2]),
+        ),
+    );
+
+    let result = optimize(expr, create_context(&[count_index], metadata))?;
+
+    // If the join turns out to be empty, the count must be 0 rather than the NULL sum of no
+    // partial counts, so the aggregation is not pushed down.
+    let aggregate = match result.plan().as_ref() {
+        Plan::PhysicalAggregate(aggregate) => aggregate.clone(),
+        plan => panic!("unexpected plan: {:?}", plan),
+    };
+    assert!(aggregate.group_items.is_empty());
+    assert_eq!(aggregate.aggregate_functions, vec![count]);
+
+    let join = &result.children()[0];
+    assert!(matches!(join.plan().as_ref(), Plan::PhysicalHashJoin(_)));
+    assert!(join
+        .children()
+        .iter()
+        .all(|child| !matches!(child.plan().as_ref(), Plan::PhysicalAggregate(_))));
+
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use common_exception::Result;
 use common_meta_types::Compression;
@@ -24,8 +25,11 @@ use common_meta_types::UserIdentity;
 use common_meta_types::UserPrivilegeSet;
 use common_meta_types::UserPrivilegeType;
 use common_planners::Optimization;
+use databend_query::sql::statements::DfAlterTable;
+use databend_query::sql::statements::DfAlterTableAction;
 use databend_query::sql::statements::DfAlterUDF;
 use databend_query::sql::statements::DfAlterUser;
+use databend_query::sql::statements::DfAnalyzeTable;
 use databend_query::sql::statements::DfAuthOption;
 use databend_query::sql::statements::DfCopy;
 use databend_query::sql::statements::DfCreateDatabase;
@@ -33,16 +37,20 @@ use databend_query::sql::statements::DfCreateStage;
 use databend_query::sql::statements::DfCreateTable;
 use databend_query::sql::statements::DfCreateUDF;
 use databend_query::sql::statements::DfCreateUser;
+use databend_query::sql::statements::DfCreateView;
+use databend_query::sql::statements::DfDeleteStatement;
 use databend_query::sql::statements::DfDescribeTable;
 use databend_query::sql::statements::DfDropDatabase;
 use databend_query::sql::statements::DfDropStage;
 use databend_query::sql::statements::DfDropTable;
 use databend_query::sql::statements::DfDropUDF;
 use databend_query::sql::statements::DfDropUser;
+use databend_query::sql::statements::DfDropView;
 use databend_query::sql::statements::DfGrantObject;
 use databend_query::sql::statements::DfGrantStatement;
 use databend_query::sql::statements::DfOptimizeTable;
 use databend_query::sql::statements::DfQueryStatement;
+use databend_query::sql::statements::DfRenameTable;
 use databend_query::sql::statements::DfRevokeStatement;
 use databend_query::sql::statements::DfShowCreateDatabase;
 use databend_query::sql::statements::DfShowCreateTable;
@@ -51,6 +59,7 @@ use databend_query::sql::statements::DfShowEngines;
 use databend_query::sql::statements::DfShowGrants;
 use databend_query::sql::statements::DfShowTables;
 use databend_query::sql::statements::DfTruncateTable;
+use databend_query::sql::statements::DfUpdateStatement;
 use databend_query::sql::statements::DfUseDatabase;
 use databend_query::sql::statements::DfUseTenant;
 use databend_query::sql::*;
@@ -58,6 +67,7 @@ use sqlparser::ast::*;
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
 use sqlparser::tokenizer::Tokenizer;
 
 fn expect_parse_ok(sql: &str, expected: DfStatement) -> Result<()> {
@@ -197,6 +207,7 @@ fn create_table() -> Result<()> {
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", DataType::Int(None))],
         engine: "Fuse".to_string(),
+        cluster_keys: vec![],
         options: maplit::hashmap! {"location".into() => "/data/33.csv".into()},
         like: None,
         query: None,
@@ -214,6 +225,7 @@ fn create_table() -> Result<()> {
             make_column_def("c3", DataType::Varchar(Some(255))),
         ],
         engine: "Fuse".to_string(),
+        cluster_keys: vec![],
 
         options: maplit::hashmap! {
             "location".into() => "foo.parquet".into(),
@@ -224,6 +236,72 @@ fn create_table() -> Result<()> {
     });
     expect_parse_ok(sql, expected)?;
 
+    // create table with cluster keys
+    let sql = "CREATE TABLE t(c1 int, c2 int) ENGINE = Fuse CLUSTER BY (c1, c2 + 1) location = '/data/33.csv'";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            make_column_def("c1", DataType::Int(None)),
+            make_column_def("c2", DataType::Int(None)),
+        ],
+        engine: "Fuse".to_string(),
+        cluster_keys: vec![Expr::Identifier(Ident::new("c1")), Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("c2"))),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+        }],
+        options: maplit::hashmap! {"location".into() => "/data/33.csv".into()},
+        like: None,
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // create table with compression and column codecs
+    let sql = "CREATE TABLE t(c1 int CODEC(ZSTD), c2 varchar NOT NULL CODEC(DICTIONARY, LZ4)) COMPRESSION = 'snappy'";
+    let mut c1 = make_column_def("c1", DataType::Int(None));
+    c1.options = vec![ColumnOptionDef {
+        name: None,
+        option: ColumnOption::DialectSpecific(vec![
+            Token::make_word("CODEC", None),
+            Token::LParen,
+            Token::make_word("ZSTD", None),
+            Token::RParen,
+        ]),
+    }];
+    let mut c2 = make_column_def("c2", DataType::Varchar(None));
+    c2.options = vec![
+        ColumnOptionDef {
+            name: None,
+            option: ColumnOption::NotNull,
+        },
+        ColumnOptionDef {
+            name: None,
+            option: ColumnOption::DialectSpecific(vec![
+                Token::make_word("CODEC", None),
+                Token::LParen,
+                Token::make_word("DICTIONARY", None),
+                Token::Comma,
+                Token::make_word("LZ4", None),
+                Token::RParen,
+            ]),
+        },
+    ];
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![c1, c2],
+        engine: "FUSE".to_string(),
+        cluster_keys: vec![],
+        options: maplit::hashmap! {"COMPRESSION".into() => "snappy".into()},
+        like: None,
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    let sql = "CREATE TABLE t(c1 int CODEC())";
+    expect_parse_err_contains(sql, "Expected identifier".to_string())?;
+
     // create table like statement
     let sql = "CREATE TABLE db1.test1 LIKE db2.test2 ENGINE = Parquet location = 'batcave'";
     let expected = DfStatement::CreateTable(DfCreateTable {
@@ -231,6 +309,7 @@ fn create_table() -> Result<()> {
         name: ObjectName(vec![Ident::new("db1"), Ident::new("test1")]),
         columns: vec![],
         engine: "Parquet".to_string(),
+        cluster_keys: vec![],
 
         options: maplit::hashmap! {"location".into() => "batcave".into()},
         like: Some(ObjectName(vec![Ident::new("db2"), Ident::new("test2")])),
@@ -248,6 +327,7 @@ fn create_table() -> Result<()> {
             make_column_def("c2", DataType::Varchar(Some(255))),
         ],
         engine: "Parquet".to_string(),
+        cluster_keys: vec![],
 
         options: maplit::hashmap! {"location".into() => "batcave".into()},
         like: None,
@@ -523,6 +603,142 @@ fn truncate_table() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn alter_table() -> Result<()> {
+    {
+        let sql = "ALTER TABLE t1 ADD COLUMN c INT";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::AddColumn(make_column_def("c", DataType::Int(None))),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE IF EXISTS db1.t1 DROP c";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            action: DfAlterTableAction::DropColumn(Ident::new("c")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 RENAME COLUMN a TO b";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::RenameColumn {
+                old_name: Ident::new("a"),
+                new_name: Ident::new("b"),
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 RENAME TO t2";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::RenameTable(ObjectName(vec![Ident::new("t2")])),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    expect_parse_err_contains(
+        "ALTER TABLE t1 MODIFY c INT",
+        "ADD, DROP or RENAME".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn rename_table() -> Result<()> {
+    let sql = "RENAME TABLE t1 TO db2.t2";
+    let expected = DfStatement::RenameTable(DfRenameTable {
+        name: ObjectName(vec![Ident::new("t1")]),
+        new_name: ObjectName(vec![Ident::new("db2"), Ident::new("t2")]),
+    });
+    expect_parse_ok(sql, expected)?;
+
+    Ok(())
+}
+
+#[test]
+fn create_view() -> Result<()> {
+    let dialect = GenericDialect {};
+    let query = |sql: &str| -> Box<Query> {
+        let tokens = Tokenizer::new(&dialect, sql).tokenize().unwrap();
+        Box::new(Parser::new(tokens, &dialect).parse_query().unwrap())
+    };
+
+    {
+        let sql = "CREATE VIEW v1 AS SELECT a FROM t1 WHERE b > 1";
+        let expected = DfStatement::CreateView(DfCreateView {
+            if_not_exists: false,
+            materialized: false,
+            name: ObjectName(vec![Ident::new("v1")]),
+            query: query("SELECT a FROM t1 WHERE b > 1"),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE VIEW IF NOT EXISTS db1.v1 AS SELECT * FROM t1";
+        let expected = DfStatement::CreateView(DfCreateView {
+            if_not_exists: true,
+            materialized: false,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("v1")]),
+            query: query("SELECT * FROM t1"),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE MATERIALIZED VIEW mv1 AS SELECT a, count(*) AS c FROM t1 GROUP BY a";
+        let expected = DfStatement::CreateView(DfCreateView {
+            if_not_exists: false,
+            materialized: true,
+            name: ObjectName(vec![Ident::new("mv1")]),
+            query: query("SELECT a, count(*) AS c FROM t1 GROUP BY a"),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    expect_parse_err_contains("CREATE VIEW v1 SELECT * FROM t1", "Expected AS".to_string())?;
+    expect_parse_err_contains(
+        "CREATE MATERIALIZED mv1 AS SELECT * FROM t1",
+        "Expected VIEW".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn drop_view() -> Result<()> {
+    expect_parse_ok(
+        "DROP VIEW v1",
+        DfStatement::DropView(DfDropView {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("v1")]),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "DROP VIEW IF EXISTS db1.v1",
+        DfStatement::DropView(DfDropView {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("v1")]),
+        }),
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn hint_test() -> Result<()> {
     {
@@ -1182,6 +1398,7 @@ fn create_table_select() -> Result<()> {
             name: ObjectName(vec![Ident::new("foo")]),
             columns: vec![],
             engine: "FUSE".to_string(),
+            cluster_keys: vec![],
             options: maplit::hashmap! {},
             like: None,
             query: Some(verified_query("SELECT a, b FROM bar")?),
@@ -1195,6 +1412,7 @@ fn create_table_select() -> Result<()> {
             name: ObjectName(vec![Ident::new("foo")]),
             columns: vec![make_column_def("a", DataType::Int(None))],
             engine: "FUSE".to_string(),
+            cluster_keys: vec![],
             options: maplit::hashmap! {},
             like: None,
             query: Some(verified_query("SELECT a, b FROM bar")?),
@@ -1211,6 +1429,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            orphans_older_than: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -1220,6 +1439,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            orphans_older_than: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -1229,6 +1449,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::PURGE,
+            orphans_older_than: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -1238,6 +1459,7 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::COMPACT,
+            orphans_older_than: None,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -1247,10 +1469,49 @@ fn optimize_table() -> Result<()> {
         let expected = DfStatement::OptimizeTable(DfOptimizeTable {
             name: ObjectName(vec![Ident::new("t1")]),
             operation: Optimization::ALL,
+            orphans_older_than: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 purge orphans";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::PURGE_ORPHANS,
+            orphans_older_than: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 purge orphans older than 3 hours";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::PURGE_ORPHANS,
+            orphans_older_than: Some(Duration::from_secs(3 * 60 * 60)),
         });
         expect_parse_ok(sql, expected)?;
     }
 
+    {
+        let sql = "optimize TABLE t1 purge orphans older than 0 seconds";
+        expect_parse_err(
+            sql,
+            "sql parser error: retention of orphans must be at least 3600 seconds, found: 0 seconds"
+                .to_string(),
+        )?;
+    }
+
+    {
+        let sql = "optimize TABLE t1 purge orphans older than 1 week";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected one of SECONDS, MINUTES, HOURS, DAYS, found: week"
+                .to_string(),
+        )?;
+    }
+
     {
         let sql = "optimize TABLE t1 unacceptable";
         expect_parse_err(
@@ -1272,6 +1533,152 @@ fn optimize_table() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn analyze_table() -> Result<()> {
+    {
+        let sql = "analyze TABLE t1";
+        let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ANALYZE table db1.t1";
+        let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "analyze TABLE t1 compact";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected end of statement, found: compact".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn delete_test() -> Result<()> {
+    {
+        let sql = "DELETE FROM t1";
+        let expected = DfStatement::Delete(DfDeleteStatement {
+            name: ObjectName(vec![Ident::new("t1")]),
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DELETE FROM db1.t1 WHERE a > 1";
+        let expected = DfStatement::Delete(DfDeleteStatement {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            selection: Some(parse_sql_to_expr("a > 1")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DELETE t1";
+        expect_parse_err(
+            sql,
+            "sql parser error: Expected FROM, found: t1".to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn time_travel_test() -> Result<()> {
+    fn travel_hints(kind: &str, point: &str) -> Vec<Expr> {
+        vec![Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new(kind))),
+            op: BinaryOperator::Eq,
+            right: Box::new(parse_sql_to_expr(point)),
+        }]
+    }
+
+    let cases = vec![
+        (
+            "SELECT * FROM t AT (SNAPSHOT => 'abc')",
+            None,
+            travel_hints("SNAPSHOT", "'abc'"),
+        ),
+        (
+            "SELECT * FROM db.t at (timestamp => now()) AS a WHERE a.c > 1",
+            Some("a"),
+            travel_hints("TIMESTAMP", "now()"),
+        ),
+        (
+            "SELECT * FROM t AT(SNAPSHOT => 'abc') a JOIN t2 ON a.c = t2.c",
+            Some("a"),
+            travel_hints("SNAPSHOT", "'abc'"),
+        ),
+    ];
+
+    for (sql, expected_alias, expected_hints) in cases {
+        let (mut statements, _) = DfParser::parse_sql(sql)?;
+        let query = match statements.remove(0) {
+            DfStatement::Query(query) => query,
+            _ => panic!("expecting query statement, sql: {}", sql),
+        };
+        match &query.from[0].relation {
+            TableFactor::Table {
+                alias, with_hints, ..
+            } => {
+                assert_eq!(
+                    expected_alias,
+                    alias.as_ref().map(|v| v.name.value.as_str()),
+                    "{}",
+                    sql
+                );
+                assert_eq!(&expected_hints, with_hints, "{}", sql);
+            }
+            _ => panic!("expecting table, sql: {}", sql),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn update_test() -> Result<()> {
+    {
+        let sql = "UPDATE t1 SET a = 1";
+        let expected = DfStatement::Update(DfUpdateStatement {
+            name: ObjectName(vec![Ident::new("t1")]),
+            assignments: vec![(Ident::new("a"), parse_sql_to_expr("1"))],
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "UPDATE db1.t1 SET a = a + 1, b = 'x' WHERE a > 1";
+        let expected = DfStatement::Update(DfUpdateStatement {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            assignments: vec![
+                (Ident::new("a"), parse_sql_to_expr("a + 1")),
+                (Ident::new("b"), parse_sql_to_expr("'x'")),
+            ],
+            selection: Some(parse_sql_to_expr("a > 1")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "UPDATE t1 a = 1";
+        expect_parse_err(sql, "sql parser error: Expected SET, found: a".to_string())?;
+    }
+
+    Ok(())
+}
+
 #[test]
 fn drop_stage_test() -> Result<()> {
     expect_parse_ok(
@@ -49,6 +49,17 @@ async fn test_joined_schema_analyzer() -> Result<()> {
             query: "SELECT * FROM (SELECT * FROM system.databases)",
             expect: "QuerySchema { short_names: [\"name\"] }",
         },
+        TestCase {
+            name: "Join query",
+            query:
+                "SELECT * FROM system.databases AS a JOIN system.databases AS b ON a.name = b.name",
+            expect: "QuerySchema { ambiguity_names: [[\"a\", \"name\"], [\"b\", \"name\"]] }",
+        },
+        TestCase {
+            name: "Join query with using",
+            query: "SELECT * FROM system.databases AS a JOIN system.databases AS b USING(name)",
+            expect: "QuerySchema { short_names: [\"name\"], ambiguity_names: [[\"b\", \"name\"]] }",
+        },
     ];
 
     for test_case in &tests {
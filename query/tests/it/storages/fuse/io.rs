@@ -13,10 +13,13 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_arrow::arrow::io::parquet::write::Compression;
 use common_base::tokio;
 use common_dal2::ops::OpWrite;
+use common_dal2::ops::OpWriteStream;
 use common_dal2::services::fs;
 use common_dal2::Accessor;
 use common_dal2::Operator;
@@ -25,13 +28,23 @@ use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::col;
+use databend_query::storages::fuse::cache::BlockDataCache;
+use databend_query::storages::fuse::io::legacy_snapshot_location;
+use databend_query::storages::fuse::io::prev_snapshot_location;
 use databend_query::storages::fuse::io::BlockRegulator;
 use databend_query::storages::fuse::io::BlockStreamWriter;
+use databend_query::storages::fuse::io::BlockWriteOptions;
+use databend_query::storages::fuse::io::ClusterKeySorter;
+use databend_query::storages::fuse::io::ColumnCodec;
+use databend_query::storages::fuse::io::TableMetaLocationGenerator;
+use databend_query::storages::fuse::meta::ClusterStatistics;
 use databend_query::storages::fuse::DEFAULT_CHUNK_BLOCK_NUM;
 use futures::StreamExt;
 use futures::TryStreamExt;
 use num::Integer;
 use tempfile::TempDir;
+use uuid::Uuid;
 
 #[tokio::test]
 async fn test_fuse_table_block_appender() {
@@ -55,6 +68,9 @@ async fn test_fuse_table_block_appender() {
         schema.clone(),
         DEFAULT_CHUNK_BLOCK_NUM,
         0,
+        ClusterKeySorter::default(),
+        BlockWriteOptions::default(),
+        TableMetaLocationGenerator::default(),
     )
     .await
     .collect::<Vec<_>>()
@@ -81,6 +97,9 @@ async fn test_fuse_table_block_appender() {
         schema.clone(),
         max_rows_per_block,
         max_blocks_per_segment,
+        ClusterKeySorter::default(),
+        BlockWriteOptions::default(),
+        TableMetaLocationGenerator::default(),
     )
     .await
     .collect::<Vec<_>>()
@@ -100,6 +119,9 @@ async fn test_fuse_table_block_appender() {
         schema,
         DEFAULT_CHUNK_BLOCK_NUM,
         0,
+        ClusterKeySorter::default(),
+        BlockWriteOptions::default(),
+        TableMetaLocationGenerator::default(),
     )
     .await
     .collect::<Vec<_>>()
@@ -108,6 +130,156 @@ async fn test_fuse_table_block_appender() {
     assert!(segments.is_empty())
 }
 
+#[tokio::test]
+async fn test_fuse_table_block_appender_with_cluster_keys() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = Operator::new(
+        fs::Backend::build()
+            .root(tmp_dir.path().to_str().unwrap())
+            .finish()
+            .await
+            .unwrap(),
+    );
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i32::to_data_type()),
+        DataField::new("b", i32::to_data_type()),
+    ]);
+
+    let blocks = vec![
+        DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![3, 1, 1]),
+            Series::from_data(vec![1, 3, 2]),
+        ]),
+        DataBlock::create(schema.clone(), vec![
+            Series::from_data(vec![6, 4, 5]),
+            Series::from_data(vec![4, 5, 6]),
+        ]),
+    ];
+    let block_stream = futures::stream::iter(blocks.into_iter().map(Ok));
+
+    // cluster by (a, b)
+    let cluster_keys = vec![col("a"), col("b")];
+    let sorter = ClusterKeySorter::try_create(schema.clone(), &cluster_keys)?;
+    let segments = BlockStreamWriter::write_block_stream(
+        local_fs,
+        Box::pin(block_stream),
+        schema,
+        3,
+        1,
+        sorter,
+        BlockWriteOptions::default(),
+        TableMetaLocationGenerator::default(),
+    )
+    .await
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    let cluster_stats = segments
+        .iter()
+        .flat_map(|segment| segment.blocks.iter())
+        .map(|block_meta| block_meta.cluster_stats.clone())
+        .collect::<Vec<_>>();
+    let expected = vec![
+        Some(ClusterStatistics {
+            min: vec![DataValue::Int64(1), DataValue::Int64(2)],
+            max: vec![DataValue::Int64(3), DataValue::Int64(1)],
+        }),
+        Some(ClusterStatistics {
+            min: vec![DataValue::Int64(4), DataValue::Int64(5)],
+            max: vec![DataValue::Int64(6), DataValue::Int64(4)],
+        }),
+    ];
+    assert_eq!(cluster_stats, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_block_write_options() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i32::to_data_type()),
+        DataField::new_nullable("b", Vu8::to_data_type()),
+    ]);
+    let field_a = schema.field_with_name("a")?.to_arrow();
+    let field_b = schema.field_with_name("b")?.to_arrow();
+
+    // defaults to LZ4 without dictionary encoding
+    let options = BlockWriteOptions::try_create(&HashMap::new())?;
+    assert_eq!(options.compression(), Compression::Lz4);
+    assert!(!options.is_dictionary_encoded(&field_b));
+
+    let table_options = HashMap::from([
+        ("compression".to_string(), "zstd".to_string()),
+        (
+            "COLUMN_CODECS".to_string(),
+            r#"{"b":["DICTIONARY","SNAPPY"]}"#.to_string(),
+        ),
+    ]);
+    let options = BlockWriteOptions::try_create(&table_options)?;
+    options.validate(&schema)?;
+    assert_eq!(options.column_compression("a"), Compression::Zstd);
+    assert_eq!(options.column_compression("b"), Compression::Snappy);
+    assert!(!options.is_dictionary_encoded(&field_a));
+    assert!(options.is_dictionary_encoded(&field_b));
+
+    // unknown compression
+    let table_options = HashMap::from([("COMPRESSION".to_string(), "lzo".to_string())]);
+    let res = BlockWriteOptions::try_create(&table_options);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::bad_arguments_code());
+
+    // more than one compression of a column
+    let codecs = vec!["ZSTD".to_string(), "LZ4".to_string()];
+    let res = ColumnCodec::try_create(&codecs);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::bad_arguments_code());
+
+    // dictionary encoding of the non-string column
+    let table_options = HashMap::from([(
+        "COLUMN_CODECS".to_string(),
+        r#"{"a":["DICTIONARY"]}"#.to_string(),
+    )]);
+    let options = BlockWriteOptions::try_create(&table_options)?;
+    let res = options.validate(&schema);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::bad_arguments_code());
+
+    Ok(())
+}
+
+#[test]
+fn test_table_meta_location_generator() {
+    let generator = TableMetaLocationGenerator::with_prefix("42".to_string());
+    assert!(generator.gen_block_location().starts_with("42/_b/"));
+    assert!(generator.gen_block_location().ends_with(".parquet"));
+    assert!(generator
+        .gen_bloom_filter_index_location()
+        .starts_with("42/_i/"));
+    assert!(generator.gen_segment_info_location().starts_with("42/_sg/"));
+    assert!(generator
+        .gen_table_statistics_location()
+        .starts_with("42/_ts/"));
+
+    let id = Uuid::new_v4();
+    let location = generator.snapshot_location_from_uuid(&id);
+    assert_eq!(location, format!("42/_ss/{}", id.simple()));
+    assert_eq!(
+        legacy_snapshot_location(&location),
+        Some(format!("_ss/{}", id.simple()))
+    );
+
+    // snapshots of the chain are looked up in the same directory
+    let prev_id = Uuid::new_v4();
+    assert_eq!(
+        prev_snapshot_location(&location, &prev_id),
+        generator.snapshot_location_from_uuid(&prev_id)
+    );
+
+    // the empty prefix stands for the legacy layout
+    let legacy = TableMetaLocationGenerator::default();
+    assert!(legacy.gen_block_location().starts_with("_b/"));
+    let location = legacy.snapshot_location_from_uuid(&id);
+    assert_eq!(location, format!("_ss/{}", id.simple()));
+    assert_eq!(legacy_snapshot_location(&location), None);
+}
+
 #[test]
 fn test_block_regulator() -> common_exception::Result<()> {
     let schema = DataSchemaRefExt::create(vec![DataField::new("a", i32::to_data_type())]);
@@ -269,6 +441,9 @@ async fn test_block_stream_writer() -> common_exception::Result<()> {
             schema,
             max_rows_per_block,
             max_blocks_per_segment,
+            ClusterKeySorter::default(),
+            BlockWriteOptions::default(),
+            TableMetaLocationGenerator::default(),
         )
         .await;
         let segs = stream.try_collect::<Vec<_>>().await?;
@@ -343,4 +518,70 @@ impl Accessor for MockDataAccessor {
         *called += 1;
         Ok(args.size as usize)
     }
+
+    async fn write_stream(
+        &self,
+        r: Reader,
+        _args: &OpWriteStream,
+    ) -> common_dal2::error::Result<usize> {
+        // drains the reader, otherwise the serialization of the block is aborted
+        let size = futures::io::copy(r, &mut futures::io::sink())
+            .await
+            .map_err(|e| common_dal2::error::Error::Unexpected(e.to_string()))?;
+        let called = &mut *self.put_stream_called.lock();
+        *called += 1;
+        Ok(size as usize)
+    }
+}
+
+#[tokio::test]
+async fn test_block_data_cache() -> Result<()> {
+    let data_dir = TempDir::new().unwrap();
+    let cache_dir = TempDir::new().unwrap();
+    let local_fs = Operator::new(
+        fs::Backend::build()
+            .root(data_dir.path().to_str().unwrap())
+            .finish()
+            .await
+            .unwrap(),
+    );
+
+    let path = "block.parquet";
+    local_fs
+        .write(path, 13)
+        .run(Box::new(futures::io::Cursor::new("Hello, world!")))
+        .await
+        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+
+    let cache = BlockDataCache::try_create(
+        cache_dir.path().to_str().unwrap(),
+        1024 * 1024,
+        "test_tenant",
+        "test_cluster",
+    )?;
+    assert!(cache.is_empty());
+
+    // miss, loaded from the data accessor
+    let bytes = cache.read_range(&local_fs, path, 7, 5).await?;
+    assert_eq!(b"world".to_vec(), bytes);
+    assert_eq!(1, cache.len());
+    // the temporary file written outside the lock is moved into the cache
+    let temp_dir = cache_dir.path().join("_tmp");
+    assert_eq!(0, std::fs::read_dir(temp_dir)?.count());
+
+    // hit, served by the local disk even if the block file is gone
+    local_fs
+        .delete(path)
+        .run()
+        .await
+        .map_err(|e| ErrorCode::DalTransportError(e.to_string()))?;
+    let bytes = cache.read_range(&local_fs, path, 7, 5).await?;
+    assert_eq!(b"world".to_vec(), bytes);
+    assert_eq!(1, cache.len());
+
+    // a different range of the same block is another entry
+    let res = cache.read_range(&local_fs, path, 0, 5).await;
+    assert!(res.is_err());
+
+    Ok(())
 }
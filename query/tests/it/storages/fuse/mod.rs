@@ -15,6 +15,7 @@
 mod io;
 mod operations;
 mod pruning;
+mod result_cache;
 mod statistics;
 mod table;
 mod table_functions;
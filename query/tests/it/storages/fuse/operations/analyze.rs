@@ -0,0 +1,104 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use databend_query::storages::fuse::FUSE_TBL_STATISTICS_PREFIX;
+use futures::TryStreamExt;
+use walkdir::WalkDir;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_analyze_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 5 blocks, 3 rows of the same value for each
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream(5, 1);
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table
+        .commit_insertion(ctx.clone(), r.try_collect().await?, false)
+        .await?;
+
+    // the table is not analyzed yet
+    let table = fixture.latest_default_table().await?;
+    let statistics = table.statistics(ctx.clone()).await?.unwrap();
+    assert!(statistics.column_distributions.is_empty());
+
+    let qry = format!("analyze table '{}'.'{}'", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let table = fixture.latest_default_table().await?;
+    let statistics = table.statistics(ctx.clone()).await?.unwrap();
+    let distribution = &statistics.column_distributions[&0];
+    assert_eq!(distribution.ndv, 5);
+    assert_eq!(distribution.null_count, 0);
+    assert_eq!(distribution.histogram.buckets.len(), 5);
+    assert_eq!(distribution.histogram.num_values(), 15);
+
+    let expected = vec![
+        "+------+-----+------------+-------------------+-------------------------------------------------------+",
+        "| name | ndv | null_count | histogram_buckets | histogram                                             |",
+        "+------+-----+------------+-------------------+-------------------------------------------------------+",
+        "| id   | 5   | 0          | 5                 | [1, 1]: 3, [2, 2]: 3, [3, 3]: 3, [4, 4]: 3, [5, 5]: 3 |",
+        "+------+-----+------------+-------------------+-------------------------------------------------------+",
+    ];
+    let qry = format!(
+        "select name, ndv, null_count, histogram_buckets, histogram from system.column_statistics where database = '{}'",
+        db
+    );
+    expects_ok(
+        "column_statistics",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // analyzing again, the statistics of the purged snapshots are removed as well
+    let qry = format!("analyze table '{}'.'{}'", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert_eq!(statistics_file_count(&fixture), 2);
+    let qry = format!("optimize table '{}'.'{}' purge", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    assert_eq!(statistics_file_count(&fixture), 1);
+
+    let table = fixture.latest_default_table().await?;
+    let statistics = table.statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(statistics.column_distributions[&0].ndv, 5);
+    Ok(())
+}
+
+fn statistics_file_count(fixture: &TestFixture) -> usize {
+    let data_path = fixture.ctx().get_config().storage.disk.data_path;
+    WalkDir::new(data_path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .to_str()
+                .unwrap()
+                .contains(FUSE_TBL_STATISTICS_PREFIX)
+        })
+        .count()
+}
@@ -14,9 +14,15 @@
 //
 use common_base::tokio;
 use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use databend_query::storages::fuse::operations::MutationOperationLogEntry;
+use databend_query::storages::fuse::operations::TableOperation;
+use databend_query::storages::fuse::FuseTable;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
 use crate::storages::fuse::table_test_fixture::execute_query;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
@@ -77,3 +83,33 @@ async fn test_fuse_occ_retry() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_occ_mutation_of_altered_table() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    append_sample_data(1, &fixture).await?;
+
+    // the mutation runs against the table before it is altered
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let log_entry = MutationOperationLogEntry {
+        replaced_segments: vec![],
+        rows_written: 0,
+        schema: table.schema().as_ref().clone(),
+    };
+
+    let qry = format!("alter table '{}'.'{}' add column c int default 10", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the retry on top of the altered table can not merge the blocks of the old layout
+    let res = fuse_table
+        .do_commit(ctx.clone(), TableOperation::Mutation(log_entry))
+        .await;
+    assert_eq!(res.unwrap_err().code(), ErrorCode::OCCRetryFailureCode());
+
+    Ok(())
+}
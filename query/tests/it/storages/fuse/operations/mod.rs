@@ -13,7 +13,9 @@
 //  limitations under the License.
 //
 
+mod analyze;
 mod commit;
+mod navigate;
 mod optimize;
 mod part_info;
 mod purge_drop;
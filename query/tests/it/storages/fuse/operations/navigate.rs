@@ -0,0 +1,112 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_navigate() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 2 blocks, 3 rows per block
+    append_sample_data(2, &fixture).await?;
+    // another 3 blocks
+    append_sample_data(3, &fixture).await?;
+
+    // the id of the first snapshot
+    let qry = format!(
+        "select snapshot_id from fuse_history('{}', '{}') where prev_snapshot_id is null",
+        db, tbl
+    );
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let snapshot_id = String::from_utf8(blocks[0].column(0).get_checked(0)?.as_string()?)
+        .map_err(|e| ErrorCode::LogicalError(e.to_string()))?;
+
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 6        |",
+        "+----------+",
+    ];
+    let qry = format!(
+        "select count(*) from '{}'.'{}' at (snapshot => '{}')",
+        db, tbl, snapshot_id
+    );
+    expects_ok(
+        "navigate_to_first_snapshot",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the latest version of table is not affected
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 15       |",
+        "+----------+",
+    ];
+    let qry = format!("select count(*) from '{}'.'{}'", db, tbl);
+    expects_ok(
+        "latest_version",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // navigate to a time point before the table was created
+    let qry = format!(
+        "select count(*) from '{}'.'{}' at (timestamp => '2000-01-01 00:00:00')",
+        db, tbl
+    );
+    expects_err(
+        "time_point_too_early",
+        ErrorCode::table_historical_data_not_found_code(),
+        execute_query(ctx.clone(), qry.as_str()).await,
+    );
+
+    // snapshot not exist
+    let qry = format!(
+        "select count(*) from '{}'.'{}' at (snapshot => '{}')",
+        db,
+        tbl,
+        uuid::Uuid::new_v4().simple()
+    );
+    expects_err(
+        "snapshot_not_exist",
+        ErrorCode::table_historical_data_not_found_code(),
+        execute_query(ctx.clone(), qry.as_str()).await,
+    );
+
+    Ok(())
+}
@@ -13,6 +13,8 @@
 //  limitations under the License.
 //
 
+use std::time::Duration;
+
 use common_base::tokio;
 use common_exception::Result;
 use futures::TryStreamExt;
@@ -104,5 +106,143 @@ async fn test_fuse_history_optimize_compact() -> Result<()> {
         execute_query(fixture.ctx(), qry.as_str()).await,
         expected,
     )
+    .await?;
+
+    // the 5 blocks (and segments) should have been merged into 1 block (and segment)
+    let expected = vec![
+        "+---------------+-------------+-----------+",
+        "| segment_count | block_count | row_count |",
+        "+---------------+-------------+-----------+",
+        "| 1             | 1           | 15        |",
+        "+---------------+-------------+-----------+",
+    ];
+    let qry = format!(
+        "select segment_count, block_count, row_count from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    expects_ok(
+        "blocks_should_be_merged",
+        execute_query(fixture.ctx(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // compact again, nothing should be changed
+    let qry = format!("optimize table '{}'.'{}' compact", db, tbl);
+    execute_command(fixture.ctx(), qry.as_str()).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 6        |",
+        "+----------+",
+    ];
+    let qry = format!("select count(*) from fuse_history('{}', '{}')", db, tbl);
+    expects_ok(
+        "no_more_compaction",
+        execute_query(fixture.ctx(), qry.as_str()).await,
+        expected,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_compact_with_concurrent_append() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // insert 3 blocks
+    for _ in 0..3 {
+        let table = fixture.latest_default_table().await?;
+        let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table
+            .commit_insertion(ctx.clone(), r.try_collect().await?, false)
+            .await?;
+    }
+
+    // keep a stale version of the table, then append another block
+    let stale_table = fixture.latest_default_table().await?;
+    {
+        let table = fixture.latest_default_table().await?;
+        let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table
+            .commit_insertion(ctx.clone(), r.try_collect().await?, false)
+            .await?;
+    }
+
+    // the 3 blocks of the stale snapshot are merged, the concurrently appended one is kept
+    stale_table.compact(ctx.clone()).await?;
+
+    let expected = vec![
+        "+---------------+-------------+-----------+",
+        "| segment_count | block_count | row_count |",
+        "+---------------+-------------+-----------+",
+        "| 2             | 2           | 12        |",
+        "+---------------+-------------+-----------+",
+    ];
+    let qry = format!(
+        "select segment_count, block_count, row_count from fuse_history('{}', '{}') limit 1",
+        db, tbl
+    );
+    expects_ok(
+        "concurrent_append_should_be_kept",
+        execute_query(fixture.ctx(), qry.as_str()).await,
+        expected,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_history_optimize_purge_orphans() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // ingests 1 block, 1 segment, 1 snapshot
+    append_sample_data(1, &fixture).await?;
+
+    // 1 block and 1 segment are written, but left uncommitted
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+    let r = table.append_data(ctx.clone(), stream).await?;
+    r.try_collect::<Vec<_>>().await?;
+    check_data_dir(&fixture, "orphans_written", 1, 2, 2).await;
+
+    // orphans which are not old enough are kept
+    let qry = format!(
+        "optimize table '{}'.'{}' purge orphans older than 1 day",
+        db, tbl
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    check_data_dir(&fixture, "orphans_too_young", 1, 2, 2).await;
+
+    // the statement accepts no retention below the minimum, the table is asked directly
+    let table = fixture.latest_default_table().await?;
+    table
+        .purge_orphans(ctx.clone(), Some(Duration::from_secs(0)))
+        .await?;
+    check_data_dir(&fixture, "orphans_purged", 1, 1, 1).await;
+
+    // the data committed is intact
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 3        |",
+        "+----------+",
+    ];
+    let qry = format!("select count(*) from {}.{}", db, tbl);
+    expects_ok(
+        "committed_data_kept",
+        execute_query(ctx, qry.as_str()).await,
+        expected,
+    )
     .await
 }
@@ -25,6 +25,13 @@ fn test_part_info_encode_decode() -> Result<()> {
     let decoded = PartInfo::decode(&encode_str)?;
     assert_eq!(info, decoded);
 
+    // With column mapping
+    let info = PartInfo::new("test_loc", 1).with_col_mapping(Some(vec![Some(0), None, Some(2)]));
+    let encode_str = info.encode();
+    let decoded = PartInfo::decode(&encode_str)?;
+    assert_eq!(info, decoded);
+    assert_eq!(decoded.col_mapping(), Some(&[Some(0), None, Some(2)][..]));
+
     // Malformed
     {
         let too_much_parts = "xxx-1-xxx";
@@ -49,6 +49,9 @@ fn test_to_partitions() -> Result<()> {
             .sum(),
         file_size: 0,
         col_stats: cols_stats.clone(),
+        bloom_filter_index_location: None,
+        cluster_stats: None,
+        col_mapping: None,
         location: BlockLocation {
             path: "".to_string(),
             meta_size: 0,
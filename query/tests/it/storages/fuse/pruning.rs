@@ -184,6 +184,20 @@ async fn test_block_pruner() -> Result<()> {
 
     assert_eq!((num_blocks - max_val_of_b as usize - 1), blocks.len());
 
+    // all the pushed down filters are taken into account
+    let mut extra = Extras::default();
+    extra.filters = vec![col("a").gt(lit(0u64)), col("b").gt(lit(max_val_of_b))];
+
+    let blocks = apply_block_pruning(
+        &snapshot,
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+
+    assert_eq!((num_blocks - max_val_of_b as usize - 1), blocks.len());
+
     Ok(())
 }
 
@@ -303,3 +317,122 @@ async fn test_block_pruner_monotonic() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_block_pruner_bloom_filter() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let test_tbl_name = "test_bloom_filter";
+    let test_schema = DataSchemaRefExt::create(vec![DataField::new("id", Vu8::to_data_type())]);
+
+    let num_blocks = 10;
+    let row_per_block = 10;
+
+    let crate_table_plan = CreateTableReq {
+        if_not_exists: false,
+        tenant: fixture.default_tenant(),
+        db: fixture.default_db_name(),
+        table: test_tbl_name.to_string(),
+        table_meta: TableMeta {
+            schema: test_schema.clone(),
+            engine: "FUSE".to_string(),
+            options: [
+                (
+                    TBL_OPT_KEY_ROW_PER_BLOCK.to_owned(),
+                    row_per_block.to_string(),
+                ),
+                (TBL_OPT_KEY_BLOCK_PER_SEGMENT.to_owned(), "1".to_owned()),
+            ]
+            .into(),
+            ..Default::default()
+        },
+    };
+
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan).await?;
+
+    let table = catalog
+        .get_table(
+            fixture.default_tenant().as_str(),
+            fixture.default_db_name().as_str(),
+            test_tbl_name,
+        )
+        .await?;
+
+    // the value of row `r` in block `i` is "{r}_{i}", the min/max of every block
+    // are "0_{i}" and "9_{i}", so that a point lookup can not be pruned by the ranges
+    let blocks = (0..num_blocks)
+        .into_iter()
+        .map(|idx| {
+            let ids = (0..row_per_block)
+                .map(|row| format!("{}_{}", row, idx))
+                .collect::<Vec<_>>();
+            Ok(DataBlock::create(test_schema.clone(), vec![
+                Series::from_data(ids),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let stream = Box::pin(futures::stream::iter(blocks));
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table
+        .commit_insertion(ctx.clone(), r.try_collect().await?, false)
+        .await?;
+
+    let table = catalog
+        .get_table(
+            fixture.default_tenant().as_str(),
+            fixture.default_db_name().as_str(),
+            test_tbl_name,
+        )
+        .await?;
+
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .unwrap();
+
+    let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+    let snapshot = reader.read(snapshot_loc.as_str()).await?;
+
+    // every written block has an index file, which carries the bloom filter of column id
+    let blocks = apply_block_pruning(
+        &snapshot,
+        table.get_table_info().schema(),
+        &None,
+        ctx.clone(),
+    )
+    .await?;
+    assert_eq!(num_blocks, blocks.len());
+    let index_reader = MetaReaders::bloom_filter_index_reader(ctx.as_ref());
+    for block in &blocks {
+        let location = block.bloom_filter_index_location.as_ref().unwrap();
+        assert!(location.contains("/_i/"));
+        let filters = index_reader.read(location.as_str()).await?;
+        assert!(filters.contains_key(&0));
+    }
+
+    // point lookup, the block which contains the value must be kept, since the false
+    // positive rate is 5%, the chance that none of the other blocks is pruned is negligible
+    let mut extra = Extras::default();
+    extra.filters = vec![col("id").eq(lit("5_7".as_bytes()))];
+
+    let blocks = apply_block_pruning(
+        &snapshot,
+        table.get_table_info().schema(),
+        &Some(extra),
+        ctx.clone(),
+    )
+    .await?;
+
+    assert!(!blocks.is_empty());
+    assert!(blocks.len() < num_blocks);
+    assert!(blocks.iter().any(|b| {
+        let stats = &b.col_stats[&0];
+        stats.min == DataValue::String(b"0_7".to_vec())
+    }));
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::QueryContextShared;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+// Every query runs in its own context, as the tables are cached in the context of a query.
+fn new_query_context(ctx: &Arc<QueryContext>, query: &str) -> Result<Arc<QueryContext>> {
+    let shared = QueryContextShared::try_create(
+        ctx.get_config(),
+        ctx.get_current_session(),
+        ctx.get_cluster(),
+    )?;
+    let ctx = QueryContext::create_from_shared(shared);
+    ctx.attach_query_str(query);
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn test_fuse_table_query_result_cache() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    ctx.get_settings().set_settings(
+        "enable_query_result_cache".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+
+    // 2 blocks, 3 rows per block
+    append_sample_data(2, &fixture).await?;
+
+    let qry = format!("select count(*) from {}.{}", db, tbl);
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 6        |",
+        "+----------+",
+    ];
+    for case in ["miss", "hit"] {
+        let query_ctx = new_query_context(&ctx, &qry)?;
+        expects_ok(case, execute_query(query_ctx, &qry).await, expected.clone()).await?;
+    }
+    assert_eq!(ctx.get_query_result_cache().len(), 1);
+
+    // The same query formatted differently shares the cached result.
+    let other_qry = format!("select  count(*)\n  from {}.{};", db, tbl);
+    let query_ctx = new_query_context(&ctx, &other_qry)?;
+    expects_ok(
+        "normalized",
+        execute_query(query_ctx, &other_qry).await,
+        expected,
+    )
+    .await?;
+    assert_eq!(ctx.get_query_result_cache().len(), 1);
+
+    // Another 3 blocks, the cached result is stale.
+    append_sample_data(3, &fixture).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 15       |",
+        "+----------+",
+    ];
+    let query_ctx = new_query_context(&ctx, &qry)?;
+    expects_ok("stale", execute_query(query_ctx, &qry).await, expected).await?;
+    assert_eq!(ctx.get_query_result_cache().len(), 1);
+
+    // The non-deterministic queries are never cached.
+    let qry = format!("select count(*), rand() > 2 from {}.{}", db, tbl);
+    let query_ctx = new_query_context(&ctx, &qry)?;
+    execute_query(query_ctx, &qry)
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    assert_eq!(ctx.get_query_result_cache().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_query_result_cache_redefined_view() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+    ctx.get_settings().set_settings(
+        "enable_query_result_cache".to_string(),
+        "1".to_string(),
+        false,
+    )?;
+
+    // 2 blocks, 3 rows per block
+    append_sample_data(2, &fixture).await?;
+
+    let create_view = |definition: &str| {
+        format!(
+            "create view {}.v as select {} as c from {}.{}",
+            db, definition, db, tbl
+        )
+    };
+    let qry = format!("select count(*) from {}.v where c > 1", db);
+
+    let command = create_view("id");
+    execute_command(new_query_context(&ctx, &command)?, &command).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 3        |",
+        "+----------+",
+    ];
+    for case in ["miss", "hit"] {
+        let query_ctx = new_query_context(&ctx, &qry)?;
+        expects_ok(case, execute_query(query_ctx, &qry).await, expected.clone()).await?;
+    }
+    assert_eq!(ctx.get_query_result_cache().len(), 1);
+
+    // The view reads the same snapshot of the table, but the cached result is stale.
+    let command = format!("drop view {}.v", db);
+    execute_command(new_query_context(&ctx, &command)?, &command).await?;
+    let command = create_view("id + 1");
+    execute_command(new_query_context(&ctx, &command)?, &command).await?;
+    let expected = vec![
+        "+----------+",
+        "| count(0) |",
+        "+----------+",
+        "| 6        |",
+        "+----------+",
+    ];
+    let query_ctx = new_query_context(&ctx, &qry)?;
+    expects_ok("redefined", execute_query(query_ctx, &qry).await, expected).await?;
+
+    Ok(())
+}
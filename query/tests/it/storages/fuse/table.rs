@@ -14,16 +14,21 @@
 //
 
 use common_base::tokio;
+use common_datavalues2::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::ReadDataSourcePlan;
 use common_planners::TruncateTablePlan;
 use databend_query::catalogs::Catalog;
 use databend_query::interpreters::InterpreterFactory;
 use databend_query::sql::PlanParser;
+use databend_query::storages::fuse::operations::tenant_storage_usage;
 use databend_query::storages::fuse::TBL_OPT_KEY_CHUNK_BLOCK_NUM;
 use databend_query::storages::ToReadDataSourcePlan;
 use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::append_sample_data_overwrite;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 #[tokio::test]
@@ -277,3 +282,105 @@ async fn test_fuse_table_optimize() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_table_storage_quota() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // unlimited
+    append_sample_data(1, &fixture).await?;
+    let usage = tenant_storage_usage(&ctx).await?;
+    assert!(usage > 0);
+
+    // the appended blocks exceed the quota
+    let mut user = ctx.get_current_user()?;
+    user.quota.max_storage_in_bytes = usage;
+    ctx.get_current_session().set_current_user(user.clone());
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+    let res = table
+        .append_data(ctx.clone(), stream)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::StorageQuotaExceededCode()
+    );
+
+    // the quota is used up
+    user.quota.max_storage_in_bytes = usage - 1;
+    ctx.get_current_session().set_current_user(user);
+    let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+    let res = table.append_data(ctx.clone(), stream).await;
+    assert_eq!(
+        res.err().unwrap().code(),
+        ErrorCode::StorageQuotaExceededCode()
+    );
+
+    // nothing is committed
+    assert_eq!(tenant_storage_usage(&ctx).await?, usage);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_storage_usage_cache() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    let tenant = ctx.get_tenant();
+    fixture.create_default_table().await?;
+
+    append_sample_data(1, &fixture).await?;
+    let usage = tenant_storage_usage(&ctx).await?;
+    assert!(usage > 0);
+
+    // the cached usage is updated by the commits
+    append_sample_data(1, &fixture).await?;
+    let cached = tenant_storage_usage(&ctx).await?;
+    assert!(cached > usage);
+    ctx.get_storage_usage_cache().invalidate(&tenant);
+    assert_eq!(tenant_storage_usage(&ctx).await?, cached);
+
+    append_sample_data_overwrite(1, true, &fixture).await?;
+    let cached = tenant_storage_usage(&ctx).await?;
+    ctx.get_storage_usage_cache().invalidate(&tenant);
+    assert_eq!(tenant_storage_usage(&ctx).await?, cached);
+
+    let truncate_plan = TruncateTablePlan {
+        db: fixture.default_db_name(),
+        table: fixture.default_table_name(),
+        purge: false,
+    };
+    let table = fixture.latest_default_table().await?;
+    table.truncate(ctx.clone(), truncate_plan).await?;
+    assert_eq!(tenant_storage_usage(&ctx).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_statistics() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // empty table
+    let table = fixture.latest_default_table().await?;
+    let stats = table.statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(stats.num_rows, 0);
+    assert!(stats.column_statistics.is_empty());
+
+    // 2 blocks of 3 rows, valued 1 and 2
+    append_sample_data(2, &fixture).await?;
+    let table = fixture.latest_default_table().await?;
+    let stats = table.statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(stats.num_rows, 6);
+    assert!(stats.data_size > 0);
+    let col_stats = stats.column_statistics.get(&0).unwrap();
+    assert_eq!(col_stats.min, DataValue::Int64(1));
+    assert_eq!(col_stats.max, DataValue::Int64(2));
+
+    Ok(())
+}
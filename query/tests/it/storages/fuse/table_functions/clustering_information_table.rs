@@ -0,0 +1,172 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::meta::ClusterStatistics;
+use databend_query::storages::fuse::ClusteringInformation;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::storages::fuse::table_test_fixture::TestFixture;
+use crate::storages::fuse::table_test_fixture::*;
+
+fn range(min: i64, max: i64) -> ClusterStatistics {
+    ClusterStatistics {
+        min: vec![DataValue::Int64(min)],
+        max: vec![DataValue::Int64(max)],
+    }
+}
+
+#[test]
+fn test_clustering_information() -> Result<()> {
+    // no blocks
+    assert_eq!(
+        ClusteringInformation::from_cluster_stats(&[]),
+        ClusteringInformation {
+            total_constant_block_count: 0,
+            average_overlaps: 0.0,
+            average_depth: 0.0,
+        }
+    );
+
+    // disjoint blocks
+    assert_eq!(
+        ClusteringInformation::from_cluster_stats(&[range(1, 3), range(4, 5), range(6, 6)]),
+        ClusteringInformation {
+            total_constant_block_count: 1,
+            average_overlaps: 0.0,
+            average_depth: 1.0,
+        }
+    );
+
+    // [1, 3] and [4, 5] overlap with [2, 6]
+    // the depths of the points 1, 2, 3, 4, 5, 6 are 1, 2, 2, 2, 2, 1
+    assert_eq!(
+        ClusteringInformation::from_cluster_stats(&[range(1, 3), range(4, 5), range(2, 6)]),
+        ClusteringInformation {
+            total_constant_block_count: 0,
+            average_overlaps: 4.0 / 3.0,
+            average_depth: 10.0 / 6.0,
+        }
+    );
+
+    // the keys are compared column by column, nulls first
+    let stats = vec![
+        ClusterStatistics {
+            min: vec![DataValue::Null, DataValue::Int64(1)],
+            max: vec![DataValue::Int64(1), DataValue::Int64(1)],
+        },
+        ClusterStatistics {
+            min: vec![DataValue::Int64(1), DataValue::Int64(2)],
+            max: vec![DataValue::Int64(2), DataValue::Int64(0)],
+        },
+    ];
+    assert_eq!(
+        ClusteringInformation::from_cluster_stats(&stats),
+        ClusteringInformation {
+            total_constant_block_count: 0,
+            average_overlaps: 0.0,
+            average_depth: 1.0,
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clustering_information_table_read() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(a int, b int) cluster by (a)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    {
+        // the rows of a block are sorted by the cluster keys
+        let qry = format!("insert into {}.t values(3, 1), (1, 2), (2, 3)", db);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+
+        let qry = format!("select * from {}.t", db);
+        let blocks: Vec<DataBlock> = execute_query(ctx.clone(), qry.as_str())
+            .await?
+            .try_collect()
+            .await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 2 |",
+            "| 2 | 3 |",
+            "| 3 | 1 |",
+            "+---+---+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, &blocks);
+    }
+
+    {
+        let qry = format!("insert into {}.t values(5, 1), (4, 2)", db);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+        let qry = format!("insert into {}.t values(7, 1), (7, 2)", db);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+
+        let expected = vec![
+            "+-----------------+-------------------+----------------------------+------------------+---------------+",
+            "| cluster_by_keys | total_block_count | total_constant_block_count | average_overlaps | average_depth |",
+            "+-----------------+-------------------+----------------------------+------------------+---------------+",
+            "| (a)             | 3                 | 1                          | 0                | 1             |",
+            "+-----------------+-------------------+----------------------------+------------------+---------------+",
+        ];
+        let qry = format!("select * from clustering_information('{}', 't')", db);
+        expects_ok(
+            "clustering_information",
+            execute_query(ctx.clone(), qry.as_str()).await,
+            expected,
+        )
+        .await?;
+    }
+
+    {
+        // table without cluster keys
+        let qry = format!("create table {}.t_no_cluster(a int)", db);
+        execute_command(ctx.clone(), qry.as_str()).await?;
+
+        let qry = format!(
+            "select * from clustering_information('{}', 't_no_cluster')",
+            db
+        );
+        expects_err(
+            "table_without_cluster_keys",
+            ErrorCode::bad_arguments_code(),
+            execute_query(ctx.clone(), qry.as_str()).await,
+        );
+    }
+
+    {
+        // cluster keys must refer to the columns of the table
+        let qry = format!("create table {}.t_bad(a int) cluster by (c)", db);
+        expects_err(
+            "unknown_cluster_key",
+            ErrorCode::bad_arguments_code(),
+            execute_command(ctx.clone(), qry.as_str()).await,
+        );
+    }
+
+    Ok(())
+}
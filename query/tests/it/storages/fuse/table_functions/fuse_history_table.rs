@@ -86,10 +86,10 @@ async fn test_fuse_history_table_read() -> Result<()> {
 
     {
         let expected = vec![
-            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+",
-            "| snapshot_id | prev_snapshot_id | segment_count | block_count | row_count | bytes_uncompressed | bytes_compressed |",
-            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+",
-            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+",
+            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+-----------+",
+            "| snapshot_id | prev_snapshot_id | segment_count | block_count | row_count | bytes_uncompressed | bytes_compressed | timestamp |",
+            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+-----------+",
+            "+-------------+------------------+---------------+-------------+-----------+--------------------+------------------+-----------+",
         ];
 
         expects_ok(
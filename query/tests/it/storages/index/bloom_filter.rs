@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
 use common_exception::Result;
 use common_planners::*;
+use databend_query::storages::index::BlockBloomFilters;
 use databend_query::storages::index::BloomFilter;
 use databend_query::storages::index::BloomFilterExprEvalResult;
 use databend_query::storages::index::BloomFilterIndexer;
@@ -45,36 +48,43 @@ fn test_num_bits_hashes() -> Result<()> {
 
 #[test]
 fn test_bloom_add_find_string() -> Result<()> {
-    let schema =
-        DataSchemaRefExt::create(vec![DataField::new_nullable("name", Vu8::to_data_type())]);
-    let block = DataBlock::create(schema, vec![Series::from_data(vec![
-        "Alice", "Bob", "Batman", "Superman",
-    ])]);
-
-    let col = block.column(0);
+    let col = Series::from_data(vec!["Alice", "Bob", "Batman", "Superman"]);
 
     let mut bloom = BloomFilter::with_rate(4, 0.000001, create_seeds());
 
-    bloom.add(col)?;
-    assert!(bloom.find(DataValue::String(Some(b"Alice".to_vec())))?);
-    assert!(bloom.find(DataValue::String(Some(b"Bob".to_vec())))?);
-    assert!(bloom.find(DataValue::String(Some(b"Batman".to_vec())))?);
-    assert!(bloom.find(DataValue::String(Some(b"Superman".to_vec())))?);
+    bloom.add(&col)?;
+    assert!(bloom.find(&DataValue::String(b"Alice".to_vec()))?);
+    assert!(bloom.find(&DataValue::String(b"Bob".to_vec()))?);
+    assert!(bloom.find(&DataValue::String(b"Batman".to_vec()))?);
+    assert!(bloom.find(&DataValue::String(b"Superman".to_vec()))?);
 
     // this case no false positive
-    assert!(!bloom.find(DataValue::String(Some(b"alice1".to_vec())))?);
-    assert!(!bloom.find(DataValue::String(Some(b"alice2".to_vec())))?);
-    assert!(!bloom.find(DataValue::String(Some(b"alice3".to_vec())))?);
+    assert!(!bloom.find(&DataValue::String(b"alice1".to_vec()))?);
+    assert!(!bloom.find(&DataValue::String(b"alice2".to_vec()))?);
+    assert!(!bloom.find(&DataValue::String(b"alice3".to_vec()))?);
 
     Ok(())
 }
 
+#[test]
+fn test_bloom_nullable_int64() -> Result<()> {
+    let col = Series::from_data([None, None, None, None, Some(1234_i64), Some(-4321_i64)]);
+
+    let mut bloom = BloomFilter::with_rate(6, 0.000001, create_seeds());
+
+    // this case false positive not exist
+    bloom.add(&col)?;
+    assert!(bloom.find(&DataValue::Int64(1234_i64))?);
+    assert!(bloom.find(&DataValue::Int64(-4321_i64))?);
+    assert!(!bloom.find(&DataValue::Int64(4321_i64))?);
+    Ok(())
+}
+
 #[test]
 fn test_bloom_interval() -> Result<()> {
-    let schema = DataSchemaRefExt::create(vec![DataField::new(
+    let schema = DataSchemaRefExt::create(vec![DataField::new_nullable(
         "Interval",
-        DataType::Interval(IntervalUnit::DayTime),
-        true,
+        IntervalType::arc(IntervalUnit::DayTime),
     )]);
 
     let block = DataBlock::create(schema, vec![Series::from_data([
@@ -92,15 +102,18 @@ fn test_bloom_interval() -> Result<()> {
 
     // this case false positive not exist
     bloom.add(col)?;
-    assert!(bloom.find(DataValue::Int64(1234_i64))?);
-    assert!(bloom.find(DataValue::Int64(-4321_i64))?);
+    assert!(bloom.find(&DataValue::Int64(1234_i64))?);
+    assert!(bloom.find(&DataValue::Int64(-4321_i64))?);
+    assert!(!bloom.find(&DataValue::Int64(4321_i64))?);
     Ok(())
 }
 
 #[test]
 fn test_bloom_f64_serialization() -> Result<()> {
-    let schema =
-        DataSchemaRefExt::create(vec![DataField::new("Float64", f64::to_data_type(), true)]);
+    let schema = DataSchemaRefExt::create(vec![DataField::new_nullable(
+        "Float64",
+        f64::to_data_type(),
+    )]);
 
     let block = DataBlock::create(schema, vec![Series::from_data([
         None,
@@ -120,36 +133,73 @@ fn test_bloom_f64_serialization() -> Result<()> {
     let buf = bloom.to_vec()?;
     let bloom = BloomFilter::from_vec(buf.as_slice())?;
 
-    assert!(bloom.find(DataValue::Float64(Some(1234.1234_f64)))?);
-    assert!(bloom.find(DataValue::Float64(Some(-4321.4321_f64)))?);
-    assert!(bloom.find(DataValue::Float64(Some(88.88_f64)))?);
+    assert!(bloom.find(&DataValue::Float64(1234.1234_f64))?);
+    assert!(bloom.find(&DataValue::Float64(-4321.4321_f64))?);
+    assert!(bloom.find(&DataValue::Float64(88.88_f64))?);
 
     // a random number not exist
-    assert!(!bloom.find(DataValue::Float64(Some(88.88001_f64)))?);
+    assert!(!bloom.find(&DataValue::Float64(88.88001_f64))?);
+    Ok(())
+}
+
+#[test]
+fn test_bloom_json_serialization() -> Result<()> {
+    let col = Series::from_data(vec!["Alice", "Bob", "Batman", "Superman"]);
+
+    let mut bloom = BloomFilter::with_rate(4, 0.05, create_seeds());
+    bloom.add(&col)?;
+
+    // the filters are stored in the bloom filter index files, which are json
+    let json = serde_json::to_string(&bloom)?;
+    let deserialized: BloomFilter = serde_json::from_str(&json)?;
+    assert_eq!(deserialized, bloom);
+    assert!(deserialized.find(&DataValue::String(b"Batman".to_vec()))?);
     Ok(())
 }
 
 // A helper function to create a bloom filter, with the same bits and hashes as other.
-fn create_bloom(data_type: DataType, series: Series, other: &BloomFilter) -> Result<BloomFilter> {
+fn create_bloom(column: ColumnRef, other: &BloomFilter) -> Result<BloomFilter> {
     let mut bloom = other.clone_empty();
-    let schema = DataSchemaRefExt::create(vec![DataField::new("num", data_type, true)]);
-    let block = DataBlock::create(schema, vec![series]);
-    let col = block.column(0);
-    bloom.add(col)?;
+    bloom.add(&column)?;
     Ok(bloom)
 }
 
+fn build_filters(blocks: &[DataBlock]) -> Result<BlockBloomFilters> {
+    BloomFilterIndexer::build_with_seeds(blocks, create_seeds())
+}
+
+#[test]
+fn test_bloom_index_json_round_trip() -> Result<()> {
+    // the index file of a block is the json of its filters, keyed by column positions
+    let table = create_blocks();
+    let filters = build_filters(&table[0..1])?;
+    let json = serde_json::to_vec(&filters)?;
+    let deserialized: BlockBloomFilters = serde_json::from_slice(&json)?;
+    assert_eq!(deserialized, filters);
+
+    let schema = table[0].schema().clone();
+    let indexer = BloomFilterIndexer::create(schema.clone(), Arc::new(filters));
+    let deserialized = BloomFilterIndexer::create(schema, Arc::new(deserialized));
+    for expr in [
+        col("ColumnString").eq(lit("Batman".as_bytes())),
+        col("ColumnString").eq(lit("Thor".as_bytes())),
+        col("ColumnUInt8").eq(lit(34u8)),
+    ] {
+        assert_eq!(deserialized.eval(&expr)?, indexer.eval(&expr)?);
+    }
+    Ok(())
+}
+
 #[test]
 fn test_bloom_uint8_existence() -> Result<()> {
     // Build the table and bloom filters, get the bloom filter for column 'ColumnUInt8'
     let table = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(table.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&table, create_seeds())?;
     let bloom = indexer.try_get_bloom("ColumnUInt8")?;
 
     // Existence case: numbers 1, 3, 5, 7, 9, 11, 13, 15 should exist in the bloom filter.
     for num in [1_u8, 3, 5, 7, 9, 11, 13, 15] {
-        let single_value_bloom =
-            create_bloom(u8::to_data_type(), Series::from_data(vec![num]), &bloom)?;
+        let single_value_bloom = create_bloom(Series::from_data(vec![num]), bloom)?;
 
         assert!(bloom.contains(&single_value_bloom));
     }
@@ -158,38 +208,55 @@ fn test_bloom_uint8_existence() -> Result<()> {
 
 #[test]
 fn test_bloom_f64_existence() -> Result<()> {
-    // Build the table and bloom filters, get the bloom filter for column 'ColumnUInt8'
+    // Build the table and bloom filters, get the bloom filter for column 'ColumnFloat64'
     let table = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(table.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&table, create_seeds())?;
     let bloom = indexer.try_get_bloom("ColumnFloat64")?;
 
     // Existence case: numbers 1, 3, 5, 7, 9, 11, 13, 15 should exist in the bloom filter.
     for num in [1.0_f64, 3.0, 5.0, 7.0, 9.0, 11.0, 13.0, 15.0] {
-        let single_value_bloom =
-            create_bloom(f64::to_data_type(), Series::from_data(vec![num]), &bloom)?;
+        let single_value_bloom = create_bloom(Series::from_data(vec![num]), bloom)?;
 
         assert!(bloom.contains(&single_value_bloom));
     }
     Ok(())
 }
 
+#[test]
+fn test_bloom_unsupported_columns() -> Result<()> {
+    let table = create_blocks();
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&table, create_seeds())?;
+
+    // Float32 values are widened and would never match a Float64 literal, no filter is built
+    assert!(indexer.try_get_bloom("ColumnFloat32").is_err());
+    assert_eq!(
+        indexer.eval(&col("ColumnFloat32").eq(lit(2_f64)))?,
+        BloomFilterExprEvalResult::NotApplicable
+    );
+
+    // Literal of another type family can't be looked up
+    assert_eq!(
+        indexer.eval(&col("ColumnFloat64").eq(lit(2_i64)))?,
+        BloomFilterExprEvalResult::NotApplicable
+    );
+    Ok(())
+}
+
 #[test]
 fn test_bloom_hash_collision() -> Result<()> {
     // Build the table and bloom filters, get the bloom filter for column 'ColumnUInt8'
     let table = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(table.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&table, create_seeds())?;
     let bloom = indexer.try_get_bloom("ColumnUInt8")?;
 
     // Values [2, 4, 6, 8] doesn't exist and doesn't cause collision, so bloom.contains should return false
     for num in [2_u8, 4, 6, 8] {
-        let single_value_bloom =
-            create_bloom(u8::to_data_type(), Series::from_data(vec![num]), &bloom)?;
+        let single_value_bloom = create_bloom(Series::from_data(vec![num]), bloom)?;
         assert!(!bloom.contains(&single_value_bloom), "{}", num);
     }
 
-    // When hash collision happens, although number 32 doesn't exist in data_blocks, the hash bits say yes.
-    let single_value_bloom =
-        create_bloom(u8::to_data_type(), Series::from_data(vec![32_u8]), &bloom)?;
+    // When hash collision happens, although number 24 doesn't exist in data_blocks, the hash bits say yes.
+    let single_value_bloom = create_bloom(Series::from_data(vec![24_u8]), bloom)?;
     assert!(bloom.contains(&single_value_bloom));
     Ok(())
 }
@@ -296,7 +363,7 @@ fn test_bloom_indexer_single_column_prune() -> Result<()> {
     ];
 
     let data_blocks = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(data_blocks.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&data_blocks, create_seeds())?;
 
     for test in tests {
         let res = indexer.eval(&test.expr)?;
@@ -341,7 +408,7 @@ fn test_bloom_indexer_logical_and_prune() -> Result<()> {
     ];
 
     let data_blocks = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(data_blocks.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&data_blocks, create_seeds())?;
 
     for test in tests {
         let res = indexer.eval(&test.expr)?;
@@ -370,7 +437,7 @@ fn test_bloom_indexer_logical_or_prune() -> Result<()> {
         Test {
             // Both values exists in the data block, should return unknown.
             name: "ColumnInt64 = -1 or ColumnString = 'Professor X'",
-            expr: col("ColumnFloat64")
+            expr: col("ColumnInt64")
                 .eq(lit(-1_i64))
                 .or(col("ColumnString").eq(lit("Professor X".as_bytes()))),
             expected_eval_result: BloomFilterExprEvalResult::Unknown,
@@ -394,7 +461,7 @@ fn test_bloom_indexer_logical_or_prune() -> Result<()> {
     ];
 
     let data_blocks = create_blocks();
-    let indexer = BloomFilterIndexer::from_data_and_seeds(data_blocks.as_ref(), create_seeds())?;
+    let indexer = BloomFilterIndexer::from_data_and_seeds(&data_blocks, create_seeds())?;
 
     for test in tests {
         let res = indexer.eval(&test.expr)?;
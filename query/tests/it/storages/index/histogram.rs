@@ -0,0 +1,86 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues2::DataValue;
+use common_exception::Result;
+use databend_query::storages::index::Histogram;
+use databend_query::storages::index::HistogramBucket;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_equi_height_histogram() -> Result<()> {
+    let values = [1, 2, 2, 2, 3, 4, 5, 6, 7, 8]
+        .iter()
+        .map(|v| DataValue::Int64(*v))
+        .collect::<Vec<_>>();
+    let histogram = Histogram::from_sorted_values(&values, 4);
+
+    // equal values are kept in the same bucket
+    let bucket = |lower: i64, upper: i64, num_values: u64, num_distinct: u64| HistogramBucket {
+        lower_bound: DataValue::Int64(lower),
+        upper_bound: DataValue::Int64(upper),
+        num_values,
+        num_distinct,
+    };
+    assert_eq!(histogram.buckets, vec![
+        bucket(1, 2, 4, 2),
+        bucket(3, 5, 3, 3),
+        bucket(6, 8, 3, 3),
+    ]);
+    assert_eq!(histogram.num_values(), 10);
+
+    assert_eq!(histogram.fraction_below(0.0), Some(0.0));
+    assert_eq!(histogram.fraction_below(3.0), Some(0.4));
+    assert_eq!(histogram.fraction_below(4.0), Some(0.55));
+    assert_eq!(histogram.fraction_below(100.0), Some(1.0));
+    Ok(())
+}
+
+#[test]
+fn test_histogram_without_numeric_bounds() -> Result<()> {
+    assert_eq!(
+        Histogram::from_sorted_values(&[], 4).fraction_below(1.0),
+        None
+    );
+
+    let values = vec![
+        DataValue::String(b"a".to_vec()),
+        DataValue::String(b"b".to_vec()),
+    ];
+    let histogram = Histogram::from_sorted_values(&values, 100);
+    assert_eq!(histogram.buckets.len(), 2);
+    assert_eq!(histogram.fraction_below(1.0), None);
+    Ok(())
+}
+
+#[test]
+fn test_histogram_from_sample() -> Result<()> {
+    let sample = [1, 2, 3, 4]
+        .iter()
+        .map(|v| DataValue::Int64(*v))
+        .collect::<Vec<_>>();
+
+    // the sample holds 4 of the 100 values of the column
+    let histogram = Histogram::from_sorted_sample(&sample, 100, 2);
+    assert_eq!(histogram.buckets.len(), 2);
+    assert_eq!(histogram.buckets[0].num_values, 50);
+    assert_eq!(histogram.buckets[0].num_distinct, 2);
+    assert_eq!(histogram.num_values(), 100);
+    assert_eq!(histogram.fraction_below(3.0), Some(0.5));
+
+    // the whole column is sampled
+    let histogram = Histogram::from_sorted_sample(&sample, 4, 2);
+    assert_eq!(histogram, Histogram::from_sorted_values(&sample, 2));
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues2::DataValue;
+use common_exception::Result;
+use databend_query::storages::index::HyperLogLog;
+
+#[test]
+fn test_hyper_log_log() -> Result<()> {
+    // small cardinalities are (almost) exact
+    let mut sketch = HyperLogLog::new();
+    for value in [1, 2, 2, 3, 3, 3] {
+        sketch.add(&DataValue::Int64(value));
+    }
+    sketch.add(&DataValue::Null);
+    assert_eq!(sketch.count(), 3);
+
+    let mut left = HyperLogLog::new();
+    let mut right = HyperLogLog::new();
+    let mut all = HyperLogLog::new();
+    for value in 0..100000u64 {
+        let value = DataValue::UInt64(value);
+        if value.as_u64()? % 2 == 0 {
+            left.add(&value);
+        } else {
+            right.add(&value);
+        }
+        all.add(&value);
+        // duplicates are not counted
+        all.add(&value);
+    }
+
+    let count = all.count() as f64;
+    assert!(
+        (count - 100000.0).abs() / 100000.0 < 0.05,
+        "estimated ndv {}",
+        count
+    );
+
+    // the sketch of the union is the merge of the sketches
+    left.merge(&right)?;
+    assert_eq!(left, all);
+    Ok(())
+}
+
+#[test]
+fn test_hyper_log_log_strings() -> Result<()> {
+    let mut sketch = HyperLogLog::new();
+    for value in 0..1000 {
+        sketch.add(&DataValue::String(
+            format!("value_{}", value % 100).into_bytes(),
+        ));
+    }
+
+    let count = sketch.count() as f64;
+    assert!(
+        (count - 100.0).abs() / 100.0 < 0.05,
+        "estimated ndv {}",
+        count
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hyper_log_log_stable_hash() -> Result<()> {
+    // the sketches are persisted, the registers set by a value must never change
+    let mut sketch = HyperLogLog::new();
+    for value in [1, 2, 3] {
+        sketch.add(&DataValue::Int64(value));
+    }
+
+    let json = serde_json::to_value(&sketch)?;
+    let registers = json["registers"].as_array().unwrap();
+    let non_zero = registers
+        .iter()
+        .enumerate()
+        .filter(|(_, register)| register.as_u64() != Some(0))
+        .map(|(idx, register)| (idx, register.as_u64().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(non_zero, vec![(1084, 1), (1844, 2), (1980, 2)]);
+    Ok(())
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// mod bloom_filter;
+mod bloom_filter;
+mod histogram;
+mod hyper_log_log;
 mod index_min_max;
 mod index_sparse;
 mod range_filter;
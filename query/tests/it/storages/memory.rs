@@ -17,6 +17,7 @@ use common_base::tokio;
 use common_datablocks::assert_blocks_sorted_eq;
 use common_datablocks::DataBlock;
 use common_datavalues2::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
@@ -183,6 +184,12 @@ async fn test_memorytable() -> Result<()> {
         );
     }
 
+    // compact is not supported by the memory engine.
+    {
+        let res = table.compact(ctx.clone()).await;
+        assert_eq!(res.unwrap_err().code(), ErrorCode::UnImplementCode());
+    }
+
     // truncate.
     {
         let truncate_plan = TruncateTablePlan {
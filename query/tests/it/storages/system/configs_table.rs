@@ -35,7 +35,7 @@ async fn test_configs_table() -> Result<()> {
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
     assert_eq!(block.num_columns(), 4);
-    assert_eq!(block.num_rows(), 60);
+    assert_eq!(block.num_rows(), 63);
 
     let expected = vec![
         "+--------------------------------------+------------------+---------+-------------+",
@@ -54,6 +54,9 @@ async fn test_configs_table() -> Result<()> {
         "| disk.data_path                       | _data            | storage |             |",
         "| disk.temp_data_path                  |                  | storage |             |",
         "| flight_api_address                   | 127.0.0.1:9090   | query   |             |",
+        "| gcs.bucket                           |                  | storage |             |",
+        "| gcs.credential                       |                  | storage |             |",
+        "| gcs.endpoint_url                     |                  | storage |             |",
         "| http_api_address                     | 127.0.0.1:8080   | query   |             |",
         "| http_handler_host                    | 127.0.0.1        | query   |             |",
         "| http_handler_port                    | 8000             | query   |             |",
@@ -76,6 +79,7 @@ async fn test_configs_table() -> Result<()> {
         "| mysql_handler_port                   | 3307             | query   |             |",
         "| num_cpus                             | 8                | query   |             |",
         "| management_mode                      | false            | query   |             |",
+        "| query_result_cache_count             | 1024             | query   |             |",
         "| rpc_tls_meta_server_root_ca_cert     |                  | meta    |             |",
         "| rpc_tls_meta_service_domain_name     | localhost        | meta    |             |",
         "| rpc_tls_query_server_root_ca_cert    |                  | query   |             |",
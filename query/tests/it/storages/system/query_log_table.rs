@@ -54,12 +54,12 @@ async fn test_query_log_table() -> Result<()> {
         let result = stream.try_collect::<Vec<_>>().await?;
         assert_blocks_sorted_eq(
             vec![
-                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
-                "| log_type | handler_type | tenant_id | cluster_id | sql_user | sql_user_quota | sql_user_privileges | query_id | query_kind | query_text | event_date | event_time | current_database | databases | tables | columns | projections | written_rows | written_bytes | scan_rows | scan_bytes | scan_byte_cost_ms | scan_seeks | scan_seek_cost_ms | scan_partitions | total_partitions | result_rows | result_bytes | cpu_usage | memory_usage | client_info | client_address | exception_code | exception_text | stack_trace | server_version | extra |",
-                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
-                "| 2        |              |           |            |          |                |                     |          |            |            |            |            |                  |           |        |         |             |              |               |           |            |                   |            |                   |                 |                  |             |              |           |              |             |                |                |                |             |                |       |",
-                "| 3        |              |           |            |          |                |                     |          |            |            |            |            |                  |           |        |         |             |              |               |           |            |                   |            |                   |                 |                  |             |              |           |              |             |                |                |                |             |                |       |",
-                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
+                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+---------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
+                "| log_type | handler_type | tenant_id | cluster_id | sql_user | sql_user_quota | sql_user_privileges | query_id | query_kind | query_text | event_date | event_time | current_database | databases | tables | columns | projections | written_rows | written_bytes | scan_rows | scan_bytes | scan_byte_cost_ms | scan_seeks | scan_seek_cost_ms | scan_partitions | total_partitions | result_rows | result_bytes | cpu_usage | memory_usage | stage_retries | client_info | client_address | exception_code | exception_text | stack_trace | server_version | extra |",
+                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+---------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
+                "| 2        |              |           |            |          |                |                     |          |            |            |            |            |                  |           |        |         |             |              |               |           |            |                   |            |                   |                 |                  |             |              |           |              |               |             |                |                |                |             |                |       |",
+                "| 3        |              |           |            |          |                |                     |          |            |            |            |            |                  |           |        |         |             |              |               |           |            |                   |            |                   |                 |                  |             |              |           |              |               |             |                |                |                |             |                |       |",
+                "+----------+--------------+-----------+------------+----------+----------------+---------------------+----------+------------+------------+------------+------------+------------------+-----------+--------+---------+-------------+--------------+---------------+-----------+------------+-------------------+------------+-------------------+-----------------+------------------+-------------+--------------+-----------+--------------+---------------+-------------+----------------+----------------+----------------+-------------+----------------+-------+",
             ],
             &result,
         );
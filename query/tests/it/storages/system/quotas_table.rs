@@ -0,0 +1,58 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use common_meta_types::UserInfo;
+use databend_query::storages::system::QuotasTable;
+use databend_query::storages::Table;
+use databend_query::storages::ToReadDataSourcePlan;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_quotas_table() -> Result<()> {
+    let ctx = crate::tests::create_query_context()?;
+    let tenant = ctx.get_tenant();
+
+    let mut user = UserInfo::new_no_auth("test".to_string(), "localhost".to_string());
+    user.quota.max_cpu = 2;
+    user.quota.max_memory_in_bytes = 1024;
+    user.quota.max_storage_in_bytes = 2048;
+    ctx.get_user_manager().add_user(&tenant, user).await?;
+
+    let user = UserInfo::new_no_auth("test1".to_string(), "%".to_string());
+    ctx.get_user_manager().add_user(&tenant, user).await?;
+
+    let table: Arc<dyn Table> = Arc::new(QuotasTable::create(1));
+    let source_plan = table.read_plan(ctx.clone(), None).await?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 6);
+
+    let expected = vec![
+        "+-------+-----------+---------+---------------------+----------------------+-----------------------+",
+        "| name  | hostname  | max_cpu | max_memory_in_bytes | max_storage_in_bytes | used_storage_in_bytes |",
+        "+-------+-----------+---------+---------------------+----------------------+-----------------------+",
+        "| test  | localhost | 2       | 1024                | 2048                 | 0                     |",
+        "| test1 | %         | 0       | 0                   | 0                    | 0                     |",
+        "+-------+-----------+---------+---------------------+----------------------+-----------------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    Ok(())
+}
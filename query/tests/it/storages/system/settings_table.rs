@@ -33,18 +33,26 @@ async fn test_settings_table() -> Result<()> {
     let result = stream.try_collect::<Vec<_>>().await?;
 
     let expected = vec![
-        "+------------------------------------+---------+---------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
-        "| name                               | value   | default | level   | description                                                                                                                                | type   |",
-        "+------------------------------------+---------+---------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
-        "| flight_client_timeout              | 60      | 60      | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds                                         | UInt64 |",
-        "| max_block_size                     | 10000   | 10000   | SESSION | Maximum block size for reading                                                                                                             | UInt64 |",
-        "| max_threads                        | 2       | 16      | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.                                          | UInt64 |",
-        "| parallel_read_threads              | 1       | 1       | SESSION | The maximum number of parallelism for reading data. By default, it is 1.                                                                   | UInt64 |",
-        "| storage_occ_backoff_init_delay_ms  | 5       | 5       | SESSION | The initial retry delay in millisecond. By default, it is 5 ms.                                                                            | UInt64 |",
-        "| storage_occ_backoff_max_delay_ms   | 20000   | 20000   | SESSION | The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing. By default, it is 20 seconds. | UInt64 |",
-        "| storage_occ_backoff_max_elapsed_ms | 120000  | 120000  | SESSION | The maximum elapsed time after the occ starts, beyond which there will be no more retries. By default, it is 2 minutes.                    | UInt64 |",
-        "| storage_read_buffer_size           | 1048576 | 1048576 | SESSION | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                                                             | UInt64 |",
-        "+------------------------------------+---------+---------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
+        "+------------------------------------+----------+----------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
+        "| name                               | value    | default  | level   | description                                                                                                                                | type   |",
+        "+------------------------------------+----------+----------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
+        "| broadcast_join_threshold           | 10485760 | 10485760 | SESSION | The maximum estimated bytes of the right input of a join to broadcast it to every node, larger ones are shuffled. By default, it is 10MB.  | UInt64 |",
+        "| enable_new_processor_framework     | 0        | 0        | SESSION | Execute the SELECT queries on the new processor pipeline if it is 1. By default, it is 0.                                                  | UInt64 |",
+        "| enable_query_result_cache          | 0        | 0        | SESSION | Reuse the result of an identical SELECT query if the tables it reads are not changed since then, if it is 1. By default, it is 0.          | UInt64 |",
+        "| flight_client_timeout              | 60       | 60       | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds                                         | UInt64 |",
+        "| max_block_size                     | 10000    | 10000    | SESSION | Maximum block size for reading                                                                                                             | UInt64 |",
+        "| max_bytes_before_external_group_by | 0        | 0        | SESSION | The memory threshold in bytes, beyond which the group by state is spilled to disk. By default, it is 0, which disables spilling.           | UInt64 |",
+        "| max_bytes_before_external_sort     | 0        | 0        | SESSION | The memory threshold in bytes, beyond which the sorted data is spilled to disk. By default, it is 0, which disables spilling.              | UInt64 |",
+        "| max_memory_usage                   | 0        | 0        | SESSION | The maximum memory usage in bytes of a query. By default, it is 0, which means unlimited.                                                  | UInt64 |",
+        "| max_stage_retries                  | 3        | 3        | SESSION | The maximum number of times to reschedule a distributed query on the healthy nodes after a node failure. By default, it is 3.              | UInt64 |",
+        "| max_threads                        | 2        | 16       | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.                                          | UInt64 |",
+        "| parallel_read_threads              | 1        | 1        | SESSION | The maximum number of parallelism for reading data. By default, it is 1.                                                                   | UInt64 |",
+        "| query_result_cache_max_bytes       | 1048576  | 1048576  | SESSION | The maximum bytes of a SELECT query result to put into the result cache. By default, it is 1MB.                                            | UInt64 |",
+        "| storage_occ_backoff_init_delay_ms  | 5        | 5        | SESSION | The initial retry delay in millisecond. By default, it is 5 ms.                                                                            | UInt64 |",
+        "| storage_occ_backoff_max_delay_ms   | 20000    | 20000    | SESSION | The maximum  back off delay in millisecond, once the retry interval reaches this value, it stops increasing. By default, it is 20 seconds. | UInt64 |",
+        "| storage_occ_backoff_max_elapsed_ms | 120000   | 120000   | SESSION | The maximum elapsed time after the occ starts, beyond which there will be no more retries. By default, it is 2 minutes.                    | UInt64 |",
+        "| storage_read_buffer_size           | 1048576  | 1048576  | SESSION | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                                                             | UInt64 |",
+        "+------------------------------------+----------+----------+---------+--------------------------------------------------------------------------------------------------------------------------------------------+--------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
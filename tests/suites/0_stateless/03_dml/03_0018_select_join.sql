@@ -0,0 +1,34 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t1(a Int32, b String) Engine = MEMORY;
+CREATE TABLE IF NOT EXISTS t2(a Int64, c String) Engine = MEMORY;
+
+INSERT INTO t1 (a,b) VALUES(1, 'a1'), (2, 'a2'), (3, 'a3');
+INSERT INTO t2 (a,c) VALUES(2, 'b2'), (3, 'b3'), (3, 'b33'), (4, 'b4');
+
+SELECT '==INNER==';
+SELECT t1.a, b, c FROM t1 JOIN t2 ON t1.a = t2.a ORDER BY t1.a, c;
+SELECT t1.a, b, c FROM t1 INNER JOIN t2 ON t1.a = t2.a AND c <> 'b3' ORDER BY t1.a, c;
+SELECT a, b, c FROM t1 JOIN t2 USING(a) ORDER BY a, c;
+SELECT a, b, c FROM t1 NATURAL JOIN t2 ORDER BY a, c;
+
+SELECT '==LEFT==';
+SELECT t1.a, b, c FROM t1 LEFT JOIN t2 ON t1.a = t2.a ORDER BY t1.a, c;
+
+SELECT '==RIGHT==';
+SELECT t2.a, b, c FROM t1 RIGHT JOIN t2 ON t1.a = t2.a ORDER BY t2.a, c;
+
+SELECT '==FULL==';
+SELECT count(*), count(t1.a), count(t2.a) FROM t1 FULL JOIN t2 ON t1.a = t2.a;
+
+SELECT '==CROSS==';
+SELECT count(*) FROM t1, t2;
+SELECT count(*) FROM t1 CROSS JOIN t2 WHERE t1.a > t2.a;
+
+SELECT '==ERROR==';
+SELECT * FROM t1 LEFT JOIN t2; -- {ErrorCode 1005}
+SELECT * FROM t1 JOIN t1 ON t1.a = t1.a; -- {ErrorCode 1005}
+
+DROP DATABASE db1;
@@ -0,0 +1,24 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(g Int32, v Int32) Engine = MEMORY;
+INSERT INTO t (g,v) VALUES(1, 10), (1, 20), (1, 20), (2, 5), (2, 15);
+
+SELECT '==RANK==';
+SELECT g, v, row_number() OVER (PARTITION BY g ORDER BY v) AS rn FROM t ORDER BY g, v, rn;
+SELECT g, v, rank() OVER (PARTITION BY g ORDER BY v) AS r, dense_rank() OVER (ORDER BY v) AS dr FROM t ORDER BY g, v;
+
+SELECT '==LAG LEAD==';
+SELECT g, v, lag(v) OVER (PARTITION BY g ORDER BY v) AS l, lead(v, 1, 0) OVER (PARTITION BY g ORDER BY v) AS ld FROM t WHERE v <> 20 ORDER BY g, v;
+
+SELECT '==AGGREGATE==';
+SELECT g, v, sum(v) OVER (PARTITION BY g ORDER BY v) AS s, sum(v) OVER (PARTITION BY g ORDER BY v ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) AS s2 FROM t ORDER BY g, v, s2;
+SELECT count(*) OVER () AS c, max(v) OVER () AS m FROM t LIMIT 1;
+SELECT g, sum(v) AS s, rank() OVER (ORDER BY sum(v) DESC) AS r FROM t GROUP BY g ORDER BY r;
+
+SELECT '==ERROR==';
+SELECT row_number() FROM t; -- {ErrorCode 1005}
+SELECT g FROM t WHERE row_number() OVER () > 1; -- {ErrorCode 1005}
+
+DROP DATABASE db1;
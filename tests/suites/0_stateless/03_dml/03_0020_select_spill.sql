@@ -0,0 +1,11 @@
+SET max_block_size = 100;
+SET max_bytes_before_external_group_by = 1;
+SET max_bytes_before_external_sort = 1;
+
+SELECT '==GROUP BY==';
+SELECT number % 3 AS k, count(*), sum(number) FROM numbers(1000) GROUP BY k ORDER BY k;
+SELECT count(*), sum(c) FROM (SELECT number % 100 AS k, count(*) AS c FROM numbers(10000) GROUP BY k);
+
+SELECT '==ORDER BY==';
+SELECT number FROM numbers(1000) ORDER BY number DESC LIMIT 3;
+SELECT number FROM numbers(1000) ORDER BY number LIMIT 3 OFFSET 500;
@@ -0,0 +1,17 @@
+SET enable_new_processor_framework = 1;
+SET max_threads = 4;
+
+SELECT '==FILTER==';
+SELECT number, number + 1 FROM numbers_mt(10) WHERE number % 4 = 1 ORDER BY number;
+
+SELECT '==AGGREGATION==';
+SELECT count(*), sum(number), max(number) FROM numbers_mt(1000) WHERE number > 10;
+SELECT number % 3 AS k, count(*), sum(number) FROM numbers_mt(1000) GROUP BY k ORDER BY k;
+SELECT number % 3 AS k, count(*) AS c FROM numbers_mt(1000) GROUP BY k HAVING c > 333 ORDER BY k;
+
+SELECT '==ORDER BY==';
+SELECT number FROM numbers_mt(1000) ORDER BY number DESC LIMIT 3;
+SELECT number FROM numbers_mt(1000) ORDER BY number LIMIT 3 OFFSET 500;
+
+SELECT '==LIMIT==';
+SELECT count(*) FROM (SELECT number FROM numbers_mt(1000) LIMIT 10);
@@ -0,0 +1,32 @@
+DROP DATABASE IF EXISTS db_05_0014;
+CREATE DATABASE db_05_0014;
+USE db_05_0014;
+
+CREATE TABLE t(a INT, b INT);
+INSERT INTO t VALUES (1, 10), (2, 20), (3, 30);
+
+CREATE VIEW v AS SELECT a, b * 2 AS c FROM t WHERE a > 1;
+SELECT * FROM v ORDER BY a;
+SELECT c FROM v WHERE a = 3;
+SELECT x.a FROM v AS x ORDER BY x.a;
+
+CREATE VIEW v AS SELECT 1; -- {ErrorCode 2302}
+CREATE VIEW IF NOT EXISTS v AS SELECT 1;
+
+-- the tables of the view are resolved in the database of the view
+USE default;
+SELECT count(*) FROM db_05_0014.v;
+USE db_05_0014;
+
+-- the view reflects the changes of the table
+INSERT INTO t VALUES (4, 40);
+SELECT t.b, v.c FROM t, v WHERE t.a = v.a ORDER BY t.b;
+
+SHOW CREATE TABLE v;
+
+DROP VIEW t; -- {ErrorCode 1006}
+DROP VIEW v;
+SELECT * FROM v; -- {ErrorCode 1025}
+DROP VIEW IF EXISTS v;
+
+DROP DATABASE db_05_0014;
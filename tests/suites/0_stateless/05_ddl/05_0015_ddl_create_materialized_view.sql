@@ -0,0 +1,46 @@
+DROP DATABASE IF EXISTS db_05_0015;
+CREATE DATABASE db_05_0015;
+USE db_05_0015;
+
+CREATE TABLE t(a INT, b INT);
+INSERT INTO t VALUES (1, 10);
+
+CREATE MATERIALIZED VIEW mv AS SELECT a, sum(b) AS s, count(*) AS c FROM t GROUP BY a;
+CREATE MATERIALIZED VIEW IF NOT EXISTS mv AS SELECT a, sum(b) AS s, count(*) AS c FROM t GROUP BY a;
+
+-- the rows inserted before the view is created are not in the view
+SELECT count(*) FROM mv;
+
+-- every insertion appends the aggregates of its own rows to the view
+INSERT INTO t VALUES (1, 10), (2, 20);
+INSERT INTO t VALUES (1, 100), (3, 30);
+SELECT a, sum(s), sum(c) FROM mv GROUP BY a ORDER BY a;
+
+-- the view is overwritten with the source
+INSERT OVERWRITE t VALUES (2, 2);
+SELECT a, s, c FROM mv ORDER BY a;
+
+CREATE MATERIALIZED VIEW mv2 AS SELECT a FROM t LIMIT 1; -- {ErrorCode 1005}
+CREATE MATERIALIZED VIEW mv2 AS SELECT t.a FROM t, mv WHERE t.a = mv.a; -- {ErrorCode 1005}
+CREATE TABLE m(a INT) ENGINE = Memory;
+CREATE MATERIALIZED VIEW mv2 AS SELECT a FROM m; -- {ErrorCode 1005}
+
+-- the partial results of the aggregates must be mergeable
+CREATE MATERIALIZED VIEW mv2 AS SELECT a, avg(b) FROM t GROUP BY a; -- {ErrorCode 1005}
+CREATE MATERIALIZED VIEW mv2 AS SELECT a, count(DISTINCT b) FROM t GROUP BY a; -- {ErrorCode 1005}
+CREATE MATERIALIZED VIEW mv2 AS SELECT a, sum(b) FROM t GROUP BY a HAVING sum(b) > 1; -- {ErrorCode 1005}
+
+-- a failing view fails the insertion, before the source table is changed
+CREATE TABLE s(a INT, b VARCHAR NOT NULL);
+CREATE MATERIALIZED VIEW mv3 AS SELECT a, sum(CAST(b AS INT)) AS s FROM s GROUP BY a;
+INSERT INTO s VALUES (1, 'x'); -- {ErrorCode 1010}
+SELECT count(*) FROM s;
+INSERT INTO s VALUES (1, '2');
+SELECT a, s FROM mv3;
+
+DROP VIEW mv;
+SELECT * FROM mv; -- {ErrorCode 1025}
+INSERT INTO t VALUES (5, 50);
+SELECT count(*) FROM t;
+
+DROP DATABASE db_05_0015;
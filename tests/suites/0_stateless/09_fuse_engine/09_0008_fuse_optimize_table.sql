@@ -14,6 +14,8 @@ optimize table t compact;
 
 -- expects 4 history items, 3 of previous insertion, 1 for last compaction
 select count(*)=4 from fuse_history('db_09_0008', 't');
+-- expects the 3 blocks (and segments) to be merged into 1
+select segment_count, block_count, row_count from fuse_history('db_09_0008', 't') limit 1;
 
 ---------------------------
 
@@ -40,13 +42,14 @@ select * from t order by a;
 
 ---------------------
 
--- optimize memory table should not panic/throws exception
+-- optimize memory table should not panic, compact is not supported by it
 
 create table m(a uint64) engine=Memory;
 optimize table m;
-optimize table m all;
+optimize table m all; -- {ErrorCode 1002}
 optimize table m purge;
-optimize table m compact;
+optimize table m compact; -- {ErrorCode 1002}
+optimize table m purge orphans; -- {ErrorCode 1002}
 drop table m;
 
 
@@ -63,6 +66,13 @@ optimize table m all;
 optimize table m purge;
 optimize table m compact;
 
+-- files referenced by the table should be kept
+optimize table m purge orphans;
+optimize table m purge orphans older than 1 hours;
+-- the files of the insertions in progress might be removed with a shorter retention
+optimize table m purge orphans older than 0 seconds; -- {ErrorCode 1005}
+select a from m order by a;
+
 ---------------------
 
 DROP TABLE m;
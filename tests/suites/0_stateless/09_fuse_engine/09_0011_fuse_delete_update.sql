@@ -0,0 +1,48 @@
+DROP DATABASE IF EXISTS db_09_0011;
+CREATE DATABASE db_09_0011;
+USE db_09_0011;
+
+create table t(a int, b varchar);
+
+insert into t values (1, 'a'), (2, 'b');
+insert into t values (3, 'c'), (4, 'd');
+insert into t values (5, 'e');
+
+---------------------------
+
+-- delete rows of a single block
+delete from t where a = 5;
+select * from t order by a;
+
+-- delete rows across blocks
+delete from t where a % 2 = 0;
+select * from t order by a;
+
+-- no rows matched, nothing changed
+delete from t where a > 100;
+select count(*) from t;
+
+---------------------------
+
+-- update with expressions and casts
+update t set b = 'x', a = a + 10 where a = 1;
+select * from t order by a;
+
+-- update all the rows
+update t set b = concat(b, '!');
+select * from t order by a;
+
+---------------------------
+
+-- delete all the rows
+delete from t;
+select count(*) from t;
+
+-- the table is still writable
+insert into t values (6, 'f');
+select * from t order by a;
+
+---------------------------
+
+DROP TABLE t;
+DROP DATABASE db_09_0011;
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "DROP DATABASE IF EXISTS db_09_0012" | $MYSQL_CLIENT_CONNECT
+echo "CREATE DATABASE db_09_0012" | $MYSQL_CLIENT_CONNECT
+echo "create table db_09_0012.t(c int)" | $MYSQL_CLIENT_CONNECT
+echo "insert into db_09_0012.t values(1),(2)" | $MYSQL_CLIENT_CONNECT
+echo "insert into db_09_0012.t values(3)" | $MYSQL_CLIENT_CONNECT
+
+## the first snapshot
+SNAPSHOT_ID=$(echo "select snapshot_id from fuse_history('db_09_0012','t') where prev_snapshot_id is null" | $MYSQL_CLIENT_CONNECT)
+TIMESTAMP=$(echo "select timestamp from fuse_history('db_09_0012','t') where prev_snapshot_id is null" | $MYSQL_CLIENT_CONNECT)
+
+## travel to the first snapshot, by snapshot id and by timestamp
+echo "select * from db_09_0012.t at (snapshot => '$SNAPSHOT_ID') order by c" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from db_09_0012.t at (TIMESTAMP => '$TIMESTAMP')" | $MYSQL_CLIENT_CONNECT
+
+## alias of the table
+echo "select a.c from db_09_0012.t at (snapshot => '$SNAPSHOT_ID') as a order by a.c" | $MYSQL_CLIENT_CONNECT
+
+## the latest data is not affected
+echo "select count(*) from db_09_0012.t" | $MYSQL_CLIENT_CONNECT
+
+echo "DROP DATABASE db_09_0012" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,24 @@
+DROP DATABASE IF EXISTS db_09_0013;
+CREATE DATABASE db_09_0013;
+USE db_09_0013;
+
+create table t(a int, b int) cluster by (a % 3, b);
+show create table t;
+
+-- the rows of a block are sorted by the cluster keys
+insert into t values (1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6);
+select * from t;
+
+insert into t values (7, 7), (7, 7);
+select cluster_by_keys, total_block_count, total_constant_block_count from clustering_information('db_09_0013', 't');
+
+-- the keys must refer to the columns of the table
+create table t1(a int) cluster by (c); -- {ErrorCode 1006}
+
+-- tables without cluster keys
+create table t2(a int);
+select * from clustering_information('db_09_0013', 't2'); -- {ErrorCode 1006}
+
+DROP TABLE t;
+DROP TABLE t2;
+DROP DATABASE db_09_0013;
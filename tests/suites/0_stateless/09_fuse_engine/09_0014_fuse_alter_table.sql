@@ -0,0 +1,37 @@
+DROP DATABASE IF EXISTS db_09_0014;
+CREATE DATABASE db_09_0014;
+USE db_09_0014;
+
+create table t(a int, b varchar);
+insert into t values (1, 'a'), (2, 'b');
+
+-- blocks written before the column is added are read with the default value
+alter table t add column c int default 10;
+select * from t order by a;
+insert into t values (3, 'c', 30);
+select * from t order by a;
+select count(*) from t where c = 10;
+
+alter table t drop column b;
+select * from t order by a;
+
+alter table t rename column c to d;
+select a, d from t order by a;
+select sum(d) from t;
+
+-- values of a dropped column are not brought back by adding a column of the same name
+alter table t add column b varchar default 'x';
+select * from t order by a;
+
+alter table t drop column x; -- {ErrorCode 1058}
+alter table t add column a int; -- {ErrorCode 1006}
+alter table t rename column a to d; -- {ErrorCode 1006}
+alter table if exists t_not_exists drop column a;
+
+rename table t to t2;
+select count(*) from t2;
+select count(*) from t; -- {ErrorCode 1025}
+alter table t2 rename to t3;
+select a, d, b from t3 order by a;
+
+DROP DATABASE db_09_0014;
@@ -0,0 +1,29 @@
+DROP DATABASE IF EXISTS db_09_0015;
+CREATE DATABASE db_09_0015;
+USE db_09_0015;
+
+create table t(a int, b varchar);
+insert into t values (1, 'a'), (2, 'b'), (2, 'b'), (3, null);
+
+-- tables which are not analyzed have no column statistics
+select count(*) from system.column_statistics where database = 'db_09_0015';
+
+analyze table t;
+select name, ndv, null_count, histogram_buckets from system.column_statistics where database = 'db_09_0015' order by name;
+
+-- the statistics are kept by later insertions, until the table is analyzed again
+insert into t values (4, 'd');
+select name, ndv from system.column_statistics where database = 'db_09_0015' order by name;
+analyze table db_09_0015.t;
+select name, ndv from system.column_statistics where database = 'db_09_0015' order by name;
+
+truncate table t;
+select count(*) from system.column_statistics where database = 'db_09_0015';
+
+analyze table t_not_exists; -- {ErrorCode 1025}
+
+-- only the FUSE tables can be analyzed
+create table m(a int) engine = Memory;
+analyze table m; -- {ErrorCode 1002}
+
+DROP DATABASE db_09_0015;
@@ -0,0 +1,31 @@
+DROP DATABASE IF EXISTS db_09_0016;
+CREATE DATABASE db_09_0016;
+USE db_09_0016;
+
+create table t(a int, b varchar codec(dictionary, snappy), c varchar null codec(none)) compression = 'zstd';
+insert into t values (1, 'x', 'a'), (2, 'y', null), (3, 'x', 'c');
+insert into t values (4, 'y', 'd'), (5, 'x', null);
+select * from t order by a;
+
+-- the merged blocks are written with the same codecs
+optimize table t compact;
+select count(*), count(c), min(b), max(b) from t;
+select * from t where b = 'y' order by a;
+
+-- the codecs are kept in step with the columns
+alter table t rename column b to b1;
+insert into t values (6, 'z', 'f');
+select a, b1 from t where a > 4 order by a;
+
+-- unknown compression
+create table t1(a int) compression = 'lzo'; -- {ErrorCode 1006}
+create table t1(a int codec(lzo)); -- {ErrorCode 1006}
+-- at most one compression of a column
+create table t1(a int codec(zstd, lz4)); -- {ErrorCode 1006}
+-- dictionary encoding is only supported by the String columns
+create table t1(a int codec(dictionary)); -- {ErrorCode 1006}
+-- codecs of the other engines
+create table t1(a int codec(zstd)) engine = Memory; -- {ErrorCode 1002}
+
+DROP TABLE t;
+DROP DATABASE db_09_0016;